@@ -0,0 +1,1110 @@
+diff --git a/src/ai.rs b/src/ai.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..52b6a6ea95f1d997a49973914919e0dc1f16f52d
+--- /dev/null
++++ b/src/ai.rs
+@@ -0,0 +1,1104 @@
++//! Decision making for bot players. Each bot plays the economy through the
++//! `BotBrain` of the strategy picked for its seat; moving, the arcade and the
++//! casino are shared by all of them.
++
++use bevy::utils::{Duration, Instant};
++use rand::{Rng, seq::SliceRandom};
++use serde::{Deserialize, Serialize};
++
++use crate::*;
++
++/// Cash a bot keeps back when deciding how high to bid.
++pub const BOT_AUCTION_RESERVE: i32 = 200;
++
++/// Cash a bot tries to keep on hand, selling shops back to the bank if needed.
++pub const BOT_CASH_RESERVE: i32 = 150;
++
++/// Turns a bot waits after proposing a trade before it proposes another.
++pub const BOT_TRADE_COOLDOWN: u32 = 3;
++
++/// Laps of the board `projected_net_worth` looks ahead under Normal rules.
++pub const PROJECTION_LAPS: i32 = 3;
++
++/// Average roll of a single die.
++const AVERAGE_DIE_ROLL: f32 = 3.5;
++
++/// Time a Hard bot may spend on rollouts in one turn, shared equally between
++/// the `HARD_DECISIONS_PER_TURN` decisions it plays out.
++pub const HARD_TURN_BUDGET: Duration = Duration::from_millis(60);
++
++/// Rollout decisions in a Hard bot's turn: the shop it lands on, a trade to
++/// propose, shares, then investment.
++pub const HARD_DECISIONS_PER_TURN: u32 = 4;
++
++/// Rollouts each choice gets when the budget allows.
++pub const HARD_ROLLOUTS: usize = 16;
++
++/// Rounds of play in each rollout.
++pub const HARD_ROLLOUT_ROUNDS: usize = 2;
++
++/// Strategies a bot seat can be given, in the order Tab cycles through them.
++pub const BOT_STRATEGIES: [BotStrategy; 4] = [
++    BotStrategy::Cautious,
++    BotStrategy::Balanced,
++    BotStrategy::Aggressive,
++    BotStrategy::Hard,
++];
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
++pub enum BotStrategy {
++    Cautious,
++    #[default]
++    Balanced,
++    Aggressive,
++    Hard,
++}
++
++impl BotStrategy {
++    pub fn brain(self) -> &'static dyn BotBrain {
++        match self {
++            BotStrategy::Cautious => &CautiousBrain,
++            BotStrategy::Balanced => &BalancedBrain,
++            BotStrategy::Aggressive => &AggressiveBrain,
++            BotStrategy::Hard => &HardBrain,
++        }
++    }
++
++    pub fn label(self) -> &'static str {
++        match self {
++            BotStrategy::Cautious => "Cautious",
++            BotStrategy::Balanced => "Balanced",
++            BotStrategy::Aggressive => "Aggressive",
++            BotStrategy::Hard => "Hard",
++        }
++    }
++
++    pub fn from_name(name: &str) -> Option<Self> {
++        BOT_STRATEGIES
++            .into_iter()
++            .find(|strategy| strategy.label().eq_ignore_ascii_case(name.trim()))
++    }
++}
++
++/// The economic decisions a bot makes. Callers only ask once the move is
++/// legal, e.g. `buy_shop` is only asked about shops the bot can afford.
++pub trait BotBrain: Sync {
++    /// Whether to buy the vacant shop the bot has landed on.
++    fn buy_shop(
++        &self,
++        player_idx: usize,
++        tile_index: usize,
++        game: &Game,
++        market: &StockMarket,
++    ) -> bool;
++
++    /// Puts spare cash into the bot's shops during its stock phase.
++    fn invest(&self, player_idx: usize, game: &mut Game, market: &mut StockMarket);
++
++    /// Buys or sells shares during the bot's stock phase.
++    fn trade_stocks(&self, player_idx: usize, game: &mut Game, market: &mut StockMarket);
++
++    /// The most the bot will bid for a shop at auction.
++    fn bid_ceiling(&self, player_idx: usize, tile_index: usize, game: &Game) -> i32;
++
++    /// How the bot answers a trade another player proposes to it.
++    fn evaluate_trade(
++        &self,
++        proposal: &TradeProposal,
++        game: &Game,
++        market: &StockMarket,
++    ) -> TradeResponse;
++
++    /// A trade the bot puts to another player in its stock phase, if any.
++    fn propose_trade(
++        &self,
++        player_idx: usize,
++        game: &Game,
++        market: &StockMarket,
++    ) -> Option<TradeProposal>;
++}
++
++/// The brain of the bot in `player_idx`'s seat.
++pub fn brain_of(player_idx: usize, game: &Game) -> &'static dyn BotBrain {
++    game.players[player_idx].strategy.brain()
++}
++
++/// Keeps a large cash cushion: only buys shops it can comfortably afford,
++/// invests and buys shares sparingly, and wants a clear gain from trades.
++pub struct CautiousBrain;
++
++impl CautiousBrain {
++    /// Cash left over after buying a shop or bidding on one.
++    const RESERVE: i32 = 500;
++    /// Cash it would rather not dip below in the stock phase.
++    const SPENDING_RESERVE: i32 = 1000;
++    const TRADE_MARGIN_PERCENT: i32 = 25;
++    /// Premium over a shop's value it offers for the last shop of a district.
++    const OFFER_PREMIUM_PERCENT: i32 = 0;
++}
++
++impl BotBrain for CautiousBrain {
++    fn buy_shop(
++        &self,
++        player_idx: usize,
++        tile_index: usize,
++        game: &Game,
++        market: &StockMarket,
++    ) -> bool {
++        let TileKind::Property { price, .. } = game.board[tile_index].kind else {
++            return false;
++        };
++        game.players[player_idx].cash - price >= Self::RESERVE
++            && worth_doing(
++                BotAction::BuyShop(tile_index),
++                player_idx,
++                Self::SPENDING_RESERVE,
++                game,
++                market,
++            )
++    }
++
++    fn invest(&self, player_idx: usize, game: &mut Game, market: &mut StockMarket) {
++        spend_on_best(
++            player_idx,
++            investment_options,
++            Self::SPENDING_RESERVE,
++            1,
++            game,
++            market,
++        );
++    }
++
++    fn trade_stocks(&self, player_idx: usize, game: &mut Game, market: &mut StockMarket) {
++        sell_shares_for_cash(player_idx, Self::RESERVE, game, market);
++        spend_on_best(
++            player_idx,
++            share_options,
++            Self::SPENDING_RESERVE,
++            1,
++            game,
++            market,
++        );
++    }
++
++    fn bid_ceiling(&self, player_idx: usize, tile_index: usize, game: &Game) -> i32 {
++        let TileKind::Property { price, .. } = game.board[tile_index].kind else {
++            return 0;
++        };
++        price.min((game.players[player_idx].cash - Self::RESERVE).get())
++    }
++
++    fn evaluate_trade(
++        &self,
++        proposal: &TradeProposal,
++        game: &Game,
++        market: &StockMarket,
++    ) -> TradeResponse {
++        evaluate_trade_with_margin(proposal, Self::TRADE_MARGIN_PERCENT, game, market)
++    }
++
++    fn propose_trade(
++        &self,
++        player_idx: usize,
++        game: &Game,
++        market: &StockMarket,
++    ) -> Option<TradeProposal> {
++        monopoly_offer(
++            player_idx,
++            Self::OFFER_PREMIUM_PERCENT,
++            Self::SPENDING_RESERVE,
++            game,
++            market,
++        )
++    }
++}
++
++/// The default: buys the shops that pay for themselves, invests once a turn
++/// where it pays best, and takes trades that gain it a little.
++pub struct BalancedBrain;
++
++impl BalancedBrain {
++    /// Cash it would rather not dip below when spending.
++    const RESERVE: i32 = 400;
++    const TRADE_MARGIN_PERCENT: i32 = 10;
++    const OFFER_PREMIUM_PERCENT: i32 = 15;
++}
++
++impl BotBrain for BalancedBrain {
++    fn buy_shop(
++        &self,
++        player_idx: usize,
++        tile_index: usize,
++        game: &Game,
++        market: &StockMarket,
++    ) -> bool {
++        worth_doing(
++            BotAction::BuyShop(tile_index),
++            player_idx,
++            Self::RESERVE,
++            game,
++            market,
++        )
++    }
++
++    fn invest(&self, player_idx: usize, game: &mut Game, market: &mut StockMarket) {
++        spend_on_best(
++            player_idx,
++            investment_options,
++            Self::RESERVE,
++            1,
++            game,
++            market,
++        );
++    }
++
++    fn trade_stocks(&self, player_idx: usize, game: &mut Game, market: &mut StockMarket) {
++        sell_shares_for_cash(player_idx, BOT_CASH_RESERVE, game, market);
++        spend_on_best(player_idx, share_options, Self::RESERVE, 1, game, market);
++    }
++
++    fn bid_ceiling(&self, player_idx: usize, tile_index: usize, game: &Game) -> i32 {
++        shop_value_to(player_idx, tile_index, game)
++            .min((game.players[player_idx].cash - BOT_AUCTION_RESERVE).get())
++    }
++
++    fn evaluate_trade(
++        &self,
++        proposal: &TradeProposal,
++        game: &Game,
++        market: &StockMarket,
++    ) -> TradeResponse {
++        evaluate_trade_with_margin(proposal, Self::TRADE_MARGIN_PERCENT, game, market)
++    }
++
++    fn propose_trade(
++        &self,
++        player_idx: usize,
++        game: &Game,
++        market: &StockMarket,
++    ) -> Option<TradeProposal> {
++        monopoly_offer(
++            player_idx,
++            Self::OFFER_PREMIUM_PERCENT,
++            Self::RESERVE,
++            game,
++            market,
++        )
++    }
++}
++
++/// Spends hard: buys nearly every shop it can, pours cash into its best shops
++/// and shares, overbids at auction and takes any even trade.
++pub struct AggressiveBrain;
++
++impl AggressiveBrain {
++    /// Cash it would rather not dip below when spending.
++    const RESERVE: i32 = 150;
++    const INVEST_STEPS: usize = 3;
++    const STOCK_BLOCKS: usize = 3;
++    const AUCTION_RESERVE: i32 = 50;
++    /// Premium over a shop's value it will bid, in percent.
++    const BID_PREMIUM_PERCENT: i32 = 25;
++    const OFFER_PREMIUM_PERCENT: i32 = 40;
++}
++
++impl BotBrain for AggressiveBrain {
++    fn buy_shop(
++        &self,
++        player_idx: usize,
++        tile_index: usize,
++        game: &Game,
++        market: &StockMarket,
++    ) -> bool {
++        worth_doing(
++            BotAction::BuyShop(tile_index),
++            player_idx,
++            Self::RESERVE,
++            game,
++            market,
++        )
++    }
++
++    fn invest(&self, player_idx: usize, game: &mut Game, market: &mut StockMarket) {
++        spend_on_best(
++            player_idx,
++            investment_options,
++            Self::RESERVE,
++            Self::INVEST_STEPS,
++            game,
++            market,
++        );
++    }
++
++    fn trade_stocks(&self, player_idx: usize, game: &mut Game, market: &mut StockMarket) {
++        sell_shares_for_cash(player_idx, BOT_CASH_RESERVE, game, market);
++        spend_on_best(
++            player_idx,
++            share_options,
++            Self::RESERVE,
++            Self::STOCK_BLOCKS,
++            game,
++            market,
++        );
++    }
++
++    fn bid_ceiling(&self, player_idx: usize, tile_index: usize, game: &Game) -> i32 {
++        let value = shop_value_to(player_idx, tile_index, game);
++        (value + value * Self::BID_PREMIUM_PERCENT / 100)
++            .min((game.players[player_idx].cash - Self::AUCTION_RESERVE).get())
++    }
++
++    fn evaluate_trade(
++        &self,
++        proposal: &TradeProposal,
++        game: &Game,
++        market: &StockMarket,
++    ) -> TradeResponse {
++        evaluate_trade_with_margin(proposal, 0, game, market)
++    }
++
++    fn propose_trade(
++        &self,
++        player_idx: usize,
++        game: &Game,
++        market: &StockMarket,
++    ) -> Option<TradeProposal> {
++        monopoly_offer(
++            player_idx,
++            Self::OFFER_PREMIUM_PERCENT,
++            Self::RESERVE,
++            game,
++            market,
++        )
++    }
++}
++
++/// Plays each choice out over random continuations of the match with the
++/// headless engine and makes whichever leaves it furthest ahead on average.
++/// Rollouts stop at `HARD_TURN_BUDGET`, so its turns never hold up the frame
++/// for long.
++pub struct HardBrain;
++
++impl HardBrain {
++    /// Cash it would rather not dip below when shortlisting options.
++    const RESERVE: i32 = 300;
++    /// Options played out against doing nothing for each decision.
++    const SHORTLIST: usize = 3;
++    const TRADE_MARGIN_PERCENT: i32 = 10;
++    const OFFER_PREMIUM_PERCENT: i32 = 15;
++
++    /// Plays out doing nothing against the best-ranked of `options` and
++    /// carries out the winner.
++    fn spend_by_rollouts(
++        player_idx: usize,
++        options: fn(usize, &Game) -> Vec<BotAction>,
++        game: &mut Game,
++        market: &mut StockMarket,
++    ) {
++        let mut choices = vec![None];
++        choices.extend(
++            rank_actions(
++                player_idx,
++                options(player_idx, game),
++                Self::RESERVE,
++                game,
++                market,
++            )
++            .into_iter()
++            .take(Self::SHORTLIST)
++            .map(|(action, _)| Some(action)),
++        );
++        if choices.len() == 1 {
++            return;
++        }
++        let best = choose_by_rollouts(
++            player_idx,
++            &choices,
++            |choice, game, market| {
++                choice.is_none_or(|action| action.apply(player_idx, game, market).is_ok())
++            },
++            game,
++            market,
++        );
++        if let Some(Some(action)) = best {
++            let _ = action.apply(player_idx, game, market);
++        }
++    }
++}
++
++impl BotBrain for HardBrain {
++    fn buy_shop(
++        &self,
++        player_idx: usize,
++        tile_index: usize,
++        game: &Game,
++        market: &StockMarket,
++    ) -> bool {
++        choose_by_rollouts(
++            player_idx,
++            &[false, true],
++            |buy, game, market| {
++                !buy || BotAction::BuyShop(tile_index)
++                    .apply(player_idx, game, market)
++                    .is_ok()
++            },
++            game,
++            market,
++        ) == Some(true)
++    }
++
++    fn invest(&self, player_idx: usize, game: &mut Game, market: &mut StockMarket) {
++        Self::spend_by_rollouts(player_idx, investment_options, game, market);
++    }
++
++    fn trade_stocks(&self, player_idx: usize, game: &mut Game, market: &mut StockMarket) {
++        sell_shares_for_cash(player_idx, BOT_CASH_RESERVE, game, market);
++        Self::spend_by_rollouts(player_idx, share_options, game, market);
++    }
++
++    fn bid_ceiling(&self, player_idx: usize, tile_index: usize, game: &Game) -> i32 {
++        shop_value_to(player_idx, tile_index, game)
++            .min((game.players[player_idx].cash - BOT_AUCTION_RESERVE).get())
++    }
++
++    /// Accepts when accepting plays out better than refusing; otherwise it
++    /// counters as a Balanced bot would.
++    fn evaluate_trade(
++        &self,
++        proposal: &TradeProposal,
++        game: &Game,
++        market: &StockMarket,
++    ) -> TradeResponse {
++        let accept = choose_by_rollouts(
++            proposal.to,
++            &[false, true],
++            |accept, game, _| !accept || execute_trade(proposal, game).is_ok(),
++            game,
++            market,
++        ) == Some(true);
++        if accept {
++            return TradeResponse::Accept;
++        }
++        match evaluate_trade_with_margin(proposal, Self::TRADE_MARGIN_PERCENT, game, market) {
++            TradeResponse::Accept => TradeResponse::Reject,
++            response => response,
++        }
++    }
++
++    /// Proposes the monopoly deal a Balanced bot would, but only if going
++    /// through with it plays out better than not.
++    fn propose_trade(
++        &self,
++        player_idx: usize,
++        game: &Game,
++        market: &StockMarket,
++    ) -> Option<TradeProposal> {
++        let proposal = monopoly_offer(
++            player_idx,
++            Self::OFFER_PREMIUM_PERCENT,
++            Self::RESERVE,
++            game,
++            market,
++        )?;
++        let worth_it = choose_by_rollouts(
++            player_idx,
++            &[false, true],
++            |trade, game, _| !trade || execute_trade(&proposal, game).is_ok(),
++            game,
++            market,
++        ) == Some(true);
++        worth_it.then_some(proposal)
++    }
++}
++
++/// Plays `HARD_ROLLOUT_ROUNDS` rounds on from `game` once `make_choice` has
++/// been applied, and returns how far `player_idx`'s projected net worth ends
++/// up ahead of its best rival's. Rollouts draw from a freshly shuffled venture
++/// deck and a fresh RNG, since the real draw order and dice are hidden, and
++/// every seat plays as a Balanced bot so rollouts never nest. `None` if the
++/// choice can't be made.
++pub fn rollout(
++    player_idx: usize,
++    make_choice: impl FnOnce(&mut Game, &mut StockMarket) -> bool,
++    game: &Game,
++    market: &StockMarket,
++) -> Option<i32> {
++    let mut game = game.clone();
++    let mut market = market.clone();
++    let mut deck = VentureDeck::for_game(&game);
++    let mut rng = MatchRng::from_entropy();
++    for player in &mut game.players {
++        player.kind = PlayerKind::Bot;
++        player.decider = Decider::Brain;
++        if player.strategy == BotStrategy::Hard {
++            player.strategy = BotStrategy::Balanced;
++        }
++    }
++    if !make_choice(&mut game, &mut market) {
++        return None;
++    }
++    game.end_turn();
++    for _ in 0..HARD_ROLLOUT_ROUNDS * game.players.len() {
++        if game.winner.is_some() {
++            break;
++        }
++        sim::advance_match(&mut game, &mut market, &mut deck, &mut rng);
++    }
++    let best_rival = (0..game.players.len())
++        .filter(|idx| *idx != player_idx)
++        .map(|idx| projected_net_worth(idx, &game, &market))
++        .max()
++        .unwrap_or(0);
++    Some(projected_net_worth(player_idx, &game, &market) - best_rival)
++}
++
++/// Rolls each of `choices` out in turn, up to `HARD_ROLLOUTS` times or until
++/// this decision's share of `HARD_TURN_BUDGET` runs out, and returns the one
++/// with the best average result. Choices `apply` can't make are dropped.
++pub fn choose_by_rollouts<C: Copy>(
++    player_idx: usize,
++    choices: &[C],
++    apply: impl Fn(C, &mut Game, &mut StockMarket) -> bool,
++    game: &Game,
++    market: &StockMarket,
++) -> Option<C> {
++    let deadline = Instant::now() + HARD_TURN_BUDGET / HARD_DECISIONS_PER_TURN;
++    let mut totals = vec![(0.0_f64, 0_usize); choices.len()];
++    'rollouts: for _ in 0..HARD_ROLLOUTS {
++        for (choice, (total, count)) in choices.iter().zip(&mut totals) {
++            if Instant::now() >= deadline {
++                break 'rollouts;
++            }
++            let made = |game: &mut Game, market: &mut StockMarket| apply(*choice, game, market);
++            if let Some(score) = rollout(player_idx, made, game, market) {
++                *total += score as f64;
++                *count += 1;
++            }
++        }
++    }
++    choices
++        .iter()
++        .zip(&totals)
++        .filter(|(_, (_, count))| *count > 0)
++        .max_by(|(_, (a, a_count)), (_, (b, b_count))| {
++            (a / *a_count as f64).total_cmp(&(b / *b_count as f64))
++        })
++        .map(|(choice, _)| *choice)
++}
++
++/// Something a bot can spend its cash on.
++#[derive(Debug, Clone, Copy, PartialEq, Eq)]
++pub enum BotAction {
++    BuyShop(usize),
++    Invest(usize),
++    /// One order of `MIN_SHARE_ORDER` shares.
++    BuyShares(DistrictId),
++}
++
++impl BotAction {
++    pub fn apply(
++        self,
++        player_idx: usize,
++        game: &mut Game,
++        market: &mut StockMarket,
++    ) -> Result<(), &'static str> {
++        match self {
++            BotAction::BuyShop(tile_index) => {
++                let TileKind::Property { price, .. } = game.board[tile_index].kind else {
++                    return Err("Not a shop");
++                };
++                if game.owner_of(tile_index).is_some() {
++                    return Err("That shop is already owned");
++                }
++                if game.players[player_idx].cash < price {
++                    return Err("Not enough cash to buy that shop");
++                }
++                buy_property(tile_index, player_idx, game, market);
++                Ok(())
++            }
++            BotAction::Invest(tile_index) => {
++                invest_in_shop(tile_index, player_idx, game, market).map(|_| ())
++            }
++            BotAction::BuyShares(district) => {
++                buy_stock(player_idx, district, MIN_SHARE_ORDER, game, market).map(|_| ())
++            }
++        }
++    }
++}
++
++/// Chance that `player_idx` stops on a given tile it passes: one in the
++/// length of its average roll.
++pub fn landing_chance(player_idx: usize, game: &Game) -> f32 {
++    1.0 / (AVERAGE_DIE_ROLL * game.players[player_idx].dice_count() as f32)
++}
++
++/// Fees an owned shop can expect to collect over one lap of the board, from
++/// every other player still in the match.
++pub fn expected_fee_income(tile_index: usize, game: &Game, market: &StockMarket) -> f32 {
++    let (Some(owner), Some(fee)) = (game.owner_of(tile_index), game.shop_fee(tile_index, market))
++    else {
++        return 0.0;
++    };
++    let visits: f32 = (0..game.players.len())
++        .filter(|idx| *idx != owner && !game.players[*idx].bankrupt)
++        .map(|idx| landing_chance(idx, game))
++        .sum();
++    fee as f32 * visits
++}
++
++/// Extra fee income per lap `player_idx` stands to gain by completing the
++/// districts it has a foothold in. Each district's gain from the monopoly
++/// multiplier is weighted by the share of it the player already owns and by
++/// how much of the rest is still vacant rather than held by rivals.
++pub fn monopoly_potential(player_idx: usize, game: &Game, market: &StockMarket) -> f32 {
++    game.district_ids()
++        .into_iter()
++        .map(|district| {
++            let size = game.district_size(district);
++            let owned: Vec<usize> = owned_shops(player_idx, game)
++                .into_iter()
++                .filter(|index| {
++                    matches!(game.board[*index].kind, TileKind::Property { district: d, .. } if d == district)
++                })
++                .collect();
++            if size < 2 || owned.is_empty() || owned.len() == size {
++                return 0.0;
++            }
++            let vacant = (0..game.board.len())
++                .filter(|index| {
++                    matches!(game.board[*index].kind, TileKind::Property { district: d, .. } if d == district)
++                        && game.owner_of(*index).is_none()
++                })
++                .count();
++            let income: f32 = owned
++                .iter()
++                .map(|index| expected_fee_income(*index, game, market))
++                .sum();
++            let boost = MONOPOLY_FEE_MULTIPLIER / game.fee_multiplier(owned[0]) - 1.0;
++            let share = owned.len() as f32 / size as f32;
++            let open = vacant as f32 / (size - owned.len()) as f32;
++            income * boost * share * open
++        })
++        .sum()
++}
++
++/// What `player_idx` can expect to be worth `bot_lookahead_laps` laps from now
++/// if the board stays as it is: today's net worth plus, every lap, the fees its
++/// shops collect, a promotion salary (whose share of its stock holdings is
++/// the return on its shares) and its monopoly potential, less the fees it
++/// pays rivals along the way.
++pub fn projected_net_worth(player_idx: usize, game: &Game, market: &StockMarket) -> i32 {
++    let player = &game.players[player_idx];
++    if player.bankrupt {
++        return 0;
++    }
++    let chance = landing_chance(player_idx, game);
++    let mut fees = 0.0;
++    for tile_index in 0..game.board.len() {
++        match game.owner_of(tile_index) {
++            Some(owner) if owner == player_idx => {
++                fees += expected_fee_income(tile_index, game, market)
++            }
++            Some(_) => fees -= game.shop_fee(tile_index, market).unwrap_or(0) as f32 * chance,
++            None => {}
++        }
++    }
++    let salary =
++        SalaryBreakdown::for_player(player, player.level + 1, &game.rules, &game.board, market)
++            .total() as f32;
++    let per_lap = fees + salary + monopoly_potential(player_idx, game, market);
++    player.net_worth(&game.board, market)
++        + (per_lap * game.rules.bot_lookahead_laps as f32).round() as i32
++}
++
++/// What running short of `reserve` costs: a bot that can't cover a fee sells
++/// shops back to the bank at `SHOP_RESALE_PERCENT`, so every gold below the
++/// reserve is counted as the resale loss, twice over for the risk.
++fn shortfall_cost(cash: Money, reserve: i32) -> i32 {
++    (reserve - cash.get()).max(0) * (100 - SHOP_RESALE_PERCENT) * 2 / 100
++}
++
++/// How much `action` raises `player_idx`'s projected net worth, less the cost
++/// of any cash it leaves the bot short of `reserve`. `None` if the action
++/// isn't possible.
++pub fn score_action(
++    action: BotAction,
++    player_idx: usize,
++    reserve: i32,
++    game: &Game,
++    market: &StockMarket,
++) -> Option<i32> {
++    let before = projected_net_worth(player_idx, game, market)
++        - shortfall_cost(game.players[player_idx].cash, reserve);
++    let mut trial_game = game.clone();
++    let mut trial_market = market.clone();
++    action
++        .apply(player_idx, &mut trial_game, &mut trial_market)
++        .ok()?;
++    let after = projected_net_worth(player_idx, &trial_game, &trial_market)
++        - shortfall_cost(trial_game.players[player_idx].cash, reserve);
++    Some(after - before)
++}
++
++/// The `actions` that would leave `player_idx` better off, best first.
++pub fn rank_actions(
++    player_idx: usize,
++    actions: impl IntoIterator<Item = BotAction>,
++    reserve: i32,
++    game: &Game,
++    market: &StockMarket,
++) -> Vec<(BotAction, i32)> {
++    let mut ranked: Vec<(BotAction, i32)> = actions
++        .into_iter()
++        .filter_map(|action| {
++            score_action(action, player_idx, reserve, game, market).map(|score| (action, score))
++        })
++        .filter(|(_, score)| *score > 0)
++        .collect();
++    ranked.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
++    ranked
++}
++
++/// Whether `action` ranks as worth doing at all.
++pub fn worth_doing(
++    action: BotAction,
++    player_idx: usize,
++    reserve: i32,
++    game: &Game,
++    market: &StockMarket,
++) -> bool {
++    !rank_actions(player_idx, [action], reserve, game, market).is_empty()
++}
++
++/// An investment in each of `player_idx`'s shops with room for capital.
++pub fn investment_options(player_idx: usize, game: &Game) -> Vec<BotAction> {
++    owned_shops(player_idx, game)
++        .into_iter()
++        .filter(|index| game.board[*index].capital_room() >= INVEST_STEP)
++        .map(BotAction::Invest)
++        .collect()
++}
++
++/// An order of shares in every district.
++pub fn share_options(_player_idx: usize, game: &Game) -> Vec<BotAction> {
++    game.district_ids()
++        .into_iter()
++        .map(BotAction::BuyShares)
++        .collect()
++}
++
++/// Carries out the best-ranked of `options` up to `steps` times, ranking them
++/// again after each one since every purchase changes what the rest are worth.
++pub fn spend_on_best(
++    player_idx: usize,
++    options: fn(usize, &Game) -> Vec<BotAction>,
++    reserve: i32,
++    steps: usize,
++    game: &mut Game,
++    market: &mut StockMarket,
++) {
++    for _ in 0..steps {
++        let ranked = rank_actions(player_idx, options(player_idx, game), reserve, game, market);
++        let Some((best, _)) = ranked.first() else {
++            return;
++        };
++        if best.apply(player_idx, game, market).is_err() {
++            return;
++        }
++    }
++}
++
++/// What `offer` is worth to `player_idx`: shops count for more in districts
++/// where the player owns other shops.
++pub fn offer_value(
++    player_idx: usize,
++    offer: &TradeOffer,
++    game: &Game,
++    market: &StockMarket,
++) -> i32 {
++    let shops: i32 = offer
++        .shops
++        .iter()
++        .map(|index| shop_value_to(player_idx, *index, game))
++        .sum();
++    let stocks: i32 = offer
++        .stocks
++        .iter()
++        .map(|(district, shares)| shares * market.price(*district))
++        .sum();
++    offer.cash + shops + stocks
++}
++
++/// A bot takes a trade that gains it at least `margin_percent` over what it
++/// gives up; otherwise it asks for the difference in cash if the proposer
++/// can pay it.
++pub fn evaluate_trade_with_margin(
++    proposal: &TradeProposal,
++    margin_percent: i32,
++    game: &Game,
++    market: &StockMarket,
++) -> TradeResponse {
++    let bot = proposal.to;
++    if game.players[bot].brain().is_none()
++        || !can_hand_over(bot, &proposal.take, game)
++        || !can_receive(bot, &proposal.give, game)
++    {
++        return TradeResponse::Reject;
++    }
++    let gain = offer_value(bot, &proposal.give, game, market);
++    let wanted = offer_value(bot, &proposal.take, game, market) * (100 + margin_percent) / 100;
++    if wanted == 0 {
++        return TradeResponse::Reject;
++    }
++    if gain >= wanted {
++        return TradeResponse::Accept;
++    }
++    let shortfall = (wanted - gain + TRADE_CASH_STEP - 1) / TRADE_CASH_STEP * TRADE_CASH_STEP;
++    let mut counter = proposal.clone();
++    counter.give.cash += shortfall;
++    if can_hand_over(counter.from, &counter.give, game) {
++        TradeResponse::Counter(counter)
++    } else {
++        TradeResponse::Reject
++    }
++}
++
++/// Cash for the one shop `player_idx` is missing from a district it otherwise
++/// owns outright, priced at what the shop is worth to its owner plus
++/// `premium_percent`. Only offered if the bot keeps `reserve` after paying
++/// and the monopoly raises its projected net worth by more than the price;
++/// with several such districts, the biggest gain wins.
++pub fn monopoly_offer(
++    player_idx: usize,
++    premium_percent: i32,
++    reserve: i32,
++    game: &Game,
++    market: &StockMarket,
++) -> Option<TradeProposal> {
++    let player = &game.players[player_idx];
++    let projected = projected_net_worth(player_idx, game, market);
++    game.district_ids()
++        .into_iter()
++        .filter_map(|district| {
++            let size = game.district_size(district);
++            if size < 2 || player.shops_in_district(district, &game.board) + 1 != size {
++                return None;
++            }
++            let missing = (0..game.board.len()).find(|index| {
++                matches!(game.board[*index].kind, TileKind::Property { district: d, .. } if d == district)
++                    && !player.properties.contains(index)
++            })?;
++            let owner = game.owner_of(missing)?;
++            let value = shop_value_to(owner, missing, game);
++            let cash = (value * (100 + premium_percent) / 100 + TRADE_CASH_STEP - 1)
++                / TRADE_CASH_STEP
++                * TRADE_CASH_STEP;
++            if player.cash - cash < reserve {
++                return None;
++            }
++            let proposal = TradeProposal {
++                from: player_idx,
++                to: owner,
++                give: TradeOffer {
++                    cash,
++                    ..Default::default()
++                },
++                take: TradeOffer {
++                    shops: vec![missing],
++                    ..Default::default()
++                },
++            };
++            let mut trial = game.clone();
++            execute_trade(&proposal, &mut trial).ok()?;
++            let gain = projected_net_worth(player_idx, &trial, market) - projected;
++            (gain > 0).then_some((gain, proposal))
++        })
++        .max_by_key(|(gain, _)| *gain)
++        .map(|(_, proposal)| proposal)
++}
++
++/// Puts the bot's trade proposal to its partner once the bot is off its
++/// trade cooldown, and returns it to be answered: by `bot_accepts_trade` for
++/// a bot partner, or by the human themselves.
++pub fn bot_propose_trade(
++    player_idx: usize,
++    game: &mut Game,
++    market: &StockMarket,
++) -> Option<TradeProposal> {
++    let player = &mut game.players[player_idx];
++    if player.trade_cooldown > 0 {
++        player.trade_cooldown -= 1;
++        return None;
++    }
++    if game.rules.bot_blunders() {
++        return None;
++    }
++    let proposal = brain_of(player_idx, game).propose_trade(player_idx, game, market)?;
++    game.players[player_idx].trade_cooldown = BOT_TRADE_COOLDOWN;
++    game.pending_events.push(GameEvent::ProposedTrade {
++        from: proposal.from,
++        to: proposal.to,
++        give: proposal.give.describe(game),
++        take: proposal.take.describe(game),
++    });
++    Some(proposal)
++}
++
++/// Whether the bot `proposal` is put to takes it as it stands, or `None` if
++/// a human has to answer it.
++pub fn bot_accepts_trade(
++    proposal: &TradeProposal,
++    game: &Game,
++    market: &StockMarket,
++) -> Option<bool> {
++    let brain = game.players[proposal.to].brain()?;
++    Some(matches!(
++        brain.evaluate_trade(proposal, game, market),
++        TradeResponse::Accept
++    ))
++}
++
++/// Picks a junction exit for a bot by scoring where each branch would leave
++/// it with the remaining steps.
++pub fn bot_branch(
++    player_idx: usize,
++    exits: &[usize],
++    steps_remaining: usize,
++    game: &Game,
++    market: &StockMarket,
++) -> usize {
++    let player = &game.players[player_idx];
++    let score = |tile_index: usize| -> i32 {
++        match &game.board[tile_index].kind {
++            TileKind::Bank if player.suits.len() == 4 => 200,
++            TileKind::Bank
++                if player.suits.len() == 3
++                    && game.rules.house.bank_landing == BankLanding::FreeSuit =>
++            {
++                150
++            }
++            TileKind::Bank => 0,
++            TileKind::Suit(suit) if !player.suits.contains(suit) => 60,
++            TileKind::Suit(_) => match game.rules.house.duplicate_suits {
++                DuplicateSuit::Nothing => 0,
++                DuplicateSuit::CashBonus => DUPLICATE_SUIT_BONUS,
++                DuplicateSuit::SuitCard if player.suits.len() < 4 => 40,
++                DuplicateSuit::SuitCard => 10,
++            },
++            TileKind::SuitYourself if player.suits.len() < 4 => 40,
++            TileKind::SuitYourself => 10,
++            TileKind::Chance => 10,
++            TileKind::TakeABreak => -50,
++            TileKind::Arcade => 30,
++            TileKind::Casino => 0,
++            TileKind::Warp { .. } => 20,
++            TileKind::Boon => BOON_LAND_CASH,
++            TileKind::TollGate { toll } => -toll,
++            TileKind::TaxOffice => -player.cash.max(Money::ZERO).percent(TAX_OFFICE_PERCENT),
++            TileKind::Property { price, .. } => match game.owner_of(tile_index) {
++                None if player.cash >= *price => price / 10,
++                None => 0,
++                Some(owner) if owner == player_idx => 5,
++                Some(_) => -game.shop_fee(tile_index, market).unwrap_or(0),
++            },
++        }
++    };
++    exits
++        .iter()
++        .copied()
++        .max_by_key(|exit| score(landing_tile(*exit, steps_remaining - 1, game)))
++        .unwrap_or(exits[0])
++}
++
++/// A shop's price plus a premium for every other shop `player_idx` owns in
++/// the same district.
++pub fn shop_value_to(player_idx: usize, tile_index: usize, game: &Game) -> i32 {
++    let TileKind::Property {
++        district, price, ..
++    } = game.board[tile_index].kind
++    else {
++        return 0;
++    };
++    let player = &game.players[player_idx];
++    let mut owned_in_district = player.shops_in_district(district, &game.board) as i32;
++    if player.properties.contains(&tile_index) {
++        owned_in_district -= 1;
++    }
++    price + price / 5 * owned_in_district
++}
++
++/// Sells shares, biggest holding first, until the bot has `min_cash` again
++/// or nothing left to sell.
++pub fn sell_shares_for_cash(
++    player_idx: usize,
++    min_cash: i32,
++    game: &mut Game,
++    market: &mut StockMarket,
++) {
++    while game.players[player_idx].cash < min_cash {
++        let biggest = game.players[player_idx]
++            .stocks
++            .iter()
++            .filter(|(_, shares)| **shares >= MIN_SHARE_ORDER)
++            .max_by_key(|(district, shares)| (**shares, **district))
++            .map(|(district, _)| *district);
++        let Some(district) = biggest else {
++            return;
++        };
++        if sell_stock(player_idx, district, MIN_SHARE_ORDER, game, market).is_err() {
++            return;
++        }
++    }
++}
++
++/// Runs a bot's stock phase: shares first, then loans and shop sales to get
++/// back above `BOT_CASH_RESERVE`, then investment.
++pub fn bot_stock_phase(player_idx: usize, game: &mut Game, market: &mut StockMarket) {
++    let brain = brain_of(player_idx, game);
++    brain.trade_stocks(player_idx, game, market);
++    bot_raise_cash(player_idx, game, market);
++    if !game.rules.bot_blunders() {
++        brain.invest(player_idx, game, market);
++    }
++}
++
++/// Brings a bot back up to `BOT_CASH_RESERVE`: it borrows what the bank will
++/// lend, then sells its least valuable shops back to the bank. Bots with cash
++/// to spare pay their loan off.
++pub fn bot_raise_cash(player_idx: usize, game: &mut Game, market: &mut StockMarket) {
++    let player = &mut game.players[player_idx];
++    if player.cash > LOAN_CASH_CEILING + BOT_CASH_RESERVE {
++        player.repay((player.cash - LOAN_CASH_CEILING - BOT_CASH_RESERVE).get());
++    } else if player.cash < BOT_CASH_RESERVE {
++        player.borrow(BOT_CASH_RESERVE - player.cash.get(), &game.board, market);
++    }
++    while game.players[player_idx].cash < BOT_CASH_RESERVE {
++        let cheapest = game.players[player_idx]
++            .properties
++            .iter()
++            .copied()
++            .min_by_key(|index| (shop_value_to(player_idx, *index, game), *index));
++        let Some(tile_index) = cheapest else {
++            return;
++        };
++        let _ = sell_shop_to_bank(tile_index, player_idx, game, market);
++    }
++}
++
++/// Bots skip the arcade mini-games and take a random roulette prize instead.
++pub fn bot_arcade_prize() -> ArcadeReward {
++    *ROULETTE_SLOTS
++        .choose(&mut rand::thread_rng())
++        .expect("roulette has slots")
++}
++
++/// Bots holding at least half the starting cash place a single casino bet of
++/// a tenth of their cash on a random side.
++pub fn bot_casino_bet(player_idx: usize, game: &Game) -> Option<TurnAction> {
++    let cash = game.players[player_idx].cash.get();
++    let stake = (cash / 10 / CASINO_STAKE_STEP * CASINO_STAKE_STEP).min(CASINO_VISIT_CAP);
++    (cash >= game.rules.starting_cash / 2 && stake > 0).then(|| TurnAction::HighLow {
++        bet_high: rand::thread_rng().gen_bool(0.5),
++        stake,
++    })
++}
@@ -0,0 +1,194 @@
+diff --git a/src/art.rs b/src/art.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..118003d116be52931c7c509a549784961809dea5
+--- /dev/null
++++ b/src/art.rs
+@@ -0,0 +1,188 @@
++//! Sprite art for the board and sidebar. Tile faces come from one texture
++//! atlas, with shops drawn by their district's theme and tinted in its color;
++//! character portraits and tokens come from another. Until an atlas has
++//! loaded, or when it is missing, what it draws keeps its flat colors.
++
++use bevy::prelude::*;
++
++use crate::*;
++
++/// The tile atlas, relative to the assets folder.
++pub const TILE_ATLAS_PATH: &str = "textures/tiles.png";
++
++/// Width and height of one atlas cell, in pixels.
++pub const TILE_ATLAS_CELL: f32 = 64.0;
++
++pub const TILE_ATLAS_COLUMNS: usize = 8;
++
++pub const TILE_ATLAS_ROWS: usize = 2;
++
++/// Shop themes the atlas has art for, in atlas order from
++/// `SHOP_ATLAS_START`. Districts with any other theme use the first.
++pub const SHOP_THEMES: [&str; 4] = ["town", "harbor", "market", "garden"];
++
++/// Atlas cell of the first shop theme.
++pub const SHOP_ATLAS_START: usize = 12;
++
++/// The character atlas: a row of portraits over a row of tokens, one column
++/// per entry of `CHARACTERS`.
++pub const CHARACTER_ATLAS_PATH: &str = "textures/characters.png";
++
++pub const CHARACTER_ATLAS_CELL: f32 = 64.0;
++
++/// Width and height of a token drawn with its art.
++pub const TOKEN_ART_SIZE: f32 = 28.0;
++
++/// Width and height of a portrait in the sidebar.
++pub const PORTRAIT_SIZE: f32 = 40.0;
++
++#[derive(Resource)]
++pub struct TileArt {
++    pub image: Handle<Image>,
++    pub layout: Handle<TextureAtlasLayout>,
++}
++
++#[derive(Resource)]
++pub struct CharacterArt {
++    pub image: Handle<Image>,
++    pub layout: Handle<TextureAtlasLayout>,
++}
++
++/// A player's portrait in the sidebar.
++#[derive(Component)]
++pub struct PlayerPortrait(pub usize);
++
++/// Starts loading the tile and character atlases.
++pub fn load_art(
++    mut commands: Commands,
++    asset_server: Res<AssetServer>,
++    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
++) {
++    let layout = TextureAtlasLayout::from_grid(
++        Vec2::splat(TILE_ATLAS_CELL),
++        TILE_ATLAS_COLUMNS,
++        TILE_ATLAS_ROWS,
++        None,
++        None,
++    );
++    commands.insert_resource(TileArt {
++        image: asset_server.load(TILE_ATLAS_PATH),
++        layout: layouts.add(layout),
++    });
++    let layout = TextureAtlasLayout::from_grid(
++        Vec2::splat(CHARACTER_ATLAS_CELL),
++        CHARACTERS.len(),
++        2,
++        None,
++        None,
++    );
++    commands.insert_resource(CharacterArt {
++        image: asset_server.load(CHARACTER_ATLAS_PATH),
++        layout: layouts.add(layout),
++    });
++}
++
++/// Atlas cell drawn for tile `index`, or `None` for boon squares and toll
++/// gates, which the atlas has no art for and keep their flat colors.
++pub fn tile_atlas_index(game: &Game, index: usize) -> Option<usize> {
++    let cell = match &game.board[index].kind {
++        TileKind::Bank => 0,
++        TileKind::Suit(Suit::Spade) => 1,
++        TileKind::Suit(Suit::Heart) => 2,
++        TileKind::Suit(Suit::Diamond) => 3,
++        TileKind::Suit(Suit::Club) => 4,
++        TileKind::SuitYourself => 5,
++        TileKind::Chance => 6,
++        TileKind::TakeABreak => 7,
++        TileKind::TaxOffice => 8,
++        TileKind::Arcade => 9,
++        TileKind::Casino => 10,
++        TileKind::Warp { .. } => 11,
++        TileKind::Property { district, .. } => {
++            let theme = &game.districts[*district].theme;
++            SHOP_ATLAS_START
++                + SHOP_THEMES
++                    .iter()
++                    .position(|known| known == theme)
++                    .unwrap_or_default()
++        }
++        TileKind::Boon | TileKind::TollGate { .. } => return None,
++    };
++    Some(cell)
++}
++
++/// Tint of tile `index`'s atlas art: shops take their district's color in
++/// `palette`.
++pub fn tile_tint(game: &Game, index: usize, palette: Palette) -> Color {
++    match game.board[index].kind {
++        TileKind::Property { .. } => palette.tile_color(game, index),
++        _ => Color::WHITE,
++    }
++}
++
++/// Swaps flat tile sprites for their atlas art once the atlas has loaded, so
++/// boards spawned before then catch up and a missing atlas leaves the colors
++/// alone.
++pub fn apply_tile_art(
++    mut commands: Commands,
++    asset_server: Res<AssetServer>,
++    art: Res<TileArt>,
++    game: Res<Game>,
++    settings: Res<Settings>,
++    mut tiles: Query<(Entity, &TileEntity, &mut Sprite), Without<TextureAtlas>>,
++) {
++    if tiles.is_empty() || !asset_server.is_loaded_with_dependencies(&art.image) {
++        return;
++    }
++    for (entity, tile, mut sprite) in tiles.iter_mut() {
++        let Some(index) = tile_atlas_index(&game, tile.0) else {
++            continue;
++        };
++        sprite.color = tile_tint(&game, tile.0, settings.palette);
++        commands.entity(entity).insert((
++            art.image.clone(),
++            TextureAtlas {
++                layout: art.layout.clone(),
++                index,
++            },
++        ));
++    }
++}
++
++/// Swaps flat tokens and portraits for the players' character art once the
++/// atlas has loaded, the same way `apply_tile_art` does for tiles.
++pub fn apply_character_art(
++    mut commands: Commands,
++    asset_server: Res<AssetServer>,
++    art: Res<CharacterArt>,
++    game: Res<Game>,
++    mut tokens: Query<(Entity, &PlayerToken, &mut Sprite), Without<TextureAtlas>>,
++    mut portraits: Query<(Entity, &PlayerPortrait, &mut BackgroundColor), Without<TextureAtlas>>,
++) {
++    if (tokens.is_empty() && portraits.is_empty())
++        || !asset_server.is_loaded_with_dependencies(&art.image)
++    {
++        return;
++    }
++    for (entity, token, mut sprite) in tokens.iter_mut() {
++        sprite.color = Color::WHITE;
++        sprite.custom_size = Some(Vec2::splat(TOKEN_ART_SIZE));
++        commands.entity(entity).insert((
++            art.image.clone(),
++            TextureAtlas {
++                layout: art.layout.clone(),
++                index: CHARACTERS.len() + game.players[token.0].character,
++            },
++        ));
++    }
++    for (entity, portrait, mut background) in portraits.iter_mut() {
++        background.0 = Color::WHITE;
++        commands.entity(entity).insert((
++            UiImage::new(art.image.clone()),
++            TextureAtlas {
++                layout: art.layout.clone(),
++                index: game.players[portrait.0].character,
++            },
++        ));
++    }
++}
@@ -0,0 +1,123 @@
+diff --git a/src/audio.rs b/src/audio.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..847c41e18806a028acd8de48d065b2f745faa8c3
+--- /dev/null
++++ b/src/audio.rs
+@@ -0,0 +1,117 @@
++//! Music and sound effects. Background music loops for the whole session,
++//! and game events each cue a `Sound`. The master volume and the music and
++//! effects channels in `Settings` set how loud they play. Playback goes
++//! through bevy_audio, behind the `audio` feature; without it sounds are
++//! still cued but nothing plays.
++
++use bevy::prelude::*;
++
++use crate::*;
++
++/// The music track, relative to the assets folder.
++pub const MUSIC_PATH: &str = "audio/music.wav";
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq)]
++pub enum Sound {
++    DiceRoll,
++    Purchase,
++    FeePaid,
++    SuitCollected,
++    Salary,
++}
++
++impl Sound {
++    /// The sound's file, relative to the assets folder.
++    pub fn path(self) -> &'static str {
++        match self {
++            Sound::DiceRoll => "audio/dice.wav",
++            Sound::Purchase => "audio/purchase.wav",
++            Sound::FeePaid => "audio/fee.wav",
++            Sound::SuitCollected => "audio/suit.wav",
++            Sound::Salary => "audio/salary.wav",
++        }
++    }
++}
++
++/// Asks for `Sound` to be played once.
++#[derive(Event, Debug, Clone, Copy)]
++pub struct PlaySound(pub Sound);
++
++/// The entity playing the music.
++#[derive(Component)]
++pub struct Music;
++
++/// Cues a sound for dice starting to tumble, shops bought, fees paid, suits
++/// picked up and salaries paid.
++pub fn cue_sounds(
++    dice: Query<(), Added<Die>>,
++    mut game_events: EventReader<GameEvent>,
++    mut salary_events: EventReader<SalaryPaid>,
++    mut sounds: EventWriter<PlaySound>,
++) {
++    if !dice.is_empty() {
++        sounds.send(PlaySound(Sound::DiceRoll));
++    }
++    for event in game_events.read() {
++        let sound = match event {
++            GameEvent::BoughtShop { .. } => Sound::Purchase,
++            GameEvent::PaidFee { .. } | GameEvent::PaidToll { .. } => Sound::FeePaid,
++            GameEvent::CollectedSuit { .. }
++            | GameEvent::GotSuitCard { .. }
++            | GameEvent::DuplicateSuitBonus { .. } => Sound::SuitCollected,
++            _ => continue,
++        };
++        sounds.send(PlaySound(sound));
++    }
++    for _ in salary_events.read() {
++        sounds.send(PlaySound(Sound::Salary));
++    }
++}
++
++#[cfg(feature = "audio")]
++pub fn start_music(
++    mut commands: Commands,
++    asset_server: Res<AssetServer>,
++    settings: Res<Settings>,
++) {
++    use bevy::audio::{PlaybackSettings, Volume};
++
++    commands.spawn((
++        AudioBundle {
++            source: asset_server.load(MUSIC_PATH),
++            settings: PlaybackSettings::LOOP.with_volume(Volume::new(settings.music_level())),
++        },
++        Music,
++    ));
++}
++
++/// Plays each cued sound at the effects volume.
++#[cfg(feature = "audio")]
++pub fn play_sounds(
++    mut commands: Commands,
++    asset_server: Res<AssetServer>,
++    settings: Res<Settings>,
++    mut sounds: EventReader<PlaySound>,
++) {
++    use bevy::audio::{PlaybackSettings, Volume};
++
++    for PlaySound(sound) in sounds.read() {
++        commands.spawn(AudioBundle {
++            source: asset_server.load(sound.path()),
++            settings: PlaybackSettings::DESPAWN.with_volume(Volume::new(settings.effects_level())),
++        });
++    }
++}
++
++/// Follows the music volume as it is changed in the settings menu.
++#[cfg(feature = "audio")]
++pub fn apply_music_volume(settings: Res<Settings>, sinks: Query<&AudioSink, With<Music>>) {
++    use bevy::audio::AudioSinkPlayback;
++
++    if !settings.is_changed() {
++        return;
++    }
++    for sink in sinks.iter() {
++        sink.set_volume(settings.music_level());
++    }
++}
@@ -0,0 +1,2071 @@
+diff --git a/src/board.rs b/src/board.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..0a3453afbed7ff76098849adbd946cdf108e9e70
+--- /dev/null
++++ b/src/board.rs
+@@ -0,0 +1,2065 @@
++//! Board layout, tiles and what happens when a player lands on them, plus
++//! the `Game` state that ties the board and players together.
++
++use bevy::{
++    asset::{AssetLoader, AsyncReadExt, LoadContext, LoadedFolder, io::Reader},
++    prelude::*,
++    utils::BoxedFuture,
++};
++use rand::{Rng, seq::SliceRandom};
++use serde::{Deserialize, Serialize};
++use std::collections::{HashMap, HashSet, VecDeque};
++
++use crate::*;
++
++pub const TILE_SIZE: f32 = 48.0;
++
++/// Folder scanned for `*.board.ron` files, relative to the `assets` directory.
++pub const BOARDS_FOLDER: &str = "boards";
++
++/// Fee multiplier for an owner holding every shop in a district.
++pub const MONOPOLY_FEE_MULTIPLIER: f32 = 2.0;
++
++/// Fee bonus for each additional shop an owner holds in a district.
++pub const DISTRICT_FEE_BONUS: f32 = 0.25;
++
++/// Share of cash taken by the tax office, in percent.
++pub const TAX_OFFICE_PERCENT: i32 = 10;
++
++/// Paid every time a player passes the bank, on top of any promotion salary.
++pub const LAP_BONUS: i32 = 100;
++
++/// Paid by a boon square to a player passing over it.
++pub const BOON_PASS_CASH: i32 = 30;
++
++/// Paid by a boon square to a player landing on it.
++pub const BOON_LAND_CASH: i32 = 100;
++
++/// Smallest board the validation accepts.
++pub const MIN_BOARD_TILES: usize = 8;
++
++/// Fewest shops a district may have, so there is a monopoly to fight over.
++pub const MIN_DISTRICT_SHOPS: usize = 2;
++
++/// Fewest steps a lap of the board may take, so a single die roll can't
++/// carry a player round.
++pub const MIN_LAP_TILES: usize = 6;
++
++/// Most districts a board may have: one per number key in the stocks menu.
++pub const MAX_DISTRICTS: usize = 9;
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
++pub enum Suit {
++    Spade,
++    Heart,
++    Diamond,
++    Club,
++}
++
++/// The four suits, in the order they are shown.
++pub const SUITS: [Suit; 4] = [Suit::Spade, Suit::Heart, Suit::Diamond, Suit::Club];
++
++impl Suit {
++    pub fn icon(&self) -> &'static str {
++        match self {
++            Suit::Spade => "\u{2660}",
++            Suit::Heart => "\u{2665}",
++            Suit::Diamond => "\u{2666}",
++            Suit::Club => "\u{2663}",
++        }
++    }
++}
++
++/// Index of a district in `Game::districts`.
++pub type DistrictId = usize;
++
++/// Colors handed to districts, in order, when the board file doesn't pick
++/// one.
++pub const DISTRICT_COLORS: [Color; MAX_DISTRICTS] = [
++    Color::rgb(0.25, 0.7, 0.45),
++    Color::rgb(0.3, 0.55, 0.85),
++    Color::rgb(0.85, 0.55, 0.25),
++    Color::rgb(0.7, 0.35, 0.7),
++    Color::rgb(0.75, 0.7, 0.3),
++    Color::rgb(0.3, 0.7, 0.7),
++    Color::rgb(0.8, 0.4, 0.45),
++    Color::rgb(0.5, 0.6, 0.3),
++    Color::rgb(0.55, 0.5, 0.8),
++];
++
++/// Theme of districts whose board file doesn't name one.
++pub const DEFAULT_DISTRICT_THEME: &str = "town";
++
++/// A group of shops that share a stock. Tiles, holdings and the market refer
++/// to districts by id.
++#[derive(Debug, Clone, PartialEq)]
++pub struct District {
++    pub id: DistrictId,
++    pub name: String,
++    /// Color of the district's shop tiles.
++    pub color: Color,
++    /// Art set the district's shops are drawn from.
++    pub theme: String,
++}
++
++#[derive(Debug, Clone)]
++pub enum TileKind {
++    Bank,
++    Property {
++        district: DistrictId,
++        price: i32,
++        base_fee: i32,
++    },
++    Suit(Suit),
++    /// Grants a Suit Yourself card.
++    SuitYourself,
++    Chance,
++    /// The player sits out their next turn.
++    TakeABreak,
++    /// The player pays `TAX_OFFICE_PERCENT` of their cash.
++    TaxOffice,
++    /// Launches a mini-game with cash or suit prizes.
++    Arcade,
++    /// High/low dice betting against the house.
++    Casino,
++    /// Sends the landing player to the `destination` tile, which then resolves.
++    Warp {
++        destination: usize,
++    },
++    /// Pays `BOON_PASS_CASH` to players passing and `BOON_LAND_CASH` to the
++    /// player landing.
++    Boon,
++    /// Charges `toll` to every player passing or landing.
++    TollGate {
++        toll: i32,
++    },
++}
++
++impl Game {
++    /// Short name of tile `index`, e.g. its district for a shop.
++    pub fn tile_label(&self, index: usize) -> String {
++        match &self.board[index].kind {
++            TileKind::Bank => "Bank".to_string(),
++            TileKind::Property { district, .. } => self.district_name(*district).to_string(),
++            TileKind::Suit(suit) => format!("{} Suit", suit.icon()),
++            TileKind::SuitYourself => "Wild".to_string(),
++            TileKind::Chance => "Chance".to_string(),
++            TileKind::TakeABreak => "Break".to_string(),
++            TileKind::TaxOffice => "Tax".to_string(),
++            TileKind::Arcade => "Arcade".to_string(),
++            TileKind::Casino => "Casino".to_string(),
++            TileKind::Warp { .. } => "Warp".to_string(),
++            TileKind::Boon => "Boon".to_string(),
++            TileKind::TollGate { toll } => format!("Toll {}", Money(*toll)),
++        }
++    }
++}
++
++#[derive(Debug, Clone)]
++pub struct Tile {
++    pub index: usize,
++    /// Grid cell from the board definition, kept so the layout can be saved.
++    pub cell: (i32, i32),
++    pub position: Vec2,
++    pub kind: TileKind,
++    /// Tiles a player can step to from here; more than one makes a junction.
++    pub next: Vec<usize>,
++    /// Capital the owner has invested in this shop on top of its price.
++    pub capital: i32,
++    /// Run for whoever stops here, after the tile's own effect.
++    pub script: Option<Script>,
++}
++
++impl Tile {
++    /// A shop's price plus the capital invested in it; zero for other tiles.
++    pub fn value(&self) -> i32 {
++        match self.kind {
++            TileKind::Property { price, .. } => price + self.capital,
++            _ => 0,
++        }
++    }
++
++    /// Capital that can still be invested before reaching the cap.
++    pub fn capital_room(&self) -> i32 {
++        match self.kind {
++            TileKind::Property { price, .. } => {
++                price * MAX_SHOP_CAPITAL_PERCENT / 100 - self.capital
++            }
++            _ => 0,
++        }
++    }
++
++    /// The definition this tile was built from, for writing the board back out.
++    pub fn to_definition(&self, districts: &[District]) -> TileDefinition {
++        let kind = match &self.kind {
++            TileKind::Bank => TileKindDefinition::Bank,
++            TileKind::Property {
++                district,
++                price,
++                base_fee,
++            } => TileKindDefinition::Property {
++                district: districts[*district].name.clone(),
++                price: *price,
++                base_fee: *base_fee,
++            },
++            TileKind::Suit(suit) => TileKindDefinition::Suit(*suit),
++            TileKind::SuitYourself => TileKindDefinition::SuitYourself,
++            TileKind::Chance => TileKindDefinition::Chance,
++            TileKind::TakeABreak => TileKindDefinition::TakeABreak,
++            TileKind::TaxOffice => TileKindDefinition::TaxOffice,
++            TileKind::Arcade => TileKindDefinition::Arcade,
++            TileKind::Casino => TileKindDefinition::Casino,
++            TileKind::Warp { destination } => TileKindDefinition::Warp {
++                destination: *destination,
++            },
++            TileKind::Boon => TileKindDefinition::Boon,
++            TileKind::TollGate { toll } => TileKindDefinition::TollGate { toll: *toll },
++        };
++        TileDefinition {
++            kind,
++            cell: self.cell,
++            next: self.next.clone(),
++            script: self
++                .script
++                .as_ref()
++                .map(|script| script.source().to_string()),
++        }
++    }
++}
++
++#[derive(Resource, Clone)]
++pub struct Game {
++    /// Name of the board definition the match is played on.
++    pub board_name: String,
++    pub board: Vec<Tile>,
++    /// The board's districts, by id.
++    pub districts: Vec<District>,
++    /// Venture cards the board adds to the deck; `VentureEffect::Script`
++    /// indexes them.
++    pub board_cards: Vec<BoardCard>,
++    pub players: Vec<PlayerState>,
++    pub current_turn: usize,
++    /// Seats in the order they take their turns, as the opening roll settled
++    /// it.
++    pub turn_order: Vec<usize>,
++    pub district_shop_count: HashMap<DistrictId, usize>,
++    /// Vacant shop the active human landed on and has not yet accepted or declined.
++    pub pending_purchase: Option<usize>,
++    /// Vacant shop the active player declined or could not afford; it goes to auction.
++    pub declined_shop: Option<usize>,
++    /// Promotions not yet published as `SalaryPaid` events.
++    pub pending_salaries: Vec<SalaryPaid>,
++    /// Happenings not yet published as `GameEvent`s for the event log.
++    pub pending_events: Vec<GameEvent>,
++    /// Shops that changed hands, not yet published as `OwnershipChanged` events.
++    pub pending_ownership: Vec<OwnershipChanged>,
++    /// Shops invested in, not yet published as `InvestmentChanged` events.
++    pub pending_investments: Vec<InvestmentChanged>,
++    /// Net worth a player must reach and then bring back to the bank to win.
++    pub target_net_worth: i32,
++    pub rules: GameRules,
++    pub winner: Option<usize>,
++}
++
++impl Game {
++    pub fn new(
++        definition: &BoardDefinition,
++        seats: &[Seat],
++        target_net_worth: i32,
++        rules: GameRules,
++    ) -> Self {
++        let (board, districts) = definition.to_board();
++        let players = seats
++            .iter()
++            .map(|seat| PlayerState {
++                name: seat.name.clone(),
++                kind: seat.kind,
++                strategy: seat.strategy,
++                decider: seat.kind.decider(),
++                character: seat.character,
++                cash: Money(rules.starting_cash),
++                ..Default::default()
++            })
++            .collect();
++        Self {
++            board_name: definition.name.clone(),
++            board,
++            districts,
++            board_cards: definition.to_board_cards(),
++            players,
++            current_turn: 0,
++            turn_order: (0..seats.len()).collect(),
++            district_shop_count: HashMap::new(),
++            pending_purchase: None,
++            declined_shop: None,
++            pending_salaries: Vec::new(),
++            pending_events: Vec::new(),
++            pending_ownership: Vec::new(),
++            pending_investments: Vec::new(),
++            target_net_worth,
++            rules,
++            winner: None,
++        }
++    }
++
++    /// Every district's id, in the board's order.
++    pub fn district_ids(&self) -> Vec<DistrictId> {
++        self.districts.iter().map(|district| district.id).collect()
++    }
++
++    pub fn district_name(&self, district: DistrictId) -> &str {
++        &self.districts[district].name
++    }
++
++    /// The board as a board file, with its districts, for saves.
++    pub fn board_definition(&self) -> BoardDefinition {
++        BoardDefinition {
++            name: self.board_name.clone(),
++            districts: self
++                .districts
++                .iter()
++                .map(|district| DistrictDefinition {
++                    name: district.name.clone(),
++                    color: Some([district.color.r(), district.color.g(), district.color.b()]),
++                    theme: Some(district.theme.clone()),
++                })
++                .collect(),
++            tiles: self
++                .board
++                .iter()
++                .map(|tile| tile.to_definition(&self.districts))
++                .collect(),
++            venture_cards: self
++                .board_cards
++                .iter()
++                .map(|card| VentureCardDefinition {
++                    title: card.title.clone(),
++                    script: card.script.source().to_string(),
++                })
++                .collect(),
++        }
++    }
++
++    /// The area the tile sprites cover, in world units.
++    pub fn bounds(&self) -> Rect {
++        self.board
++            .iter()
++            .map(|tile| Rect::from_center_size(tile.position, Vec2::splat(TILE_SIZE)))
++            .reduce(|bounds, tile| bounds.union(tile))
++            .unwrap_or_default()
++    }
++
++    /// Plays the match in `order`, starting with its first seat.
++    pub fn set_turn_order(&mut self, order: Vec<usize>) {
++        self.current_turn = order.first().copied().unwrap_or(0);
++        self.turn_order = order;
++    }
++
++    /// Passes the turn to the next player still in the game. Players on a
++    /// break sit this turn out instead.
++    pub fn end_turn(&mut self) {
++        let mut slot = self
++            .turn_order
++            .iter()
++            .position(|seat| *seat == self.current_turn)
++            .unwrap_or(0);
++        for _ in 0..self.players.len() * 2 {
++            slot = (slot + 1) % self.turn_order.len();
++            self.current_turn = self.turn_order[slot];
++            let player = &mut self.players[self.current_turn];
++            if player.bankrupt {
++                continue;
++            }
++            player.double_fees = false;
++            if !std::mem::take(&mut player.on_break) {
++                break;
++            }
++        }
++        self.players[self.current_turn].settle_decider();
++    }
++
++    /// Sends a shop the active player passed on to auction, if the house
++    /// rules hold auctions.
++    pub fn decline_shop(&mut self, tile_index: usize) {
++        if self.rules.house.auctions_on_decline {
++            self.declined_shop = Some(tile_index);
++        }
++    }
++
++    /// Records that `tile` now belongs to `owner`, or to the bank if `None`.
++    pub fn note_ownership(&mut self, tile: usize, owner: Option<usize>) {
++        self.pending_ownership
++            .push(OwnershipChanged { tile, owner });
++    }
++
++    /// Records that capital was put into `tile`.
++    pub fn note_investment(&mut self, tile: usize) {
++        let capital = self.board[tile].capital;
++        self.pending_investments
++            .push(InvestmentChanged { tile, capital });
++    }
++
++    /// Number of shop tiles in `district`.
++    pub fn district_size(&self, district: DistrictId) -> usize {
++        self.board
++            .iter()
++            .filter(
++                |tile| matches!(tile.kind, TileKind::Property { district: d, .. } if d == district),
++            )
++            .count()
++    }
++
++    pub fn owner_of(&self, tile_index: usize) -> Option<usize> {
++        self.players
++            .iter()
++            .position(|player| player.properties.contains(&tile_index))
++    }
++
++    /// How much the owner's hold on the shop's district scales its fee: a
++    /// monopoly doubles it unless the house rules say otherwise, and each
++    /// extra shop short of one adds a bonus.
++    pub fn fee_multiplier(&self, tile_index: usize) -> f32 {
++        let (TileKind::Property { district, .. }, Some(owner)) =
++            (&self.board[tile_index].kind, self.owner_of(tile_index))
++        else {
++            return 1.0;
++        };
++        let owned = self.players[owner].shops_in_district(*district, &self.board);
++        let size = self.district_size(*district);
++        if size > 1 && owned == size && self.rules.house.double_fee_on_monopoly {
++            MONOPOLY_FEE_MULTIPLIER
++        } else {
++            1.0 + DISTRICT_FEE_BONUS * owned.saturating_sub(1) as f32
++        }
++    }
++
++    /// Fee charged for landing on an owned shop, or `None` if it is vacant.
++    pub fn shop_fee(&self, tile_index: usize, market: &StockMarket) -> Option<i32> {
++        let tile = &self.board[tile_index];
++        let TileKind::Property {
++            district,
++            price,
++            base_fee,
++        } = tile.kind
++        else {
++            return None;
++        };
++        let owner = &self.players[self.owner_of(tile_index)?];
++        let multiplier = self.fee_multiplier(tile_index)
++            * market.value_multiplier(district)
++            * tile.value() as f32
++            / price as f32;
++        let fee = (base_fee as f32 * multiplier).round() as i32;
++        Some(if owner.double_fees { fee * 2 } else { fee })
++    }
++
++    pub fn bank_index(&self) -> Option<usize> {
++        self.board
++            .iter()
++            .position(|tile| matches!(tile.kind, TileKind::Bank))
++    }
++}
++
++/// Board layout as stored in `assets/boards/*.board.ron`.
++#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
++pub struct BoardDefinition {
++    pub name: String,
++    /// Looks of the districts the shops name. Districts left out get the
++    /// next of `DISTRICT_COLORS` and `DEFAULT_DISTRICT_THEME`.
++    #[serde(default, skip_serializing_if = "Vec::is_empty")]
++    pub districts: Vec<DistrictDefinition>,
++    pub tiles: Vec<TileDefinition>,
++    /// Cards the board shuffles into the venture deck alongside the usual
++    /// ones.
++    #[serde(default, skip_serializing_if = "Vec::is_empty")]
++    pub venture_cards: Vec<VentureCardDefinition>,
++}
++
++/// A venture card a board adds, its effect written as a `script`.
++#[derive(Debug, Clone, Serialize, Deserialize)]
++pub struct VentureCardDefinition {
++    pub title: String,
++    pub script: String,
++}
++
++#[derive(Debug, Clone, Serialize, Deserialize)]
++pub struct DistrictDefinition {
++    pub name: String,
++    /// Red, green and blue, from 0 to 1.
++    #[serde(default)]
++    pub color: Option<[f32; 3]>,
++    #[serde(default)]
++    pub theme: Option<String>,
++}
++
++#[derive(Debug, Clone, Serialize, Deserialize)]
++pub struct TileDefinition {
++    pub kind: TileKindDefinition,
++    /// Grid cell in tile units; the board is centred on screen.
++    pub cell: (i32, i32),
++    /// Exits from this tile. Defaults to the following tile, wrapping to the
++    /// first one after the last.
++    #[serde(default, skip_serializing_if = "Vec::is_empty")]
++    pub next: Vec<usize>,
++    /// A script run for whoever stops on the tile.
++    #[serde(default, skip_serializing_if = "Option::is_none")]
++    pub script: Option<String>,
++}
++
++#[derive(Debug, Clone, Serialize, Deserialize)]
++pub enum TileKindDefinition {
++    Bank,
++    Property {
++        district: String,
++        price: i32,
++        base_fee: i32,
++    },
++    Suit(Suit),
++    SuitYourself,
++    Chance,
++    TakeABreak,
++    TaxOffice,
++    Arcade,
++    Casino,
++    Warp {
++        destination: usize,
++    },
++    Boon,
++    TollGate {
++        toll: i32,
++    },
++}
++
++/// Why a board layout can't be played.
++#[derive(Debug, thiserror::Error)]
++pub enum BoardLayoutError {
++    #[error("a board needs at least {} tiles", MIN_BOARD_TILES)]
++    TooSmall,
++    #[error("a board needs exactly one bank, not {0}")]
++    BankCount(usize),
++    #[error("tiles {0} and {1} are on the same cell")]
++    SharedCell(usize, usize),
++    #[error("tile {0} leads to tile {1}, which doesn't exist")]
++    MissingExit(usize, usize),
++    #[error("tile {0} leads back to itself")]
++    SelfExit(usize),
++    #[error("tile {0} can't be reached from the bank")]
++    Unreachable(usize),
++    #[error("tile {0} has no way back to the bank")]
++    NoWayBack(usize),
++    #[error("shop {0} needs a positive price and fee")]
++    Unpriced(usize),
++    #[error("a board can have at most {} districts", MAX_DISTRICTS)]
++    TooManyDistricts,
++    #[error("district \"{0}\" needs at least {} shops", MIN_DISTRICT_SHOPS)]
++    LoneShop(String),
++    #[error("a board needs a {} suit tile", .0.icon())]
++    MissingSuit(Suit),
++    #[error(
++        "the bank can be lapped in {0} steps; a lap needs at least {}",
++        MIN_LAP_TILES
++    )]
++    ShortLap(usize),
++    #[error("tile {0}'s script: {1}")]
++    TileScript(usize, ScriptError),
++    #[error("venture card \"{0}\": {1}")]
++    CardScript(String, ScriptError),
++    #[error("there is already a venture card called \"{0}\"")]
++    DuplicateCard(String),
++}
++
++impl BoardDefinition {
++    /// Tiles a player can go to from tile `index`: its exits, or the
++    /// following tile, plus a warp's destination.
++    pub fn exits(&self, index: usize) -> Vec<usize> {
++        let tile = &self.tiles[index];
++        let mut exits = if tile.next.is_empty() {
++            vec![(index + 1) % self.tiles.len()]
++        } else {
++            tile.next.clone()
++        };
++        if let TileKindDefinition::Warp { destination } = tile.kind {
++            exits.push(destination);
++        }
++        exits
++    }
++
++    /// Checks that the board can be played, returning the first problem
++    /// `problems` finds.
++    pub fn validate(&self) -> Result<(), BoardLayoutError> {
++        match self.problems().into_iter().next() {
++            Some(problem) => Err(problem),
++            None => Ok(()),
++        }
++    }
++
++    /// Everything that keeps the board from being played: it needs one bank,
++    /// no stacked tiles, exits that exist, priced shops in districts of at
++    /// least `MIN_DISTRICT_SHOPS`, a tile for every suit, scripts that parse,
++    /// venture cards with titles of their own, and a path graph in which
++    /// every tile can be reached from the bank and leads back to it, with no
++    /// way round shorter than `MIN_LAP_TILES`.
++    pub fn problems(&self) -> Vec<BoardLayoutError> {
++        let mut problems = Vec::new();
++        if self.tiles.len() < MIN_BOARD_TILES {
++            problems.push(BoardLayoutError::TooSmall);
++        }
++        let banks: Vec<usize> = (0..self.tiles.len())
++            .filter(|idx| matches!(self.tiles[*idx].kind, TileKindDefinition::Bank))
++            .collect();
++        if banks.len() != 1 {
++            problems.push(BoardLayoutError::BankCount(banks.len()));
++        }
++        let mut cells = HashMap::new();
++        let mut district_shops: Vec<(&str, usize)> = Vec::new();
++        let mut suits = HashSet::new();
++        let mut exits_exist = true;
++        for (idx, tile) in self.tiles.iter().enumerate() {
++            if let Some(other) = cells.insert(tile.cell, idx) {
++                problems.push(BoardLayoutError::SharedCell(other, idx));
++            }
++            if let Some(Err(err)) = tile.script.as_deref().map(parse_script) {
++                problems.push(BoardLayoutError::TileScript(idx, err));
++            }
++            for exit in self.exits(idx) {
++                if exit >= self.tiles.len() {
++                    problems.push(BoardLayoutError::MissingExit(idx, exit));
++                    exits_exist = false;
++                }
++                if exit == idx {
++                    problems.push(BoardLayoutError::SelfExit(idx));
++                }
++            }
++            match &tile.kind {
++                TileKindDefinition::Property {
++                    district,
++                    price,
++                    base_fee,
++                } => {
++                    if *price <= 0 || *base_fee <= 0 {
++                        problems.push(BoardLayoutError::Unpriced(idx));
++                    }
++                    match district_shops.iter_mut().find(|(name, _)| name == district) {
++                        Some((_, count)) => *count += 1,
++                        None => district_shops.push((district.as_str(), 1)),
++                    }
++                }
++                TileKindDefinition::Suit(suit) => {
++                    suits.insert(*suit);
++                }
++                _ => {}
++            }
++        }
++        if district_shops.len() > MAX_DISTRICTS {
++            problems.push(BoardLayoutError::TooManyDistricts);
++        }
++        for (district, count) in &district_shops {
++            if *count < MIN_DISTRICT_SHOPS {
++                problems.push(BoardLayoutError::LoneShop(district.to_string()));
++            }
++        }
++        for suit in SUITS {
++            if !suits.contains(&suit) {
++                problems.push(BoardLayoutError::MissingSuit(suit));
++            }
++        }
++        let mut titles: HashSet<&str> = VENTURE_CARDS.iter().map(|card| card.title).collect();
++        for card in &self.venture_cards {
++            if !titles.insert(&card.title) {
++                problems.push(BoardLayoutError::DuplicateCard(card.title.clone()));
++            }
++            if let Err(err) = parse_script(&card.script) {
++                problems.push(BoardLayoutError::CardScript(card.title.clone(), err));
++            }
++        }
++        // The path checks need a single bank to start from and exits that
++        // lead somewhere.
++        let (&[bank], true) = (banks.as_slice(), exits_exist) else {
++            return problems;
++        };
++
++        let forward: Vec<Vec<usize>> = (0..self.tiles.len()).map(|idx| self.exits(idx)).collect();
++        let mut backward = vec![Vec::new(); self.tiles.len()];
++        for (idx, exits) in forward.iter().enumerate() {
++            for exit in exits {
++                backward[*exit].push(idx);
++            }
++        }
++        let reached = |edges: &[Vec<usize>]| {
++            let mut seen = vec![false; edges.len()];
++            let mut stack = vec![bank];
++            while let Some(idx) = stack.pop() {
++                if !std::mem::replace(&mut seen[idx], true) {
++                    stack.extend(&edges[idx]);
++                }
++            }
++            seen
++        };
++        if let Some(idx) = reached(&forward).iter().position(|seen| !seen) {
++            problems.push(BoardLayoutError::Unreachable(idx));
++        }
++        if let Some(idx) = reached(&backward).iter().position(|seen| !seen) {
++            problems.push(BoardLayoutError::NoWayBack(idx));
++        }
++        if let Some(lap) = self.shortest_lap(bank, &forward)
++            && lap < MIN_LAP_TILES
++        {
++            problems.push(BoardLayoutError::ShortLap(lap));
++        }
++        problems
++    }
++
++    /// Fewest steps from the bank back round to it, if there is a way.
++    fn shortest_lap(&self, bank: usize, forward: &[Vec<usize>]) -> Option<usize> {
++        let mut distance = vec![None; forward.len()];
++        let mut queue: VecDeque<(usize, usize)> =
++            forward[bank].iter().map(|exit| (*exit, 1)).collect();
++        while let Some((idx, steps)) = queue.pop_front() {
++            if idx == bank {
++                return Some(steps);
++            }
++            if distance[idx].is_some() {
++                continue;
++            }
++            distance[idx] = Some(steps);
++            queue.extend(forward[idx].iter().map(|exit| (*exit, steps + 1)));
++        }
++        None
++    }
++
++    /// The board's districts, numbered in the order the `districts` list
++    /// gives, then in the order the rest first appear on the board. Only
++    /// districts with shops are kept.
++    pub fn to_districts(&self) -> Vec<District> {
++        let mut names: Vec<&str> = Vec::new();
++        let on_board = self.tiles.iter().filter_map(|tile| match &tile.kind {
++            TileKindDefinition::Property { district, .. } => Some(district.as_str()),
++            _ => None,
++        });
++        let listed = self.districts.iter().map(|district| district.name.as_str());
++        for name in listed.chain(on_board.clone()) {
++            if !names.contains(&name) && on_board.clone().any(|used| used == name) {
++                names.push(name);
++            }
++        }
++        names
++            .into_iter()
++            .enumerate()
++            .map(|(id, name)| {
++                let listed = self.districts.iter().find(|district| district.name == name);
++                District {
++                    id,
++                    name: name.to_string(),
++                    color: listed
++                        .and_then(|district| district.color)
++                        .map_or(DISTRICT_COLORS[id % DISTRICT_COLORS.len()], |[r, g, b]| {
++                            Color::rgb(r, g, b)
++                        }),
++                    theme: listed
++                        .and_then(|district| district.theme.clone())
++                        .unwrap_or_else(|| DEFAULT_DISTRICT_THEME.to_string()),
++                }
++            })
++            .collect()
++    }
++
++    /// The tiles and districts a match on this board starts with.
++    pub fn to_board(&self) -> (Vec<Tile>, Vec<District>) {
++        let districts = self.to_districts();
++        let cells: Vec<Vec2> = self
++            .tiles
++            .iter()
++            .map(|tile| Vec2::new(tile.cell.0 as f32, tile.cell.1 as f32))
++            .collect();
++        let min = cells.iter().copied().fold(Vec2::splat(f32::MAX), Vec2::min);
++        let max = cells.iter().copied().fold(Vec2::splat(f32::MIN), Vec2::max);
++        let center = (min + max) / 2.0;
++
++        let tiles = self
++            .tiles
++            .iter()
++            .zip(cells)
++            .enumerate()
++            .map(|(index, (tile, cell))| {
++                let kind = match &tile.kind {
++                    TileKindDefinition::Bank => TileKind::Bank,
++                    TileKindDefinition::Property {
++                        district,
++                        price,
++                        base_fee,
++                    } => TileKind::Property {
++                        district: districts
++                            .iter()
++                            .position(|known| known.name == *district)
++                            .unwrap_or_default(),
++                        price: *price,
++                        base_fee: *base_fee,
++                    },
++                    TileKindDefinition::Suit(suit) => TileKind::Suit(*suit),
++                    TileKindDefinition::SuitYourself => TileKind::SuitYourself,
++                    TileKindDefinition::Chance => TileKind::Chance,
++                    TileKindDefinition::TakeABreak => TileKind::TakeABreak,
++                    TileKindDefinition::TaxOffice => TileKind::TaxOffice,
++                    TileKindDefinition::Arcade => TileKind::Arcade,
++                    TileKindDefinition::Casino => TileKind::Casino,
++                    TileKindDefinition::Warp { destination } => TileKind::Warp {
++                        destination: *destination,
++                    },
++                    TileKindDefinition::Boon => TileKind::Boon,
++                    TileKindDefinition::TollGate { toll } => TileKind::TollGate { toll: *toll },
++                };
++                let next = if tile.next.is_empty() {
++                    vec![(index + 1) % self.tiles.len()]
++                } else {
++                    tile.next.clone()
++                };
++                Tile {
++                    index,
++                    cell: tile.cell,
++                    position: (cell - center) * TILE_SIZE,
++                    kind,
++                    next,
++                    capital: 0,
++                    script: tile
++                        .script
++                        .as_deref()
++                        .and_then(|script| parse_script(script).ok()),
++                }
++            })
++            .collect();
++        (tiles, districts)
++    }
++
++    /// The venture cards the board adds, with their scripts parsed. Cards
++    /// whose scripts don't parse are left out.
++    pub fn to_board_cards(&self) -> Vec<BoardCard> {
++        self.venture_cards
++            .iter()
++            .filter_map(|card| {
++                Some(BoardCard {
++                    title: card.title.clone(),
++                    script: parse_script(&card.script).ok()?,
++                })
++            })
++            .collect()
++    }
++}
++
++#[derive(Default)]
++pub struct BoardDefinitionLoader;
++
++#[derive(Debug, thiserror::Error)]
++pub enum BoardLoadError {
++    #[error("could not read board file: {0}")]
++    Io(#[from] std::io::Error),
++    #[error("could not parse board file: {0}")]
++    Ron(#[from] ron::error::SpannedError),
++}
++
++impl AssetLoader for BoardDefinitionLoader {
++    type Asset = BoardDefinition;
++    type Settings = ();
++    type Error = BoardLoadError;
++
++    fn load<'a>(
++        &'a self,
++        reader: &'a mut Reader,
++        _settings: &'a (),
++        _load_context: &'a mut LoadContext,
++    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
++        Box::pin(async move {
++            let mut bytes = Vec::new();
++            reader.read_to_end(&mut bytes).await?;
++            Ok(ron::de::from_bytes(&bytes)?)
++        })
++    }
++
++    fn extensions(&self) -> &[&str] {
++        &["board.ron"]
++    }
++}
++
++/// Board files loaded in the browser, where the boards folder can't be
++/// listed. Keep in step with `assets/boards`.
++pub const WEB_BOARDS: [&str; 3] = [
++    "classic.board.ron",
++    "twin_harbors.board.ron",
++    "warp_gardens.board.ron",
++];
++
++/// Boards loading at startup: the whole `BOARDS_FOLDER` on desktop, or the
++/// `WEB_BOARDS` files in the browser.
++#[derive(Resource)]
++pub enum BoardSource {
++    Folder(Handle<LoadedFolder>),
++    Files(Vec<Handle<BoardDefinition>>),
++}
++
++/// Boards found under `BOARDS_FOLDER`, sorted by name.
++#[derive(Resource, Default)]
++pub struct BoardLibrary(pub Vec<Handle<BoardDefinition>>);
++
++/// Steps the player onto `to`, which must be one of their tile's `next` exits.
++/// Coming round to the bank completes a lap, pays `LAP_BONUS`, charges
++/// interest on any outstanding loan and pays every shareholder dividends.
++pub fn advance_player(player_idx: usize, to: usize, game: &mut Game) {
++    debug_assert!(
++        game.board[game.players[player_idx].position]
++            .next
++            .contains(&to)
++    );
++    let player = &mut game.players[player_idx];
++    player.position = to;
++    if matches!(game.board[to].kind, TileKind::Bank) {
++        player.laps += 1;
++        player.cash += LAP_BONUS;
++        let mut interest = 0;
++        if player.debt > 0 {
++            interest =
++                ((player.debt.get() as i64 * LOAN_INTEREST_PERCENT as i64 + 99) / 100) as i32;
++            player.debt += interest;
++        }
++        let lap = player.laps;
++        game.pending_events.push(GameEvent::CompletedLap {
++            player: player_idx,
++            lap,
++            interest,
++        });
++        pay_dividends(game);
++    }
++}
++
++/// Follows the first exit of each tile for `steps` tiles starting at `from`.
++pub fn landing_tile(from: usize, steps: usize, game: &Game) -> usize {
++    (0..steps).fold(from, |tile, _| game.board[tile].next[0])
++}
++
++/// Describes a junction's exits as arrow directions for the prompt, each with
++/// the tile the player would land on with `steps` left to walk and what
++/// landing there would do.
++pub fn junction_options(
++    from: usize,
++    steps: usize,
++    player_idx: usize,
++    game: &Game,
++    market: &StockMarket,
++) -> String {
++    let origin = game.board[from].position;
++    game.board[from]
++        .next
++        .iter()
++        .map(|exit| {
++            let delta = game.board[*exit].position - origin;
++            let arrow = if delta.x.abs() >= delta.y.abs() {
++                if delta.x > 0.0 { "Right" } else { "Left" }
++            } else if delta.y > 0.0 {
++                "Up"
++            } else {
++                "Down"
++            };
++            let landing = landing_tile(*exit, steps.saturating_sub(1), game);
++            format!(
++                "{}: {} \u{2192} {}, {}",
++                arrow,
++                game.tile_label(*exit),
++                game.tile_label(landing),
++                simulate_landing(landing, player_idx, game, market).describe(game)
++            )
++        })
++        .collect::<Vec<_>>()
++        .join(", ")
++}
++
++/// What landing on a tile would do to a player, worked out by
++/// `simulate_landing` without changing anything. `handle_tile` carries it
++/// out; the junction prompt and the venture panel preview it.
++#[derive(Debug, Clone, Copy, PartialEq, Eq)]
++pub enum Landing {
++    /// A visit to the bank; `promotion` if the player holds every suit.
++    Bank {
++        promotion: bool,
++    },
++    PayFee {
++        owner: usize,
++        district: DistrictId,
++        fee: i32,
++    },
++    /// A vacant shop the player can afford.
++    BuyOffer {
++        price: i32,
++    },
++    /// A vacant shop the player can't afford, which goes to auction.
++    CannotAfford {
++        price: i32,
++    },
++    OwnShop,
++    CollectSuit(Suit),
++    /// A suit the player already holds; the house rules pick what it gives.
++    DuplicateSuit(Suit, DuplicateSuit),
++    SuitCard,
++    VentureCard,
++    Arcade,
++    Casino,
++    Warp,
++    TakeABreak,
++    Tax(i32),
++    Boon(i32),
++    Toll(i32),
++}
++
++impl Landing {
++    /// Short description for previews, e.g. "pay 120G to Bruno".
++    pub fn describe(&self, game: &Game) -> String {
++        match self {
++            Landing::Bank { promotion: true } => "promotion at the bank".to_string(),
++            Landing::Bank { promotion: false } => "visit the bank".to_string(),
++            Landing::PayFee { owner, fee, .. } => {
++                format!("pay {} to {}", Money(*fee), game.players[*owner].name)
++            }
++            Landing::BuyOffer { price } => format!("buy for {}", Money(*price)),
++            Landing::CannotAfford { price } => format!("can't afford ({})", Money(*price)),
++            Landing::OwnShop => "your own shop".to_string(),
++            Landing::CollectSuit(suit) => format!("collect {}", suit.icon()),
++            Landing::DuplicateSuit(_, DuplicateSuit::Nothing) => "suit already held".to_string(),
++            Landing::DuplicateSuit(_, DuplicateSuit::CashBonus) => {
++                format!("{} bonus", Money(DUPLICATE_SUIT_BONUS))
++            }
++            Landing::DuplicateSuit(_, DuplicateSuit::SuitCard) | Landing::SuitCard => {
++                "Suit Yourself card".to_string()
++            }
++            Landing::VentureCard => "venture card".to_string(),
++            Landing::Arcade => "arcade".to_string(),
++            Landing::Casino => "casino".to_string(),
++            Landing::Warp => "warp".to_string(),
++            Landing::TakeABreak => "take a break".to_string(),
++            Landing::Tax(tax) => format!("pay {} tax", Money(*tax)),
++            Landing::Boon(amount) => format!("collect {}", Money(*amount)),
++            Landing::Toll(toll) => format!("pay {} toll", Money(*toll)),
++        }
++    }
++}
++
++/// Works out what landing on `tile_index` would do to the player, leaving
++/// the game untouched.
++pub fn simulate_landing(
++    tile_index: usize,
++    player_idx: usize,
++    game: &Game,
++    market: &StockMarket,
++) -> Landing {
++    let player = &game.players[player_idx];
++    match game.board[tile_index].kind {
++        TileKind::Bank => Landing::Bank {
++            promotion: player.suits.len() == 4,
++        },
++        TileKind::Property {
++            district, price, ..
++        } => match game.owner_of(tile_index) {
++            Some(owner) if owner != player_idx => {
++                let mut fee = game.shop_fee(tile_index, market).unwrap_or(0);
++                if game.rules.house.no_negative_cash {
++                    fee = fee.min(player.cash.available());
++                }
++                Landing::PayFee {
++                    owner,
++                    district,
++                    fee,
++                }
++            }
++            Some(_) => Landing::OwnShop,
++            None if player.cash >= price => Landing::BuyOffer { price },
++            None => Landing::CannotAfford { price },
++        },
++        TileKind::Suit(suit) if player.suits.contains(&suit) => {
++            Landing::DuplicateSuit(suit, game.rules.house.duplicate_suits)
++        }
++        TileKind::Suit(suit) => Landing::CollectSuit(suit),
++        TileKind::SuitYourself => Landing::SuitCard,
++        TileKind::Chance => Landing::VentureCard,
++        TileKind::Arcade => Landing::Arcade,
++        TileKind::Casino => Landing::Casino,
++        TileKind::Warp { .. } => Landing::Warp,
++        TileKind::TakeABreak => Landing::TakeABreak,
++        TileKind::TaxOffice => {
++            Landing::Tax(player.cash.max(Money::ZERO).percent(TAX_OFFICE_PERCENT))
++        }
++        TileKind::Boon => Landing::Boon(BOON_LAND_CASH),
++        TileKind::TollGate { toll } => Landing::Toll(toll_due(player_idx, toll, game)),
++    }
++}
++
++pub fn handle_tile(
++    tile_index: usize,
++    player_idx: usize,
++    game: &mut Game,
++    market: &mut StockMarket,
++) {
++    match simulate_landing(tile_index, player_idx, game, market) {
++        Landing::Bank { .. } => {
++            visit_bank(player_idx, true, game, market);
++            let player = &game.players[player_idx];
++            if player.net_worth(&game.board, market) >= game.target_net_worth {
++                game.winner = Some(player_idx);
++            }
++        }
++        Landing::PayFee {
++            owner,
++            district,
++            fee,
++        } => {
++            game.players[player_idx].cash -= fee;
++            game.players[owner].cash += fee;
++            let shop_count = game
++                .district_shop_count
++                .get(&district)
++                .copied()
++                .unwrap_or(0);
++            market.shift_sentiment(district, shop_count, FEE_SENTIMENT);
++            game.pending_events.push(GameEvent::PaidFee {
++                payer: player_idx,
++                owner,
++                tile: tile_index,
++                fee,
++            });
++        }
++        Landing::BuyOffer { .. } => match game.players[player_idx].brain() {
++            Some(brain)
++                if brain.buy_shop(player_idx, tile_index, game, market)
++                    && !game.rules.bot_blunders() =>
++            {
++                buy_property(tile_index, player_idx, game, market)
++            }
++            Some(_) => game.decline_shop(tile_index),
++            None => game.pending_purchase = Some(tile_index),
++        },
++        Landing::CannotAfford { .. } => game.decline_shop(tile_index),
++        Landing::OwnShop => {}
++        Landing::CollectSuit(suit) => {
++            game.players[player_idx].suits.insert(suit);
++            game.pending_events.push(GameEvent::CollectedSuit {
++                player: player_idx,
++                suit,
++            });
++        }
++        Landing::DuplicateSuit(_, DuplicateSuit::Nothing) => {}
++        Landing::DuplicateSuit(suit, DuplicateSuit::CashBonus) => {
++            game.players[player_idx].cash += DUPLICATE_SUIT_BONUS;
++            game.pending_events.push(GameEvent::DuplicateSuitBonus {
++                player: player_idx,
++                suit,
++                bonus: DUPLICATE_SUIT_BONUS,
++            });
++        }
++        Landing::DuplicateSuit(_, DuplicateSuit::SuitCard) | Landing::SuitCard => {
++            game.players[player_idx].suit_cards += 1;
++            game.pending_events
++                .push(GameEvent::GotSuitCard { player: player_idx });
++        }
++        Landing::VentureCard => {
++            // Venture cards are drawn and applied in `TurnPhase::VentureCard`.
++        }
++        Landing::Arcade => {
++            // Mini-games are played in `TurnPhase::Arcade`.
++        }
++        Landing::Casino => {
++            // Bets are placed in `TurnPhase::Casino`.
++        }
++        Landing::Warp => {
++            // Reached only when arriving by warp; the destination does not chain.
++        }
++        Landing::TakeABreak => {
++            game.players[player_idx].on_break = true;
++            game.pending_events
++                .push(GameEvent::TookBreak { player: player_idx });
++        }
++        Landing::Tax(tax) => {
++            game.players[player_idx].cash -= tax;
++            game.pending_events.push(GameEvent::PaidTax {
++                player: player_idx,
++                tax,
++            });
++        }
++        Landing::Boon(amount) => receive_boon(player_idx, amount, game),
++        Landing::Toll(toll) => pay_toll(player_idx, toll, game),
++    }
++}
++
++/// Runs the script of tile `tile_index`, if it has one, for the player who
++/// stopped there. Called once the tile's own effect is done.
++pub fn run_tile_script(
++    tile_index: usize,
++    player_idx: usize,
++    game: &mut Game,
++    market: &StockMarket,
++) {
++    if let Some(script) = game.board[tile_index].script.clone() {
++        run_script(&script, player_idx, game, market);
++        game.pending_events.push(GameEvent::RanTileScript {
++            player: player_idx,
++            tile: tile_index,
++        });
++    }
++}
++
++/// What happens to a player passing over tile `tile_index` without stopping.
++/// Passing the bank is handled by the movement systems, since it may wait on
++/// the player's Suit Yourself cards.
++pub fn pass_tile(tile_index: usize, player_idx: usize, game: &mut Game) {
++    match game.board[tile_index].kind {
++        TileKind::Suit(suit)
++            if game.rules.house.suits_on_pass && game.players[player_idx].suits.insert(suit) =>
++        {
++            game.pending_events.push(GameEvent::CollectedSuit {
++                player: player_idx,
++                suit,
++            });
++        }
++        TileKind::Boon => receive_boon(player_idx, BOON_PASS_CASH, game),
++        TileKind::TollGate { toll } => pay_toll(player_idx, toll, game),
++        _ => {}
++    }
++}
++
++fn receive_boon(player_idx: usize, amount: i32, game: &mut Game) {
++    game.players[player_idx].cash += amount;
++    game.pending_events.push(GameEvent::ReceivedBoon {
++        player: player_idx,
++        amount,
++    });
++}
++
++/// The part of `toll` the player pays: all of it, or only what they have
++/// under the no-negative-cash house rule.
++fn toll_due(player_idx: usize, toll: i32, game: &Game) -> i32 {
++    if game.rules.house.no_negative_cash {
++        toll.min(game.players[player_idx].cash.available())
++    } else {
++        toll
++    }
++}
++
++/// Charges the player what they owe of `toll` to the bank.
++fn pay_toll(player_idx: usize, toll: i32, game: &mut Game) {
++    let toll = toll_due(player_idx, toll, game);
++    game.players[player_idx].cash -= toll;
++    game.pending_events.push(GameEvent::PaidToll {
++        player: player_idx,
++        toll,
++    });
++}
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
++pub enum VentureEffect {
++    GainCash(i32),
++    LoseCash(i32),
++    CollectFromEach(i32),
++    PayEach(i32),
++    /// Receive this much per level reached.
++    LevelBonus(i32),
++    /// Pay this percentage of current cash.
++    CashTax(i32),
++    WarpToBank,
++    FreeSuit,
++    SuitYourself,
++    LoseSuit,
++    RollAgain,
++    Advance(usize),
++    DoubleFees,
++    FreeShares(i32),
++    StockBoom,
++    StockSlump,
++    /// Every district's price jumps 20-40%.
++    MarketBoom,
++    /// Every district's price falls 20-40%.
++    MarketCrash,
++    /// Runs the script of the board's card with this index.
++    Script(usize),
++}
++
++/// A venture card the board file adds.
++#[derive(Debug, Clone)]
++pub struct BoardCard {
++    pub title: String,
++    pub script: Script,
++}
++
++#[derive(Debug, Clone, Copy)]
++pub struct VentureCard {
++    pub title: &'static str,
++    pub effect: VentureEffect,
++}
++
++impl VentureCard {
++    pub const fn new(title: &'static str, effect: VentureEffect) -> Self {
++        Self { title, effect }
++    }
++
++    /// A card running the script of `game`'s board card `index`.
++    pub const fn board_card(index: usize) -> Self {
++        Self::new("", VentureEffect::Script(index))
++    }
++
++    /// The card's title, taken from the board for its own cards.
++    pub fn name<'a>(&self, game: &'a Game) -> &'a str {
++        match self.effect {
++            VentureEffect::Script(index) => &game.board_cards[index].title,
++            _ => self.title,
++        }
++    }
++
++    /// What the card does, with penalties scaled by `game`'s rules.
++    pub fn description(&self, game: &Game) -> String {
++        let rules = &game.rules;
++        match self.effect {
++            VentureEffect::GainCash(amount) => format!("Collect {} from the bank.", Money(amount)),
++            VentureEffect::LoseCash(amount) => {
++                format!("Pay {} to the bank.", Money(rules.venture_penalty(amount)))
++            }
++            VentureEffect::CollectFromEach(amount) => {
++                format!("Collect {} from every other player.", Money(amount))
++            }
++            VentureEffect::PayEach(amount) => format!(
++                "Pay {} to every other player.",
++                Money(rules.venture_penalty(amount))
++            ),
++            VentureEffect::LevelBonus(amount) => {
++                format!("Collect {} for each level you have reached.", Money(amount))
++            }
++            VentureEffect::CashTax(percent) => format!(
++                "Pay {}% of your cash in taxes.",
++                rules.venture_penalty(percent)
++            ),
++            VentureEffect::WarpToBank => "Warp straight to the bank.".to_string(),
++            VentureEffect::FreeSuit => "Receive a suit you are missing.".to_string(),
++            VentureEffect::SuitYourself => {
++                "Receive a Suit Yourself card: play it at the bank as any missing suit.".to_string()
++            }
++            VentureEffect::LoseSuit => "Drop one of your suits.".to_string(),
++            VentureEffect::RollAgain => "Roll the die again!".to_string(),
++            VentureEffect::Advance(steps) => format!("Move forward {} spaces.", steps),
++            VentureEffect::DoubleFees => {
++                "Your shops charge double fees until your next turn.".to_string()
++            }
++            VentureEffect::FreeShares(shares) => {
++                format!("Receive {} free shares in a random district.", shares)
++            }
++            VentureEffect::StockBoom => "A random district's stock price jumps 15%.".to_string(),
++            VentureEffect::StockSlump => "A random district's stock price falls 15%.".to_string(),
++            VentureEffect::MarketBoom => {
++                "Boom! Every district's stock price jumps 20-40%.".to_string()
++            }
++            VentureEffect::MarketCrash => {
++                "Crash! Every district's stock price falls 20-40%.".to_string()
++            }
++            VentureEffect::Script(index) => game.board_cards[index].script.describe(),
++        }
++    }
++}
++
++pub const VENTURE_CARDS: [VentureCard; 32] = [
++    VentureCard::new("Lucky Find", VentureEffect::GainCash(50)),
++    VentureCard::new("Windfall", VentureEffect::GainCash(100)),
++    VentureCard::new("Tax Refund", VentureEffect::GainCash(150)),
++    VentureCard::new("Lottery Win", VentureEffect::GainCash(200)),
++    VentureCard::new("Jackpot", VentureEffect::GainCash(300)),
++    VentureCard::new("Parking Ticket", VentureEffect::LoseCash(50)),
++    VentureCard::new("Broken Window", VentureEffect::LoseCash(100)),
++    VentureCard::new("Hospital Bill", VentureEffect::LoseCash(150)),
++    VentureCard::new("Birthday", VentureEffect::CollectFromEach(30)),
++    VentureCard::new("Festival Sponsor", VentureEffect::CollectFromEach(50)),
++    VentureCard::new("Round of Drinks", VentureEffect::PayEach(30)),
++    VentureCard::new("Charity Dinner", VentureEffect::PayEach(50)),
++    VentureCard::new("Seniority Pay", VentureEffect::LevelBonus(50)),
++    VentureCard::new("Audit", VentureEffect::CashTax(10)),
++    VentureCard::new("Express Train", VentureEffect::WarpToBank),
++    VentureCard::new("Taxi to the Bank", VentureEffect::WarpToBank),
++    VentureCard::new("Suit Yourself", VentureEffect::SuitYourself),
++    VentureCard::new("Card Shark", VentureEffect::FreeSuit),
++    VentureCard::new("Pickpocket", VentureEffect::LoseSuit),
++    VentureCard::new("Second Wind", VentureEffect::RollAgain),
++    VentureCard::new("Energy Drink", VentureEffect::RollAgain),
++    VentureCard::new("Shortcut", VentureEffect::Advance(2)),
++    VentureCard::new("Tailwind", VentureEffect::Advance(3)),
++    VentureCard::new("Rush Hour", VentureEffect::DoubleFees),
++    VentureCard::new("Holiday Crowds", VentureEffect::DoubleFees),
++    VentureCard::new("Dividend Gift", VentureEffect::FreeShares(10)),
++    VentureCard::new("Inheritance", VentureEffect::FreeShares(20)),
++    VentureCard::new("Bull Market", VentureEffect::StockBoom),
++    VentureCard::new("Market Rumor", VentureEffect::StockBoom),
++    VentureCard::new("Scandal", VentureEffect::StockSlump),
++    VentureCard::new("Gold Rush", VentureEffect::MarketBoom),
++    VentureCard::new("Black Monday", VentureEffect::MarketCrash),
++];
++
++/// Venture cards are drawn without replacement; the deck is reshuffled once
++/// it runs out.
++#[derive(Resource)]
++pub struct VentureDeck {
++    pub draw_pile: Vec<VentureCard>,
++    /// Card drawn this turn, shown until it is resolved.
++    pub active: Option<VentureCard>,
++    /// How many of the board's own cards are shuffled in with the usual ones.
++    pub board_cards: usize,
++}
++
++impl VentureDeck {
++    pub fn new() -> Self {
++        Self::with_board_cards(0)
++    }
++
++    /// A deck for a match on `game`'s board, its cards included.
++    pub fn for_game(game: &Game) -> Self {
++        Self::with_board_cards(game.board_cards.len())
++    }
++
++    fn with_board_cards(board_cards: usize) -> Self {
++        let mut deck = Self {
++            draw_pile: Vec::new(),
++            active: None,
++            board_cards,
++        };
++        deck.reshuffle();
++        deck
++    }
++
++    pub fn reshuffle(&mut self) {
++        self.draw_pile = VENTURE_CARDS.to_vec();
++        self.draw_pile
++            .extend((0..self.board_cards).map(VentureCard::board_card));
++        self.draw_pile.shuffle(&mut rand::thread_rng());
++    }
++
++    pub fn draw(&mut self) -> VentureCard {
++        if self.draw_pile.is_empty() {
++            self.reshuffle();
++        }
++        self.draw_pile.pop().expect("venture deck is never empty")
++    }
++}
++
++impl Default for VentureDeck {
++    fn default() -> Self {
++        Self::new()
++    }
++}
++
++/// Where the turn goes after a venture card is applied.
++pub enum VentureOutcome {
++    Done,
++    RollAgain,
++    Move(usize),
++    Resolve,
++}
++
++/// Where the turn goes once `effect` has been applied on `game`'s board.
++pub fn venture_outcome(effect: VentureEffect, game: &Game) -> VentureOutcome {
++    match effect {
++        VentureEffect::WarpToBank if game.bank_index().is_some() => VentureOutcome::Resolve,
++        VentureEffect::RollAgain => VentureOutcome::RollAgain,
++        VentureEffect::Advance(steps) => VentureOutcome::Move(steps),
++        _ => VentureOutcome::Done,
++    }
++}
++
++pub fn apply_venture(
++    effect: VentureEffect,
++    player_idx: usize,
++    game: &mut Game,
++    market: &mut StockMarket,
++    rng: &mut impl Rng,
++) {
++    let others: Vec<usize> = (0..game.players.len())
++        .filter(|idx| *idx != player_idx && !game.players[*idx].bankrupt)
++        .collect();
++    match effect {
++        VentureEffect::GainCash(amount) => game.players[player_idx].cash += amount,
++        VentureEffect::LoseCash(amount) => {
++            game.players[player_idx].cash -= game.rules.venture_penalty(amount)
++        }
++        VentureEffect::CollectFromEach(amount) => {
++            for other in others {
++                game.players[other].cash -= amount;
++                game.players[player_idx].cash += amount;
++            }
++        }
++        VentureEffect::PayEach(amount) => {
++            let amount = game.rules.venture_penalty(amount);
++            for other in others {
++                game.players[other].cash += amount;
++                game.players[player_idx].cash -= amount;
++            }
++        }
++        VentureEffect::LevelBonus(amount) => {
++            let player = &mut game.players[player_idx];
++            player.cash += amount * player.level.max(1) as i32;
++        }
++        VentureEffect::CashTax(percent) => {
++            let percent = game.rules.venture_penalty(percent);
++            let player = &mut game.players[player_idx];
++            player.cash -= player.cash.max(Money::ZERO).percent(percent);
++        }
++        VentureEffect::WarpToBank => {
++            if let Some(bank) = game.bank_index() {
++                game.players[player_idx].position = bank;
++            }
++        }
++        VentureEffect::FreeSuit => {
++            let player = &mut game.players[player_idx];
++            if let Some(suit) = player.missing_suits().choose(rng) {
++                player.suits.insert(*suit);
++            }
++        }
++        VentureEffect::SuitYourself => game.players[player_idx].suit_cards += 1,
++        VentureEffect::LoseSuit => {
++            let player = &mut game.players[player_idx];
++            let held: Vec<Suit> = player.suits.iter().copied().collect();
++            if let Some(suit) = held.choose(rng) {
++                player.suits.remove(suit);
++            }
++        }
++        VentureEffect::RollAgain | VentureEffect::Advance(_) => {}
++        VentureEffect::DoubleFees => game.players[player_idx].double_fees = true,
++        VentureEffect::FreeShares(shares) => {
++            if let Some(&district) = game.district_ids().choose(rng) {
++                let player = &mut game.players[player_idx];
++                let held = player.stocks.get(&district).copied().unwrap_or(0);
++                let granted = shares.min(MAX_SHARES_PER_DISTRICT - held).max(0);
++                if granted > 0 {
++                    player.add_shares(district, granted, 0);
++                }
++                *market.shares_outstanding.entry(district).or_default() += granted;
++                let shop_count = game
++                    .district_shop_count
++                    .get(&district)
++                    .copied()
++                    .unwrap_or(0);
++                market.reprice(district, shop_count);
++            }
++        }
++        VentureEffect::StockBoom | VentureEffect::StockSlump => {
++            if let Some(&district) = game.district_ids().choose(rng) {
++                let change = if matches!(effect, VentureEffect::StockBoom) {
++                    0.15
++                } else {
++                    -0.15
++                };
++                let shop_count = game
++                    .district_shop_count
++                    .get(&district)
++                    .copied()
++                    .unwrap_or(0);
++                market.shift_sentiment(district, shop_count, change);
++            }
++        }
++        VentureEffect::MarketBoom => market_swing(true, game, market, rng),
++        VentureEffect::MarketCrash => market_swing(false, game, market, rng),
++        VentureEffect::Script(index) => {
++            let script = game.board_cards[index].script.clone();
++            run_script(&script, player_idx, game, market);
++        }
++    }
++}
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
++pub enum ArcadeReward {
++    Nothing,
++    Cash(i32),
++    Suit(Suit),
++    SuitCard,
++}
++
++/// Prizes on the roulette wheel; bots are paid out as if they spun it.
++pub const ROULETTE_SLOTS: [ArcadeReward; 8] = [
++    ArcadeReward::Nothing,
++    ArcadeReward::Cash(50),
++    ArcadeReward::Cash(100),
++    ArcadeReward::Nothing,
++    ArcadeReward::Cash(200),
++    ArcadeReward::SuitCard,
++    ArcadeReward::Cash(50),
++    ArcadeReward::Cash(300),
++];
++
++/// Announces what `reward` will pay `player_idx`, before it is paid.
++pub fn describe_arcade_reward(reward: ArcadeReward, player_idx: usize, game: &Game) -> String {
++    let player = &game.players[player_idx];
++    match reward {
++        ArcadeReward::Nothing => "No prize this time.".to_string(),
++        ArcadeReward::Cash(amount) => format!("{} wins {}!", player.name, Money(amount)),
++        ArcadeReward::Suit(suit) if !player.suits.contains(&suit) => {
++            format!("{} collects the {} suit!", player.name, suit.icon())
++        }
++        ArcadeReward::Suit(_) => format!(
++            "{} already has that suit and wins 50G instead.",
++            player.name
++        ),
++        ArcadeReward::SuitCard => format!("{} wins a Suit Yourself card!", player.name),
++    }
++}
++
++pub fn apply_arcade_reward(reward: ArcadeReward, player_idx: usize, game: &mut Game) {
++    let player = &mut game.players[player_idx];
++    match reward {
++        ArcadeReward::Nothing => {}
++        ArcadeReward::Cash(amount) => player.cash += amount,
++        ArcadeReward::Suit(suit) if !player.suits.contains(&suit) => {
++            player.suits.insert(suit);
++        }
++        ArcadeReward::Suit(_) => player.cash += 50,
++        ArcadeReward::SuitCard => player.suit_cards += 1,
++    }
++}
++
++/// Publishes the game's queued promotions as `SalaryPaid` events.
++/// Sent whenever a shop changes hands: bought, traded, auctioned off, sold
++/// back to the bank (`owner: None`) or forfeited in bankruptcy.
++#[derive(Event, Debug, Clone, Copy)]
++pub struct OwnershipChanged {
++    pub tile: usize,
++    pub owner: Option<usize>,
++}
++
++/// Sent whenever capital is invested in a shop. A shop returning to the bank
++/// loses its capital too, but that is announced as an `OwnershipChanged`.
++#[derive(Event, Debug, Clone, Copy)]
++pub struct InvestmentChanged {
++    pub tile: usize,
++    pub capital: i32,
++}
++
++/// Something that happened on the board, written up in the event log.
++#[derive(Event, Debug, Clone, Serialize, Deserialize)]
++pub enum GameEvent {
++    Rolled {
++        player: usize,
++        faces: Vec<usize>,
++    },
++    /// Came back round to the bank; `interest` was added to any loan.
++    CompletedLap {
++        player: usize,
++        lap: u32,
++        interest: i32,
++    },
++    BoughtShop {
++        player: usize,
++        tile: usize,
++        cost: i32,
++    },
++    PaidFee {
++        payer: usize,
++        owner: usize,
++        tile: usize,
++        fee: i32,
++    },
++    CollectedSuit {
++        player: usize,
++        suit: Suit,
++    },
++    GotSuitCard {
++        player: usize,
++    },
++    /// Landed on a suit already held and was paid for it instead.
++    DuplicateSuitBonus {
++        player: usize,
++        suit: Suit,
++        bonus: i32,
++    },
++    TookBreak {
++        player: usize,
++    },
++    PaidTax {
++        player: usize,
++        tax: i32,
++    },
++    ReceivedBoon {
++        player: usize,
++        amount: i32,
++    },
++    PaidToll {
++        player: usize,
++        toll: i32,
++    },
++    /// Stopped on a tile with a script, which has run.
++    RanTileScript {
++        player: usize,
++        tile: usize,
++    },
++    /// Every district's price moved by `percent`: a boom or, when negative,
++    /// a crash.
++    MarketSwing {
++        percent: i32,
++    },
++    /// Paid out on the player's shares when someone completed a lap; negative
++    /// when their short positions owed more than their shares paid.
++    DividendsPaid {
++        player: usize,
++        amount: i32,
++    },
++    /// The bank bought back `shares` the player had sold short, for `cost`,
++    /// because their cash no longer covered the margin.
++    MarginCall {
++        player: usize,
++        district: DistrictId,
++        shares: i32,
++        cost: i32,
++    },
++    Invested {
++        player: usize,
++        tile: usize,
++        amount: i32,
++    },
++    DrewVenture {
++        player: usize,
++        title: String,
++    },
++    /// Bet `stake` at the casino and rolled `roll` on two dice.
++    PlayedHighLow {
++        player: usize,
++        roll: usize,
++        stake: i32,
++        won: bool,
++    },
++    Traded {
++        from: usize,
++        to: usize,
++    },
++    /// A bot put a trade to another player; `give` and `take` are described
++    /// from the bot's side.
++    ProposedTrade {
++        from: usize,
++        to: usize,
++        give: String,
++        take: String,
++    },
++    RefusedTrade {
++        from: usize,
++        to: usize,
++    },
++    UndidTurn {
++        player: usize,
++    },
++    /// The remote player dropped out and a bot took over their seat.
++    Disconnected {
++        player: usize,
++    },
++    Saved {
++        path: String,
++    },
++    Loaded {
++        path: String,
++    },
++    SaveFailed {
++        reason: String,
++    },
++}
++
++impl GameEvent {
++    pub fn describe(&self, game: &Game) -> String {
++        let name = |idx: usize| game.players[idx].name.as_str();
++        match self {
++            GameEvent::Rolled { player, faces } => {
++                format!("{} rolled {}", name(*player), describe_roll(faces))
++            }
++            GameEvent::CompletedLap {
++                player,
++                lap,
++                interest,
++            } => {
++                let mut line = format!(
++                    "{} passed the bank (lap {}) and collected {}",
++                    name(*player),
++                    lap,
++                    Money(LAP_BONUS)
++                );
++                if *interest > 0 {
++                    line.push_str(&format!("; loan interest {}", Money(*interest)));
++                }
++                line
++            }
++            GameEvent::BoughtShop { player, tile, cost } => format!(
++                "{} bought {} for {}",
++                name(*player),
++                game.tile_label(*tile),
++                Money(*cost)
++            ),
++            GameEvent::PaidFee {
++                payer,
++                owner,
++                tile,
++                fee,
++            } => format!(
++                "{} paid {} to {} at {}",
++                name(*payer),
++                Money(*fee),
++                name(*owner),
++                game.tile_label(*tile)
++            ),
++            GameEvent::CollectedSuit { player, suit } => {
++                format!("{} collected {}", name(*player), suit.icon())
++            }
++            GameEvent::GotSuitCard { player } => {
++                format!("{} got a Suit Yourself card", name(*player))
++            }
++            GameEvent::DuplicateSuitBonus {
++                player,
++                suit,
++                bonus,
++            } => format!(
++                "{} already holds {} and collected {}",
++                name(*player),
++                suit.icon(),
++                Money(*bonus)
++            ),
++            GameEvent::TookBreak { player } => format!("{} is taking a break", name(*player)),
++            GameEvent::PaidTax { player, tax } => {
++                format!("{} paid {} at the tax office", name(*player), Money(*tax))
++            }
++            GameEvent::ReceivedBoon { player, amount } => {
++                format!(
++                    "{} collected {} from a boon square",
++                    name(*player),
++                    Money(*amount)
++                )
++            }
++            GameEvent::PaidToll { player, toll } => {
++                format!("{} paid a {} toll", name(*player), Money(*toll))
++            }
++            GameEvent::RanTileScript { player, tile } => format!(
++                "{} set off {}: {}",
++                name(*player),
++                game.tile_label(*tile),
++                game.board[*tile]
++                    .script
++                    .as_ref()
++                    .map_or_else(String::new, Script::describe)
++            ),
++            GameEvent::MarketSwing { percent } if *percent >= 0 => format!(
++                "Market boom! Every district's stock price jumped {}%",
++                percent
++            ),
++            GameEvent::MarketSwing { percent } => format!(
++                "Market crash! Every district's stock price fell {}%",
++                -percent
++            ),
++            GameEvent::DividendsPaid { player, amount } if *amount >= 0 => {
++                format!("{} received {} in dividends", name(*player), Money(*amount))
++            }
++            GameEvent::DividendsPaid { player, amount } => format!(
++                "{} paid {} in dividends on shares sold short",
++                name(*player),
++                -Money(*amount)
++            ),
++            GameEvent::MarginCall {
++                player,
++                district,
++                shares,
++                cost,
++            } => format!(
++                "Margin call: the bank bought back {} {} shares {} sold short for {}",
++                shares,
++                game.district_name(*district),
++                name(*player),
++                Money(*cost)
++            ),
++            GameEvent::Invested {
++                player,
++                tile,
++                amount,
++            } => format!(
++                "{} invested {} in {}",
++                name(*player),
++                Money(*amount),
++                game.tile_label(*tile)
++            ),
++            GameEvent::DrewVenture { player, title } => {
++                format!("{} drew \"{}\"", name(*player), title)
++            }
++            GameEvent::PlayedHighLow {
++                player,
++                roll,
++                stake,
++                won: true,
++            } => format!("Rolled {}: {} wins {}!", roll, name(*player), Money(*stake)),
++            GameEvent::PlayedHighLow {
++                player,
++                roll,
++                stake,
++                won: false,
++            } => format!(
++                "Rolled {}: {} loses {}.",
++                roll,
++                name(*player),
++                Money(*stake)
++            ),
++            GameEvent::Traded { from, to } => {
++                format!("{} traded with {}", name(*from), name(*to))
++            }
++            GameEvent::ProposedTrade {
++                from,
++                to,
++                give,
++                take,
++            } => format!(
++                "{} offered {} {} for {}",
++                name(*from),
++                name(*to),
++                give,
++                take
++            ),
++            GameEvent::RefusedTrade { from, to } => {
++                format!("{} turned down {}'s offer", name(*to), name(*from))
++            }
++            GameEvent::UndidTurn { player } => {
++                format!("{} took back their last turn", name(*player))
++            }
++            GameEvent::Disconnected { player } => {
++                format!("{} disconnected; a bot takes over", name(*player))
++            }
++            GameEvent::Saved { path } => format!("Game saved to {}", path),
++            GameEvent::Loaded { path } => format!("Resumed the game from {}", path),
++            GameEvent::SaveFailed { reason } => format!("Save failed: {}", reason),
++        }
++    }
++}
++
++#[cfg(test)]
++mod tests {
++    use super::*;
++    use crate::sim::load_board_files;
++
++    fn tile(kind: TileKindDefinition, cell: (i32, i32)) -> TileDefinition {
++        TileDefinition {
++            kind,
++            cell,
++            next: Vec::new(),
++            script: None,
++        }
++    }
++
++    fn shop(district: &str) -> TileKindDefinition {
++        TileKindDefinition::Property {
++            district: district.to_string(),
++            price: 100,
++            base_fee: 25,
++        }
++    }
++
++    /// The smallest playable board: a ring of eight tiles with a bank, a
++    /// district of two shops and a tile for each suit.
++    fn ring() -> BoardDefinition {
++        BoardDefinition {
++            name: "Ring".to_string(),
++            districts: Vec::new(),
++            tiles: vec![
++                tile(TileKindDefinition::Bank, (0, 0)),
++                tile(shop("A"), (1, 0)),
++                tile(TileKindDefinition::Suit(Suit::Spade), (2, 0)),
++                tile(TileKindDefinition::Suit(Suit::Heart), (3, 0)),
++                tile(shop("A"), (3, 1)),
++                tile(TileKindDefinition::Suit(Suit::Diamond), (2, 1)),
++                tile(TileKindDefinition::Suit(Suit::Club), (1, 1)),
++                tile(TileKindDefinition::Chance, (0, 1)),
++            ],
++            venture_cards: Vec::new(),
++        }
++    }
++
++    #[test]
++    fn ring_is_playable() {
++        assert!(ring().problems().is_empty(), "{:?}", ring().problems());
++    }
++
++    #[test]
++    fn shipped_boards_have_no_problems() {
++        for (name, definition) in load_board_files().expect("the board files load") {
++            assert!(
++                definition.problems().is_empty(),
++                "{}: {:?}",
++                name,
++                definition.problems()
++            );
++        }
++    }
++
++    #[test]
++    fn unreachable_tile() {
++        let mut board = ring();
++        board.tiles[7].next = vec![0];
++        let mut island = tile(TileKindDefinition::Chance, (5, 5));
++        island.next = vec![0];
++        board.tiles.push(island);
++        let problems = board.problems();
++        assert!(
++            problems
++                .iter()
++                .any(|problem| matches!(problem, BoardLayoutError::Unreachable(8))),
++            "{:?}",
++            problems
++        );
++    }
++
++    #[test]
++    fn no_way_back() {
++        let mut board = ring();
++        board.tiles[1].next = vec![2, 8];
++        board.tiles[7].next = vec![0];
++        let mut dead_end = tile(TileKindDefinition::Chance, (1, -1));
++        dead_end.next = vec![9];
++        board.tiles.push(dead_end);
++        let mut loop_back = tile(TileKindDefinition::Chance, (2, -1));
++        loop_back.next = vec![8];
++        board.tiles.push(loop_back);
++        let problems = board.problems();
++        assert!(
++            problems
++                .iter()
++                .any(|problem| matches!(problem, BoardLayoutError::NoWayBack(8))),
++            "{:?}",
++            problems
++        );
++    }
++
++    #[test]
++    fn lap_too_short() {
++        let mut board = ring();
++        board.tiles[2].next = vec![3, 7];
++        let problems = board.problems();
++        assert!(
++            problems
++                .iter()
++                .any(|problem| matches!(problem, BoardLayoutError::ShortLap(4))),
++            "{:?}",
++            problems
++        );
++    }
++
++    #[test]
++    fn missing_suit() {
++        let mut board = ring();
++        board.tiles[6].kind = TileKindDefinition::Chance;
++        let problems = board.problems();
++        assert!(
++            problems
++                .iter()
++                .any(|problem| matches!(problem, BoardLayoutError::MissingSuit(Suit::Club))),
++            "{:?}",
++            problems
++        );
++    }
++
++    #[test]
++    fn lone_shop() {
++        let mut board = ring();
++        board.tiles[4].kind = shop("B");
++        let problems = board.problems();
++        for district in ["A", "B"] {
++            assert!(
++                problems.iter().any(
++                    |problem| matches!(problem, BoardLayoutError::LoneShop(name) if name == district)
++                ),
++                "{:?}",
++                problems
++            );
++        }
++    }
++}
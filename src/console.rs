@@ -0,0 +1,422 @@
+diff --git a/src/console.rs b/src/console.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..8d90ffc3117fde0a3f892a01ae6336d62c00f2bf
+--- /dev/null
++++ b/src/console.rs
+@@ -0,0 +1,416 @@
++//! The developer console. In dev builds the backquote key (`` ` ``, `~` with
++//! Shift) drops a console over the top of the match for setting up
++//! situations by hand: giving cash, moving tokens, setting share prices and
++//! ending turns. Each command is parsed into the `TurnAction` it stands for
++//! and applied through the `ActionLog`, so it plays by the same rules code
++//! and is recorded like any other action. The match holds while the console
++//! is open.
++
++use bevy::prelude::*;
++use std::collections::VecDeque;
++
++use crate::*;
++
++/// Lines of output the console keeps above its prompt.
++pub const CONSOLE_LINES: usize = 8;
++
++/// Most gold the console lets a player hold or owe, so net worth stays far
++/// from overflowing.
++pub const MAX_CONSOLE_CASH: i32 = 10_000_000;
++
++/// Highest share price the console can set: 200 shares in every district at
++/// this price still add up to a net worth that fits.
++pub const MAX_CONSOLE_SHARE_PRICE: i32 = 10_000;
++
++pub const CONSOLE_HELP: &str = "Commands: give cash <player> <amount>, teleport <player> <tile>, set stock <district> <price>, end turn, help";
++
++/// A line typed into the console, parsed.
++#[derive(Debug, Clone, PartialEq, Eq)]
++pub enum ConsoleCommand {
++    /// Applied for the active player through the `ActionLog`.
++    Action(TurnAction),
++    /// Ends the active player's turn through the end-of-turn phase.
++    EndTurn,
++    Help,
++}
++
++/// Parses `line`. Keywords and names are matched regardless of case, and
++/// player and district names may contain spaces.
++pub fn parse_command(line: &str, game: &Game) -> Result<ConsoleCommand, String> {
++    let line = line.to_lowercase();
++    let words: Vec<&str> = line.split_whitespace().collect();
++    let number = |word: &str| {
++        word.parse::<i32>()
++            .map_err(|_| format!("{} isn't a number", word))
++    };
++    match words.as_slice() {
++        ["help"] => Ok(ConsoleCommand::Help),
++        ["end", "turn"] => Ok(ConsoleCommand::EndTurn),
++        ["give", "cash", name @ .., amount] if !name.is_empty() => {
++            Ok(ConsoleCommand::Action(TurnAction::GiveCash {
++                player: find_player(&name.join(" "), game)?,
++                amount: number(amount)?,
++            }))
++        }
++        ["teleport", name @ .., tile] if !name.is_empty() => {
++            let tile = tile
++                .parse::<usize>()
++                .map_err(|_| format!("{} isn't a tile number", tile))?;
++            Ok(ConsoleCommand::Action(TurnAction::Teleport {
++                player: find_player(&name.join(" "), game)?,
++                tile,
++            }))
++        }
++        ["set", "stock", name @ .., price] if !name.is_empty() => {
++            Ok(ConsoleCommand::Action(TurnAction::SetStockPrice {
++                district: find_district(&name.join(" "), game)?,
++                price: number(price)?,
++            }))
++        }
++        _ => Err(format!("Unknown command \"{}\"; try help", line.trim())),
++    }
++}
++
++/// The seat of the player called `name`, already lowercased.
++fn find_player(name: &str, game: &Game) -> Result<usize, String> {
++    game.players
++        .iter()
++        .position(|player| player.name.to_lowercase() == name)
++        .ok_or_else(|| format!("No player called {}", name))
++}
++
++/// The district called `name`, already lowercased.
++fn find_district(name: &str, game: &Game) -> Result<DistrictId, String> {
++    game.districts
++        .iter()
++        .find(|district| district.name.to_lowercase() == name)
++        .map(|district| district.id)
++        .ok_or_else(|| format!("No district called {}", name))
++}
++
++/// What the console shows and the line being typed.
++#[derive(Resource, Default)]
++pub struct DevConsole {
++    pub open: bool,
++    pub input: String,
++    /// Commands run and their results, oldest first.
++    pub output: VecDeque<String>,
++}
++
++impl DevConsole {
++    pub fn print(&mut self, line: String) {
++        self.output.push_back(line);
++        if self.output.len() > CONSOLE_LINES {
++            self.output.pop_front();
++        }
++    }
++}
++
++#[derive(Component)]
++pub struct ConsolePanel;
++
++#[derive(Component)]
++pub struct ConsoleText;
++
++pub fn spawn_console_panel(mut commands: Commands, asset_server: Res<AssetServer>) {
++    commands
++        .spawn((
++            NodeBundle {
++                style: Style {
++                    position_type: PositionType::Absolute,
++                    top: Val::Px(0.0),
++                    left: Val::Px(0.0),
++                    right: Val::Px(0.0),
++                    padding: UiRect::all(Val::Px(8.0)),
++                    display: Display::None,
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.85)),
++                z_index: ZIndex::Global(10),
++                ..Default::default()
++            },
++            ConsolePanel,
++        ))
++        .with_children(|panel| {
++            panel.spawn((
++                TextBundle::from_section(
++                    "",
++                    TextStyle {
++                        font: asset_server.load(DEFAULT_FONT),
++                        font_size: 16.0,
++                        color: Color::rgb(0.7, 1.0, 0.7),
++                    },
++                ),
++                ConsoleText,
++            ));
++        });
++}
++
++/// The console key opens and closes the console in dev builds. While it is
++/// open it takes every key: typing edits the command, Enter runs it and
++/// Escape closes the console.
++#[allow(clippy::too_many_arguments)]
++pub fn console_input(
++    mut console: ResMut<DevConsole>,
++    mut actions: ResMut<ActionInput>,
++    mut typed: EventReader<ReceivedCharacter>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    phase: Res<State<TurnPhase>>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    dice: Query<(), With<Die>>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    if !cfg!(debug_assertions) {
++        return;
++    }
++    if actions.just_pressed(Action::Console) {
++        console.open = !console.open;
++        typed.clear();
++        actions.reset_all();
++        return;
++    }
++    if !console.open {
++        typed.clear();
++        return;
++    }
++    for event in typed.read() {
++        console
++            .input
++            .extend(event.char.chars().filter(|c| !c.is_control()));
++    }
++    if actions.just_pressed(Action::Erase) {
++        console.input.pop();
++    }
++    if actions.just_pressed(Action::Back) {
++        console.open = false;
++    } else if actions.just_pressed(Action::Confirm) && !console.input.trim().is_empty() {
++        let line = std::mem::take(&mut console.input);
++        console.print(format!("> {}", line));
++        let result = match parse_command(&line, &game) {
++            Ok(ConsoleCommand::Help) => Ok(CONSOLE_HELP.to_string()),
++            Ok(ConsoleCommand::EndTurn) => {
++                end_turn_now(*phase.get(), !dice.is_empty(), &mut game, &mut next_phase)
++            }
++            Ok(ConsoleCommand::Action(action)) => log
++                .apply(action.clone(), &mut game, &mut market, &mut rng)
++                .map(|events| {
++                    game_events.send_batch(events);
++                    describe_result(action, &game, &market)
++                })
++                .map_err(str::to_string),
++            Err(err) => Err(err),
++        };
++        console.print(result.unwrap_or_else(|err| err));
++    }
++    actions.reset_all();
++}
++
++/// Sends the active player's turn to its end, dropping a shop they were
++/// offered. Refused while they are on the move, when ending it would leave
++/// the move half done.
++fn end_turn_now(
++    phase: TurnPhase,
++    rolling: bool,
++    game: &mut Game,
++    next_phase: &mut NextState<TurnPhase>,
++) -> Result<String, String> {
++    let ends = match phase {
++        TurnPhase::RollDice => !rolling,
++        TurnPhase::ShopDecision | TurnPhase::StockPhase => true,
++        _ => false,
++    };
++    if !ends || game.winner.is_some() {
++        return Err(
++            "The turn can only be ended before rolling, at a shop offer or in the stock phase"
++                .to_string(),
++        );
++    }
++    game.pending_purchase = None;
++    next_phase.set(TurnPhase::EndTurn);
++    Ok(format!(
++        "Ended {}'s turn",
++        game.players[game.current_turn].name
++    ))
++}
++
++/// The console's report of a developer action that went through.
++fn describe_result(action: TurnAction, game: &Game, market: &StockMarket) -> String {
++    match action {
++        TurnAction::GiveCash { player, .. } => {
++            let player = &game.players[player];
++            format!("{} now has {}", player.name, player.cash)
++        }
++        TurnAction::Teleport { player, tile } => {
++            format!("{} is on tile {}", game.players[player].name, tile)
++        }
++        TurnAction::SetStockPrice { district, .. } => format!(
++            "{} shares trade at {}",
++            game.districts[district].name,
++            Money(market.price(district))
++        ),
++        _ => "Done".to_string(),
++    }
++}
++
++/// Shows the console with its output and the line being typed.
++pub fn update_console_panel(
++    console: Res<DevConsole>,
++    mut panels: Query<&mut Style, With<ConsolePanel>>,
++    mut texts: Query<&mut Text, With<ConsoleText>>,
++) {
++    if !console.is_changed() {
++        return;
++    }
++    let display = if console.open {
++        Display::Flex
++    } else {
++        Display::None
++    };
++    for mut style in panels.iter_mut() {
++        if style.display != display {
++            style.display = display;
++        }
++    }
++    let mut content: Vec<&str> = console.output.iter().map(String::as_str).collect();
++    let prompt = format!("> {}_", console.input);
++    content.push(&prompt);
++    let content = content.join("\n");
++    for mut text in texts.iter_mut() {
++        if text.sections[0].value != content {
++            text.sections[0].value.clone_from(&content);
++        }
++    }
++}
++
++/// Closes the console when the match is left.
++pub fn close_console(mut console: ResMut<DevConsole>) {
++    console.open = false;
++    console.input.clear();
++}
++
++#[cfg(test)]
++mod tests {
++    use super::*;
++    use crate::sim::load_board_files;
++
++    fn new_match() -> (Game, StockMarket) {
++        let boards = load_board_files().expect("the board files load");
++        let seats: Vec<Seat> = ["Ann", "Bo Rae"]
++            .into_iter()
++            .enumerate()
++            .map(|(character, name)| Seat {
++                name: name.to_string(),
++                kind: PlayerKind::Human,
++                strategy: BotStrategy::Balanced,
++                character,
++            })
++            .collect();
++        let game = Game::new(
++            &boards[0].1,
++            &seats,
++            TARGET_NET_WORTHS[0],
++            GameRules::default(),
++        );
++        let market = StockMarket::new(&game);
++        (game, market)
++    }
++
++    #[test]
++    fn parses_each_command() {
++        let (game, _) = new_match();
++        let district = &game.districts[0];
++        assert_eq!(parse_command("help", &game), Ok(ConsoleCommand::Help));
++        assert_eq!(
++            parse_command(" End  Turn ", &game),
++            Ok(ConsoleCommand::EndTurn)
++        );
++        assert_eq!(
++            parse_command("give cash bo rae -300", &game),
++            Ok(ConsoleCommand::Action(TurnAction::GiveCash {
++                player: 1,
++                amount: -300,
++            }))
++        );
++        assert_eq!(
++            parse_command("teleport Ann 5", &game),
++            Ok(ConsoleCommand::Action(TurnAction::Teleport {
++                player: 0,
++                tile: 5
++            }))
++        );
++        assert_eq!(
++            parse_command(&format!("set stock {} 40", district.name), &game),
++            Ok(ConsoleCommand::Action(TurnAction::SetStockPrice {
++                district: district.id,
++                price: 40,
++            }))
++        );
++    }
++
++    #[test]
++    fn rejects_what_it_cant_parse() {
++        let (game, _) = new_match();
++        for line in [
++            "give cash nobody 5",
++            "give cash ann lots",
++            "give cash 5",
++            "teleport ann -1",
++            "set stock nowhere 40",
++            "dance",
++        ] {
++            assert!(parse_command(line, &game).is_err(), "{}", line);
++        }
++    }
++
++    #[test]
++    fn rejects_amounts_out_of_range() {
++        let (mut game, mut market) = new_match();
++        let district = game.districts[0].name.clone();
++        for line in [
++            "give cash ann 2147483647".to_string(),
++            "give cash ann -2147483648".to_string(),
++            format!("give cash ann {}", MAX_CONSOLE_CASH),
++            format!("set stock {} 0", district),
++            format!("set stock {} {}", district, MAX_CONSOLE_SHARE_PRICE + 1),
++            format!("set stock {} 20000000", district),
++            format!("teleport ann {}", game.board.len()),
++        ] {
++            let Ok(ConsoleCommand::Action(action)) = parse_command(&line, &game) else {
++                panic!("{} doesn't parse", line);
++            };
++            assert!(
++                engine::apply(action, &mut game, &mut market, &mut MatchRng::seeded(0)).is_err(),
++                "{}",
++                line
++            );
++        }
++        assert_eq!(violations(&game, &market), Vec::new());
++    }
++
++    #[test]
++    fn applies_amounts_in_range() {
++        let (mut game, mut market) = new_match();
++        let district = game.districts[0].clone();
++        game.players[0].add_shares(district.id, MAX_SHARES_PER_DISTRICT, 0);
++        let top_up = MAX_CONSOLE_CASH - game.players[0].cash.get();
++        for line in [
++            format!("give cash ann {}", top_up),
++            format!("set stock {} {}", district.name, MAX_CONSOLE_SHARE_PRICE),
++        ] {
++            let Ok(ConsoleCommand::Action(action)) = parse_command(&line, &game) else {
++                panic!("{} doesn't parse", line);
++            };
++            assert!(
++                engine::apply(action, &mut game, &mut market, &mut MatchRng::seeded(0)).is_ok(),
++                "{}",
++                line
++            );
++        }
++        assert_eq!(game.players[0].cash, MAX_CONSOLE_CASH);
++        assert_eq!(market.price(district.id), MAX_CONSOLE_SHARE_PRICE);
++        assert_eq!(violations(&game, &market), Vec::new());
++    }
++}
@@ -0,0 +1,459 @@
+diff --git a/src/controls.rs b/src/controls.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..246be60309fa272bdcc8d3f3b11147beb1cfab15
+--- /dev/null
++++ b/src/controls.rs
+@@ -0,0 +1,453 @@
++//! Input mapping: game systems read abstract `Action`s from `ActionInput`
++//! instead of raw keys, and the player's `InputMap` decides which keys and
++//! gamepad buttons trigger each one.
++
++use bevy::{
++    input::gamepad::{GamepadConnection, GamepadConnectionEvent},
++    prelude::*,
++};
++use serde::{Deserialize, Serialize};
++use std::collections::{BTreeMap, HashSet};
++
++use crate::*;
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
++pub enum Action {
++    Confirm,
++    Back,
++    Yes,
++    No,
++    /// Rolls the dice, ends the stock phase and spins the arcade games.
++    Roll,
++    Up,
++    Down,
++    Left,
++    Right,
++    PanUp,
++    PanDown,
++    PanLeft,
++    PanRight,
++    ZoomIn,
++    ZoomOut,
++    /// Switches between following the active player and a free camera.
++    FollowCamera,
++    Increase,
++    Decrease,
++    PrevRow,
++    NextRow,
++    PrevShop,
++    NextShop,
++    /// Cycles a seat's player type, or the trade partner.
++    Cycle,
++    Erase,
++    Menu,
++    Stocks,
++    Trade,
++    Settings,
++    Leaderboard,
++    Portfolio,
++    DistrictMap,
++    Borrow,
++    Repay,
++    SellShop,
++    Bid,
++    BetHigh,
++    BetLow,
++    Undo,
++    Ready,
++    /// Opens the board editor from the setup screen, or starts a blank board
++    /// in it.
++    Editor,
++    FastForward,
++    /// Has the active human's brain play their turns, or gives them back.
++    Autopilot,
++    /// Opens and closes the developer console in dev builds.
++    Console,
++    Pause,
++    QuickSave,
++    QuickLoad,
++    SaveReplay,
++    /// Number keys: districts, memory cards and seat bids.
++    Pick(u8),
++}
++
++/// Every action, in settings menu order.
++pub const ACTIONS: [Action; 56] = [
++    Action::Confirm,
++    Action::Back,
++    Action::Yes,
++    Action::No,
++    Action::Roll,
++    Action::Up,
++    Action::Down,
++    Action::Left,
++    Action::Right,
++    Action::PanUp,
++    Action::PanDown,
++    Action::PanLeft,
++    Action::PanRight,
++    Action::ZoomIn,
++    Action::ZoomOut,
++    Action::FollowCamera,
++    Action::Increase,
++    Action::Decrease,
++    Action::PrevRow,
++    Action::NextRow,
++    Action::PrevShop,
++    Action::NextShop,
++    Action::Cycle,
++    Action::Erase,
++    Action::Menu,
++    Action::Stocks,
++    Action::Trade,
++    Action::Settings,
++    Action::Leaderboard,
++    Action::Portfolio,
++    Action::DistrictMap,
++    Action::Borrow,
++    Action::Repay,
++    Action::SellShop,
++    Action::Bid,
++    Action::BetHigh,
++    Action::BetLow,
++    Action::Undo,
++    Action::Ready,
++    Action::Editor,
++    Action::FastForward,
++    Action::Autopilot,
++    Action::Console,
++    Action::Pause,
++    Action::QuickSave,
++    Action::QuickLoad,
++    Action::SaveReplay,
++    Action::Pick(1),
++    Action::Pick(2),
++    Action::Pick(3),
++    Action::Pick(4),
++    Action::Pick(5),
++    Action::Pick(6),
++    Action::Pick(7),
++    Action::Pick(8),
++    Action::Pick(9),
++];
++
++impl Action {
++    pub fn label(self) -> String {
++        let label = match self {
++            Action::Confirm => "Confirm",
++            Action::Back => "Back",
++            Action::Yes => "Yes",
++            Action::No => "No",
++            Action::Roll => "Roll / end turn",
++            Action::Up => "Up",
++            Action::Down => "Down",
++            Action::Left => "Left",
++            Action::Right => "Right",
++            Action::PanUp => "Pan up",
++            Action::PanDown => "Pan down",
++            Action::PanLeft => "Pan left",
++            Action::PanRight => "Pan right",
++            Action::ZoomIn => "Zoom in",
++            Action::ZoomOut => "Zoom out",
++            Action::FollowCamera => "Follow camera",
++            Action::Increase => "Increase",
++            Action::Decrease => "Decrease",
++            Action::PrevRow => "Previous row",
++            Action::NextRow => "Next row",
++            Action::PrevShop => "Previous shop",
++            Action::NextShop => "Next shop",
++            Action::Cycle => "Cycle",
++            Action::Erase => "Erase",
++            Action::Menu => "Main menu",
++            Action::Stocks => "Stocks",
++            Action::Trade => "Trade",
++            Action::Settings => "Settings",
++            Action::Leaderboard => "Leaderboard",
++            Action::Portfolio => "Portfolio",
++            Action::DistrictMap => "District map",
++            Action::Borrow => "Borrow",
++            Action::Repay => "Repay",
++            Action::SellShop => "Sell shop",
++            Action::Bid => "Bid",
++            Action::BetHigh => "Bet high",
++            Action::BetLow => "Bet low",
++            Action::Undo => "Undo",
++            Action::Ready => "Ready",
++            Action::Editor => "Board editor",
++            Action::FastForward => "Fast-forward",
++            Action::Autopilot => "Autopilot",
++            Action::Console => "Developer console",
++            Action::Pause => "Pause replay",
++            Action::QuickSave => "Quick save",
++            Action::QuickLoad => "Quick load",
++            Action::SaveReplay => "Save replay",
++            Action::Pick(number) => return format!("Pick {}", number),
++        };
++        label.to_string()
++    }
++
++    /// Whether a remote player's client sends this action to the host. The
++    /// camera, settings, save files and undo stay local to each machine.
++    pub fn relayed(self) -> bool {
++        !matches!(
++            self,
++            Action::Back
++                | Action::PanUp
++                | Action::PanDown
++                | Action::PanLeft
++                | Action::PanRight
++                | Action::ZoomIn
++                | Action::ZoomOut
++                | Action::FollowCamera
++                | Action::Settings
++                | Action::Leaderboard
++                | Action::Portfolio
++                | Action::DistrictMap
++                | Action::Undo
++                | Action::Ready
++                | Action::Editor
++                | Action::FastForward
++                | Action::Console
++                | Action::Pause
++                | Action::QuickSave
++                | Action::QuickLoad
++                | Action::SaveReplay
++        )
++    }
++
++    /// The keys and gamepad buttons that trigger the action until the
++    /// player rebinds it.
++    pub fn default_bindings(self) -> Vec<Binding> {
++        let mut bindings: Vec<Binding> =
++            self.default_keys().into_iter().map(Binding::Key).collect();
++        bindings.extend(self.default_buttons().into_iter().map(Binding::Gamepad));
++        bindings
++    }
++
++    fn default_keys(self) -> Vec<KeyCode> {
++        match self {
++            Action::Confirm => vec![KeyCode::Enter, KeyCode::NumpadEnter],
++            Action::Back => vec![KeyCode::Escape],
++            Action::Yes => vec![KeyCode::KeyY],
++            Action::No => vec![KeyCode::KeyN],
++            Action::Roll | Action::Pause => vec![KeyCode::Space],
++            Action::Up => vec![KeyCode::ArrowUp],
++            Action::Down => vec![KeyCode::ArrowDown],
++            Action::Left => vec![KeyCode::ArrowLeft],
++            Action::Right => vec![KeyCode::ArrowRight],
++            Action::PanUp => vec![KeyCode::KeyW, KeyCode::ArrowUp],
++            Action::PanDown => vec![KeyCode::KeyS, KeyCode::ArrowDown],
++            Action::PanLeft => vec![KeyCode::KeyA, KeyCode::ArrowLeft],
++            Action::PanRight => vec![KeyCode::KeyD, KeyCode::ArrowRight],
++            Action::ZoomIn | Action::ZoomOut => Vec::new(),
++            Action::Increase => vec![KeyCode::Equal, KeyCode::NumpadAdd],
++            Action::Decrease => vec![KeyCode::Minus, KeyCode::NumpadSubtract],
++            Action::PrevRow => vec![KeyCode::Comma],
++            Action::NextRow => vec![KeyCode::Period],
++            Action::PrevShop => vec![KeyCode::BracketLeft],
++            Action::NextShop => vec![KeyCode::BracketRight],
++            Action::Cycle => vec![KeyCode::Tab],
++            Action::Erase => vec![KeyCode::Backspace],
++            Action::Menu => vec![KeyCode::KeyM],
++            Action::Stocks => vec![KeyCode::KeyS],
++            Action::Trade => vec![KeyCode::KeyT],
++            Action::Settings => vec![KeyCode::KeyO],
++            Action::Leaderboard => vec![KeyCode::KeyG],
++            Action::Portfolio => vec![KeyCode::KeyV],
++            Action::DistrictMap => vec![KeyCode::KeyK],
++            Action::Borrow | Action::BetLow => vec![KeyCode::KeyL],
++            Action::Repay => vec![KeyCode::KeyP],
++            Action::SellShop => vec![KeyCode::KeyX],
++            Action::Bid => vec![KeyCode::KeyB],
++            Action::BetHigh => vec![KeyCode::KeyH],
++            Action::Undo => vec![KeyCode::KeyU],
++            Action::Ready => vec![KeyCode::KeyR],
++            Action::Editor => vec![KeyCode::KeyE],
++            Action::FastForward => vec![KeyCode::KeyF],
++            Action::Autopilot => vec![KeyCode::KeyQ],
++            Action::Console => vec![KeyCode::Backquote],
++            Action::FollowCamera => vec![KeyCode::KeyC],
++            Action::QuickSave => vec![KeyCode::F5],
++            Action::QuickLoad => vec![KeyCode::F9],
++            Action::SaveReplay => vec![KeyCode::F6],
++            Action::Pick(number) => DIGIT_KEYS
++                .get(number as usize)
++                .copied()
++                .into_iter()
++                .collect(),
++        }
++    }
++
++    /// Face buttons confirm, cancel and roll, the D-pad moves through menus
++    /// and panels, the bumpers pick shops and the triggers zoom. The left
++    /// stick pans the camera outside the bindings.
++    fn default_buttons(self) -> Vec<GamepadButtonType> {
++        use GamepadButtonType::*;
++        match self {
++            Action::Confirm | Action::Yes | Action::Bid => vec![South],
++            Action::Back | Action::No => vec![East],
++            Action::Roll | Action::Pause => vec![West],
++            Action::Menu => vec![North],
++            Action::Settings => vec![Start],
++            Action::Stocks => vec![Select],
++            Action::Trade => vec![LeftThumb],
++            Action::Cycle => vec![RightThumb],
++            Action::Up | Action::PrevRow => vec![DPadUp],
++            Action::Down | Action::NextRow => vec![DPadDown],
++            Action::Left | Action::Decrease => vec![DPadLeft],
++            Action::Right | Action::Increase => vec![DPadRight],
++            Action::PrevShop => vec![LeftTrigger],
++            Action::NextShop => vec![RightTrigger],
++            Action::ZoomOut => vec![LeftTrigger2],
++            Action::ZoomIn => vec![RightTrigger2],
++            _ => Vec::new(),
++        }
++    }
++}
++
++/// Number keys by value, `Digit0` first.
++const DIGIT_KEYS: [KeyCode; 10] = [
++    KeyCode::Digit0,
++    KeyCode::Digit1,
++    KeyCode::Digit2,
++    KeyCode::Digit3,
++    KeyCode::Digit4,
++    KeyCode::Digit5,
++    KeyCode::Digit6,
++    KeyCode::Digit7,
++    KeyCode::Digit8,
++    KeyCode::Digit9,
++];
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
++pub enum Binding {
++    Key(KeyCode),
++    /// A button on any connected gamepad.
++    Gamepad(GamepadButtonType),
++}
++
++impl Binding {
++    pub fn label(self) -> String {
++        match self {
++            Binding::Key(key) => format!("{:?}", key),
++            Binding::Gamepad(button) => format!("Pad {:?}", button),
++        }
++    }
++}
++
++/// The bindings of every action the player has changed; the rest keep
++/// their defaults.
++#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
++pub struct InputMap(pub BTreeMap<Action, Vec<Binding>>);
++
++impl InputMap {
++    pub fn bindings(&self, action: Action) -> Vec<Binding> {
++        match self.0.get(&action) {
++            Some(bindings) => bindings.clone(),
++            None => action.default_bindings(),
++        }
++    }
++
++    pub fn rebind(&mut self, action: Action, binding: Binding) {
++        self.0.insert(action, vec![binding]);
++    }
++
++    pub fn reset(&mut self, action: Action) {
++        self.0.remove(&action);
++    }
++}
++
++/// The actions held and newly pressed this frame.
++#[derive(Resource, Default)]
++pub struct ActionInput {
++    pressed: HashSet<Action>,
++    just_pressed: HashSet<Action>,
++}
++
++impl ActionInput {
++    pub fn pressed(&self, action: Action) -> bool {
++        self.pressed.contains(&action)
++    }
++
++    pub fn just_pressed(&self, action: Action) -> bool {
++        self.just_pressed.contains(&action)
++    }
++
++    pub fn any_just_pressed(&self, actions: impl IntoIterator<Item = Action>) -> bool {
++        actions.into_iter().any(|action| self.just_pressed(action))
++    }
++
++    pub fn get_just_pressed(&self) -> impl Iterator<Item = Action> + '_ {
++        self.just_pressed.iter().copied()
++    }
++
++    /// The first of `Pick(1)` to `Pick(count)` pressed this frame, as an
++    /// index from 0.
++    pub fn just_picked(&self, count: usize) -> Option<usize> {
++        (1..=count.min(9))
++            .find(|number| self.just_pressed(Action::Pick(*number as u8)))
++            .map(|number| number - 1)
++    }
++
++    pub fn press(&mut self, action: Action) {
++        if self.pressed.insert(action) {
++            self.just_pressed.insert(action);
++        }
++    }
++
++    pub fn reset(&mut self, action: Action) {
++        self.pressed.remove(&action);
++        self.just_pressed.remove(&action);
++    }
++
++    pub fn reset_all(&mut self) {
++        self.pressed.clear();
++        self.just_pressed.clear();
++    }
++}
++
++/// Turns this frame's key and gamepad button state into actions through the
++/// player's bindings.
++pub fn read_actions(
++    keyboard: Res<ButtonInput<KeyCode>>,
++    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
++    settings: Res<Settings>,
++    mut actions: ResMut<ActionInput>,
++) {
++    let held = |binding: &Binding| match *binding {
++        Binding::Key(key) => keyboard.pressed(key),
++        Binding::Gamepad(button) => gamepad_buttons
++            .get_pressed()
++            .any(|pressed| pressed.button_type == button),
++    };
++    let struck = |binding: &Binding| match *binding {
++        Binding::Key(key) => keyboard.just_pressed(key),
++        Binding::Gamepad(button) => gamepad_buttons
++            .get_just_pressed()
++            .any(|pressed| pressed.button_type == button),
++    };
++    actions.reset_all();
++    for action in ACTIONS {
++        let bindings = settings.bindings.bindings(action);
++        if bindings.iter().any(held) {
++            actions.pressed.insert(action);
++        }
++        if bindings.iter().any(struck) {
++            actions.just_pressed.insert(action);
++        }
++    }
++}
++
++/// Logs gamepads as they connect and disconnect; any of them can play.
++pub fn announce_gamepads(mut connections: EventReader<GamepadConnectionEvent>) {
++    for event in connections.read() {
++        match &event.connection {
++            GamepadConnection::Connected(info) => {
++                info!("Gamepad {} connected: {}", event.gamepad.id, info.name)
++            }
++            GamepadConnection::Disconnected => {
++                info!("Gamepad {} disconnected", event.gamepad.id)
++            }
++        }
++    }
++}
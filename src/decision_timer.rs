@@ -0,0 +1,238 @@
+diff --git a/src/decision_timer.rs b/src/decision_timer.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..0924b7e53fff2108651164cdd2ec77da63633e30
+--- /dev/null
++++ b/src/decision_timer.rs
+@@ -0,0 +1,232 @@
++//! Time limits on human decisions. With a decision timer picked on the setup
++//! screen, a human has a set time to roll, to answer a shop offer and to play
++//! their stock phase. A bar over the action bar shrinks as the time runs
++//! down, and when it runs out the default is taken for them: the dice are
++//! rolled, the shop is declined or the turn is ended. The host keeps the
++//! clock in online matches.
++
++use bevy::prelude::*;
++
++use crate::*;
++
++/// Seconds to roll at the Standard limit.
++pub const ROLL_SECONDS: f32 = 30.0;
++
++/// Seconds to buy or decline a shop at the Standard limit.
++pub const SHOP_DECISION_SECONDS: f32 = 30.0;
++
++/// Seconds for the stock phase at the Standard limit.
++pub const STOCK_PHASE_SECONDS: f32 = 60.0;
++
++/// Share of the time left below which the bar turns red.
++pub const DECISION_BAR_WARNING: f32 = 0.25;
++
++/// Limits picked on the setup screen, in the order Left/Right cycle them.
++pub const DECISION_TIMERS: [DecisionTimer; 4] = [
++    DecisionTimer::Off,
++    DecisionTimer::Relaxed,
++    DecisionTimer::Standard,
++    DecisionTimer::Blitz,
++];
++
++/// How long humans have for each decision.
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
++pub enum DecisionTimer {
++    #[default]
++    Off,
++    /// Twice the Standard time.
++    Relaxed,
++    Standard,
++    /// A third of the Standard time.
++    Blitz,
++}
++
++impl DecisionTimer {
++    pub fn label(self) -> &'static str {
++        match self {
++            DecisionTimer::Off => "Off",
++            DecisionTimer::Relaxed => "Relaxed",
++            DecisionTimer::Standard => "Standard",
++            DecisionTimer::Blitz => "Blitz",
++        }
++    }
++
++    /// Seconds a human has for the decision `phase` waits on, or `None` if
++    /// it isn't timed.
++    pub fn seconds(self, phase: TurnPhase) -> Option<f32> {
++        let scale = match self {
++            DecisionTimer::Off => return None,
++            DecisionTimer::Relaxed => 2.0,
++            DecisionTimer::Standard => 1.0,
++            DecisionTimer::Blitz => 1.0 / 3.0,
++        };
++        let seconds = match phase {
++            TurnPhase::RollDice => ROLL_SECONDS,
++            TurnPhase::ShopDecision => SHOP_DECISION_SECONDS,
++            TurnPhase::StockPhase => STOCK_PHASE_SECONDS,
++            _ => return None,
++        };
++        Some(seconds * scale)
++    }
++}
++
++/// The action pressed for a human whose time for `phase` runs out.
++pub fn timeout_action(phase: TurnPhase) -> Option<Action> {
++    match phase {
++        TurnPhase::RollDice | TurnPhase::StockPhase => Some(Action::Roll),
++        TurnPhase::ShopDecision => Some(Action::No),
++        _ => None,
++    }
++}
++
++/// The time `player` has left for the decision `phase` waits on.
++#[derive(Resource)]
++pub struct DecisionClock {
++    pub player: usize,
++    pub phase: TurnPhase,
++    pub timer: Timer,
++}
++
++impl DecisionClock {
++    pub fn new(player: usize, phase: TurnPhase, seconds: f32) -> Self {
++        Self {
++            player,
++            phase,
++            timer: Timer::from_seconds(seconds, TimerMode::Once),
++        }
++    }
++
++    /// Share of the time still left, from 1 down to 0.
++    pub fn fraction_left(&self) -> f32 {
++        self.timer.fraction_remaining()
++    }
++}
++
++#[derive(Component)]
++pub struct DecisionBar;
++
++#[derive(Component)]
++pub struct DecisionBarFill;
++
++pub fn spawn_decision_bar(mut commands: Commands) {
++    commands
++        .spawn((
++            NodeBundle {
++                style: Style {
++                    position_type: PositionType::Absolute,
++                    left: Val::Px(0.0),
++                    right: Val::Px(0.0),
++                    bottom: Val::Px(58.0),
++                    justify_content: JustifyContent::Center,
++                    display: Display::None,
++                    ..Default::default()
++                },
++                ..Default::default()
++            },
++            DecisionBar,
++        ))
++        .with_children(|bar| {
++            bar.spawn(NodeBundle {
++                style: Style {
++                    width: Val::Px(300.0),
++                    height: Val::Px(8.0),
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.6)),
++                ..Default::default()
++            })
++            .with_children(|track| {
++                track.spawn((
++                    NodeBundle {
++                        style: Style {
++                            width: Val::Percent(100.0),
++                            height: Val::Percent(100.0),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(ROLL_BUTTON_COLOR),
++                        ..Default::default()
++                    },
++                    DecisionBarFill,
++                ));
++            });
++        });
++}
++
++/// Times the active human's decision while the setup screen's timer is on.
++/// The clock starts over with each new decision, and when it runs out the
++/// default action is pressed as if they had chosen it. Bots, and humans on
++/// autopilot, are never timed.
++pub fn run_decision_clock(
++    mut commands: Commands,
++    time: Res<Time>,
++    settings: Res<MatchSettings>,
++    game: Res<Game>,
++    phase: Res<State<TurnPhase>>,
++    clock: Option<ResMut<DecisionClock>>,
++    mut actions: ResMut<ActionInput>,
++) {
++    let phase = *phase.get();
++    let current = game.current_turn;
++    let seconds = settings
++        .decision_timer
++        .seconds(phase)
++        .filter(|_| game.winner.is_none() && game.players[current].decider == Decider::Person);
++    let Some(seconds) = seconds else {
++        if clock.is_some() {
++            commands.remove_resource::<DecisionClock>();
++        }
++        return;
++    };
++    match clock {
++        Some(mut clock) if clock.player == current && clock.phase == phase => {
++            if clock.timer.tick(time.delta()).just_finished()
++                && let Some(action) = timeout_action(phase)
++            {
++                actions.press(action);
++            }
++        }
++        _ => commands.insert_resource(DecisionClock::new(current, phase, seconds)),
++    }
++}
++
++/// Shows the bar while a decision is timed, shrinking it with the time left
++/// and turning it red near the end.
++pub fn update_decision_bar(
++    clock: Option<Res<DecisionClock>>,
++    mut bars: Query<&mut Style, (With<DecisionBar>, Without<DecisionBarFill>)>,
++    mut fills: Query<(&mut Style, &mut BackgroundColor), With<DecisionBarFill>>,
++) {
++    let display = if clock.is_some() {
++        Display::Flex
++    } else {
++        Display::None
++    };
++    for mut style in bars.iter_mut() {
++        if style.display != display {
++            style.display = display;
++        }
++    }
++    let Some(clock) = clock else {
++        return;
++    };
++    let left = clock.fraction_left();
++    let width = Val::Percent(left * 100.0);
++    let color = if left < DECISION_BAR_WARNING {
++        Color::rgb(0.85, 0.2, 0.2)
++    } else {
++        ROLL_BUTTON_COLOR
++    };
++    for (mut style, mut background) in fills.iter_mut() {
++        if style.width != width {
++            style.width = width;
++        }
++        if background.0 != color {
++            background.0 = color;
++        }
++    }
++}
++
++/// Drops the clock when the match is left.
++pub fn end_decision_clock(mut commands: Commands) {
++    commands.remove_resource::<DecisionClock>();
++}
@@ -0,0 +1,182 @@
+diff --git a/src/district_map.rs b/src/district_map.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..18fd52162611c26a2e493623bce1b3f2497328e4
+--- /dev/null
++++ b/src/district_map.rs
+@@ -0,0 +1,176 @@
++//! The district map: an overlay that tints every tile in its district's
++//! color, greying out the tiles outside any district, and floats a label over
++//! each district with the total value of its shops and its biggest
++//! shareholder, to help plan monopolies.
++
++use bevy::prelude::*;
++
++use crate::*;
++
++/// Opacity of the district tint laid over each tile.
++pub const DISTRICT_MAP_ALPHA: f32 = 0.75;
++
++/// Tint over tiles that belong to no district.
++pub const DISTRICT_MAP_NEUTRAL: Color = Color::rgba(0.12, 0.12, 0.14, DISTRICT_MAP_ALPHA);
++
++pub const DISTRICT_MAP_LABEL_BACKDROP: Color = Color::rgba(0.05, 0.05, 0.08, 0.85);
++
++/// Tint laid over a tile while the district map is shown.
++#[derive(Component)]
++pub struct DistrictMapTint;
++
++/// Floating label over a district while the district map is shown.
++#[derive(Component)]
++pub struct DistrictMapLabel;
++
++/// What the district map shows for one district.
++pub struct DistrictStats {
++    /// Price plus capital of every shop in the district, owned or not.
++    pub shop_value: i32,
++    /// The player holding the most shares in the district, with their count.
++    pub top_holder: Option<(usize, i32)>,
++}
++
++impl DistrictStats {
++    pub fn of(district: DistrictId, game: &Game) -> Self {
++        let shop_value = game
++            .board
++            .iter()
++            .filter(
++                |tile| matches!(tile.kind, TileKind::Property { district: d, .. } if d == district),
++            )
++            .map(Tile::value)
++            .sum();
++        let top_holder = game
++            .players
++            .iter()
++            .enumerate()
++            .filter(|(_, player)| !player.bankrupt)
++            .filter_map(|(idx, player)| {
++                let shares = player.stocks.get(&district).copied().unwrap_or(0);
++                (shares > 0).then_some((idx, shares))
++            })
++            .max_by_key(|(idx, shares)| (*shares, std::cmp::Reverse(*idx)));
++        Self {
++            shop_value,
++            top_holder,
++        }
++    }
++}
++
++pub fn toggle_district_map(actions: Res<ActionInput>, mut ui_state: ResMut<UiState>) {
++    if actions.just_pressed(Action::DistrictMap) {
++        ui_state.district_map_open = !ui_state.district_map_open;
++    }
++}
++
++/// Redraws the district map when it is toggled, the palette changes, the
++/// board is respawned or a shop, share or capital changes hands or value.
++#[allow(clippy::type_complexity, clippy::too_many_arguments)]
++pub fn update_district_map(
++    mut commands: Commands,
++    ui_state: Res<UiState>,
++    settings: Res<Settings>,
++    game: Res<Game>,
++    locale: Res<Locale>,
++    mut shown: Local<bool>,
++    tiles: Query<(Entity, Ref<TileEntity>)>,
++    overlay: Query<Entity, Or<(With<DistrictMapTint>, With<DistrictMapLabel>)>>,
++) {
++    if game.is_added() {
++        *shown = false;
++    }
++    let open = ui_state.district_map_open;
++    let added = tiles.iter().any(|(_, tile)| tile.is_added());
++    if open == *shown && !(open && (game.is_changed() || settings.is_changed() || added)) {
++        return;
++    }
++    *shown = open;
++    for entity in overlay.iter() {
++        commands.entity(entity).despawn_recursive();
++    }
++    if !open {
++        return;
++    }
++    let palette = settings.palette;
++    for (entity, tile) in tiles.iter() {
++        let color = match game.board[tile.0].kind {
++            TileKind::Property { district, .. } => palette
++                .district_color(&game, district)
++                .with_a(DISTRICT_MAP_ALPHA),
++            _ => DISTRICT_MAP_NEUTRAL,
++        };
++        commands.entity(entity).with_children(|parent| {
++            parent.spawn((
++                SpriteBundle {
++                    sprite: Sprite {
++                        color,
++                        custom_size: Some(Vec2::splat(TILE_SIZE)),
++                        ..Default::default()
++                    },
++                    transform: Transform::from_xyz(0.0, 0.0, 0.8),
++                    ..Default::default()
++                },
++                DistrictMapTint,
++            ));
++        });
++    }
++    for district in game.district_ids() {
++        let positions: Vec<Vec2> = game
++            .board
++            .iter()
++            .filter(
++                |tile| matches!(tile.kind, TileKind::Property { district: d, .. } if d == district),
++            )
++            .map(|tile| tile.position)
++            .collect();
++        if positions.is_empty() {
++            continue;
++        }
++        let center = positions.iter().sum::<Vec2>() / positions.len() as f32;
++        let stats = DistrictStats::of(district, &game);
++        let holder = match stats.top_holder {
++            Some((idx, shares)) => locale.format(
++                "district_map.holder",
++                &[("name", &game.players[idx].name), ("shares", &shares)],
++            ),
++            None => locale.get("district_map.no_holder").to_string(),
++        };
++        let text = locale.format(
++            "district_map.label",
++            &[
++                ("district", &game.district_name(district)),
++                ("value", &stats.shop_value),
++                ("holder", &holder),
++            ],
++        );
++        commands
++            .spawn((
++                SpriteBundle {
++                    sprite: Sprite {
++                        color: DISTRICT_MAP_LABEL_BACKDROP,
++                        custom_size: Some(Vec2::new(TILE_SIZE * 2.4, TILE_SIZE * 0.9)),
++                        ..Default::default()
++                    },
++                    transform: Transform::from_translation(center.extend(5.0)),
++                    ..Default::default()
++                },
++                DistrictMapLabel,
++            ))
++            .with_children(|label| {
++                label.spawn(Text2dBundle {
++                    text: Text::from_section(
++                        text,
++                        TextStyle {
++                            font_size: 13.0,
++                            color: Color::WHITE,
++                            ..Default::default()
++                        },
++                    )
++                    .with_justify(JustifyText::Center),
++                    transform: Transform::from_xyz(0.0, 0.0, 0.1),
++                    ..Default::default()
++                });
++            });
++    }
++}
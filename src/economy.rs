@@ -0,0 +1,1068 @@
+diff --git a/src/economy.rs b/src/economy.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..2e43613d862b034125016c2ed4817d2f2722b6c5
+--- /dev/null
++++ b/src/economy.rs
+@@ -0,0 +1,1062 @@
++//! Money moving between players and the bank: the stock market, salaries,
++//! shop sales and investment, trades, auctions, the casino and debt settlement.
++
++use bevy::prelude::*;
++use rand::Rng;
++use serde::{Deserialize, Serialize};
++use std::collections::HashMap;
++
++use crate::*;
++
++/// Stocks trade in blocks of this many shares.
++pub const MIN_SHARE_ORDER: i32 = 10;
++
++/// Most shares a single player may hold in one district.
++pub const MAX_SHARES_PER_DISTRICT: i32 = 200;
++
++pub const BASE_SHARE_PRICE: i32 = 8;
++
++/// Margin a short seller must keep in cash on top of what buying their
++/// short positions back would cost, in percent.
++pub const SHORT_MARGIN_PERCENT: i32 = 30;
++
++/// Auction bids rise in steps of this many G.
++pub const AUCTION_INCREMENT: i32 = 10;
++
++/// Seconds without a new bid before the auction closes.
++pub const AUCTION_COUNTDOWN: f32 = 3.0;
++
++/// Salary paid on every promotion, before the per-level pay, under Normal
++/// rules.
++pub const SALARY_BASE: i32 = 250;
++
++/// Extra salary per level reached under Normal rules.
++pub const SALARY_PER_LEVEL: i32 = 150;
++
++/// Share of the player's shop value added to their salary, in percent.
++pub const SALARY_SHOP_PERCENT: i32 = 10;
++
++/// Share of the player's stock value added to their salary, in percent.
++pub const SALARY_STOCK_PERCENT: i32 = 5;
++
++/// Most a player may wager at the casino in a single visit.
++pub const CASINO_VISIT_CAP: i32 = 500;
++
++/// Step by which the casino panel adjusts a stake.
++pub const CASINO_STAKE_STEP: i32 = 50;
++
++/// Share of a shop's price the bank pays to buy it back, in percent.
++pub const SHOP_RESALE_PERCENT: i32 = 75;
++
++/// Step by which an owner invests capital in one of their shops.
++pub const INVEST_STEP: i32 = 100;
++
++/// Most capital a shop can hold, as a percentage of its price.
++pub const MAX_SHOP_CAPITAL_PERCENT: i32 = 200;
++
++/// Sentiment shift in a district each time capital is invested there.
++pub const INVEST_SENTIMENT: f32 = 0.02;
++
++/// Share of the base fees of a district's owned shops paid out to its
++/// shareholders whenever anyone completes a lap, in percent.
++pub const DIVIDEND_PERCENT: i32 = 10;
++
++/// Smallest and largest move of a market boom or crash, in percent.
++pub const MARKET_SWING_RANGE: (i32, i32) = (20, 40);
++
++/// Turns after a boom or crash before the next can come up by chance.
++pub const MARKET_CALM_TURNS: u32 = 20;
++
++/// Chance of a boom or crash each turn once the market has been calm for
++/// `MARKET_CALM_TURNS`, in percent.
++pub const MARKET_SWING_CHANCE_PERCENT: u32 = 3;
++
++/// Per-district share prices. A district's fundamental value climbs with the
++/// number of shops bought there and with the shares players have invested in
++/// it; game events then push a per-district sentiment multiplier up or down.
++#[derive(Resource, Default, Clone)]
++pub struct StockMarket {
++    pub prices: HashMap<DistrictId, i32>,
++    pub shares_outstanding: HashMap<DistrictId, i32>,
++    pub sentiment: HashMap<DistrictId, f32>,
++    /// Turns played since the last market boom or crash.
++    pub calm_turns: u32,
++    /// Price moves not yet published as `StockPriceChanged` events.
++    pub pending_changes: Vec<StockPriceChanged>,
++}
++
++/// Sent whenever a district's share price moves.
++#[derive(Event, Debug, Clone, Copy)]
++pub struct StockPriceChanged {
++    pub district: DistrictId,
++    pub old_price: i32,
++    pub new_price: i32,
++}
++
++impl StockMarket {
++    pub fn new(game: &Game) -> Self {
++        let mut market = Self::default();
++        for district in game.district_ids() {
++            market.reprice(district, 0);
++        }
++        market
++    }
++
++    pub fn price(&self, district: DistrictId) -> i32 {
++        self.prices
++            .get(&district)
++            .copied()
++            .unwrap_or(BASE_SHARE_PRICE)
++    }
++
++    /// How much a district's value scales its shop fees. It follows the share
++    /// price, which itself grows with the shops and capital invested there.
++    pub fn value_multiplier(&self, district: DistrictId) -> f32 {
++        let premium = (self.price(district) - BASE_SHARE_PRICE) as f32;
++        (1.0 + premium / (4.0 * BASE_SHARE_PRICE as f32)).max(0.5)
++    }
++
++    /// Price the district would trade at with `shop_count` shops bought.
++    pub fn price_with_shops(&self, district: DistrictId, shop_count: usize) -> i32 {
++        let outstanding = self.shares_outstanding.get(&district).copied().unwrap_or(0);
++        let fundamental = BASE_SHARE_PRICE + 4 * shop_count as i32 + outstanding / 25;
++        let sentiment = self.sentiment.get(&district).copied().unwrap_or(1.0);
++        ((fundamental as f32 * sentiment).round() as i32).max(1)
++    }
++
++    /// Moves `district`'s price to `price` through its sentiment, so later
++    /// repricing carries on from the new price.
++    pub fn set_price(&mut self, district: DistrictId, price: i32, shop_count: usize) {
++        self.sentiment.insert(district, 1.0);
++        let fundamental = self.price_with_shops(district, shop_count);
++        self.sentiment
++            .insert(district, price as f32 / fundamental as f32);
++        self.reprice(district, shop_count);
++    }
++
++    pub fn reprice(&mut self, district: DistrictId, shop_count: usize) {
++        let price = self.price_with_shops(district, shop_count);
++        if let Some(old_price) = self.prices.insert(district, price)
++            && old_price != price
++        {
++            self.pending_changes.push(StockPriceChanged {
++                district,
++                old_price,
++                new_price: price,
++            });
++        }
++    }
++
++    /// Scales the district's sentiment by `1.0 + change` and reprices it.
++    pub fn shift_sentiment(&mut self, district: DistrictId, shop_count: usize, change: f32) {
++        let sentiment = self.sentiment.entry(district).or_insert(1.0);
++        *sentiment = (*sentiment * (1.0 + change)).clamp(0.5, 3.0);
++        self.reprice(district, shop_count);
++    }
++}
++
++/// Sentiment drop in each district where a bankrupt player owned shops.
++pub const BANKRUPTCY_SENTIMENT: f32 = -0.1;
++
++/// Sentiment boost when a shop fee is paid in a district.
++pub const FEE_SENTIMENT: f32 = 0.03;
++
++/// Sentiment change per block of `MIN_SHARE_ORDER` shares bought or sold.
++pub const TRADE_SENTIMENT: f32 = 0.01;
++
++/// Sentiment boost in each district a promoted player holds shares in.
++pub const PROMOTION_SENTIMENT: f32 = 0.05;
++
++/// Gold a district shares out among its shareholders each lap:
++/// `DIVIDEND_PERCENT` of the base fees of its shops that have been bought, so
++/// a district nobody has built up pays nothing.
++pub fn dividend_pool(game: &Game, district: DistrictId) -> i32 {
++    let base_fees: i32 = game
++        .board
++        .iter()
++        .filter(|tile| game.owner_of(tile.index).is_some())
++        .filter_map(|tile| match tile.kind {
++            TileKind::Property {
++                district: d,
++                base_fee,
++                ..
++            } if d == district => Some(base_fee),
++            _ => None,
++        })
++        .sum();
++    base_fees * DIVIDEND_PERCENT / 100
++}
++
++/// Shares of `district` the players hold, not counting short positions.
++pub fn long_shares(game: &Game, district: DistrictId) -> i32 {
++    game.players
++        .iter()
++        .filter_map(|player| player.stocks.get(&district))
++        .map(|shares| (*shares).max(0))
++        .sum()
++}
++
++/// Dividend paid on `shares` for one lap: their part of a district's `pool`,
++/// by the `issued` shares held long there. It follows the shops rather than
++/// the price, so it can't run away as prices climb, and the holders never
++/// share out more than the pool.
++pub fn dividend(shares: i32, issued: i32, pool: i32) -> i32 {
++    let issued = issued.max(shares.abs()).max(1);
++    (pool as i64 * shares as i64 / issued as i64) as i32
++}
++
++/// Pays every shareholder still in the game their dividends, after someone
++/// completes a lap. Short sellers pay the dividend on the shares they owe.
++pub fn pay_dividends(game: &mut Game) {
++    let pools: HashMap<DistrictId, (i32, i32)> = game
++        .district_ids()
++        .into_iter()
++        .map(|district| {
++            let pool = dividend_pool(game, district);
++            (district, (pool, long_shares(game, district)))
++        })
++        .collect();
++    for (player_idx, player) in game.players.iter_mut().enumerate() {
++        if player.bankrupt {
++            continue;
++        }
++        let amount: i32 = player
++            .stocks
++            .iter()
++            .map(|(district, shares)| {
++                let (pool, issued) = pools.get(district).copied().unwrap_or_default();
++                dividend(*shares, issued, pool)
++            })
++            .sum();
++        if amount != 0 {
++            player.cash += amount;
++            game.pending_events.push(GameEvent::DividendsPaid {
++                player: player_idx,
++                amount,
++            });
++        }
++    }
++}
++
++/// Moves every district's price by the same 20-40%: up in a boom, down in a
++/// crash.
++pub fn market_swing(boom: bool, game: &mut Game, market: &mut StockMarket, rng: &mut impl Rng) {
++    let size = rng.gen_range(MARKET_SWING_RANGE.0..=MARKET_SWING_RANGE.1);
++    let percent = if boom { size } else { -size };
++    for district in game.district_ids() {
++        let shop_count = game
++            .district_shop_count
++            .get(&district)
++            .copied()
++            .unwrap_or(0);
++        market.shift_sentiment(district, shop_count, percent as f32 / 100.0);
++    }
++    market.calm_turns = 0;
++    game.pending_events.push(GameEvent::MarketSwing { percent });
++}
++
++/// Counts a turn towards the next boom or crash, and sets one off at random
++/// once the market has been calm for long enough.
++pub fn tick_market(game: &mut Game, market: &mut StockMarket, rng: &mut impl Rng) {
++    market.calm_turns += 1;
++    if market.calm_turns >= MARKET_CALM_TURNS && rng.gen_range(0..100) < MARKET_SWING_CHANCE_PERCENT
++    {
++        let boom = rng.gen_bool(0.5);
++        market_swing(boom, game, market, rng);
++    }
++}
++
++/// Buys `shares` of `district` for the player at the current market price.
++pub fn buy_stock(
++    player_idx: usize,
++    district: DistrictId,
++    shares: i32,
++    game: &mut Game,
++    market: &mut StockMarket,
++) -> Result<i32, &'static str> {
++    if shares < MIN_SHARE_ORDER || shares % MIN_SHARE_ORDER != 0 {
++        return Err("Orders must be in blocks of 10 shares");
++    }
++    let player = &mut game.players[player_idx];
++    let held = player.stocks.get(&district).copied().unwrap_or(0);
++    if held + shares > MAX_SHARES_PER_DISTRICT {
++        return Err("Ownership cap reached for this district");
++    }
++    let cost = shares * market.price(district);
++    if player.cash < cost {
++        return Err("Not enough cash");
++    }
++    player.cash -= cost;
++    player.add_shares(district, shares, cost);
++    // Buying back shares sold short returns them to the bank rather than
++    // issuing new ones.
++    let covered = shares.min((-held).max(0));
++    *market.shares_outstanding.entry(district).or_default() += shares - covered;
++    let shop_count = game
++        .district_shop_count
++        .get(&district)
++        .copied()
++        .unwrap_or(0);
++    let blocks = (shares / MIN_SHARE_ORDER) as f32;
++    market.shift_sentiment(district, shop_count, TRADE_SENTIMENT * blocks);
++    Ok(cost)
++}
++
++/// Sells `shares` of `district` back to the market at the current price.
++/// Under the short-selling house rule the player may sell more than they
++/// hold, borrowing the rest from the bank, as long as their cash covers the
++/// margin on all their short positions afterwards.
++pub fn sell_stock(
++    player_idx: usize,
++    district: DistrictId,
++    shares: i32,
++    game: &mut Game,
++    market: &mut StockMarket,
++) -> Result<i32, &'static str> {
++    if shares < MIN_SHARE_ORDER || shares % MIN_SHARE_ORDER != 0 {
++        return Err("Orders must be in blocks of 10 shares");
++    }
++    let short_selling = game.rules.house.short_selling;
++    let player = &mut game.players[player_idx];
++    let held = player.stocks.get(&district).copied().unwrap_or(0);
++    let price = market.price(district);
++    let proceeds = shares * price;
++    if held - shares < 0 {
++        if !short_selling {
++            return Err("Not enough shares to sell");
++        }
++        if held - shares < -MAX_SHARES_PER_DISTRICT {
++            return Err("Short limit reached for this district");
++        }
++        let newly_short = (shares - held.max(0)) * price;
++        let required =
++            (player.short_value(market) + newly_short).percent(100 + SHORT_MARGIN_PERCENT);
++        if player.cash + proceeds < required {
++            return Err("Not enough cash to cover the margin");
++        }
++    }
++    player.cash += proceeds;
++    player.add_shares(district, -shares, -proceeds);
++    // Shares sold short are borrowed from the bank, so only shares the player
++    // owned leave the market.
++    let closed = shares.min(held.max(0));
++    *market.shares_outstanding.entry(district).or_default() -= closed;
++    let shop_count = game
++        .district_shop_count
++        .get(&district)
++        .copied()
++        .unwrap_or(0);
++    let blocks = (shares / MIN_SHARE_ORDER) as f32;
++    market.shift_sentiment(district, shop_count, -TRADE_SENTIMENT * blocks);
++    Ok(proceeds)
++}
++
++/// Calls in short positions whose margin has run out. While a short seller
++/// holds less cash than `PlayerState::margin_required`, the bank buys back
++/// their largest short a block at a time at the current price. Cash can go
++/// negative doing so, and is then settled like any other debt.
++pub fn margin_calls(game: &mut Game, market: &mut StockMarket) {
++    for player_idx in 0..game.players.len() {
++        let mut covered: Vec<(DistrictId, i32, i32)> = Vec::new();
++        loop {
++            let player = &mut game.players[player_idx];
++            if player.bankrupt || player.cash >= player.margin_required(market) {
++                break;
++            }
++            let Some(district) = player
++                .stocks
++                .iter()
++                .filter(|(_, shares)| **shares < 0)
++                .max_by_key(|(district, shares)| (-**shares * market.price(**district), **district))
++                .map(|(district, _)| *district)
++            else {
++                break;
++            };
++            let held = player.stocks[&district];
++            let shares = MIN_SHARE_ORDER.min(-held);
++            let cost = shares * market.price(district);
++            player.cash -= cost;
++            player.add_shares(district, shares, cost);
++            match covered.iter_mut().find(|(d, ..)| *d == district) {
++                Some(entry) => {
++                    entry.1 += shares;
++                    entry.2 += cost;
++                }
++                None => covered.push((district, shares, cost)),
++            }
++            let shop_count = game
++                .district_shop_count
++                .get(&district)
++                .copied()
++                .unwrap_or(0);
++            let blocks = (shares / MIN_SHARE_ORDER) as f32;
++            market.shift_sentiment(district, shop_count, TRADE_SENTIMENT * blocks);
++        }
++        for (district, shares, cost) in covered {
++            game.pending_events.push(GameEvent::MarginCall {
++                player: player_idx,
++                district,
++                shares,
++                cost,
++            });
++        }
++    }
++}
++
++/// One side of a trade: what a player hands over.
++#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
++pub struct TradeOffer {
++    pub shops: Vec<usize>,
++    /// Shares per district.
++    pub stocks: HashMap<DistrictId, i32>,
++    pub cash: i32,
++}
++
++impl TradeOffer {
++    pub fn is_empty(&self) -> bool {
++        self.shops.is_empty() && self.stocks.is_empty() && self.cash == 0
++    }
++
++    pub fn describe(&self, game: &Game) -> String {
++        let mut parts: Vec<String> = self
++            .shops
++            .iter()
++            .filter_map(|index| match game.board[*index].kind {
++                TileKind::Property { district, .. } => {
++                    Some(format!("{} shop #{}", game.district_name(district), index))
++                }
++                _ => None,
++            })
++            .collect();
++        let mut stocks: Vec<_> = self.stocks.iter().collect();
++        stocks.sort();
++        parts.extend(stocks.into_iter().map(|(district, shares)| {
++            format!("{} {} shares", shares, game.district_name(*district))
++        }));
++        if self.cash > 0 {
++            parts.push(Money(self.cash).to_string());
++        }
++        if parts.is_empty() {
++            "nothing".to_string()
++        } else {
++            parts.join(", ")
++        }
++    }
++}
++
++/// A swap proposed by `from` to `to`.
++#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
++pub struct TradeProposal {
++    pub from: usize,
++    pub to: usize,
++    /// What `from` hands over.
++    pub give: TradeOffer,
++    /// What `from` asks for in return.
++    pub take: TradeOffer,
++}
++
++pub enum TradeResponse {
++    Accept,
++    Reject,
++    /// The bot would take the deal if the proposer added this much cash.
++    Counter(TradeProposal),
++}
++
++/// Whether `player_idx` actually holds everything in `offer`.
++pub fn can_hand_over(player_idx: usize, offer: &TradeOffer, game: &Game) -> bool {
++    let player = &game.players[player_idx];
++    offer.cash >= 0
++        && player.cash >= offer.cash
++        && offer
++            .shops
++            .iter()
++            .all(|index| player.properties.contains(index))
++        && offer.stocks.iter().all(|(district, shares)| {
++            *shares > 0 && player.stocks.get(district).copied().unwrap_or(0) >= *shares
++        })
++}
++
++/// Whether `player_idx` can receive the shares in `offer` without passing
++/// `MAX_SHARES_PER_DISTRICT`.
++pub fn can_receive(player_idx: usize, offer: &TradeOffer, game: &Game) -> bool {
++    let player = &game.players[player_idx];
++    offer.stocks.iter().all(|(district, shares)| {
++        player.stocks.get(district).copied().unwrap_or(0) + shares <= MAX_SHARES_PER_DISTRICT
++    })
++}
++
++pub fn transfer(offer: &TradeOffer, from: usize, to: usize, game: &mut Game) {
++    game.players[from].cash -= offer.cash;
++    game.players[to].cash += offer.cash;
++    for index in &offer.shops {
++        game.players[from].properties.remove(index);
++        game.players[to].properties.insert(*index);
++        game.note_ownership(*index, Some(to));
++    }
++    for (district, shares) in &offer.stocks {
++        // The shares carry their share of the giver's cost basis with them.
++        let basis = game.players[from].basis_of(*district, *shares);
++        game.players[from].add_shares(*district, -shares, -basis);
++        game.players[to].add_shares(*district, *shares, basis);
++    }
++}
++
++/// Swaps both sides of an accepted trade, or changes nothing if either side
++/// can no longer deliver.
++pub fn execute_trade(proposal: &TradeProposal, game: &mut Game) -> Result<(), &'static str> {
++    let (from, to) = (proposal.from, proposal.to);
++    if from == to || game.players[from].bankrupt || game.players[to].bankrupt {
++        return Err("Not a valid trading partner");
++    }
++    if proposal.give.is_empty() && proposal.take.is_empty() {
++        return Err("The offer is empty");
++    }
++    if !can_hand_over(from, &proposal.give, game) || !can_hand_over(to, &proposal.take, game) {
++        return Err("One side no longer holds what was offered");
++    }
++    if !can_receive(to, &proposal.give, game) || !can_receive(from, &proposal.take, game) {
++        return Err("Would exceed the 200 share limit");
++    }
++    transfer(&proposal.give, from, to, game);
++    transfer(&proposal.take, to, from, game);
++    game.pending_events.push(GameEvent::Traded { from, to });
++    Ok(())
++}
++
++/// How a promotion salary was made up.
++#[derive(Debug, Clone, Copy)]
++pub struct SalaryBreakdown {
++    pub level_pay: i32,
++    pub shop_bonus: i32,
++    pub stock_bonus: i32,
++    /// Extra pay for landing exactly on the bank, under
++    /// `BankLanding::SalaryBonus`.
++    pub landing_bonus: i32,
++}
++
++impl SalaryBreakdown {
++    /// Salary for reaching `level`: the rules' base plus pay per level, topped
++    /// up by a share of the player's shop and stock holdings.
++    pub fn for_player(
++        player: &PlayerState,
++        level: u32,
++        rules: &GameRules,
++        board: &[Tile],
++        market: &StockMarket,
++    ) -> Self {
++        Self {
++            level_pay: rules.salary_base + rules.salary_per_level * level as i32,
++            shop_bonus: player.property_value(board).percent(SALARY_SHOP_PERCENT),
++            stock_bonus: player
++                .stock_value(market)
++                .max(Money::ZERO)
++                .percent(SALARY_STOCK_PERCENT),
++            landing_bonus: 0,
++        }
++    }
++
++    pub fn total(&self) -> i32 {
++        self.level_pay + self.shop_bonus + self.stock_bonus + self.landing_bonus
++    }
++}
++
++/// Sent when a player is promoted at the bank and paid their salary.
++#[derive(Event, Debug, Clone)]
++pub struct SalaryPaid {
++    pub player: usize,
++    pub level: u32,
++    pub breakdown: SalaryBreakdown,
++    /// Districts whose stock rose because the player holds shares there.
++    pub boosted_districts: Vec<DistrictId>,
++    /// Loan paid off out of the salary.
++    pub loan_repaid: i32,
++}
++
++/// Passing or landing on the bank with all four suits promotes the player:
++/// they level up, collect a salary, lift the stock of every district they
++/// hold shares in, and start a new suit collection. Any outstanding loan is
++/// repaid from their cash first. Having `landed` exactly on the bank earns
++/// the house rules' `bank_landing` bonus on top.
++pub fn visit_bank(player_idx: usize, landed: bool, game: &mut Game, market: &mut StockMarket) {
++    let bank_landing = if landed {
++        game.rules.house.bank_landing
++    } else {
++        BankLanding::Nothing
++    };
++    let player = &mut game.players[player_idx];
++    if bank_landing == BankLanding::FreeSuit
++        && let Some(suit) = player.missing_suits().first().copied()
++    {
++        player.suits.insert(suit);
++        game.pending_events.push(GameEvent::CollectedSuit {
++            player: player_idx,
++            suit,
++        });
++    }
++    let player = &mut game.players[player_idx];
++    if player.suits.len() != 4 {
++        return;
++    }
++    player.level += 1;
++    let mut breakdown =
++        SalaryBreakdown::for_player(player, player.level, &game.rules, &game.board, market);
++    if bank_landing == BankLanding::SalaryBonus {
++        breakdown.landing_bonus = breakdown.total() * BANK_LANDING_SALARY_PERCENT / 100;
++    }
++    player.cash += breakdown.total();
++    player.suits.clear();
++    let loan_repaid = player.repay(player.debt.get());
++    let level = player.level;
++
++    let mut boosted_districts: Vec<DistrictId> = player
++        .stocks
++        .iter()
++        .filter(|(_, shares)| **shares > 0)
++        .map(|(district, _)| *district)
++        .collect();
++    boosted_districts.sort_unstable();
++    for district in &boosted_districts {
++        let shop_count = game.district_shop_count.get(district).copied().unwrap_or(0);
++        market.shift_sentiment(*district, shop_count, PROMOTION_SENTIMENT);
++    }
++    game.pending_salaries.push(SalaryPaid {
++        player: player_idx,
++        level,
++        breakdown,
++        boosted_districts,
++        loan_repaid,
++    });
++}
++
++/// The active player's stay at a casino tile. Bets are double-or-nothing on
++/// the sum of two dice landing high (8-12) or low (2-6); a 7 goes to the
++/// house. Stakes across the visit add up to at most `CASINO_VISIT_CAP`.
++#[derive(Resource)]
++pub struct CasinoVisit {
++    pub stake: i32,
++    pub wagered: i32,
++    pub last_result: String,
++}
++
++impl CasinoVisit {
++    pub fn max_stake(&self, cash: Money) -> i32 {
++        (CASINO_VISIT_CAP - self.wagered).min(cash.available())
++    }
++}
++
++/// Rolls two dice for a high/low bet and settles it, reporting the roll in a
++/// `GameEvent::PlayedHighLow`.
++pub fn play_high_low(
++    bet_high: bool,
++    stake: i32,
++    player_idx: usize,
++    game: &mut Game,
++    rng: &mut impl Rng,
++) {
++    let roll = rng.gen_range(1..=6) + rng.gen_range(1..=6);
++    let won = match roll {
++        7 => false,
++        8.. => bet_high,
++        _ => !bet_high,
++    };
++    let player = &mut game.players[player_idx];
++    if won {
++        player.cash += stake;
++    } else {
++        player.cash -= stake;
++    }
++    game.pending_events.push(GameEvent::PlayedHighLow {
++        player: player_idx,
++        roll,
++        stake,
++        won,
++    });
++}
++
++/// Sent when a player cannot cover their debt and is eliminated.
++#[derive(Event, Debug, Clone, Copy)]
++pub struct PlayerBankrupt {
++    pub player: usize,
++}
++
++/// Covers a negative cash balance by selling the player's stocks, then
++/// auctioning their shops (selling any left unsold to the bank). Returns
++/// `false` if the debt could not be paid, in which case the player is
++/// eliminated and their remaining shops return to the bank. Under the
++/// no-negative-cash house rule the debt is written off instead.
++pub fn settle_debt(player_idx: usize, game: &mut Game, market: &mut StockMarket) -> bool {
++    if game.rules.house.no_negative_cash {
++        let player = &mut game.players[player_idx];
++        player.cash = player.cash.max(Money::ZERO);
++        return true;
++    }
++    let holdings: Vec<(DistrictId, i32)> = game.players[player_idx]
++        .stocks
++        .iter()
++        .map(|(district, shares)| (*district, *shares))
++        .collect();
++    for (district, shares) in holdings {
++        if !game.players[player_idx].cash.is_debt() {
++            return true;
++        }
++        let _ = sell_stock(player_idx, district, shares, game, market);
++    }
++
++    let mut shops: Vec<usize> = game.players[player_idx]
++        .properties
++        .iter()
++        .copied()
++        .collect();
++    shops.sort_unstable();
++    for tile_index in shops {
++        if !game.players[player_idx].cash.is_debt() {
++            return true;
++        }
++        auction_shop(tile_index, player_idx, game);
++    }
++    // Shops nobody could afford at auction go back to the bank.
++    let unsold: Vec<usize> = game.players[player_idx]
++        .properties
++        .iter()
++        .copied()
++        .collect();
++    for tile_index in unsold {
++        if !game.players[player_idx].cash.is_debt() {
++            return true;
++        }
++        let _ = sell_shop_to_bank(tile_index, player_idx, game, market);
++    }
++    if !game.players[player_idx].cash.is_debt() {
++        return true;
++    }
++
++    let player = &mut game.players[player_idx];
++    player.bankrupt = true;
++    player.cash = Money::ZERO;
++    // The bank writes off any shares the player still owes.
++    player.stocks.retain(|_, shares| *shares > 0);
++    let stocks = &player.stocks;
++    player
++        .cost_basis
++        .retain(|district, _| stocks.contains_key(district));
++    let forfeited: Vec<usize> = player.properties.drain().collect();
++    for tile_index in forfeited {
++        game.note_ownership(tile_index, None);
++        game.board[tile_index].capital = 0;
++        if let TileKind::Property { district, .. } = game.board[tile_index].kind {
++            let shop_count = game.district_shop_count.entry(district).or_default();
++            *shop_count = shop_count.saturating_sub(1);
++            let shop_count = *shop_count;
++            market.shift_sentiment(district, shop_count, BANKRUPTCY_SENTIMENT);
++        }
++    }
++    false
++}
++
++/// Settles every player left with negative cash through the `ActionLog`,
++/// sending `PlayerBankrupt` for each one eliminated. Returns whether the
++/// active player went bankrupt.
++pub fn settle_debts(
++    log: &mut ActionLog,
++    game: &mut Game,
++    market: &mut StockMarket,
++    rng: &mut MatchRng,
++    game_events: &mut EventWriter<GameEvent>,
++    bankrupt_events: &mut EventWriter<PlayerBankrupt>,
++) -> bool {
++    let was_bankrupt: Vec<bool> = game.players.iter().map(|player| player.bankrupt).collect();
++    if game
++        .players
++        .iter()
++        .any(|player| !player.bankrupt && player.cash.is_debt())
++    {
++        game_events.send_batch(
++            log.apply(TurnAction::SettleDebts, game, market, rng)
++                .unwrap_or_default(),
++        );
++    }
++    for (player, was_bankrupt) in was_bankrupt.into_iter().enumerate() {
++        if !was_bankrupt && game.players[player].bankrupt {
++            bankrupt_events.send(PlayerBankrupt { player });
++        }
++    }
++    game.players[game.current_turn].bankrupt
++}
++
++/// Forced sale of a shop during liquidation: it goes to the other player who
++/// can pay its full value and has the most cash left over. Unsold shops stay
++/// with the seller.
++pub fn auction_shop(tile_index: usize, seller: usize, game: &mut Game) {
++    if !matches!(game.board[tile_index].kind, TileKind::Property { .. }) {
++        return;
++    }
++    let price = game.board[tile_index].value();
++    let winner = game
++        .players
++        .iter()
++        .enumerate()
++        .filter(|(idx, p)| *idx != seller && !p.bankrupt && p.cash >= price)
++        .max_by_key(|(_, p)| p.cash)
++        .map(|(idx, _)| idx);
++    let Some(winner) = winner else {
++        return;
++    };
++    game.players[winner].cash -= price;
++    game.players[winner].properties.insert(tile_index);
++    game.players[seller].cash += price;
++    game.players[seller].properties.remove(&tile_index);
++    game.note_ownership(tile_index, Some(winner));
++}
++
++/// A vacant shop going under the hammer. Everyone but the player who passed
++/// on it may bid; each bid restarts the countdown, and when it runs out the
++/// leader buys the shop for their bid.
++#[derive(Resource)]
++pub struct Auction {
++    pub tile: usize,
++    pub declined_by: usize,
++    pub high_bid: i32,
++    pub leader: Option<usize>,
++    pub countdown: Timer,
++    /// Paces bot bids so humans can follow (and answer) them.
++    pub bot_timer: Timer,
++}
++
++impl Auction {
++    /// Opens bidding on a shop at half its price, rounded down to the bid step.
++    pub fn open(tile: usize, declined_by: usize, price: i32) -> Self {
++        Self {
++            tile,
++            declined_by,
++            high_bid: (price / 2 / AUCTION_INCREMENT * AUCTION_INCREMENT).max(AUCTION_INCREMENT),
++            leader: None,
++            countdown: Timer::from_seconds(AUCTION_COUNTDOWN, TimerMode::Once),
++            bot_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
++        }
++    }
++
++    pub fn next_bid(&self) -> i32 {
++        match self.leader {
++            Some(_) => self.high_bid + AUCTION_INCREMENT,
++            None => self.high_bid,
++        }
++    }
++
++    pub fn can_bid(&self, player_idx: usize, game: &Game) -> bool {
++        let player = &game.players[player_idx];
++        player_idx != self.declined_by
++            && self.leader != Some(player_idx)
++            && !player.bankrupt
++            && player.cash >= self.next_bid()
++    }
++}
++
++/// Sells an owned shop back to the bank for `SHOP_RESALE_PERCENT` of its
++/// value, capital included. The district loses a shop, so its stock price
++/// falls.
++pub fn sell_shop_to_bank(
++    tile_index: usize,
++    player_idx: usize,
++    game: &mut Game,
++    market: &mut StockMarket,
++) -> Result<i32, &'static str> {
++    let TileKind::Property { district, .. } = game.board[tile_index].kind else {
++        return Err("Not a shop");
++    };
++    let player = &mut game.players[player_idx];
++    if !player.properties.remove(&tile_index) {
++        return Err("You don't own that shop");
++    }
++    let proceeds = game.board[tile_index].value() * SHOP_RESALE_PERCENT / 100;
++    player.cash += proceeds;
++    game.board[tile_index].capital = 0;
++    game.note_ownership(tile_index, None);
++    let shop_count = game.district_shop_count.entry(district).or_default();
++    *shop_count = shop_count.saturating_sub(1);
++    market.reprice(district, *shop_count);
++    Ok(proceeds)
++}
++
++/// Invests `INVEST_STEP` of the owner's cash in a shop, raising its value and
++/// therefore its fee. Returns the shop's new capital.
++pub fn invest_in_shop(
++    tile_index: usize,
++    player_idx: usize,
++    game: &mut Game,
++    market: &mut StockMarket,
++) -> Result<i32, &'static str> {
++    let TileKind::Property { district, .. } = game.board[tile_index].kind else {
++        return Err("Not a shop");
++    };
++    if game.owner_of(tile_index) != Some(player_idx) {
++        return Err("You don't own that shop");
++    }
++    if game.board[tile_index].capital_room() < INVEST_STEP {
++        return Err("This shop can't take any more capital");
++    }
++    let player = &mut game.players[player_idx];
++    if player.cash < INVEST_STEP {
++        return Err("Not enough cash to invest");
++    }
++    player.cash -= INVEST_STEP;
++    let tile = &mut game.board[tile_index];
++    tile.capital += INVEST_STEP;
++    let capital = tile.capital;
++    let shop_count = game
++        .district_shop_count
++        .get(&district)
++        .copied()
++        .unwrap_or(0);
++    market.shift_sentiment(district, shop_count, INVEST_SENTIMENT);
++    game.note_investment(tile_index);
++    game.pending_events.push(GameEvent::Invested {
++        player: player_idx,
++        tile: tile_index,
++        amount: INVEST_STEP,
++    });
++    Ok(capital)
++}
++
++pub fn buy_property(
++    tile_index: usize,
++    player_idx: usize,
++    game: &mut Game,
++    market: &mut StockMarket,
++) {
++    if let TileKind::Property { price, .. } = game.board[tile_index].kind {
++        sell_vacant_shop(tile_index, player_idx, price, game, market);
++    }
++}
++
++/// Hands a vacant shop to `player_idx` for `cost`, counting it towards its
++/// district's stock price.
++pub fn sell_vacant_shop(
++    tile_index: usize,
++    player_idx: usize,
++    cost: i32,
++    game: &mut Game,
++    market: &mut StockMarket,
++) {
++    let TileKind::Property { district, .. } = game.board[tile_index].kind else {
++        return;
++    };
++    let buyer = &mut game.players[player_idx];
++    if buyer.cash < cost {
++        return;
++    }
++    buyer.cash -= cost;
++    buyer.properties.insert(tile_index);
++    game.note_ownership(tile_index, Some(player_idx));
++    let shop_count = game.district_shop_count.entry(district).or_default();
++    *shop_count += 1;
++    market.reprice(district, *shop_count);
++    game.pending_events.push(GameEvent::BoughtShop {
++        player: player_idx,
++        tile: tile_index,
++        cost,
++    });
++}
++
++#[cfg(test)]
++mod tests {
++    use super::*;
++    use crate::sim::load_board_files;
++
++    /// A lap's dividends stay within a few percent of the smallest target on
++    /// every shipped board, even with every shop bought, every share held by
++    /// one player and prices far above anything a match reaches.
++    #[test]
++    fn dividend_lap_stays_small() {
++        let target = TARGET_NET_WORTHS[0];
++        for (name, definition) in load_board_files().expect("the board files load") {
++            let seats: Vec<Seat> = (0..2)
++                .map(|character| Seat {
++                    name: format!("Player {}", character + 1),
++                    kind: PlayerKind::Bot,
++                    strategy: BotStrategy::Balanced,
++                    character,
++                })
++                .collect();
++            let mut game = Game::new(&definition, &seats, target, GameRules::default());
++            let mut market = StockMarket::new(&game);
++            for tile in 0..game.board.len() {
++                if let TileKind::Property { district, .. } = game.board[tile].kind {
++                    game.players[0].properties.insert(tile);
++                    *game.district_shop_count.entry(district).or_default() += 1;
++                }
++            }
++            for district in game.district_ids() {
++                game.players[0].add_shares(district, MAX_SHARES_PER_DISTRICT, 0);
++                market
++                    .shares_outstanding
++                    .insert(district, MAX_SHARES_PER_DISTRICT);
++                let shop_count = game.district_shop_count[&district];
++                market.set_price(district, 10_000, shop_count);
++            }
++            let before = game.players[0].cash;
++            pay_dividends(&mut game);
++            let paid = (game.players[0].cash - before).get();
++            assert!(paid > 0, "{} paid no dividends", name);
++            assert!(
++                paid * 100 <= target * 3,
++                "a dividend lap on {} paid {}, over 3% of {}",
++                name,
++                Money(paid),
++                Money(target)
++            );
++        }
++    }
++
++    /// Short positions pay their dividends but don't shrink the shares the
++    /// pool is split by, so the holders are paid the pool and no more.
++    #[test]
++    fn short_positions_dont_inflate_dividends() {
++        let boards = load_board_files().expect("the board files load");
++        let seats: Vec<Seat> = (0..3)
++            .map(|character| Seat {
++                name: format!("Player {}", character + 1),
++                kind: PlayerKind::Bot,
++                strategy: BotStrategy::Balanced,
++                character,
++            })
++            .collect();
++        let rules = GameRules {
++            starting_cash: 20_000,
++            ..Default::default()
++        };
++        let mut game = Game::new(&boards[0].1, &seats, TARGET_NET_WORTHS[0], rules);
++        game.rules.house.short_selling = true;
++        let mut market = StockMarket::new(&game);
++        let (shop, district) = (0..game.board.len())
++            .find_map(|tile| match game.board[tile].kind {
++                TileKind::Property { district, .. } => Some((tile, district)),
++                _ => None,
++            })
++            .expect("the board has shops");
++        buy_property(shop, 0, &mut game, &mut market);
++        buy_stock(0, district, 100, &mut game, &mut market).expect("the buy goes through");
++        buy_stock(1, district, 50, &mut game, &mut market).expect("the buy goes through");
++        sell_stock(2, district, 100, &mut game, &mut market).expect("the short goes through");
++        buy_stock(2, district, 50, &mut game, &mut market).expect("the cover goes through");
++        assert_eq!(game.players[2].stocks[&district], -50);
++
++        let pool = dividend_pool(&game, district);
++        assert!(pool > 0);
++        let before: Vec<i32> = game
++            .players
++            .iter()
++            .map(|player| player.cash.get())
++            .collect();
++        pay_dividends(&mut game);
++        let paid: Vec<i32> = game
++            .players
++            .iter()
++            .zip(&before)
++            .map(|(player, before)| player.cash.get() - before)
++            .collect();
++        assert!(paid[0] + paid[1] <= pool, "{:?} out of {}", paid, pool);
++        assert_eq!(paid[0], pool * 100 / 150);
++        assert_eq!(paid[1], pool * 50 / 150);
++        assert_eq!(paid[2], -(pool * 50 / 150));
++    }
++}
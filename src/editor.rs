@@ -0,0 +1,622 @@
+diff --git a/src/editor.rs b/src/editor.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..c2de321d89d00ad5eb648bb03eef1c2b96d2afc2
+--- /dev/null
++++ b/src/editor.rs
+@@ -0,0 +1,616 @@
++//! The board editor: tiles are placed on a grid and given kinds, districts,
++//! prices and exits, then checked with `BoardDefinition::validate` and written
++//! to the boards folder as a `*.board.ron` file, ready to play straight away.
++
++use bevy::prelude::*;
++use std::path::PathBuf;
++
++use crate::*;
++
++/// District names the number keys give a shop, in order.
++pub const EDITOR_DISTRICTS: [&str; MAX_DISTRICTS] = [
++    "Downtown",
++    "Plaza",
++    "Harbor",
++    "Grove",
++    "Backstreet",
++    "Market",
++    "Old Town",
++    "Orchard",
++    "Meadow",
++];
++
++/// Price of a newly placed shop.
++pub const EDITOR_SHOP_PRICE: i32 = 250;
++
++/// How much `+`/`-` change a shop's price.
++pub const EDITOR_PRICE_STEP: i32 = 20;
++
++/// Toll of a newly placed toll gate, and how much `+`/`-` change it.
++pub const EDITOR_TOLL_STEP: i32 = 20;
++
++/// A shop's base fee as a share of its price, in percent, in line with the
++/// bundled boards.
++pub const EDITOR_FEE_PERCENT: i32 = 27;
++
++/// Empty cells drawn around the tiles, so there is room to grow the board.
++pub const EDITOR_GRID_PADDING: i32 = 2;
++
++pub const EDITOR_GRID_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.06);
++
++pub const EDITOR_CURSOR_COLOR: Color = Color::rgb(0.95, 0.95, 0.95);
++
++pub const EDITOR_LINK_COLOR: Color = Color::rgb(0.95, 0.8, 0.2);
++
++/// Problems with the draft listed in the editor panel before the rest are
++/// summed up.
++pub const EDITOR_PROBLEMS_SHOWN: usize = 4;
++
++/// The board being edited and the editor's cursor.
++#[derive(Resource)]
++pub struct BoardEditor {
++    pub draft: BoardDefinition,
++    /// Grid cell under the cursor.
++    pub cursor: (i32, i32),
++    /// Tile the next link starts from, once one end has been picked.
++    pub link_from: Option<usize>,
++    pub message: String,
++}
++
++impl BoardEditor {
++    /// Edits a copy of `board`, saved under a new name so the original is
++    /// left alone.
++    pub fn new(board: &BoardDefinition) -> Self {
++        let mut draft = board.clone();
++        if !draft.name.ends_with(" (edited)") {
++            draft.name.push_str(" (edited)");
++        }
++        Self::from_draft(draft)
++    }
++
++    pub fn blank() -> Self {
++        Self::from_draft(BoardDefinition {
++            name: "Custom Board".to_string(),
++            districts: Vec::new(),
++            tiles: Vec::new(),
++            venture_cards: Vec::new(),
++        })
++    }
++
++    fn from_draft(draft: BoardDefinition) -> Self {
++        Self {
++            cursor: draft.tiles.first().map_or((0, 0), |tile| tile.cell),
++            draft,
++            link_from: None,
++            message: String::new(),
++        }
++    }
++
++    /// The tile under the cursor.
++    pub fn selected(&self) -> Option<usize> {
++        self.draft
++            .tiles
++            .iter()
++            .position(|tile| tile.cell == self.cursor)
++    }
++
++    /// Adds a tile under the cursor as the last step of the path. The first
++    /// tile placed is the bank and later ones are shops.
++    pub fn place(&mut self) {
++        if self.selected().is_some() {
++            return;
++        }
++        let kind = if self.draft.tiles.is_empty() {
++            TileKindDefinition::Bank
++        } else {
++            shop_definition(EDITOR_DISTRICTS[0], EDITOR_SHOP_PRICE)
++        };
++        self.draft.tiles.push(TileDefinition {
++            kind,
++            cell: self.cursor,
++            next: Vec::new(),
++            script: None,
++        });
++    }
++
++    /// Removes tile `index` and renumbers the exits and warps that pointed
++    /// past it. Exits to it are dropped and warps to it go to the first tile.
++    pub fn remove(&mut self, index: usize) {
++        self.draft.tiles.remove(index);
++        let renumber = |target: usize| if target > index { target - 1 } else { target };
++        for tile in &mut self.draft.tiles {
++            tile.next.retain(|exit| *exit != index);
++            for exit in &mut tile.next {
++                *exit = renumber(*exit);
++            }
++            if let TileKindDefinition::Warp { destination } = &mut tile.kind {
++                *destination = if *destination == index {
++                    0
++                } else {
++                    renumber(*destination)
++                };
++            }
++        }
++        self.link_from = None;
++    }
++
++    /// Changes tile `index` to the next kind, from shop through the bank,
++    /// the four suits and the special tiles back to shop.
++    pub fn cycle_kind(&mut self, index: usize) {
++        let kind = &mut self.draft.tiles[index].kind;
++        *kind = match kind {
++            TileKindDefinition::Property { .. } => TileKindDefinition::Bank,
++            TileKindDefinition::Bank => TileKindDefinition::Suit(Suit::Spade),
++            TileKindDefinition::Suit(Suit::Spade) => TileKindDefinition::Suit(Suit::Heart),
++            TileKindDefinition::Suit(Suit::Heart) => TileKindDefinition::Suit(Suit::Diamond),
++            TileKindDefinition::Suit(Suit::Diamond) => TileKindDefinition::Suit(Suit::Club),
++            TileKindDefinition::Suit(Suit::Club) => TileKindDefinition::SuitYourself,
++            TileKindDefinition::SuitYourself => TileKindDefinition::Chance,
++            TileKindDefinition::Chance => TileKindDefinition::TakeABreak,
++            TileKindDefinition::TakeABreak => TileKindDefinition::TaxOffice,
++            TileKindDefinition::TaxOffice => TileKindDefinition::Arcade,
++            TileKindDefinition::Arcade => TileKindDefinition::Casino,
++            TileKindDefinition::Casino => TileKindDefinition::Warp { destination: 0 },
++            TileKindDefinition::Warp { .. } => TileKindDefinition::Boon,
++            TileKindDefinition::Boon => TileKindDefinition::TollGate {
++                toll: EDITOR_TOLL_STEP,
++            },
++            TileKindDefinition::TollGate { .. } => {
++                shop_definition(EDITOR_DISTRICTS[0], EDITOR_SHOP_PRICE)
++            }
++        };
++    }
++
++    /// Moves a shop's price or a toll gate's toll by `delta` steps, keeping a
++    /// shop's fee in proportion, or a warp's destination by `delta` tiles.
++    pub fn adjust(&mut self, index: usize, delta: i32) {
++        let len = self.draft.tiles.len();
++        match &mut self.draft.tiles[index].kind {
++            TileKindDefinition::Property {
++                district, price, ..
++            } => {
++                let price = (*price + delta * EDITOR_PRICE_STEP).max(EDITOR_PRICE_STEP);
++                let district = district.clone();
++                self.draft.tiles[index].kind = shop_definition(&district, price);
++            }
++            TileKindDefinition::Warp { destination } => {
++                *destination = (*destination as i32 + delta).rem_euclid(len as i32) as usize;
++            }
++            TileKindDefinition::TollGate { toll } => {
++                *toll = (*toll + delta * EDITOR_TOLL_STEP).max(EDITOR_TOLL_STEP);
++            }
++            _ => {}
++        }
++    }
++
++    pub fn set_district(&mut self, index: usize, district: usize) {
++        if let TileKindDefinition::Property { district: name, .. } =
++            &mut self.draft.tiles[index].kind
++        {
++            *name = EDITOR_DISTRICTS[district].to_string();
++        }
++    }
++
++    /// The first call picks tile `index` to link from; the second adds an
++    /// exit from that tile to `index`, or takes it away if it is already
++    /// there. A tile left without exits goes on to the following tile.
++    pub fn link(&mut self, index: usize) {
++        let Some(from) = self.link_from.take() else {
++            self.link_from = Some(index);
++            self.message = format!("Linking from tile {}: pick where it leads", index);
++            return;
++        };
++        let len = self.draft.tiles.len();
++        let next = &mut self.draft.tiles[from].next;
++        if next.is_empty() {
++            next.push((from + 1) % len);
++        }
++        if let Some(position) = next.iter().position(|exit| *exit == index) {
++            next.remove(position);
++        } else {
++            next.push(index);
++        }
++        // A single exit to the following tile is the default anyway.
++        if next[..] == [(from + 1) % len] {
++            next.clear();
++        }
++        self.message = format!(
++            "Tile {} leads to {}",
++            from,
++            join_tiles(&self.draft.exits(from))
++        );
++    }
++
++    /// Where the board is written: its name in snake case, in the boards
++    /// folder.
++    pub fn export_path(&self) -> PathBuf {
++        let stem = self
++            .draft
++            .name
++            .to_lowercase()
++            .split(|c: char| !c.is_alphanumeric())
++            .filter(|word| !word.is_empty())
++            .collect::<Vec<_>>()
++            .join("_");
++        PathBuf::from("assets")
++            .join(BOARDS_FOLDER)
++            .join(format!("{}.board.ron", stem))
++    }
++}
++
++/// A shop in `district` costing `price`, with a fee of
++/// `EDITOR_FEE_PERCENT` of the price.
++pub fn shop_definition(district: &str, price: i32) -> TileKindDefinition {
++    TileKindDefinition::Property {
++        district: district.to_string(),
++        price,
++        base_fee: price * EDITOR_FEE_PERCENT / 100,
++    }
++}
++
++fn join_tiles(tiles: &[usize]) -> String {
++    tiles
++        .iter()
++        .map(|tile| tile.to_string())
++        .collect::<Vec<_>>()
++        .join(", ")
++}
++
++/// The tile's color on the board; shops take their district's color from
++/// `districts`.
++pub fn definition_color(kind: &TileKindDefinition, districts: &[District]) -> Color {
++    match kind {
++        TileKindDefinition::Bank => BANK_COLOR,
++        TileKindDefinition::Property { district, .. } => districts
++            .iter()
++            .find(|known| known.name == *district)
++            .map_or(PROPERTY_COLOR, |known| known.color),
++        TileKindDefinition::Suit(_) => SUIT_COLOR,
++        TileKindDefinition::SuitYourself => SUIT_YOURSELF_COLOR,
++        TileKindDefinition::Chance => CHANCE_COLOR,
++        TileKindDefinition::TakeABreak => BREAK_COLOR,
++        TileKindDefinition::TaxOffice => TAX_COLOR,
++        TileKindDefinition::Arcade => ARCADE_COLOR,
++        TileKindDefinition::Casino => CASINO_COLOR,
++        TileKindDefinition::Warp { .. } => WARP_COLOR,
++        TileKindDefinition::Boon => BOON_COLOR,
++        TileKindDefinition::TollGate { .. } => TOLL_COLOR,
++    }
++}
++
++/// Short description of a tile for its label in the editor.
++pub fn definition_label(kind: &TileKindDefinition) -> String {
++    match kind {
++        TileKindDefinition::Bank => "Bank".to_string(),
++        TileKindDefinition::Property {
++            district, price, ..
++        } => format!("{}\n{}", district, Money(*price)),
++        TileKindDefinition::Suit(suit) => format!("{} Suit", suit.icon()),
++        TileKindDefinition::SuitYourself => "Wild".to_string(),
++        TileKindDefinition::Chance => "Chance".to_string(),
++        TileKindDefinition::TakeABreak => "Break".to_string(),
++        TileKindDefinition::TaxOffice => "Tax".to_string(),
++        TileKindDefinition::Arcade => "Arcade".to_string(),
++        TileKindDefinition::Casino => "Casino".to_string(),
++        TileKindDefinition::Warp { destination } => format!("Warp\nto {}", destination),
++        TileKindDefinition::Boon => "Boon".to_string(),
++        TileKindDefinition::TollGate { toll } => format!("Toll\n{}", Money(*toll)),
++    }
++}
++
++/// Sprites making up the editor's grid, tiles and cursor.
++#[derive(Component)]
++pub struct EditorEntity;
++
++#[derive(Component)]
++pub struct EditorPanel;
++
++#[derive(Component)]
++pub struct EditorText;
++
++/// Hides the match HUD and opens the editor's help and status panel.
++pub fn spawn_editor(
++    mut commands: Commands,
++    asset_server: Res<AssetServer>,
++    mut roots: Query<&mut Visibility, With<UiRoot>>,
++) {
++    for mut visibility in roots.iter_mut() {
++        *visibility = Visibility::Hidden;
++    }
++    commands
++        .spawn((
++            NodeBundle {
++                style: Style {
++                    position_type: PositionType::Absolute,
++                    left: Val::Px(12.0),
++                    top: Val::Px(12.0),
++                    width: Val::Px(440.0),
++                    padding: UiRect::all(Val::Px(8.0)),
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(Color::rgba(0.1, 0.1, 0.15, 0.9)),
++                ..Default::default()
++            },
++            EditorPanel,
++        ))
++        .with_children(|panel| {
++            panel.spawn((
++                TextBundle::from_section(
++                    "",
++                    TextStyle {
++                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
++                        font_size: 16.0,
++                        color: Color::WHITE,
++                    },
++                ),
++                EditorText,
++            ));
++        });
++}
++
++#[allow(clippy::type_complexity)]
++pub fn despawn_editor(
++    mut commands: Commands,
++    entities: Query<Entity, Or<(With<EditorPanel>, With<EditorEntity>)>>,
++    mut roots: Query<&mut Visibility, With<UiRoot>>,
++) {
++    for entity in entities.iter() {
++        commands.entity(entity).despawn_recursive();
++    }
++    for mut visibility in roots.iter_mut() {
++        *visibility = Visibility::Inherited;
++    }
++    commands.remove_resource::<BoardEditor>();
++}
++
++/// Arrows move the cursor. Enter places a tile, Backspace removes it and Tab
++/// changes its kind. `+`/`-` set a shop's price or a warp's destination and
++/// `1`-`9` a shop's district. `Y` on one tile and then another links the
++/// first to the second, and `N` sends a tile back to the following one. `E`
++/// starts a blank board, Space checks and saves the board and returns to the
++/// setup screen with it picked, and Escape leaves without saving.
++pub fn editor_input(
++    actions: Res<ActionInput>,
++    mut editor: ResMut<BoardEditor>,
++    mut boards: ResMut<Assets<BoardDefinition>>,
++    mut library: ResMut<BoardLibrary>,
++    mut settings: ResMut<MatchSettings>,
++    mut next_state: ResMut<NextState<AppState>>,
++) {
++    if actions.just_pressed(Action::Back) {
++        next_state.set(AppState::Setup);
++        return;
++    }
++    if actions.just_pressed(Action::Editor) {
++        *editor = BoardEditor::blank();
++        return;
++    }
++    if actions.just_pressed(Action::Roll) {
++        if let Err(err) = editor.draft.validate() {
++            editor.message = format!("Can't save yet: {}", err);
++            return;
++        }
++        let path = editor.export_path();
++        if let Err(err) = write_ron(&editor.draft, &path) {
++            editor.message = format!("Could not write {}: {}", path.display(), err);
++            return;
++        }
++        info!(
++            "Board \"{}\" saved to {}",
++            editor.draft.name,
++            path.display()
++        );
++        // A board saved again replaces the copy from last time.
++        library.0.retain(|handle| {
++            boards
++                .get(handle)
++                .is_none_or(|board| board.name != editor.draft.name)
++        });
++        let handle = boards.add(editor.draft.clone());
++        library.0.push(handle.clone());
++        library
++            .0
++            .sort_by_key(|handle| boards.get(handle).map(|board| board.name.clone()));
++        settings.board = library.0.iter().position(|h| *h == handle).unwrap_or(0);
++        next_state.set(AppState::Setup);
++        return;
++    }
++
++    let moves = [
++        (Action::Up, (0, 1)),
++        (Action::Down, (0, -1)),
++        (Action::Left, (-1, 0)),
++        (Action::Right, (1, 0)),
++    ];
++    for (action, (dx, dy)) in moves {
++        if actions.just_pressed(action) {
++            editor.cursor.0 += dx;
++            editor.cursor.1 += dy;
++        }
++    }
++    if actions.just_pressed(Action::Confirm) {
++        editor.place();
++    }
++    let Some(index) = editor.selected() else {
++        return;
++    };
++    if actions.just_pressed(Action::Erase) {
++        editor.remove(index);
++        return;
++    }
++    if actions.just_pressed(Action::Cycle) {
++        editor.cycle_kind(index);
++    }
++    if actions.just_pressed(Action::Increase) {
++        editor.adjust(index, 1);
++    }
++    if actions.just_pressed(Action::Decrease) {
++        editor.adjust(index, -1);
++    }
++    if let Some(district) = actions.just_picked(EDITOR_DISTRICTS.len()) {
++        editor.set_district(index, district);
++    }
++    if actions.just_pressed(Action::Yes) {
++        editor.link(index);
++    }
++    if actions.just_pressed(Action::No) {
++        editor.draft.tiles[index].next.clear();
++    }
++}
++
++/// Redraws the grid, tiles and cursor after each edit, keeps the camera on
++/// the cursor and refreshes the panel, including whether the board is ready
++/// to save.
++pub fn draw_editor(
++    mut commands: Commands,
++    editor: Res<BoardEditor>,
++    entities: Query<Entity, With<EditorEntity>>,
++    mut texts: Query<&mut Text, With<EditorText>>,
++    mut cameras: Query<&mut Transform, With<MainCamera>>,
++) {
++    if !editor.is_changed() {
++        return;
++    }
++    for entity in entities.iter() {
++        commands.entity(entity).despawn_recursive();
++    }
++    let world = |cell: (i32, i32)| Vec2::new(cell.0 as f32, cell.1 as f32) * TILE_SIZE;
++    let sprite = |color: Color, size: f32, position: Vec3| SpriteBundle {
++        sprite: Sprite {
++            color,
++            custom_size: Some(Vec2::splat(size)),
++            ..Default::default()
++        },
++        transform: Transform::from_translation(position),
++        ..Default::default()
++    };
++
++    let cells: Vec<(i32, i32)> = editor
++        .draft
++        .tiles
++        .iter()
++        .map(|tile| tile.cell)
++        .chain([editor.cursor])
++        .collect();
++    let (min_x, max_x) = (
++        cells.iter().map(|c| c.0).min(),
++        cells.iter().map(|c| c.0).max(),
++    );
++    let (min_y, max_y) = (
++        cells.iter().map(|c| c.1).min(),
++        cells.iter().map(|c| c.1).max(),
++    );
++    if let (Some(min_x), Some(max_x), Some(min_y), Some(max_y)) = (min_x, max_x, min_y, max_y) {
++        for x in min_x - EDITOR_GRID_PADDING..=max_x + EDITOR_GRID_PADDING {
++            for y in min_y - EDITOR_GRID_PADDING..=max_y + EDITOR_GRID_PADDING {
++                commands.spawn((
++                    sprite(
++                        EDITOR_GRID_COLOR,
++                        TILE_SIZE - 4.0,
++                        world((x, y)).extend(-1.0),
++                    ),
++                    EditorEntity,
++                ));
++            }
++        }
++    }
++    commands.spawn((
++        sprite(
++            EDITOR_CURSOR_COLOR,
++            TILE_SIZE + 8.0,
++            world(editor.cursor).extend(-0.5),
++        ),
++        EditorEntity,
++    ));
++    if let Some(from) = editor.link_from {
++        commands.spawn((
++            sprite(
++                EDITOR_LINK_COLOR,
++                TILE_SIZE + 4.0,
++                world(editor.draft.tiles[from].cell).extend(-0.4),
++            ),
++            EditorEntity,
++        ));
++    }
++    let districts = editor.draft.to_districts();
++    for (idx, tile) in editor.draft.tiles.iter().enumerate() {
++        let mut label = format!("{}: {}", idx, definition_label(&tile.kind));
++        if !tile.next.is_empty() {
++            label.push_str(&format!("\n> {}", join_tiles(&tile.next)));
++        }
++        commands
++            .spawn((
++                sprite(
++                    definition_color(&tile.kind, &districts),
++                    TILE_SIZE,
++                    world(tile.cell).extend(0.0),
++                ),
++                EditorEntity,
++            ))
++            .with_children(|parent| {
++                parent.spawn(Text2dBundle {
++                    text: Text::from_section(
++                        label,
++                        TextStyle {
++                            font_size: 11.0,
++                            color: Color::WHITE,
++                            ..Default::default()
++                        },
++                    ),
++                    transform: Transform::from_xyz(0.0, 0.0, 1.0),
++                    ..Default::default()
++                });
++            });
++    }
++
++    for mut transform in cameras.iter_mut() {
++        transform.translation = world(editor.cursor).extend(transform.translation.z);
++    }
++
++    let mut content = format!(
++        "Board editor: {}\n{} tiles, cursor at ({}, {})\n",
++        editor.draft.name,
++        editor.draft.tiles.len(),
++        editor.cursor.0,
++        editor.cursor.1
++    );
++    if let Some(index) = editor.selected() {
++        let kind = definition_label(&editor.draft.tiles[index].kind).replace('\n', ", ");
++        content.push_str(&format!(
++            "Tile {}: {}, leads to {}\n",
++            index,
++            kind,
++            join_tiles(&editor.draft.exits(index))
++        ));
++    }
++    let problems = editor.draft.problems();
++    if problems.is_empty() {
++        content.push_str(&format!(
++            "Ready to save as {}\n",
++            editor.export_path().display()
++        ));
++    } else {
++        content.push_str("Not playable yet:\n");
++        for problem in problems.iter().take(EDITOR_PROBLEMS_SHOWN) {
++            content.push_str(&format!("- {}\n", problem));
++        }
++        if problems.len() > EDITOR_PROBLEMS_SHOWN {
++            content.push_str(&format!(
++                "- and {} more\n",
++                problems.len() - EDITOR_PROBLEMS_SHOWN
++            ));
++        }
++    }
++    if !editor.message.is_empty() {
++        content.push_str(&format!("{}\n", editor.message));
++    }
++    content.push_str(
++        "\nArrows move, Enter places a tile at the end of the path, Backspace removes it\n\
++         Tab changes its kind, +/- a shop's price or a warp's destination, 1-9 a shop's district\n\
++         Y on two tiles links the first to the second, N resets a tile's exits\n\
++         E starts a blank board, Space saves and plays, Esc leaves without saving",
++    );
++    for mut text in texts.iter_mut() {
++        text.sections[0].value = content.clone();
++    }
++}
@@ -0,0 +1,442 @@
+diff --git a/src/engine.rs b/src/engine.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..dc0357d8373fd4819fcd04c15d498d6cd6ba8b8c
+--- /dev/null
++++ b/src/engine.rs
+@@ -0,0 +1,436 @@
++//! The turn rules as plain data in, events out: a `TurnAction` applied to the
++//! `Game` and its `StockMarket` returns the `GameEvent`s it caused, with no
++//! systems, queries or resources involved. Every chance it takes is drawn
++//! from the `MatchRng` it is handed, so the same actions from the same state
++//! and seed always play out the same way. The dice, movement, tile, venture,
++//! arcade, casino, auction, trade, stock, loan and end-of-turn systems are
++//! thin adapters over `apply`, which records each action they take in the
++//! `ActionLog` that replays are played back from, and the headless simulator
++//! and the bots' rollouts drive it directly, so every path plays by one
++//! implementation. (The difficulty presets and house rules it reads live in
++//! `rules`.)
++
++use rand::Rng;
++use serde::{Deserialize, Serialize};
++
++use crate::*;
++
++/// One step of the active player's turn.
++#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
++pub enum TurnAction {
++    /// Roll the player's dice; the faces come back in a `GameEvent::Rolled`.
++    Roll,
++    /// Walk onto `to`, one of the exits of the player's tile, with
++    /// `remaining` steps of the roll left after it.
++    Step {
++        to: usize,
++        remaining: usize,
++    },
++    /// The player's answer, on reaching the bank, to whether to play their
++    /// Suit Yourself cards; `remaining` as for `Step`.
++    SuitCards {
++        play: bool,
++        remaining: usize,
++    },
++    /// Resolve the tile the player stopped on.
++    Land,
++    /// Buy the vacant shop the player was offered.
++    BuyShop,
++    /// Turn down the shop the player was offered, which may send it to
++    /// auction.
++    DeclineShop,
++    /// Sell the shop that went under the hammer to the highest bidder.
++    AuctionSale {
++        tile: usize,
++        buyer: usize,
++        bid: i32,
++    },
++    /// Play the venture card the player drew.
++    Venture(VentureEffect),
++    /// Pay out an arcade mini-game's prize.
++    ArcadePrize(ArcadeReward),
++    /// Bet `stake` on the casino's two dice coming up high or low.
++    HighLow {
++        bet_high: bool,
++        stake: i32,
++    },
++    /// Swap what an accepted trade proposal offers, between any two players.
++    Trade(TradeProposal),
++    /// Make everyone left in debt sell up, at forced auction if need be, or
++    /// go bankrupt.
++    SettleDebts,
++    BuyStock {
++        district: DistrictId,
++        shares: i32,
++    },
++    SellStock {
++        district: DistrictId,
++        shares: i32,
++    },
++    /// Put capital into one of the player's shops.
++    Invest {
++        tile: usize,
++    },
++    /// Sell one of the player's shops back to the bank.
++    SellShop {
++        tile: usize,
++    },
++    Borrow(i32),
++    Repay(i32),
++    /// Finish the turn: declare the last player standing the winner, or hand
++    /// over to the next player and move the market on.
++    EndTurn,
++    /// Developer console: hand `player` `amount` gold from nowhere, or take
++    /// it away if negative.
++    GiveCash {
++        player: usize,
++        amount: i32,
++    },
++    /// Put `player` on `tile` without walking there or resolving it: a warp,
++    /// or the developer console.
++    Teleport {
++        player: usize,
++        tile: usize,
++    },
++    /// Developer console: move `district`'s share price to `price`.
++    SetStockPrice {
++        district: DistrictId,
++        price: i32,
++    },
++}
++
++/// Whether the player, standing on the bank, must be asked about playing
++/// their Suit Yourself cards before they can pass it. A brain playing for
++/// them decides by itself.
++pub fn awaits_suit_cards(player_idx: usize, game: &Game) -> bool {
++    let player = &game.players[player_idx];
++    player.decider == Decider::Person && player.suit_cards > 0 && !player.missing_suits().is_empty()
++}
++
++/// Every action the active player may take in `phase`: the answers to a
++/// shop offer, and in the stock phase investing in or selling each of their
++/// shops, an order of `MIN_SHARE_ORDER` shares either way in each district,
++/// a `LOAN_STEP` loan or repayment, and ending the turn. Walking and landing
++/// follow from the roll rather than being chosen, so they never appear.
++pub fn legal_actions(game: &Game, market: &StockMarket, phase: TurnPhase) -> Vec<TurnAction> {
++    let player_idx = game.current_turn;
++    let player = &game.players[player_idx];
++    let mut candidates = Vec::new();
++    match phase {
++        TurnPhase::ShopDecision if game.pending_purchase.is_some() => {
++            candidates.extend([TurnAction::BuyShop, TurnAction::DeclineShop]);
++        }
++        TurnPhase::StockPhase => {
++            for tile in owned_shops(player_idx, game) {
++                candidates.extend([TurnAction::Invest { tile }, TurnAction::SellShop { tile }]);
++            }
++            for district in game.district_ids() {
++                let shares = MIN_SHARE_ORDER;
++                candidates.extend([
++                    TurnAction::BuyStock { district, shares },
++                    TurnAction::SellStock { district, shares },
++                ]);
++            }
++            if player.loan_available(&game.board, market) > 0 {
++                candidates.push(TurnAction::Borrow(LOAN_STEP));
++            }
++            if player.debt > 0 && player.cash > 0 {
++                candidates.push(TurnAction::Repay(LOAN_STEP));
++            }
++            candidates.push(TurnAction::EndTurn);
++        }
++        _ => {}
++    }
++    // The rules that can refuse an action live in `apply`, so try each one
++    // on a copy rather than restate them here.
++    // The scratch RNG leaves the match's own untouched.
++    candidates.retain(|action| {
++        apply(
++            action.clone(),
++            &mut game.clone(),
++            &mut market.clone(),
++            &mut MatchRng::seeded(0),
++        )
++        .is_ok()
++    });
++    candidates
++}
++
++/// Applies `action` for the active player and returns the events it caused,
++/// along with any still waiting in `Game::pending_events`. An action the
++/// rules forbid changes nothing and is refused with the reason.
++pub fn apply(
++    action: TurnAction,
++    game: &mut Game,
++    market: &mut StockMarket,
++    rng: &mut MatchRng,
++) -> Result<Vec<GameEvent>, &'static str> {
++    let player_idx = game.current_turn;
++    match action {
++        TurnAction::Roll => {
++            let dice = game.players[player_idx].dice_count();
++            let faces = (0..dice).map(|_| rng.gen_range(1..=6)).collect();
++            game.pending_events.push(GameEvent::Rolled {
++                player: player_idx,
++                faces,
++            });
++        }
++        TurnAction::Step { to, remaining } => {
++            advance_player(player_idx, to, game);
++            if remaining > 0 {
++                pass_tile(to, player_idx, game);
++            }
++            if game.bank_index() == Some(to) && !awaits_suit_cards(player_idx, game) {
++                let player = &mut game.players[player_idx];
++                let missing = player.missing_suits().len() as u32;
++                if missing > 0 && player.suit_cards >= missing {
++                    player.use_suit_cards();
++                }
++                pass_bank(player_idx, remaining, game, market);
++            }
++        }
++        TurnAction::SuitCards { play, remaining } => {
++            if play {
++                game.players[player_idx].use_suit_cards();
++            }
++            pass_bank(player_idx, remaining, game, market);
++        }
++        TurnAction::Land => {
++            let tile_index = game.players[player_idx].position;
++            let pays_fee = matches!(
++                simulate_landing(tile_index, player_idx, game, market),
++                Landing::PayFee { .. }
++            );
++            let cash_before = total_cash(game);
++            handle_tile(tile_index, player_idx, game, market);
++            if pays_fee {
++                debug_assert_eq!(
++                    total_cash(game),
++                    cash_before,
++                    "a shop fee changed the cash in play"
++                );
++            }
++            // Scripts may trade with the bank, so they run outside the check.
++            run_tile_script(tile_index, player_idx, game, market);
++        }
++        TurnAction::BuyShop => {
++            let tile_index = game.pending_purchase.take().ok_or("No shop on offer")?;
++            buy_property(tile_index, player_idx, game, market);
++        }
++        TurnAction::DeclineShop => {
++            let tile_index = game.pending_purchase.take().ok_or("No shop on offer")?;
++            game.decline_shop(tile_index);
++        }
++        TurnAction::AuctionSale { tile, buyer, bid } => {
++            if game.owner_of(tile).is_some() {
++                return Err("That shop is already owned");
++            }
++            if game
++                .players
++                .get(buyer)
++                .is_none_or(|player| player.cash < bid)
++            {
++                return Err("The bidder can't pay");
++            }
++            sell_vacant_shop(tile, buyer, bid, game, market);
++        }
++        TurnAction::Venture(effect) => apply_venture(effect, player_idx, game, market, rng),
++        TurnAction::ArcadePrize(reward) => apply_arcade_reward(reward, player_idx, game),
++        TurnAction::HighLow { bet_high, stake } => {
++            if stake <= 0 || game.players[player_idx].cash < stake {
++                return Err("Can't cover that stake");
++            }
++            play_high_low(bet_high, stake, player_idx, game, rng);
++        }
++        TurnAction::Trade(proposal) => execute_trade(&proposal, game)?,
++        TurnAction::SettleDebts => {
++            for debtor in 0..game.players.len() {
++                let player = &game.players[debtor];
++                if !player.bankrupt && player.cash.is_debt() {
++                    settle_debt(debtor, game, market);
++                }
++            }
++        }
++        TurnAction::BuyStock { district, shares } => {
++            buy_stock(player_idx, district, shares, game, market)?;
++        }
++        TurnAction::SellStock { district, shares } => {
++            sell_stock(player_idx, district, shares, game, market)?;
++        }
++        TurnAction::Invest { tile } => {
++            invest_in_shop(tile, player_idx, game, market)?;
++        }
++        TurnAction::SellShop { tile } => {
++            sell_shop_to_bank(tile, player_idx, game, market)?;
++        }
++        TurnAction::Borrow(amount) => {
++            let game = &mut *game;
++            game.players[player_idx].borrow(amount, &game.board, market);
++        }
++        TurnAction::Repay(amount) => {
++            game.players[player_idx].repay(amount);
++        }
++        TurnAction::EndTurn => {
++            let mut standing = game
++                .players
++                .iter()
++                .enumerate()
++                .filter(|(_, player)| !player.bankrupt);
++            if let (Some((last, _)), None) = (standing.next(), standing.next()) {
++                game.winner.get_or_insert(last);
++            }
++            if game.winner.is_none() {
++                game.end_turn();
++                tick_market(game, market, rng);
++                margin_calls(game, market);
++            }
++        }
++        TurnAction::GiveCash { player, amount } => {
++            let player = game.players.get_mut(player).ok_or("No such player")?;
++            let cash = player.cash.get() as i64 + amount as i64;
++            if cash.abs() > MAX_CONSOLE_CASH as i64 {
++                return Err("Cash must stay within 10,000,000G either way");
++            }
++            player.cash += amount;
++        }
++        TurnAction::Teleport { player, tile } => {
++            if tile >= game.board.len() {
++                return Err("No such tile");
++            }
++            game.players
++                .get_mut(player)
++                .ok_or("No such player")?
++                .position = tile;
++        }
++        TurnAction::SetStockPrice { district, price } => {
++            if district >= game.districts.len() {
++                return Err("No such district");
++            }
++            if !(1..=MAX_CONSOLE_SHARE_PRICE).contains(&price) {
++                return Err("Share prices run from 1G to 10,000G");
++            }
++            let shop_count = game
++                .district_shop_count
++                .get(&district)
++                .copied()
++                .unwrap_or(0);
++            market.set_price(district, price, shop_count);
++        }
++    }
++    Ok(std::mem::take(&mut game.pending_events))
++}
++
++/// Promotes a player walking on past the bank. Landing on it is handled when
++/// the tile resolves.
++fn pass_bank(player_idx: usize, remaining: usize, game: &mut Game, market: &mut StockMarket) {
++    if remaining > 0 {
++        visit_bank(player_idx, false, game, market);
++    }
++}
++
++#[cfg(test)]
++mod tests {
++    use super::*;
++    use crate::sim::load_board_files;
++
++    fn new_match() -> (Game, StockMarket) {
++        let boards = load_board_files().expect("the board files load");
++        let seats: Vec<Seat> = (0..3)
++            .map(|character| Seat {
++                name: format!("Bot {}", character + 1),
++                kind: PlayerKind::Bot,
++                strategy: BotStrategy::Balanced,
++                character,
++            })
++            .collect();
++        let game = Game::new(
++            &boards[0].1,
++            &seats,
++            TARGET_NET_WORTHS[1],
++            GameRules::default(),
++        );
++        let market = StockMarket::new(&game);
++        (game, market)
++    }
++
++    /// Plays `turns` turns through an `ActionLog`: roll, take the first exit
++    /// at every junction, draw a venture card, bet at the casino or win an
++    /// arcade prize in rotation, land, settle debts, buy any shop offered
++    /// and end the turn.
++    fn play(
++        turns: usize,
++        game: &mut Game,
++        market: &mut StockMarket,
++        rng: &mut MatchRng,
++    ) -> ActionLog {
++        let mut log = ActionLog::default();
++        for turn in 0..turns {
++            if game.winner.is_some() {
++                break;
++            }
++            let steps: usize = log
++                .apply(TurnAction::Roll, game, market, rng)
++                .expect("rolling is allowed")
++                .iter()
++                .find_map(|event| match event {
++                    GameEvent::Rolled { faces, .. } => Some(faces.iter().sum()),
++                    _ => None,
++                })
++                .expect("the roll is announced");
++            for remaining in (0..steps).rev() {
++                let to = game.board[game.players[game.current_turn].position].next[0];
++                log.apply(TurnAction::Step { to, remaining }, game, market, rng)
++                    .expect("walking is allowed");
++            }
++            let chance = match turn % 3 {
++                0 => TurnAction::Venture(VentureEffect::FreeShares(10)),
++                1 => TurnAction::HighLow {
++                    bet_high: turn % 2 == 0,
++                    stake: 100,
++                },
++                _ => TurnAction::ArcadePrize(ArcadeReward::Cash(50)),
++            };
++            for action in [
++                chance,
++                TurnAction::Land,
++                TurnAction::SettleDebts,
++                TurnAction::BuyShop,
++                TurnAction::EndTurn,
++            ] {
++                let _ = log.apply(action, game, market, rng);
++            }
++        }
++        log
++    }
++
++    #[test]
++    fn logged_actions_replay_the_match() {
++        let (start, start_market) = new_match();
++        let (mut game, mut market) = (start.clone(), start_market.clone());
++        let log = play(60, &mut game, &mut market, &mut MatchRng::seeded(11));
++        assert!(
++            log.0
++                .iter()
++                .any(|logged| matches!(logged.action, TurnAction::HighLow { .. })),
++            "no bets were placed"
++        );
++
++        let (mut replayed, mut replayed_market) = (start, start_market);
++        let mut rng = MatchRng::seeded(11);
++        for logged in &log.0 {
++            assert_eq!(logged.player, replayed.current_turn);
++            apply(
++                logged.action.clone(),
++                &mut replayed,
++                &mut replayed_market,
++                &mut rng,
++            )
++            .expect("a logged action replays");
++        }
++        let deck = VentureDeck::new();
++        assert_eq!(
++            MatchState::capture(&replayed, &replayed_market, &deck),
++            MatchState::capture(&game, &market, &deck)
++        );
++    }
++}
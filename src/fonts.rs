@@ -0,0 +1,260 @@
+diff --git a/src/fonts.rs b/src/fonts.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..c80bf7a78368d875a512d9deadc07c35ba1cf59b
+--- /dev/null
++++ b/src/fonts.rs
+@@ -0,0 +1,254 @@
++//! Font fallback. No one font covers everything the game shows: the bundled
++//! Fira Sans has Latin text but no Japanese or suit glyphs. Each text section
++//! is drawn with the first font in the chain that has a glyph for every one
++//! of its characters, so Japanese board and player names and the suit
++//! symbols render wherever they appear, HUD and board labels alike.
++//!
++//! The one-character markers drawn on every tile, its pattern symbol and its
++//! owner's seat number, are sprites cut from a glyph atlas rasterized from
++//! the same chain, rather than a text entity each, so large boards don't pay
++//! for hundreds of text layouts.
++
++use ab_glyph::Font as _;
++use bevy::{
++    prelude::*,
++    render::{
++        render_asset::RenderAssetUsages,
++        render_resource::{Extent3d, TextureDimension, TextureFormat},
++    },
++};
++use std::collections::HashMap;
++
++use crate::*;
++
++/// The font text is spawned with, relative to the assets folder.
++pub const DEFAULT_FONT: &str = "fonts/FiraSans-Bold.ttf";
++
++/// Fonts tried after the language's own font and the default, in order.
++/// Noto Sans JP is not bundled; without it the chain skips straight to the
++/// next font.
++pub const FALLBACK_FONTS: [&str; 2] = ["fonts/NotoSansJP-Bold.ttf", "fonts/DejaVuSans-Bold.ttf"];
++
++/// The fonts text may be drawn with, in the order they are tried.
++#[derive(Resource, Default)]
++pub struct FontChain {
++    pub default: Handle<Font>,
++    pub fallbacks: Vec<Handle<Font>>,
++}
++
++impl FontChain {
++    /// The fonts to try, with the language's font first when it has one.
++    fn candidates<'a>(&'a self, preferred: Option<&'a Handle<Font>>) -> Vec<&'a Handle<Font>> {
++        let mut candidates: Vec<&Handle<Font>> = preferred.into_iter().collect();
++        for font in std::iter::once(&self.default).chain(&self.fallbacks) {
++            if !candidates.contains(&font) {
++                candidates.push(font);
++            }
++        }
++        candidates
++    }
++}
++
++/// How many characters of `text` `font` has no glyph for. Whitespace is
++/// never drawn, so it always counts as covered.
++fn missing_glyphs(font: &Font, text: &str) -> usize {
++    text.chars()
++        .filter(|c| !c.is_whitespace() && font.font.glyph_id(*c).0 == 0)
++        .count()
++}
++
++/// The first loaded font among `candidates` with a glyph for every
++/// character of `text`, or the one missing the fewest if none has them all.
++fn pick_font<'a>(
++    candidates: &[&'a Handle<Font>],
++    fonts: &Assets<Font>,
++    text: &str,
++) -> Option<&'a Handle<Font>> {
++    let mut best: Option<(&Handle<Font>, usize)> = None;
++    for handle in candidates {
++        let Some(font) = fonts.get(*handle) else {
++            continue;
++        };
++        let missing = missing_glyphs(font, text);
++        if missing == 0 {
++            return Some(handle);
++        }
++        if best.is_none_or(|(_, fewest)| missing < fewest) {
++            best = Some((handle, missing));
++        }
++    }
++    best.map(|(handle, _)| handle)
++}
++
++/// Starts loading the default font and every fallback.
++pub fn load_fonts(mut chain: ResMut<FontChain>, asset_server: Res<AssetServer>) {
++    chain.default = asset_server.load(DEFAULT_FONT);
++    chain.fallbacks = FALLBACK_FONTS
++        .iter()
++        .map(|path| asset_server.load(*path))
++        .collect();
++}
++
++/// Picks each text section's font from the chain when its text changes, a
++/// font finishes loading or the language changes.
++pub fn apply_font_fallback(
++    chain: Res<FontChain>,
++    locale: Res<Locale>,
++    fonts: Res<Assets<Font>>,
++    mut font_events: EventReader<AssetEvent<Font>>,
++    mut texts: Query<&mut Text>,
++) {
++    let refresh = font_events.read().count() > 0 || locale.is_changed() || chain.is_changed();
++    let candidates = chain.candidates(locale.font.as_ref());
++    for mut text in texts.iter_mut() {
++        if !refresh && !text.is_changed() {
++            continue;
++        }
++        let picks: Vec<Option<Handle<Font>>> = text
++            .sections
++            .iter()
++            .map(|section| {
++                pick_font(&candidates, &fonts, &section.value)
++                    .filter(|font| **font != section.style.font)
++                    .cloned()
++            })
++            .collect();
++        // Only touch the text when a font actually changes, so the change
++        // this makes doesn't trigger another pass.
++        if picks.iter().all(Option::is_none) {
++            continue;
++        }
++        for (section, pick) in text.sections.iter_mut().zip(picks) {
++            if let Some(font) = pick {
++                section.style.font = font;
++            }
++        }
++    }
++}
++
++/// Characters the glyph atlas holds: every tile pattern symbol and the
++/// seat numbers.
++pub const ATLAS_GLYPHS: &str = "0123456789$*?Z%7+#\u{266b}\u{bb}\u{2660}\u{2665}\u{2666}\u{2663}";
++
++/// Height glyphs are rasterized at, and the side of each atlas cell, in
++/// pixels.
++pub const GLYPH_CELL: u32 = 32;
++
++/// Single characters rasterized once into a texture, to be drawn as sprites.
++#[derive(Resource)]
++pub struct GlyphAtlas {
++    pub image: Handle<Image>,
++    pub layout: Handle<TextureAtlasLayout>,
++    /// Atlas cell of each character.
++    pub cells: HashMap<char, usize>,
++}
++
++impl GlyphAtlas {
++    /// A sprite drawing `text` at about the size text of `font_size` would
++    /// be, or `None` unless `text` is a single character the atlas holds.
++    pub fn sprite(
++        &self,
++        text: &str,
++        font_size: f32,
++        color: Color,
++        transform: Transform,
++    ) -> Option<SpriteSheetBundle> {
++        let mut chars = text.chars();
++        let (Some(glyph), None) = (chars.next(), chars.next()) else {
++            return None;
++        };
++        let index = *self.cells.get(&glyph)?;
++        Some(SpriteSheetBundle {
++            sprite: Sprite {
++                color,
++                custom_size: Some(Vec2::splat(font_size)),
++                ..Default::default()
++            },
++            texture: self.image.clone(),
++            atlas: TextureAtlas {
++                layout: self.layout.clone(),
++                index,
++            },
++            transform,
++            ..Default::default()
++        })
++    }
++}
++
++/// Rasterizes `ATLAS_GLYPHS` into the glyph atlas once every font in the
++/// chain has loaded or failed, each character from the first font with a
++/// glyph for it. Glyphs are white, so sprites tint them.
++pub fn build_glyph_atlas(
++    mut commands: Commands,
++    chain: Res<FontChain>,
++    fonts: Res<Assets<Font>>,
++    asset_server: Res<AssetServer>,
++    atlas: Option<Res<GlyphAtlas>>,
++    mut images: ResMut<Assets<Image>>,
++    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
++) {
++    if atlas.is_some() {
++        return;
++    }
++    let candidates = chain.candidates(None);
++    let settled = candidates.iter().all(|font| {
++        matches!(
++            asset_server.load_state(*font),
++            bevy::asset::LoadState::Loaded | bevy::asset::LoadState::Failed
++        )
++    });
++    if !settled {
++        return;
++    }
++    let glyphs: Vec<char> = ATLAS_GLYPHS.chars().collect();
++    let cell = GLYPH_CELL as usize;
++    let width = cell * glyphs.len();
++    let mut data = vec![0u8; width * cell * 4];
++    let mut cells = HashMap::new();
++    for (idx, glyph) in glyphs.iter().enumerate() {
++        let Some(font) =
++            pick_font(&candidates, &fonts, &glyph.to_string()).and_then(|font| fonts.get(font))
++        else {
++            continue;
++        };
++        let glyph_id = font.font.glyph_id(*glyph);
++        if glyph_id.0 == 0 {
++            continue;
++        }
++        let Some(outline) = font
++            .font
++            .outline_glyph(glyph_id.with_scale(GLYPH_CELL as f32))
++        else {
++            continue;
++        };
++        let bounds = outline.px_bounds();
++        let left = ((cell as f32 - bounds.width()) / 2.0).max(0.0) as usize;
++        let top = ((cell as f32 - bounds.height()) / 2.0).max(0.0) as usize;
++        outline.draw(|x, y, coverage| {
++            let (x, y) = (left + x as usize, top + y as usize);
++            if x < cell && y < cell {
++                let pixel = (y * width + idx * cell + x) * 4;
++                data[pixel..pixel + 4].copy_from_slice(&[255, 255, 255, (coverage * 255.0) as u8]);
++            }
++        });
++        cells.insert(*glyph, idx);
++    }
++    let image = Image::new(
++        Extent3d {
++            width: width as u32,
++            height: GLYPH_CELL,
++            depth_or_array_layers: 1,
++        },
++        TextureDimension::D2,
++        data,
++        TextureFormat::Rgba8UnormSrgb,
++        RenderAssetUsages::RENDER_WORLD,
++    );
++    let layout =
++        TextureAtlasLayout::from_grid(Vec2::splat(GLYPH_CELL as f32), glyphs.len(), 1, None, None);
++    commands.insert_resource(GlyphAtlas {
++        image: images.add(image),
++        layout: layouts.add(layout),
++        cells,
++    });
++}
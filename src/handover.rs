@@ -0,0 +1,167 @@
+diff --git a/src/handover.rs b/src/handover.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..a7b254868d2abb4347d51a688f87319c24f44545
+--- /dev/null
++++ b/src/handover.rs
+@@ -0,0 +1,161 @@
++//! Passing the device in hot-seat play. When the turn passes to a human and
++//! more than one human shares this screen, a screen covers the board, the
++//! sidebar and the panels until the next player confirms they have it, so
++//! nobody sees another's Suit Yourself cards or half-made trade offer. The
++//! open panels and any trade draft are dropped at the same time.
++
++use bevy::prelude::*;
++
++use crate::*;
++
++/// The turn has passed to this human, who hasn't yet said they have the
++/// device. Turns wait until they do.
++#[derive(Resource)]
++pub struct Handover {
++    pub player: usize,
++}
++
++#[derive(Component)]
++pub struct HandoverPanel;
++
++#[derive(Component)]
++pub struct HandoverText;
++
++pub fn spawn_handover_panel(mut commands: Commands, asset_server: Res<AssetServer>) {
++    commands
++        .spawn((
++            ButtonBundle {
++                style: Style {
++                    position_type: PositionType::Absolute,
++                    width: Val::Percent(100.0),
++                    height: Val::Percent(100.0),
++                    display: Display::None,
++                    justify_content: JustifyContent::Center,
++                    align_items: AlignItems::Center,
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(Color::rgb(0.05, 0.05, 0.08)),
++                z_index: ZIndex::Global(9),
++                ..Default::default()
++            },
++            HandoverPanel,
++        ))
++        .with_children(|overlay| {
++            overlay.spawn((
++                TextBundle::from_section(
++                    "",
++                    TextStyle {
++                        font: asset_server.load(DEFAULT_FONT),
++                        font_size: 28.0,
++                        color: Color::WHITE,
++                    },
++                )
++                .with_text_justify(JustifyText::Center),
++                HandoverText,
++            ));
++        });
++}
++
++/// Holds the match for a handover when the turn passes from one seat to a
++/// human, if another human plays at this screen too. Humans on autopilot
++/// are away from it and get no handover. Online matches have a
++/// screen per player and never hold, and the first turn follows straight on
++/// from the opening roll everyone watched.
++pub fn start_handover(
++    mut commands: Commands,
++    game: Res<Game>,
++    host: Option<Res<NetHost>>,
++    opening: Option<Res<OpeningCeremony>>,
++    mut last_turn: Local<Option<usize>>,
++    mut ui_state: ResMut<UiState>,
++    mut desk: ResMut<TradeDesk>,
++) {
++    if game.is_added() {
++        // A match quit to the title left its last turn behind.
++        *last_turn = None;
++    }
++    if opening.is_some() {
++        *last_turn = Some(game.current_turn);
++        return;
++    }
++    if !game.is_changed() {
++        return;
++    }
++    let previous = last_turn.replace(game.current_turn);
++    if previous.is_none_or(|previous| previous == game.current_turn) || host.is_some() {
++        return;
++    }
++    let humans = game
++        .players
++        .iter()
++        .filter(|player| player.kind == PlayerKind::Human && !player.autopilot && !player.bankrupt)
++        .count();
++    if humans < 2 || game.players[game.current_turn].decider != Decider::Person {
++        return;
++    }
++    ui_state.menu_open = false;
++    ui_state.stocks_open = false;
++    ui_state.trade_open = false;
++    ui_state.portfolio_open = false;
++    ui_state.stock_order = 0;
++    ui_state.stock_message.clear();
++    ui_state.menu_message.clear();
++    *desk = TradeDesk::default();
++    commands.insert_resource(Handover {
++        player: game.current_turn,
++    });
++}
++
++/// Enter, or a click anywhere on the handover screen, hands the turn over.
++/// Until then the screen takes every action so nothing behind it reacts.
++pub fn handover_input(
++    mut commands: Commands,
++    mut actions: ResMut<ActionInput>,
++    panels: Query<&Interaction, (Changed<Interaction>, With<HandoverPanel>)>,
++) {
++    let clicked = panels
++        .iter()
++        .any(|interaction| *interaction == Interaction::Pressed);
++    if actions.just_pressed(Action::Confirm) || clicked {
++        commands.remove_resource::<Handover>();
++    }
++    actions.reset_all();
++}
++
++/// Shows the handover screen, naming the player to pass the device to,
++/// while a handover is waiting.
++pub fn update_handover_panel(
++    handover: Option<Res<Handover>>,
++    game: Res<Game>,
++    mut panels: Query<&mut Style, With<HandoverPanel>>,
++    mut texts: Query<&mut Text, With<HandoverText>>,
++) {
++    let display = if handover.is_some() {
++        Display::Flex
++    } else {
++        Display::None
++    };
++    for mut style in panels.iter_mut() {
++        if style.display != display {
++            style.display = display;
++        }
++    }
++    let Some(handover) = handover.filter(|handover| handover.is_added()) else {
++        return;
++    };
++    let name = &game.players[handover.player].name;
++    for mut text in texts.iter_mut() {
++        text.sections[0].value = format!(
++            "Pass the device to {}\n\nPress Enter or click when {} is ready",
++            name, name
++        );
++    }
++}
++
++/// Drops a handover still waiting when the match is left.
++pub fn end_handover(mut commands: Commands, mut panels: Query<&mut Style, With<HandoverPanel>>) {
++    commands.remove_resource::<Handover>();
++    for mut style in panels.iter_mut() {
++        style.display = Display::None;
++    }
++}
@@ -0,0 +1,320 @@
+diff --git a/src/invariants.rs b/src/invariants.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..f84a4f529b759c33dd2de58b55bea1f45a8c3767
+--- /dev/null
++++ b/src/invariants.rs
+@@ -0,0 +1,314 @@
++//! Consistency checks on the match state. Dev builds run them every frame
++//! and after every simulated turn, and panic on the first broken rule, so a
++//! bug in the rules shows up where it happens rather than turns later in a
++//! strange standing. Release builds skip them.
++
++use bevy::prelude::*;
++use std::collections::HashMap;
++
++use crate::*;
++
++/// A rule the match state must never break.
++#[derive(Debug, Clone, PartialEq, thiserror::Error)]
++pub enum Violation {
++    #[error("it is the turn of player {0}, who doesn't exist")]
++    TurnOutOfRange(usize),
++    #[error("the turn order {0:?} doesn't list every player once")]
++    BadTurnOrder(Vec<usize>),
++    #[error("player {0} stands on tile {1}, which doesn't exist")]
++    OffBoard(usize, usize),
++    #[error("player {0} owns tile {1}, which isn't a shop")]
++    NotAShop(usize, usize),
++    #[error("shop {0} is owned by both player {1} and player {2}")]
++    SharedShop(usize, usize, usize),
++    #[error("bankrupt player {0} still holds shops")]
++    BankruptOwner(usize),
++    #[error("player {0} owes a negative loan of {1}")]
++    NegativeDebt(usize, i32),
++    #[error("player {0}'s net worth overflows")]
++    NetWorthOverflow(usize),
++    #[error("district {0}'s share price is {1}")]
++    Unpriced(DistrictId, i32),
++    #[error("district {0}'s market sentiment is {1}")]
++    BadSentiment(DistrictId, f32),
++}
++
++/// Every rule `game` and `market` currently break.
++pub fn violations(game: &Game, market: &StockMarket) -> Vec<Violation> {
++    let mut violations = Vec::new();
++    if game.current_turn >= game.players.len() {
++        violations.push(Violation::TurnOutOfRange(game.current_turn));
++    }
++    if !is_turn_order(&game.turn_order, game.players.len()) {
++        violations.push(Violation::BadTurnOrder(game.turn_order.clone()));
++    }
++    let mut owners: HashMap<usize, usize> = HashMap::new();
++    for (idx, player) in game.players.iter().enumerate() {
++        if player.position >= game.board.len() {
++            violations.push(Violation::OffBoard(idx, player.position));
++        }
++        if player.bankrupt && !player.properties.is_empty() {
++            violations.push(Violation::BankruptOwner(idx));
++        }
++        if player.debt < 0 {
++            violations.push(Violation::NegativeDebt(idx, player.debt.get()));
++        }
++        let mut properties: Vec<usize> = player.properties.iter().copied().collect();
++        properties.sort_unstable();
++        for tile in properties {
++            if !matches!(
++                game.board.get(tile).map(|tile| &tile.kind),
++                Some(TileKind::Property { .. })
++            ) {
++                violations.push(Violation::NotAShop(idx, tile));
++            } else if let Some(other) = owners.insert(tile, idx) {
++                violations.push(Violation::SharedShop(tile, other, idx));
++            }
++        }
++        if checked_net_worth(player, &game.board, market).is_none() {
++            violations.push(Violation::NetWorthOverflow(idx));
++        }
++    }
++    for district in game.district_ids() {
++        let price = market.price(district);
++        if price < 1 {
++            violations.push(Violation::Unpriced(district, price));
++        }
++        let sentiment = market.sentiment.get(&district).copied().unwrap_or(1.0);
++        if !sentiment.is_finite() || sentiment <= 0.0 {
++            violations.push(Violation::BadSentiment(district, sentiment));
++        }
++    }
++    violations
++}
++
++/// Whether `order` lists each of `players` seats exactly once.
++pub fn is_turn_order(order: &[usize], players: usize) -> bool {
++    let mut seats = order.to_vec();
++    seats.sort_unstable();
++    seats.into_iter().eq(0..players)
++}
++
++/// The player's net worth, or `None` if working it out overflows.
++pub fn checked_net_worth(
++    player: &PlayerState,
++    board: &[Tile],
++    market: &StockMarket,
++) -> Option<i32> {
++    let mut total = player.cash.checked_sub(player.debt)?.get();
++    for tile in &player.properties {
++        total = total.checked_add(board.get(*tile)?.value())?;
++    }
++    for (district, shares) in &player.stocks {
++        total = total.checked_add(shares.checked_mul(market.price(*district))?)?;
++    }
++    Some(total)
++}
++
++/// Cash held by every player together, which passing money between players
++/// must leave unchanged.
++pub fn total_cash(game: &Game) -> i64 {
++    game.players
++        .iter()
++        .map(|player| player.cash.get() as i64)
++        .sum()
++}
++
++/// Panics naming every broken rule, if any.
++pub fn assert_valid(game: &Game, market: &StockMarket) {
++    let violations = violations(game, market);
++    if !violations.is_empty() {
++        let lines: Vec<String> = violations.iter().map(Violation::to_string).collect();
++        panic!("the match state is inconsistent:\n{}", lines.join("\n"));
++    }
++}
++
++/// Checks the match state every frame in dev builds.
++pub fn validate_game(game: Res<Game>, market: Res<StockMarket>) {
++    if cfg!(debug_assertions) && (game.is_changed() || market.is_changed()) {
++        assert_valid(&game, &market);
++    }
++}
++
++#[cfg(test)]
++mod tests {
++    use super::*;
++    use crate::sim::load_board_files;
++    use proptest::collection::vec;
++    use proptest::prelude::*;
++    use std::sync::OnceLock;
++
++    /// The shipped boards, read once for every case.
++    fn boards() -> &'static [(String, BoardDefinition)] {
++        static BOARDS: OnceLock<Vec<(String, BoardDefinition)>> = OnceLock::new();
++        BOARDS.get_or_init(|| load_board_files().expect("the board files load"))
++    }
++
++    fn new_match(board: usize, seats: usize, cash: i32) -> (Game, StockMarket) {
++        let (_, definition) = &boards()[board % boards().len()];
++        let seats: Vec<Seat> = (0..seats)
++            .map(|character| Seat {
++                name: format!("Player {}", character + 1),
++                kind: PlayerKind::Bot,
++                strategy: BotStrategy::Balanced,
++                character,
++            })
++            .collect();
++        let rules = GameRules {
++            starting_cash: cash,
++            ..Default::default()
++        };
++        let game = Game::new(definition, &seats, TARGET_NET_WORTHS[1], rules);
++        let market = StockMarket::new(&game);
++        (game, market)
++    }
++
++    /// One turn's worth of choices: the roll, the exit taken at each
++    /// junction and picks among the legal actions.
++    #[derive(Debug, Clone)]
++    struct TurnPlan {
++        roll: usize,
++        exits: Vec<usize>,
++        picks: Vec<usize>,
++    }
++
++    fn turn_plan() -> impl Strategy<Value = TurnPlan> {
++        (
++            2..=12usize,
++            vec(any::<usize>(), 1..4),
++            vec(any::<usize>(), 0..8),
++        )
++            .prop_map(|(roll, exits, picks)| TurnPlan { roll, exits, picks })
++    }
++
++    /// Applies `action` and checks that the match is still consistent.
++    fn apply_checked(
++        action: TurnAction,
++        game: &mut Game,
++        market: &mut StockMarket,
++        rng: &mut MatchRng,
++    ) -> Result<(), TestCaseError> {
++        let _ = engine::apply(action.clone(), game, market, rng);
++        prop_assert_eq!(violations(game, market), Vec::new(), "after {:?}", action);
++        Ok(())
++    }
++
++    /// Plays the active player's turn by `plan`, the way the turn systems
++    /// do: walk, land, answer any shop offer, play the stock phase from
++    /// `legal_actions` and end the turn.
++    fn play_turn(
++        plan: &TurnPlan,
++        game: &mut Game,
++        market: &mut StockMarket,
++        rng: &mut MatchRng,
++    ) -> Result<(), TestCaseError> {
++        let current = game.current_turn;
++        for (remaining, exit) in (0..plan.roll).rev().zip(plan.exits.iter().cycle()) {
++            let exits = &game.board[game.players[current].position].next;
++            let to = exits[exit % exits.len()];
++            apply_checked(TurnAction::Step { to, remaining }, game, market, rng)?;
++            if game.bank_index() == Some(to) && engine::awaits_suit_cards(current, game) {
++                let play = exit % 2 == 0;
++                let action = TurnAction::SuitCards { play, remaining };
++                apply_checked(action, game, market, rng)?;
++            }
++        }
++
++        let tile = game.players[current].position;
++        let pays_fee = matches!(
++            simulate_landing(tile, current, game, market),
++            Landing::PayFee { .. }
++        );
++        let cash_before = total_cash(game);
++        apply_checked(TurnAction::Land, game, market, rng)?;
++        if pays_fee && game.board[tile].script.is_none() {
++            prop_assert_eq!(total_cash(game), cash_before, "a fee changed the cash");
++        }
++        apply_checked(TurnAction::SettleDebts, game, market, rng)?;
++        game.declined_shop = None;
++
++        if !game.players[current].bankrupt {
++            let mut picks = plan.picks.iter();
++            let offer = engine::legal_actions(game, market, TurnPhase::ShopDecision);
++            if let (false, Some(pick)) = (offer.is_empty(), picks.next()) {
++                apply_checked(offer[pick % offer.len()].clone(), game, market, rng)?;
++            }
++            game.pending_purchase = None;
++            for pick in picks {
++                let legal = engine::legal_actions(game, market, TurnPhase::StockPhase);
++                let Some(action) = legal.get(pick % legal.len().max(1)).cloned() else {
++                    break;
++                };
++                if action == TurnAction::EndTurn {
++                    break;
++                }
++                apply_checked(action, game, market, rng)?;
++            }
++        }
++        apply_checked(TurnAction::EndTurn, game, market, rng)?;
++        game.pending_salaries.clear();
++        game.pending_events.clear();
++        game.pending_ownership.clear();
++        game.pending_investments.clear();
++        market.pending_changes.clear();
++        Ok(())
++    }
++
++    proptest! {
++        #![proptest_config(ProptestConfig::with_cases(48))]
++
++        /// No sequence of legal actions breaks a rule of the match state:
++        /// no shop has two owners, net worth never overflows, and fees only
++        /// move cash between players.
++        #[test]
++        fn legal_play_keeps_the_match_valid(
++            board in any::<usize>(),
++            seats in 2..=4usize,
++            cash in 0..=20_000i32,
++            seed in any::<u64>(),
++            turns in vec(turn_plan(), 1..40),
++        ) {
++            let (mut game, mut market) = new_match(board, seats, cash);
++            let mut rng = MatchRng::seeded(seed);
++            prop_assert_eq!(violations(&game, &market), Vec::new());
++            for plan in &turns {
++                if game.winner.is_some() {
++                    break;
++                }
++                play_turn(plan, &mut game, &mut market, &mut rng)?;
++            }
++        }
++
++        /// Landing on a shop someone else owns moves the fee from the
++        /// visitor to the owner and leaves the cash in play unchanged.
++        #[test]
++        fn shop_fees_conserve_cash(
++            board in any::<usize>(),
++            shop in any::<usize>(),
++            cash in -5_000..=20_000i32,
++            owner_cash in 0..=20_000i32,
++            capital in 0..=500i32,
++        ) {
++            let (mut game, mut market) = new_match(board, 2, 20_000);
++            let shops: Vec<usize> = (0..game.board.len())
++                .filter(|tile| matches!(game.board[*tile].kind, TileKind::Property { .. }))
++                .collect();
++            let tile = shops[shop % shops.len()];
++            let visitor = game.current_turn;
++            let owner = 1 - visitor;
++            buy_property(tile, owner, &mut game, &mut market);
++            prop_assert_eq!(game.owner_of(tile), Some(owner));
++            game.players[owner].cash = Money(owner_cash);
++            game.board[tile].capital = capital.min(game.board[tile].capital_room());
++            game.players[visitor].cash = Money(cash);
++            game.players[visitor].position = tile;
++            let cash_before = total_cash(&game);
++            let owner_before = game.players[owner].cash;
++            engine::apply(TurnAction::Land, &mut game, &mut market, &mut MatchRng::seeded(0))
++                .expect("landing is allowed");
++            prop_assert_eq!(total_cash(&game), cash_before);
++            prop_assert!(game.players[owner].cash >= owner_before);
++            prop_assert_eq!(violations(&game, &market), Vec::new());
++        }
++    }
++}
@@ -0,0 +1,579 @@
+diff --git a/src/lib.rs b/src/lib.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..d2e4aaec3bdc47e9dd43e6c99cdde1f1fff822d0
+--- /dev/null
++++ b/src/lib.rs
+@@ -0,0 +1,573 @@
++//! Prototype Fortune Street (Itadaki Street) board game using Bevy.
++//! The implementation follows the Wii "Fortune Street" flow: players roll dice,
++//! move along a looping path of shops, collect suits (spade/heart/diamond/club),
++//! visit the bank to level up and receive salary, pay shop fees, invest in stocks
++//! for districts, and can upgrade shops they own. This prototype focuses on a 2D
++//! UI that visualizes the board, players, and key menus.
++
++pub mod ai;
++pub mod art;
++pub mod audio;
++pub mod board;
++pub mod console;
++pub mod controls;
++pub mod decision_timer;
++pub mod district_map;
++pub mod economy;
++pub mod editor;
++pub mod engine;
++pub mod fonts;
++pub mod handover;
++pub mod invariants;
++pub mod locale;
++pub mod minimap;
++pub mod money;
++pub mod narration;
++pub mod net;
++pub mod opening;
++pub mod palette;
++pub mod pause;
++pub mod player;
++pub mod profile;
++pub mod replay;
++pub mod rng;
++pub mod rules;
++pub mod save;
++pub mod scaling;
++pub mod script;
++pub mod settings;
++pub mod sim;
++pub mod stats;
++pub mod systems;
++pub mod title;
++pub mod tutorial;
++pub mod ui;
++
++pub use ai::*;
++pub use art::*;
++pub use audio::*;
++pub use board::*;
++pub use console::*;
++pub use controls::*;
++pub use decision_timer::*;
++pub use district_map::*;
++pub use economy::*;
++pub use editor::*;
++pub use engine::*;
++pub use fonts::*;
++pub use handover::*;
++pub use invariants::*;
++pub use locale::*;
++pub use minimap::*;
++pub use money::*;
++pub use narration::*;
++pub use net::*;
++pub use opening::*;
++pub use palette::*;
++pub use pause::*;
++pub use player::*;
++pub use profile::*;
++pub use replay::*;
++pub use rng::*;
++pub use rules::*;
++pub use save::*;
++pub use scaling::*;
++pub use script::*;
++pub use settings::*;
++pub use stats::*;
++pub use systems::*;
++pub use title::*;
++pub use tutorial::*;
++pub use ui::*;
++
++use bevy::prelude::*;
++
++/// Registers the game's assets, state, resources, events and systems. Add it
++/// alongside `DefaultPlugins` to run the full game.
++pub struct GamePlugin;
++
++impl Plugin for GamePlugin {
++    fn build(&self, app: &mut App) {
++        app.init_asset::<BoardDefinition>()
++            .init_asset_loader::<BoardDefinitionLoader>()
++            .init_asset::<StringTable>()
++            .init_asset_loader::<StringTableLoader>()
++            .init_state::<AppState>()
++            .insert_resource(UiState::default())
++            .insert_resource(TurnTimer(Timer::from_seconds(2.0, TimerMode::Repeating)))
++            .init_resource::<MoveState>()
++            .init_resource::<MatchSettings>()
++            .init_resource::<GameRules>()
++            .init_resource::<Settings>()
++            .init_resource::<Locale>()
++            .init_resource::<FontChain>()
++            .init_resource::<Profiles>()
++            .init_resource::<SettingsMenu>()
++            .init_resource::<PauseMenu>()
++            .init_resource::<DevConsole>()
++            .init_resource::<TitleMenu>()
++            .init_resource::<CameraFollow>()
++            .init_resource::<ActionInput>()
++            .init_resource::<TradeDesk>()
++            .init_resource::<SelectedTile>()
++            .insert_resource(VentureDeck::new())
++            .add_event::<StockPriceChanged>()
++            .add_event::<PlayerBankrupt>()
++            .add_event::<DiceRolled>()
++            .add_event::<SalaryPaid>()
++            .add_event::<CashChanged>()
++            .add_event::<GameEvent>()
++            .add_event::<OwnershipChanged>()
++            .add_event::<InvestmentChanged>()
++            .add_event::<PlaySound>()
++            .init_resource::<EventLog>()
++            .init_resource::<CashTracker>()
++            .init_resource::<MatchStats>()
++            .init_state::<TurnPhase>()
++            .init_state::<ArcadeGame>()
++            .init_resource::<ArcadeOutcome>()
++            .init_resource::<WarpState>()
++            .add_systems(
++                Startup,
++                (
++                    setup_camera,
++                    setup_minimap_camera,
++                    setup_ui,
++                    load_boards,
++                    load_art,
++                    load_rules,
++                    load_settings,
++                    load_locale,
++                    load_fonts,
++                    load_profiles,
++                    spawn_pause_panel,
++                    spawn_handover_panel,
++                    spawn_decision_bar,
++                    spawn_console_panel,
++                    start_hosting,
++                ),
++            )
++            .add_systems(Update, collect_boards.run_if(in_state(AppState::Loading)))
++            .add_systems(
++                Update,
++                (
++                    announce_gamepads,
++                    animate_phase_banners,
++                    animate_cash_popups,
++                    animate_sparkles,
++                    make_event_log_accessible,
++                ),
++            )
++            .add_systems(Update, (refresh_locale, apply_localized_text).chain())
++            .add_systems(Update, (apply_ui_scale, apply_text_size))
++            .add_systems(
++                PostUpdate,
++                apply_font_fallback
++                    .before(bevy::ui::widget::measure_text_system)
++                    .before(bevy::text::update_text2d_layout),
++            )
++            .add_systems(
++                Update,
++                (
++                    apply_tile_art,
++                    apply_character_art,
++                    apply_palette.after(apply_tile_art),
++                    build_glyph_atlas,
++                    update_tile_patterns.after(build_glyph_atlas),
++                )
++                    .run_if(resource_exists::<Game>),
++            )
++            .add_systems(
++                OnEnter(AppState::Title),
++                (spawn_title_screen, clear_match_view),
++            )
++            .add_systems(
++                Update,
++                (title_input, update_title_screen, update_settings_panel)
++                    .chain()
++                    .run_if(in_state(AppState::Title)),
++            )
++            .add_systems(OnExit(AppState::Title), despawn_title_screen)
++            .add_systems(OnEnter(AppState::Setup), spawn_setup_screen)
++            .add_systems(
++                Update,
++                (setup_screen_input, update_setup_screen)
++                    .chain()
++                    .run_if(in_state(AppState::Setup)),
++            )
++            .add_systems(OnExit(AppState::Setup), despawn_setup_screen)
++            .add_systems(OnEnter(AppState::Editor), spawn_editor)
++            .add_systems(
++                Update,
++                (editor_input, draw_editor)
++                    .chain()
++                    .run_if(in_state(AppState::Editor)),
++            )
++            .add_systems(OnExit(AppState::Editor), despawn_editor)
++            .add_systems(OnEnter(AppState::Joining), spawn_setup_screen)
++            .add_systems(
++                Update,
++                (client_receive, lobby_input, update_join_screen)
++                    .chain()
++                    .run_if(in_state(AppState::Joining)),
++            )
++            .add_systems(OnExit(AppState::Joining), despawn_setup_screen)
++            .add_systems(
++                OnEnter(AppState::InGame),
++                (
++                    setup_board,
++                    spawn_player_panels,
++                    frame_board,
++                    start_recording,
++                    start_undo_history,
++                    start_action_log,
++                    start_match_stats,
++                ),
++            )
++            .add_systems(
++                OnEnter(AppState::InGame),
++                start_remote_match
++                    .after(start_recording)
++                    .run_if(resource_exists::<NetHost>),
++            )
++            .add_systems(
++                OnEnter(AppState::Replay),
++                (setup_board, spawn_player_panels, frame_board),
++            )
++            .add_systems(
++                OnEnter(AppState::Remote),
++                (
++                    setup_board,
++                    spawn_player_panels,
++                    spawn_remote_panel,
++                    frame_board,
++                ),
++            )
++            .add_systems(
++                OnEnter(AppState::GameOver),
++                (
++                    write_replay,
++                    record_career_stats,
++                    (track_match_stats, spawn_stats_screen).chain(),
++                ),
++            )
++            .add_systems(
++                Update,
++                toggle_stats_screen.run_if(in_state(AppState::GameOver)),
++            )
++            .add_systems(
++                OnExit(AppState::InGame),
++                (
++                    reset_game_speed,
++                    end_opening_ceremony,
++                    end_handover,
++                    end_decision_clock,
++                    close_console,
++                ),
++            )
++            .add_systems(
++                OnEnter(AppState::InGame),
++                spawn_opening_panel.run_if(resource_exists::<OpeningCeremony>),
++            )
++            .add_systems(
++                Update,
++                run_opening_ceremony.run_if(
++                    resource_exists::<OpeningCeremony>.and_then(in_state(AppState::InGame)),
++                ),
++            )
++            .add_systems(
++                Update,
++                (
++                    camera_controls,
++                    touch_camera,
++                    refit_on_resize,
++                    minimap_click,
++                    clamp_camera
++                        .after(camera_controls)
++                        .after(touch_camera)
++                        .after(refit_on_resize)
++                        .after(minimap_click),
++                    update_minimap.after(clamp_camera),
++                    enlarge_tap_targets,
++                    tile_tooltip,
++                    select_tile,
++                    update_shop_detail_panel,
++                    update_tile_labels,
++                    cull_board_text.after(clamp_camera),
++                    (
++                        update_prompt,
++                        update_prompt_buttons,
++                        update_info_text,
++                        update_player_panels,
++                    ),
++                    animate_suit_icons,
++                    (
++                        update_stock_panel,
++                        update_trade_panel,
++                        update_trade_buttons,
++                        update_arcade_buttons,
++                        update_menu_panel,
++                        update_settings_panel,
++                        update_action_bar,
++                    ),
++                    (toggle_leaderboard, update_leaderboard).chain(),
++                    (toggle_portfolio, update_portfolio).chain(),
++                    (toggle_district_map, update_district_map).chain(),
++                )
++                    .run_if(
++                        in_state(AppState::InGame)
++                            .or_else(in_state(AppState::GameOver))
++                            .or_else(in_state(AppState::Replay))
++                            .or_else(in_state(AppState::Remote)),
++                    ),
++            )
++            .add_systems(
++                Update,
++                (
++                    (publish_price_changes, track_price_changes).chain(),
++                    (publish_salaries, promotion_banner).chain(),
++                    announce_phase.run_if(state_changed::<TurnPhase>),
++                    suit_effects,
++                    (
++                        detect_cash_changes,
++                        (animate_cash_deltas, spawn_cash_popups),
++                    )
++                        .chain(),
++                    (
++                        publish_game_events,
++                        record_game_events,
++                        narrate_game_events,
++                        announce_market_swings,
++                        record_replay_events,
++                        track_match_stats,
++                    )
++                        .chain(),
++                    (publish_ownership_changes, update_ownership_markers).chain(),
++                    publish_investment_changes,
++                    highlight_landing_tiles,
++                    pace_bots,
++                    toggle_menu,
++                    quick_save_load,
++                    save_replay_controls,
++                    button_style,
++                    (layout_tokens, sync_tokens).chain(),
++                    remove_bankrupt_tokens,
++                    (animate_dice, start_move).chain(),
++                    validate_game,
++                )
++                    .run_if(in_state(AppState::InGame)),
++            )
++            .add_systems(
++                Update,
++                (replay_playback, update_replay_prompt.after(update_prompt))
++                    .chain()
++                    .run_if(in_state(AppState::Replay)),
++            )
++            .add_systems(
++                Update,
++                (
++                    client_receive,
++                    client_send_actions,
++                    update_remote_panels.after(update_prompt),
++                )
++                    .run_if(in_state(AppState::Remote)),
++            )
++            .add_systems(
++                Update,
++                (accept_connections, receive_from_clients).run_if(resource_exists::<NetHost>),
++            )
++            .add_systems(
++                Update,
++                broadcast_lobby
++                    .run_if(resource_exists::<NetHost>.and_then(in_state(AppState::Setup))),
++            )
++            .add_systems(
++                Update,
++                broadcast_to_clients
++                    .run_if(resource_exists::<NetHost>.and_then(
++                        in_state(AppState::InGame).or_else(in_state(AppState::GameOver)),
++                    )),
++            )
++            .add_systems(
++                PreUpdate,
++                (
++                    read_actions.after(bevy::input::InputSystem),
++                    settings_menu_input.after(read_actions).run_if(
++                        in_state(AppState::Title)
++                            .or_else(in_state(AppState::InGame))
++                            .or_else(in_state(AppState::GameOver))
++                            .or_else(in_state(AppState::Replay))
++                            .or_else(in_state(AppState::Remote)),
++                    ),
++                ),
++            )
++            .add_systems(
++                PreUpdate,
++                press_action_buttons
++                    .after(read_actions)
++                    .after(bevy::ui::UiSystem::Focus)
++                    .before(console_input)
++                    .run_if(in_state(AppState::InGame)),
++            )
++            .add_systems(
++                PreUpdate,
++                pause_menu_input
++                    .after(settings_menu_input)
++                    .run_if(in_state(AppState::InGame)),
++            )
++            .add_systems(
++                Update,
++                (hold_game_clock, update_pause_panel).run_if(in_state(AppState::InGame)),
++            )
++            .add_systems(
++                PreUpdate,
++                console_input
++                    .after(settings_menu_input)
++                    .before(handover_input)
++                    .before(pause_menu_input)
++                    .run_if(in_state(AppState::InGame)),
++            )
++            .add_systems(
++                Update,
++                update_console_panel.run_if(in_state(AppState::InGame)),
++            )
++            .add_systems(
++                PreUpdate,
++                handover_input
++                    .after(settings_menu_input)
++                    .before(pause_menu_input)
++                    .run_if(resource_exists::<Handover>.and_then(in_state(AppState::InGame))),
++            )
++            .add_systems(
++                Update,
++                (start_handover, update_handover_panel)
++                    .chain()
++                    .run_if(in_state(AppState::InGame)),
++            )
++            .add_systems(
++                PreUpdate,
++                tutorial_input
++                    .after(pause_menu_input)
++                    .run_if(resource_exists::<Tutorial>.and_then(in_state(AppState::InGame))),
++            )
++            .add_systems(
++                OnEnter(AppState::InGame),
++                spawn_tutorial_panel.run_if(resource_exists::<Tutorial>),
++            )
++            .add_systems(
++                Update,
++                run_tutorial
++                    .run_if(resource_exists::<Tutorial>.and_then(in_state(AppState::InGame))),
++            )
++            .add_systems(
++                PreUpdate,
++                run_decision_clock
++                    .after(pause_menu_input)
++                    .after(press_remote_actions)
++                    .run_if(in_state(AppState::InGame).and_then(play_allowed)),
++            )
++            .add_systems(
++                Update,
++                update_decision_bar.run_if(in_state(AppState::InGame)),
++            )
++            .add_systems(
++                PreUpdate,
++                press_remote_actions
++                    .after(settings_menu_input)
++                    .before(bevy::ui::UiSystem::Focus)
++                    .run_if(resource_exists::<NetHost>.and_then(in_state(AppState::InGame))),
++            )
++            .add_systems(
++                Update,
++                (
++                    (publish_price_changes, track_price_changes).chain(),
++                    (
++                        detect_cash_changes,
++                        (animate_cash_deltas, spawn_cash_popups),
++                    )
++                        .chain(),
++                    (
++                        publish_game_events,
++                        record_game_events,
++                        narrate_game_events,
++                        announce_market_swings,
++                    )
++                        .chain(),
++                    suit_effects,
++                    (publish_ownership_changes, update_ownership_markers).chain(),
++                    publish_investment_changes,
++                    (layout_tokens, sync_tokens).chain(),
++                    remove_bankrupt_tokens,
++                )
++                    .run_if(in_state(AppState::Replay).or_else(in_state(AppState::Remote))),
++            )
++            .add_systems(
++                Update,
++                (
++                    toggle_autopilot.before(roll_dice),
++                    (roll_dice, undo_turn).run_if(in_state(TurnPhase::RollDice)),
++                    move_player.run_if(in_state(TurnPhase::Moving)),
++                    warp_player.run_if(in_state(TurnPhase::Warping)),
++                    shop_decision.run_if(in_state(TurnPhase::ShopDecision)),
++                    (
++                        stock_trading,
++                        trade_controls,
++                        answer_bot_offer,
++                        sell_shop_controls,
++                        loan_controls,
++                        shop_detail_actions,
++                    )
++                        .run_if(in_state(TurnPhase::StockPhase)),
++                    venture_card.run_if(in_state(TurnPhase::VentureCard)),
++                    (auction, update_auction_panel)
++                        .chain()
++                        .run_if(in_state(TurnPhase::Auction)),
++                )
++                    .run_if(in_state(AppState::InGame).and_then(play_allowed)),
++            )
++            .add_systems(OnEnter(TurnPhase::ResolveTile), resolve_tile)
++            .add_systems(OnEnter(TurnPhase::StockPhase), stock_phase)
++            .add_systems(
++                OnEnter(TurnPhase::EndTurn),
++                (end_turn, record_replay_turn, record_undo_point).chain(),
++            )
++            .add_systems(OnEnter(TurnPhase::VentureCard), show_venture_panel)
++            .add_systems(OnExit(TurnPhase::VentureCard), hide_venture_panel)
++            .add_systems(OnEnter(TurnPhase::ShopDecision), show_purchase_dialog)
++            .add_systems(OnExit(TurnPhase::ShopDecision), hide_purchase_dialog)
++            .add_systems(OnEnter(TurnPhase::Arcade), enter_arcade)
++            .add_systems(OnExit(TurnPhase::Arcade), hide_arcade_panel)
++            .add_systems(OnEnter(ArcadeGame::DiceDuel), start_dice_duel)
++            .add_systems(OnEnter(ArcadeGame::CardMemory), start_card_memory)
++            .add_systems(OnEnter(ArcadeGame::Roulette), start_roulette)
++            .add_systems(
++                Update,
++                (
++                    dice_duel.run_if(in_state(ArcadeGame::DiceDuel)),
++                    card_memory.run_if(in_state(ArcadeGame::CardMemory)),
++                    roulette.run_if(in_state(ArcadeGame::Roulette)),
++                    leave_arcade,
++                )
++                    .run_if(in_state(TurnPhase::Arcade).and_then(in_state(AppState::InGame))),
++            )
++            .add_systems(OnEnter(TurnPhase::Casino), enter_casino)
++            .add_systems(OnExit(TurnPhase::Casino), hide_casino_panel)
++            .add_systems(
++                Update,
++                casino.run_if(in_state(TurnPhase::Casino).and_then(in_state(AppState::InGame))),
++            )
++            .add_systems(OnEnter(TurnPhase::Auction), start_auction)
++            .add_systems(OnExit(TurnPhase::Auction), hide_auction_panel)
++            .add_systems(
++                Update,
++                cue_sounds.run_if(
++                    in_state(AppState::InGame)
++                        .or_else(in_state(AppState::Replay))
++                        .or_else(in_state(AppState::Remote)),
++                ),
++            );
++        #[cfg(feature = "audio")]
++        app.add_systems(Startup, start_music.after(load_settings))
++            .add_systems(Update, (play_sounds, apply_music_volume));
++    }
++}
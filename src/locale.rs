@@ -0,0 +1,223 @@
+diff --git a/src/locale.rs b/src/locale.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..eea63d0a53b98d11e0c0d835b8e1fcedf546aa0a
+--- /dev/null
++++ b/src/locale.rs
+@@ -0,0 +1,217 @@
++//! Translations of the HUD and menu text. Each language is a string table
++//! under `assets/locale/` mapping keys to text, with `{name}` placeholders
++//! filled in by the caller. The settings menu picks the language; keys the
++//! chosen table lacks fall back to English, and keys English lacks show as
++//! themselves. A table's font heads the `fonts` fallback chain.
++
++use bevy::{
++    asset::{AssetLoader, AsyncReadExt, LoadContext, io::Reader},
++    prelude::*,
++    utils::BoxedFuture,
++};
++use serde::{Deserialize, Serialize};
++use std::{collections::HashMap, fmt::Display};
++
++use crate::*;
++
++/// Folder holding the `*.strings.ron` tables, relative to the `assets`
++/// directory.
++pub const LOCALE_FOLDER: &str = "locale";
++
++#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
++pub enum Language {
++    #[default]
++    English,
++    Japanese,
++}
++
++/// Languages in the order the settings menu cycles through them.
++pub const LANGUAGES: [Language; 2] = [Language::English, Language::Japanese];
++
++impl Language {
++    /// The language's name, written in that language.
++    pub fn label(self) -> &'static str {
++        match self {
++            Language::English => "English",
++            Language::Japanese => "日本語",
++        }
++    }
++
++    /// The string table's path inside the assets folder.
++    pub fn table_path(self) -> String {
++        let code = match self {
++            Language::English => "en",
++            Language::Japanese => "ja",
++        };
++        format!("{}/{}.strings.ron", LOCALE_FOLDER, code)
++    }
++
++    /// The language `delta` places on, wrapping around.
++    pub fn step(self, delta: i32) -> Self {
++        let idx = LANGUAGES
++            .iter()
++            .position(|language| *language == self)
++            .unwrap_or(0);
++        LANGUAGES[(idx as i32 + delta).rem_euclid(LANGUAGES.len() as i32) as usize]
++    }
++}
++
++/// One language's text, as stored in `assets/locale/*.strings.ron`.
++#[derive(Asset, TypePath, Debug, Clone, Default, Deserialize)]
++pub struct StringTable {
++    /// Font to draw the language with, for scripts the default font lacks.
++    #[serde(default)]
++    pub font: Option<String>,
++    pub strings: HashMap<String, String>,
++}
++
++#[derive(Default)]
++pub struct StringTableLoader;
++
++#[derive(Debug, thiserror::Error)]
++pub enum StringTableLoadError {
++    #[error("could not read string table: {0}")]
++    Io(#[from] std::io::Error),
++    #[error("could not parse string table: {0}")]
++    Ron(#[from] ron::error::SpannedError),
++}
++
++impl AssetLoader for StringTableLoader {
++    type Asset = StringTable;
++    type Settings = ();
++    type Error = StringTableLoadError;
++
++    fn load<'a>(
++        &'a self,
++        reader: &'a mut Reader,
++        _settings: &'a (),
++        _load_context: &'a mut LoadContext,
++    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
++        Box::pin(async move {
++            let mut bytes = Vec::new();
++            reader.read_to_end(&mut bytes).await?;
++            Ok(ron::de::from_bytes(&bytes)?)
++        })
++    }
++
++    fn extensions(&self) -> &[&str] {
++        &["strings.ron"]
++    }
++}
++
++/// The text of the language in use.
++#[derive(Resource, Default)]
++pub struct Locale {
++    pub language: Language,
++    /// Every language's table, indexed like `LANGUAGES`.
++    pub tables: Vec<Handle<StringTable>>,
++    /// English overlaid with the chosen language.
++    pub strings: HashMap<String, String>,
++    pub font: Option<Handle<Font>>,
++}
++
++impl Locale {
++    /// The text for `key`, or the key itself if no table has it.
++    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
++        self.strings.get(key).map_or(key, String::as_str)
++    }
++
++    /// The text for `key` with each `{name}` placeholder replaced by its
++    /// value in `args`.
++    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
++        let mut text = self.get(key).to_string();
++        for (name, value) in args {
++            text = text.replace(&format!("{{{}}}", name), &value.to_string());
++        }
++        text
++    }
++}
++
++/// UI text drawn from the string tables, redrawn whenever the language
++/// changes.
++#[derive(Component)]
++pub struct Localized {
++    /// Key of each text section in order; an empty key leaves its section
++    /// alone.
++    pub keys: Vec<&'static str>,
++    pub args: Vec<(&'static str, String)>,
++}
++
++impl Localized {
++    pub fn new(keys: &[&'static str]) -> Self {
++        Self {
++            keys: keys.to_vec(),
++            args: Vec::new(),
++        }
++    }
++
++    pub fn with_arg(mut self, name: &'static str, value: impl Display) -> Self {
++        self.args.push((name, value.to_string()));
++        self
++    }
++}
++
++/// Starts loading every language's string table.
++pub fn load_locale(mut locale: ResMut<Locale>, asset_server: Res<AssetServer>) {
++    locale.tables = LANGUAGES
++        .iter()
++        .map(|language| asset_server.load(language.table_path()))
++        .collect();
++}
++
++/// Rebuilds the text in use when the language is changed or a table
++/// finishes loading.
++pub fn refresh_locale(
++    mut locale: ResMut<Locale>,
++    settings: Res<Settings>,
++    tables: Res<Assets<StringTable>>,
++    mut table_events: EventReader<AssetEvent<StringTable>>,
++    asset_server: Res<AssetServer>,
++) {
++    let loaded = table_events.read().count() > 0;
++    if !loaded && settings.language == locale.language {
++        return;
++    }
++    let language = settings.language;
++    let table = |language: Language| {
++        let idx = LANGUAGES.iter().position(|l| *l == language)?;
++        tables.get(locale.tables.get(idx)?)
++    };
++    let mut strings = table(Language::English)
++        .map(|english| english.strings.clone())
++        .unwrap_or_default();
++    let chosen = table(language);
++    if let Some(chosen) = chosen {
++        strings.extend(chosen.strings.clone());
++    }
++    let font = chosen
++        .and_then(|chosen| chosen.font.as_ref())
++        .map(|path| asset_server.load(path.clone()));
++    locale.language = language;
++    locale.strings = strings;
++    locale.font = font;
++}
++
++/// Fills in `Localized` text when the language changes or the text is
++/// first spawned.
++pub fn apply_localized_text(locale: Res<Locale>, mut texts: Query<(Ref<Localized>, &mut Text)>) {
++    // Until a table loads there is nothing better than what was spawned.
++    if locale.strings.is_empty() {
++        return;
++    }
++    for (localized, mut text) in texts.iter_mut() {
++        if !locale.is_changed() && !localized.is_added() {
++            continue;
++        }
++        let args: Vec<(&str, &dyn Display)> = localized
++            .args
++            .iter()
++            .map(|(name, value)| (*name, value as &dyn Display))
++            .collect();
++        for (section, key) in text.sections.iter_mut().zip(&localized.keys) {
++            if !key.is_empty() {
++                section.value = locale.format(key, &args);
++            }
++        }
++    }
++}
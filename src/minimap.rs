@@ -0,0 +1,225 @@
+diff --git a/src/minimap.rs b/src/minimap.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..21cc9c1a82a19dc384dbde5e0066093cbc3c08e4
+--- /dev/null
++++ b/src/minimap.rs
+@@ -0,0 +1,219 @@
++//! The minimap: a second camera drawing a small copy of the whole board in
++//! the bottom-right corner, with shops in their owners' colors and a dot per
++//! player. Clicking or tapping it moves the main camera to that spot.
++
++use bevy::{prelude::*, render::view::RenderLayers, window::PrimaryWindow};
++
++use crate::*;
++
++/// Render layer that only the minimap camera draws.
++pub const MINIMAP_LAYER: u8 = 1;
++
++/// Width and height of the minimap, in logical pixels.
++pub const MINIMAP_SIZE: f32 = 220.0;
++
++/// Gap between the minimap and the window's edges, in logical pixels.
++pub const MINIMAP_MARGIN: f32 = 12.0;
++
++pub const MINIMAP_BACKGROUND: Color = Color::rgba(0.05, 0.05, 0.1, 0.9);
++
++#[derive(Component)]
++pub struct MinimapCamera;
++
++#[derive(Component)]
++pub struct MinimapBackdrop;
++
++#[derive(Component)]
++pub struct MinimapTile(pub usize);
++
++#[derive(Component)]
++pub struct MinimapToken(pub usize);
++
++/// Spawns the minimap camera, switched off until a match is on screen. It
++/// draws after the main camera without clearing, over the board and UI.
++pub fn setup_minimap_camera(mut commands: Commands) {
++    commands.spawn((
++        Camera2dBundle {
++            camera: Camera {
++                order: 1,
++                is_active: false,
++                clear_color: ClearColorConfig::None,
++                ..Default::default()
++            },
++            transform: Transform::from_xyz(0.0, 0.0, 999.0),
++            ..Default::default()
++        },
++        RenderLayers::layer(MINIMAP_LAYER),
++        MinimapCamera,
++    ));
++}
++
++/// Spawns the minimap's backdrop, tiles and player dots for `game`.
++pub fn spawn_minimap(commands: &mut Commands, game: &Game) {
++    let layer = RenderLayers::layer(MINIMAP_LAYER);
++    let bounds = game.bounds();
++    commands.spawn((
++        SpriteBundle {
++            sprite: Sprite {
++                color: MINIMAP_BACKGROUND,
++                custom_size: Some(bounds.size() + Vec2::splat(TILE_SIZE)),
++                ..Default::default()
++            },
++            transform: Transform::from_translation(bounds.center().extend(-1.0)),
++            ..Default::default()
++        },
++        layer,
++        MinimapBackdrop,
++    ));
++    for tile in &game.board {
++        commands.spawn((
++            SpriteBundle {
++                sprite: Sprite {
++                    color: minimap_tile_color(game, tile.index, Palette::Standard),
++                    custom_size: Some(Vec2::splat(TILE_SIZE * 0.9)),
++                    ..Default::default()
++                },
++                transform: Transform::from_translation(tile.position.extend(0.0)),
++                ..Default::default()
++            },
++            layer,
++            MinimapTile(tile.index),
++        ));
++    }
++    for idx in 0..game.players.len() {
++        commands.spawn((
++            SpriteBundle {
++                sprite: Sprite {
++                    color: player_color(game, idx),
++                    custom_size: Some(Vec2::splat(TILE_SIZE * 0.6)),
++                    ..Default::default()
++                },
++                visibility: Visibility::Hidden,
++                ..Default::default()
++            },
++            layer,
++            MinimapToken(idx),
++        ));
++    }
++}
++
++/// A shop in its owner's color; anything else in its tile color in
++/// `palette`.
++pub fn minimap_tile_color(game: &Game, tile: usize, palette: Palette) -> Color {
++    match game.owner_of(tile) {
++        Some(owner) => player_color(game, owner),
++        None => palette.tile_color(game, tile),
++    }
++}
++
++/// Where the minimap sits in `window`, in logical pixels from the top left.
++pub fn minimap_rect(window: &Window) -> Rect {
++    let max = Vec2::new(window.width(), window.height()) - Vec2::splat(MINIMAP_MARGIN);
++    Rect::from_corners(max - Vec2::splat(MINIMAP_SIZE), max)
++}
++
++/// Whether `point` lands on the minimap while it is shown.
++pub fn over_minimap(
++    point: Vec2,
++    window: &Window,
++    minimap: &Query<&Camera, With<MinimapCamera>>,
++) -> bool {
++    minimap.iter().any(|camera| camera.is_active) && minimap_rect(window).contains(point)
++}
++
++/// Places and frames the minimap camera, hiding it while a bottom-right
++/// panel is open, and keeps shop colors and player dots current. Dots copy
++/// the board tokens, so they walk along with them.
++#[allow(clippy::too_many_arguments, clippy::type_complexity)]
++pub fn update_minimap(
++    game: Res<Game>,
++    settings: Res<Settings>,
++    windows: Query<&Window, With<PrimaryWindow>>,
++    panels: Query<&Style, Or<(With<MenuPanel>, With<RemotePanel>)>>,
++    mut cameras: Query<
++        (&mut Camera, &mut Transform, &mut OrthographicProjection),
++        With<MinimapCamera>,
++    >,
++    mut tiles: Query<(&mut Sprite, &MinimapTile)>,
++    board_tokens: Query<(&Transform, &PlayerToken), Without<MinimapCamera>>,
++    mut dots: Query<
++        (&mut Transform, &mut Visibility, &MinimapToken),
++        (Without<MinimapCamera>, Without<PlayerToken>),
++    >,
++) {
++    let Ok(window) = windows.get_single() else {
++        return;
++    };
++    let bounds = game.bounds();
++    let panel_open = panels.iter().any(|style| style.display != Display::None);
++    for (mut camera, mut transform, mut projection) in cameras.iter_mut() {
++        camera.is_active = !panel_open;
++        let rect = minimap_rect(window);
++        let scale = window.scale_factor();
++        camera.viewport = Some(bevy::render::camera::Viewport {
++            physical_position: (rect.min * scale).as_uvec2(),
++            physical_size: (rect.size() * scale).as_uvec2(),
++            ..Default::default()
++        });
++        let size = bounds.size() + Vec2::splat(TILE_SIZE);
++        projection.scale = size.x.max(size.y) / MINIMAP_SIZE;
++        transform.translation = bounds.center().extend(transform.translation.z);
++    }
++    if game.is_changed() {
++        for (mut sprite, tile) in tiles.iter_mut() {
++            sprite.color = minimap_tile_color(&game, tile.0, settings.palette);
++        }
++    }
++    for (mut transform, mut visibility, dot) in dots.iter_mut() {
++        match board_tokens.iter().find(|(_, token)| token.0 == dot.0) {
++            Some((token, _)) => {
++                transform.translation = token.translation.truncate().extend(1.0);
++                *visibility = Visibility::Visible;
++            }
++            None => *visibility = Visibility::Hidden,
++        }
++    }
++}
++
++/// Clicking or tapping the minimap centres the main camera on that spot and
++/// stops it following the active player.
++#[allow(clippy::type_complexity)]
++pub fn minimap_click(
++    mouse: Res<ButtonInput<MouseButton>>,
++    touches: Res<Touches>,
++    windows: Query<&Window, With<PrimaryWindow>>,
++    minimaps: Query<(&Camera, &GlobalTransform), With<MinimapCamera>>,
++    mut main_cameras: Query<&mut Transform, (With<MainCamera>, Without<MinimapCamera>)>,
++    mut follow: ResMut<CameraFollow>,
++) {
++    let Ok(window) = windows.get_single() else {
++        return;
++    };
++    let point = if mouse.just_pressed(MouseButton::Left) {
++        window.cursor_position()
++    } else {
++        touches
++            .iter_just_released()
++            .find(|touch| touch.distance().length() <= TAP_SLOP)
++            .map(|touch| touch.position())
++    };
++    let Some(point) = point else {
++        return;
++    };
++    let rect = minimap_rect(window);
++    if !rect.contains(point) {
++        return;
++    }
++    for (camera, camera_transform) in minimaps.iter() {
++        if !camera.is_active {
++            continue;
++        }
++        let Some(target) = camera.viewport_to_world_2d(camera_transform, point - rect.min) else {
++            continue;
++        };
++        for mut transform in main_cameras.iter_mut() {
++            transform.translation = target.extend(transform.translation.z);
++        }
++        follow.0 = false;
++    }
++}
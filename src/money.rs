@@ -0,0 +1,220 @@
+diff --git a/src/money.rs b/src/money.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..0e1c46d68529aee33c3ddee58be059588a0b5e19
+--- /dev/null
++++ b/src/money.rs
+@@ -0,0 +1,214 @@
++//! Amounts of gold. Players' cash and loans are `Money` rather than bare
++//! integers, so a long match or a runaway combo saturates at the limits
++//! instead of overflowing, and a negative balance reads as what it is: a
++//! debt the player must settle.
++
++use serde::{Deserialize, Serialize};
++use std::{
++    fmt,
++    iter::Sum,
++    ops::{Add, AddAssign, Neg, Sub, SubAssign},
++};
++
++/// An amount of gold, negative when owed. Arithmetic saturates rather than
++/// overflowing; `checked_add` and `checked_sub` report it instead.
++#[derive(
++    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
++)]
++#[serde(transparent)]
++pub struct Money(pub i32);
++
++impl Money {
++    pub const ZERO: Money = Money(0);
++
++    pub fn get(self) -> i32 {
++        self.0
++    }
++
++    /// `amount`, clamped to the range `Money` can hold.
++    pub fn saturating(amount: i64) -> Money {
++        Money(amount.clamp(i32::MIN as i64, i32::MAX as i64) as i32)
++    }
++
++    /// `count` lots of `each`, such as shares at a price, saturating at the
++    /// limits.
++    pub fn times(count: i32, each: i32) -> Money {
++        Money::saturating(count as i64 * each as i64)
++    }
++
++    pub fn checked_add(self, other: impl Into<Money>) -> Option<Money> {
++        self.0.checked_add(other.into().0).map(Money)
++    }
++
++    pub fn checked_sub(self, other: impl Into<Money>) -> Option<Money> {
++        self.0.checked_sub(other.into().0).map(Money)
++    }
++
++    /// Whether the amount is owed rather than held.
++    pub fn is_debt(self) -> bool {
++        self.0 < 0
++    }
++
++    /// What is actually in hand: nothing while in debt.
++    pub fn available(self) -> i32 {
++        self.0.max(0)
++    }
++
++    /// `percent` of the amount, rounded towards zero and saturating at the
++    /// limits.
++    pub fn percent(self, percent: i32) -> i32 {
++        Money::saturating(self.0 as i64 * percent as i64 / 100).0
++    }
++}
++
++impl From<i32> for Money {
++    fn from(amount: i32) -> Self {
++        Money(amount)
++    }
++}
++
++impl From<Money> for i32 {
++    fn from(money: Money) -> Self {
++        money.0
++    }
++}
++
++/// Digits grouped in threes with commas, then the "G" for gold:
++/// `-12,500G`. The `+` flag signs gains too (`{:+}` gives `+300G`), and
++/// width and alignment pad the whole amount.
++impl fmt::Display for Money {
++    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
++        let digits = self.0.unsigned_abs().to_string();
++        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
++        for (idx, digit) in digits.chars().enumerate() {
++            if idx > 0 && (digits.len() - idx).is_multiple_of(3) {
++                grouped.push(',');
++            }
++            grouped.push(digit);
++        }
++        let sign = if self.0 < 0 {
++            "-"
++        } else if f.sign_plus() && self.0 > 0 {
++            "+"
++        } else {
++            ""
++        };
++        f.pad(&format!("{}{}G", sign, grouped))
++    }
++}
++
++/// Adds another `Money` or a bare `i32`, saturating at the limits.
++impl<T: Into<Money>> Add<T> for Money {
++    type Output = Money;
++
++    fn add(self, other: T) -> Money {
++        Money(self.0.saturating_add(other.into().0))
++    }
++}
++
++/// Subtracts another `Money` or a bare `i32`, saturating at the limits. The
++/// difference of two balances, such as cash before and after a sale, is
++/// itself `Money` and displays as gold.
++impl<T: Into<Money>> Sub<T> for Money {
++    type Output = Money;
++
++    fn sub(self, other: T) -> Money {
++        Money(self.0.saturating_sub(other.into().0))
++    }
++}
++
++impl<T: Into<Money>> AddAssign<T> for Money {
++    fn add_assign(&mut self, other: T) {
++        *self = *self + other;
++    }
++}
++
++impl<T: Into<Money>> SubAssign<T> for Money {
++    fn sub_assign(&mut self, other: T) {
++        *self = *self - other;
++    }
++}
++
++impl Neg for Money {
++    type Output = Money;
++
++    fn neg(self) -> Money {
++        Money(self.0.saturating_neg())
++    }
++}
++
++impl PartialEq<i32> for Money {
++    fn eq(&self, other: &i32) -> bool {
++        self.0 == *other
++    }
++}
++
++impl PartialOrd<i32> for Money {
++    fn partial_cmp(&self, other: &i32) -> Option<std::cmp::Ordering> {
++        self.0.partial_cmp(other)
++    }
++}
++
++impl Sum for Money {
++    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
++        iter.fold(Money::ZERO, |total, amount| total + amount)
++    }
++}
++
++#[cfg(test)]
++mod tests {
++    use super::*;
++
++    #[test]
++    fn groups_digits_in_threes() {
++        assert_eq!(Money(0).to_string(), "0G");
++        assert_eq!(Money(999).to_string(), "999G");
++        assert_eq!(Money(1000).to_string(), "1,000G");
++        assert_eq!(Money(-12_500).to_string(), "-12,500G");
++        assert_eq!(Money(1_234_567).to_string(), "1,234,567G");
++        assert_eq!(Money(i32::MAX).to_string(), "2,147,483,647G");
++        assert_eq!(Money(i32::MIN).to_string(), "-2,147,483,648G");
++    }
++
++    #[test]
++    fn plus_flag_signs_gains_only() {
++        assert_eq!(format!("{:+}", Money(300)), "+300G");
++        assert_eq!(format!("{:+}", Money(-300)), "-300G");
++        assert_eq!(format!("{:+}", Money(0)), "0G");
++    }
++
++    #[test]
++    fn width_pads_the_whole_amount() {
++        assert_eq!(format!("{:>8}", Money(1500)), "  1,500G");
++        assert_eq!(format!("{:<8}|", Money(-5)), "-5G     |");
++        assert_eq!(format!("{:>+8}", Money(1500)), " +1,500G");
++    }
++
++    #[test]
++    fn arithmetic_saturates() {
++        assert_eq!(Money(i32::MAX) + 1, Money(i32::MAX));
++        assert_eq!(Money(i32::MIN) - Money(1), Money(i32::MIN));
++        assert_eq!(-Money(i32::MIN), Money(i32::MAX));
++        let mut cash = Money(i32::MAX - 5);
++        cash += 10;
++        assert_eq!(cash, i32::MAX);
++        cash -= Money(i32::MAX);
++        cash -= i32::MAX;
++        assert_eq!(cash, -i32::MAX);
++        assert_eq!(
++            [Money(i32::MAX), Money(i32::MAX)]
++                .into_iter()
++                .sum::<Money>(),
++            Money(i32::MAX)
++        );
++        assert_eq!(Money::times(200, 20_000_000), Money(i32::MAX));
++        assert_eq!(Money(i32::MAX).percent(130), i32::MAX);
++    }
++
++    #[test]
++    fn checked_arithmetic_reports_overflow() {
++        assert_eq!(Money(i32::MAX).checked_add(1), None);
++        assert_eq!(Money(i32::MIN).checked_sub(Money(1)), None);
++        assert_eq!(Money(5).checked_sub(8), Some(Money(-3)));
++    }
++}
@@ -0,0 +1,62 @@
+diff --git a/src/narration.rs b/src/narration.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..c14fba5b1fe5295f2e59b942b1413d5fcf843fb9
+--- /dev/null
++++ b/src/narration.rs
+@@ -0,0 +1,56 @@
++//! The event log as a feed screen readers can follow. The log panel is a
++//! live region in the accessibility tree, so a screen reader announces each
++//! line as it is added, and `--narrate` also prints every line to standard
++//! output for terminal screen readers and scripts.
++
++use bevy::{
++    a11y::{
++        AccessibilityNode,
++        accesskit::{Live, NodeBuilder, Role},
++    },
++    prelude::*,
++};
++
++use crate::*;
++
++/// Set by `--narrate`: every game event is printed to standard output.
++#[derive(Resource)]
++pub struct NarrateRequest;
++
++/// Makes the event log panel a polite live region, so screen readers read
++/// new lines without interrupting.
++pub fn make_event_log_accessible(
++    mut commands: Commands,
++    logs: Query<Entity, (With<EventLogText>, Without<AccessibilityNode>)>,
++) {
++    for entity in logs.iter() {
++        let mut node = NodeBuilder::new(Role::Log);
++        node.set_live(Live::Polite);
++        commands.entity(entity).insert(AccessibilityNode(node));
++    }
++}
++
++/// Announces each game event through the event log's live region, and
++/// prints it when narrating.
++pub fn narrate_game_events(
++    game: Res<Game>,
++    narrate: Option<Res<NarrateRequest>>,
++    mut game_events: EventReader<GameEvent>,
++    mut logs: Query<&mut AccessibilityNode, With<EventLogText>>,
++) {
++    let lines: Vec<String> = game_events
++        .read()
++        .map(|event| event.describe(&game))
++        .collect();
++    if lines.is_empty() {
++        return;
++    }
++    if narrate.is_some() {
++        for line in &lines {
++            println!("{}", line);
++        }
++    }
++    for mut log in logs.iter_mut() {
++        log.set_name(lines.join("\n"));
++    }
++}
@@ -0,0 +1,1076 @@
+diff --git a/src/net.rs b/src/net.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..95e31b14af4b280608fd767431c2ee8fa3bc4562
+--- /dev/null
++++ b/src/net.rs
+@@ -0,0 +1,1070 @@
++//! Online play over TCP. One player hosts a room with `--host`: the setup
++//! screen becomes its lobby, where the host picks the board and rules and
++//! opens seats that other players take with `--connect` and the room code.
++//! Once everyone is ready the host starts the match and runs it: remote key
++//! presses are relayed to the host and replayed there as input, and the host
++//! streams back the match state, game events and whatever panels are open.
++//! Spectators connect with `--spectate` at any time and get the same stream
++//! without a seat. Messages are newline-delimited RON.
++
++use bevy::prelude::*;
++use rand::Rng;
++use serde::{Deserialize, Serialize, de::DeserializeOwned};
++use std::{
++    io::{ErrorKind, Read, Write},
++    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
++    time::Duration,
++};
++
++use crate::*;
++
++pub const DEFAULT_PORT: u16 = 7878;
++
++/// Seat opened for remote players when hosting starts.
++pub const DEFAULT_OPEN_SEAT: usize = 1;
++
++pub const ROOM_CODE_LENGTH: usize = 4;
++
++/// Letters room codes are made of, leaving out ones easily misread.
++pub const ROOM_CODE_LETTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
++
++/// Minimum seconds between match state updates sent to the client.
++pub const STATE_SYNC_SECONDS: f32 = 0.1;
++
++/// How long a client waits for the host to accept the connection.
++pub const CONNECT_TIMEOUT_SECONDS: u64 = 5;
++
++/// Longest message line either side accepts; a peer sending a longer one is
++/// dropped rather than buffered without end.
++pub const MAX_LINE_BYTES: usize = 1 << 20;
++
++/// Most bytes waiting to go out to a peer before it counts as gone: it has
++/// stopped reading, and the match won't wait for it.
++pub const MAX_QUEUED_BYTES: usize = 8 << 20;
++
++#[derive(Debug, thiserror::Error)]
++pub enum NetError {
++    #[error("network error: {0}")]
++    Io(#[from] std::io::Error),
++    #[error("could not encode message: {0}")]
++    Encode(#[from] ron::Error),
++    #[error("could not decode message: {0}")]
++    Decode(#[from] ron::error::SpannedError),
++    #[error("could not restore the match: {0}")]
++    Save(#[from] SaveError),
++    #[error("connection closed")]
++    Closed,
++    #[error("a message ran over {} bytes", MAX_LINE_BYTES)]
++    LineTooLong,
++    #[error("the other side stopped reading")]
++    Stalled,
++}
++
++#[derive(Debug, Serialize, Deserialize)]
++pub enum ClientMessage {
++    Hello {
++        name: String,
++        code: String,
++    },
++    /// Join as a spectator rather than take a seat.
++    Watch {
++        code: String,
++    },
++    Ready(bool),
++    /// Relayed actions pressed this frame.
++    Actions(Vec<Action>),
++}
++
++#[derive(Debug, Serialize, Deserialize)]
++pub enum ServerMessage {
++    Welcome {
++        seat: usize,
++    },
++    Refused {
++        reason: String,
++    },
++    /// The client was let in as a spectator.
++    Watching,
++    Lobby(LobbyInfo),
++    /// The match is starting on this board and state.
++    Start {
++        save: Box<SaveFile>,
++    },
++    State {
++        state: MatchState,
++        prompt: String,
++        /// Text of the host's open panels, such as menus and dialogs.
++        panels: Vec<String>,
++    },
++    Events(Vec<GameEvent>),
++}
++
++/// What players waiting in the lobby see of the room.
++#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
++pub struct LobbyInfo {
++    pub code: String,
++    pub board: String,
++    pub target_net_worth: i32,
++    pub difficulty: Difficulty,
++    pub seats: Vec<LobbySeat>,
++    pub spectators: usize,
++}
++
++#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
++pub struct LobbySeat {
++    pub name: String,
++    pub status: SeatStatus,
++}
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
++pub enum SeatStatus {
++    /// Played at the host's own keyboard.
++    Local,
++    Bot,
++    /// Waiting for a remote player.
++    Open,
++    Joined {
++        ready: bool,
++    },
++}
++
++impl SeatStatus {
++    pub fn label(self) -> &'static str {
++        match self {
++            SeatStatus::Local => "Human",
++            SeatStatus::Bot => "Bot",
++            SeatStatus::Open => "Open",
++            SeatStatus::Joined { ready: true } => "Remote, ready",
++            SeatStatus::Joined { ready: false } => "Remote, not ready",
++        }
++    }
++}
++
++/// A TCP stream carrying one RON message per line. Neither side ever blocks
++/// on it: messages sent are queued and written as the socket takes them.
++pub struct Connection {
++    pub stream: TcpStream,
++    received: Vec<u8>,
++    /// Bytes sent but not yet taken by the socket.
++    outgoing: Vec<u8>,
++}
++
++impl Connection {
++    pub fn new(stream: TcpStream) -> Result<Self, NetError> {
++        stream.set_nonblocking(true)?;
++        stream.set_nodelay(true)?;
++        Ok(Self {
++            stream,
++            received: Vec::new(),
++            outgoing: Vec::new(),
++        })
++    }
++
++    /// Queues `message` and writes as much of the queue as the socket takes.
++    pub fn send<T: Serialize>(&mut self, message: &T) -> Result<(), NetError> {
++        let mut line = ron::to_string(message)?;
++        line.push('\n');
++        self.outgoing.extend_from_slice(line.as_bytes());
++        self.flush()
++    }
++
++    /// Writes what the socket will take of the queue without blocking.
++    pub fn flush(&mut self) -> Result<(), NetError> {
++        let mut written = 0;
++        while written < self.outgoing.len() {
++            match self.stream.write(&self.outgoing[written..]) {
++                Ok(0) => return Err(NetError::Closed),
++                Ok(count) => written += count,
++                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
++                Err(err) if err.kind() == ErrorKind::Interrupted => {}
++                Err(err) => return Err(err.into()),
++            }
++        }
++        self.outgoing.drain(..written);
++        if self.outgoing.len() > MAX_QUEUED_BYTES {
++            return Err(NetError::Stalled);
++        }
++        Ok(())
++    }
++
++    /// Every complete message that has arrived since the last call. Also
++    /// writes out what is still queued, so polling it each frame keeps both
++    /// directions moving.
++    pub fn receive<T: DeserializeOwned>(&mut self) -> Result<Vec<T>, NetError> {
++        self.flush()?;
++        let mut chunk = [0; 4096];
++        // Whatever is past a line's worth waits in the socket for the next
++        // call.
++        while self.received.len() <= MAX_LINE_BYTES {
++            match self.stream.read(&mut chunk) {
++                Ok(0) => return Err(NetError::Closed),
++                Ok(read) => self.received.extend_from_slice(&chunk[..read]),
++                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
++                Err(err) => return Err(err.into()),
++            }
++        }
++        let mut messages = Vec::new();
++        while let Some(end) = self.received.iter().position(|byte| *byte == b'\n') {
++            if end > MAX_LINE_BYTES {
++                return Err(NetError::LineTooLong);
++            }
++            let line: Vec<u8> = self.received.drain(..=end).collect();
++            messages.push(ron::de::from_bytes(&line[..end])?);
++        }
++        if self.received.len() > MAX_LINE_BYTES {
++            return Err(NetError::LineTooLong);
++        }
++        Ok(messages)
++    }
++}
++
++/// Port named by `--host`.
++#[derive(Resource)]
++pub struct HostRequest(pub u16);
++
++/// Address, room code and player name from `--connect`, `--code` and
++/// `--name`, and whether `--spectate` asked to only watch.
++#[derive(Resource)]
++pub struct ConnectRequest {
++    pub address: String,
++    pub code: String,
++    pub name: String,
++    pub spectate: bool,
++}
++
++/// The host's view of one connected player or spectator.
++pub struct RemoteClient {
++    pub connection: Connection,
++    /// Seat taken once the player said hello with the right room code.
++    pub seat: Option<usize>,
++    /// Let in to watch with the right room code.
++    pub spectator: bool,
++    pub ready: bool,
++    /// Relayed actions not yet pressed on the host.
++    pub pending_actions: Vec<Action>,
++}
++
++#[derive(Resource)]
++pub struct NetHost {
++    pub port: u16,
++    /// Code players give to join the room.
++    pub code: String,
++    pub listener: TcpListener,
++    pub clients: Vec<RemoteClient>,
++    pub sync_timer: Timer,
++    /// The match changed since the last state update.
++    pub dirty: bool,
++    pub last_prompt: String,
++    pub last_panels: Vec<String>,
++    /// Lobby last sent to the clients, to send it again only when it changes.
++    pub last_lobby: Option<LobbyInfo>,
++}
++
++impl RemoteClient {
++    /// Whether the client has been let into the room, to play or to watch.
++    pub fn joined(&self) -> bool {
++        self.seat.is_some() || self.spectator
++    }
++}
++
++impl NetHost {
++    pub fn client_at(&self, seat: usize) -> Option<&RemoteClient> {
++        self.clients.iter().find(|client| client.seat == Some(seat))
++    }
++
++    /// Whether every player who has taken a seat is ready to start.
++    pub fn all_ready(&self) -> bool {
++        self.clients
++            .iter()
++            .all(|client| client.seat.is_none() || client.ready)
++    }
++
++    /// The first open seat nobody has taken yet.
++    pub fn open_seat(&self, settings: &MatchSettings) -> Option<usize> {
++        (0..settings.seats.len())
++            .find(|seat| seat_status(settings, Some(self), *seat) == SeatStatus::Open)
++    }
++
++    pub fn lobby(&self, settings: &MatchSettings, rules: &GameRules, board: &str) -> LobbyInfo {
++        LobbyInfo {
++            code: self.code.clone(),
++            board: board.to_string(),
++            target_net_worth: TARGET_NET_WORTHS[settings.target],
++            difficulty: rules.difficulty,
++            seats: settings
++                .seats
++                .iter()
++                .enumerate()
++                .map(|(seat, roster)| LobbySeat {
++                    name: roster.name.clone(),
++                    status: seat_status(settings, Some(self), seat),
++                })
++                .collect(),
++            spectators: self.spectators(),
++        }
++    }
++
++    pub fn spectators(&self) -> usize {
++        self.clients
++            .iter()
++            .filter(|client| client.spectator)
++            .count()
++    }
++}
++
++pub fn seat_status(settings: &MatchSettings, host: Option<&NetHost>, seat: usize) -> SeatStatus {
++    if let Some(client) = host.and_then(|host| host.client_at(seat)) {
++        return SeatStatus::Joined {
++            ready: client.ready,
++        };
++    }
++    match settings.seats[seat].kind {
++        PlayerKind::Human if settings.remote[seat] => SeatStatus::Open,
++        PlayerKind::Human => SeatStatus::Local,
++        PlayerKind::Bot => SeatStatus::Bot,
++    }
++}
++
++#[derive(Resource)]
++pub struct NetClient {
++    pub address: SocketAddr,
++    pub connection: Option<Connection>,
++    pub seat: Option<usize>,
++    pub spectator: bool,
++    pub ready: bool,
++    /// Connection progress shown while waiting for the match to start.
++    pub status: String,
++    pub lobby: Option<LobbyInfo>,
++    pub prompt: String,
++    pub panels: Vec<String>,
++}
++
++/// Panel on the client mirroring the host's open panels.
++#[derive(Component)]
++pub struct RemotePanel;
++
++#[derive(Component)]
++pub struct RemotePanelText;
++
++fn room_code() -> String {
++    let mut rng = rand::thread_rng();
++    (0..ROOM_CODE_LENGTH)
++        .map(|_| ROOM_CODE_LETTERS[rng.gen_range(0..ROOM_CODE_LETTERS.len())] as char)
++        .collect()
++}
++
++/// Opens the host's listening socket and the first open seat.
++pub fn start_hosting(
++    mut commands: Commands,
++    request: Option<Res<HostRequest>>,
++    mut settings: ResMut<MatchSettings>,
++) {
++    let Some(request) = request else {
++        return;
++    };
++    let listener = match TcpListener::bind(("0.0.0.0", request.0))
++        .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
++    {
++        Ok(listener) => listener,
++        Err(err) => {
++            error!("Could not host on port {}: {}", request.0, err);
++            return;
++        }
++    };
++    let code = room_code();
++    info!("Hosting room {} on port {}", code, request.0);
++    settings.seats[DEFAULT_OPEN_SEAT].kind = PlayerKind::Human;
++    settings.remote[DEFAULT_OPEN_SEAT] = true;
++    settings.refresh_default_names();
++    commands.insert_resource(NetHost {
++        port: request.0,
++        code,
++        listener,
++        clients: Vec::new(),
++        sync_timer: Timer::from_seconds(STATE_SYNC_SECONDS, TimerMode::Repeating),
++        dirty: true,
++        last_prompt: String::new(),
++        last_panels: Vec::new(),
++        last_lobby: None,
++    });
++}
++
++/// Takes new connections; they join the room once they say hello or ask to
++/// watch.
++pub fn accept_connections(mut host: ResMut<NetHost>) {
++    while let Ok((stream, address)) = host.listener.accept() {
++        let Ok(connection) = Connection::new(stream) else {
++            continue;
++        };
++        info!("{} connected", address);
++        host.clients.push(RemoteClient {
++            connection,
++            seat: None,
++            spectator: false,
++            ready: false,
++            pending_actions: Vec::new(),
++        });
++    }
++}
++
++/// Seats players who say hello with the room code, lets spectators in, and
++/// reads ready flags and key presses. If someone drops out mid-match a bot
++/// takes over their seat.
++#[allow(clippy::too_many_arguments)]
++pub fn receive_from_clients(
++    mut host: ResMut<NetHost>,
++    mut settings: ResMut<MatchSettings>,
++    mut game: Option<ResMut<Game>>,
++    market: Option<Res<StockMarket>>,
++    deck: Option<Res<VentureDeck>>,
++    recorder: Option<Res<ReplayRecorder>>,
++) {
++    let mut dropped = Vec::new();
++    for idx in 0..host.clients.len() {
++        let messages = match host.clients[idx].connection.receive::<ClientMessage>() {
++            Ok(messages) => messages,
++            Err(err) => {
++                info!("A remote player left: {}", err);
++                dropped.push(idx);
++                continue;
++            }
++        };
++        for message in messages {
++            match message {
++                ClientMessage::Hello { name, code } => {
++                    if host.clients[idx].seat.is_some() {
++                        continue;
++                    }
++                    let seat = if !code.trim().eq_ignore_ascii_case(&host.code) {
++                        Err("wrong room code")
++                    } else if game.is_some() {
++                        Err("the match has already started, join with --spectate to watch")
++                    } else {
++                        host.open_seat(&settings).ok_or("the room is full")
++                    };
++                    let client = &mut host.clients[idx];
++                    match seat {
++                        Ok(seat) => {
++                            let name: String = name.trim().chars().take(MAX_NAME_LENGTH).collect();
++                            settings.seats[seat].name = if name.is_empty() {
++                                format!("Guest {}", seat + 1)
++                            } else {
++                                name
++                            };
++                            settings.renamed[seat] = true;
++                            client.seat = Some(seat);
++                            let _ = client.connection.send(&ServerMessage::Welcome { seat });
++                            // Newcomers need the whole lobby.
++                            host.last_lobby = None;
++                        }
++                        Err(reason) => {
++                            let _ = client.connection.send(&ServerMessage::Refused {
++                                reason: reason.to_string(),
++                            });
++                            dropped.push(idx);
++                        }
++                    }
++                }
++                ClientMessage::Watch { code } => {
++                    let right_code = code.trim().eq_ignore_ascii_case(&host.code);
++                    let client = &mut host.clients[idx];
++                    if client.joined() {
++                        continue;
++                    }
++                    if !right_code {
++                        let _ = client.connection.send(&ServerMessage::Refused {
++                            reason: "wrong room code".to_string(),
++                        });
++                        dropped.push(idx);
++                        continue;
++                    }
++                    client.spectator = true;
++                    let mut sent = client.connection.send(&ServerMessage::Watching);
++                    // Someone arriving mid-match gets the position and the
++                    // latest events for their log.
++                    if let (Some(game), Some(market), Some(deck)) = (&game, &market, &deck) {
++                        let save = Box::new(SaveFile::capture(game, market, deck));
++                        sent = sent
++                            .and_then(|_| client.connection.send(&ServerMessage::Start { save }));
++                        let mut recent: Vec<GameEvent> = recorder
++                            .iter()
++                            .flat_map(|recorder| {
++                                let turns = recorder.replay.turns.iter();
++                                turns
++                                    .flat_map(|turn| turn.events.iter())
++                                    .chain(recorder.events.iter())
++                            })
++                            .rev()
++                            .take(EVENT_LOG_LINES)
++                            .cloned()
++                            .collect();
++                        recent.reverse();
++                        sent = sent
++                            .and_then(|_| client.connection.send(&ServerMessage::Events(recent)));
++                    }
++                    if sent.is_err() {
++                        dropped.push(idx);
++                    }
++                    host.last_lobby = None;
++                }
++                ClientMessage::Ready(ready) => host.clients[idx].ready = ready,
++                ClientMessage::Actions(actions) => {
++                    let actions = actions.into_iter().filter(|action| action.relayed());
++                    host.clients[idx].pending_actions.extend(actions);
++                }
++            }
++        }
++    }
++    drop_clients(&mut host, dropped, &mut settings, game.as_deref_mut());
++}
++
++/// Forgets the players at `dropped`, reopening their seats in the lobby or
++/// handing them to bots if the match is on.
++fn drop_clients(
++    host: &mut NetHost,
++    mut dropped: Vec<usize>,
++    settings: &mut MatchSettings,
++    mut game: Option<&mut Game>,
++) {
++    dropped.sort_unstable();
++    dropped.dedup();
++    for idx in dropped.into_iter().rev() {
++        let Some(seat) = host.clients.remove(idx).seat else {
++            continue;
++        };
++        settings.renamed[seat] = false;
++        settings.refresh_default_names();
++        if let Some(game) = game.as_deref_mut()
++            && game.players[seat].kind == PlayerKind::Human
++        {
++            game.players[seat].kind = PlayerKind::Bot;
++            game.players[seat].decider = Decider::Brain;
++            game.pending_events
++                .push(GameEvent::Disconnected { player: seat });
++        }
++    }
++}
++
++/// Presses each remote player's relayed actions on the host whenever they
++/// are the one acting: on their own turn (when the host's own input is held
++/// back), when bidding in an auction, and when answering a trade offer.
++pub fn press_remote_actions(
++    mut host: ResMut<NetHost>,
++    game: Res<Game>,
++    phase: Res<State<TurnPhase>>,
++    desk: Res<TradeDesk>,
++    mut actions: ResMut<ActionInput>,
++    mut mouse: ResMut<ButtonInput<MouseButton>>,
++) {
++    for client in host.clients.iter_mut() {
++        let pressed = std::mem::take(&mut client.pending_actions);
++        let Some(seat) = client.seat else {
++            continue;
++        };
++        if game.players[seat].kind != PlayerKind::Human {
++            continue;
++        }
++        if game.current_turn == seat {
++            actions.reset_all();
++            mouse.reset_all();
++            for action in pressed {
++                actions.press(action);
++            }
++        } else if *phase.get() == TurnPhase::Auction {
++            actions.reset(seat_bid(seat));
++            if pressed.contains(&Action::Bid) {
++                actions.press(seat_bid(seat));
++            }
++        } else if desk.counter.as_ref().is_some_and(|offer| offer.to == seat) {
++            actions.reset(Action::Yes);
++            actions.reset(Action::No);
++            for action in pressed {
++                if matches!(action, Action::Yes | Action::No) {
++                    actions.press(action);
++                }
++            }
++        }
++    }
++}
++
++/// Sends the lobby to every seated player whenever the board, target or
++/// roster changes.
++pub fn broadcast_lobby(
++    mut host: ResMut<NetHost>,
++    mut settings: ResMut<MatchSettings>,
++    rules: Res<GameRules>,
++    library: Res<BoardLibrary>,
++    boards: Res<Assets<BoardDefinition>>,
++) {
++    let board = boards
++        .get(&library.0[settings.board])
++        .map_or("?", |board| board.name.as_str());
++    let lobby = host.lobby(&settings, &rules, board);
++    if host.last_lobby.as_ref() == Some(&lobby) {
++        return;
++    }
++    let mut dropped = Vec::new();
++    for (idx, client) in host.clients.iter_mut().enumerate() {
++        if client.joined()
++            && client
++                .connection
++                .send(&ServerMessage::Lobby(lobby.clone()))
++                .is_err()
++        {
++            dropped.push(idx);
++        }
++    }
++    host.last_lobby = Some(lobby);
++    drop_clients(&mut host, dropped, &mut settings, None);
++}
++
++/// Sends the starting position to every player and spectator, and turns away
++/// anyone who never joined the room.
++pub fn start_remote_match(
++    mut host: ResMut<NetHost>,
++    mut settings: ResMut<MatchSettings>,
++    mut game: ResMut<Game>,
++    market: Res<StockMarket>,
++    deck: Res<VentureDeck>,
++) {
++    let mut dropped = Vec::new();
++    for (idx, client) in host.clients.iter_mut().enumerate() {
++        let sent = if client.joined() {
++            client.connection.send(&ServerMessage::Start {
++                save: Box::new(SaveFile::capture(&game, &market, &deck)),
++            })
++        } else {
++            Err(NetError::Closed)
++        };
++        if sent.is_err() {
++            dropped.push(idx);
++        }
++    }
++    drop_clients(&mut host, dropped, &mut settings, Some(&mut game));
++    host.dirty = true;
++}
++
++/// Streams game events as they happen and the match state, prompt and open
++/// panels whenever they change, at most every `STATE_SYNC_SECONDS`.
++#[allow(clippy::too_many_arguments, clippy::type_complexity)]
++pub fn broadcast_to_clients(
++    time: Res<Time>,
++    mut host: ResMut<NetHost>,
++    mut settings: ResMut<MatchSettings>,
++    mut game: ResMut<Game>,
++    market: Res<StockMarket>,
++    deck: Res<VentureDeck>,
++    mut game_events: EventReader<GameEvent>,
++    prompt_text: Query<&Text, With<PromptText>>,
++    panels: Query<
++        (&Style, &Children),
++        Or<(
++            With<MenuPanel>,
++            With<StockPanel>,
++            With<TradePanel>,
++            With<VenturePanel>,
++            With<PurchaseDialog>,
++            With<AuctionPanel>,
++            With<ArcadePanel>,
++            With<CasinoPanel>,
++        )>,
++    >,
++    texts: Query<&Text>,
++) {
++    let events: Vec<GameEvent> = game_events.read().cloned().collect();
++    if host.clients.is_empty() {
++        return;
++    }
++    host.dirty |= game.is_changed() || market.is_changed();
++    let prompt = prompt_text
++        .get_single()
++        .map(|text| text.sections[0].value.clone())
++        .unwrap_or_default();
++    let open_panels: Vec<String> = panels
++        .iter()
++        .filter(|(style, _)| style.display != Display::None)
++        .map(|(_, children)| {
++            children
++                .iter()
++                .filter_map(|child| texts.get(*child).ok())
++                .flat_map(|text| text.sections.iter().map(|section| section.value.as_str()))
++                .collect::<Vec<_>>()
++                .join("\n")
++        })
++        .collect();
++    let due = host.sync_timer.tick(time.delta()).finished();
++
++    let mut messages = Vec::new();
++    if !events.is_empty() {
++        messages.push(ServerMessage::Events(events));
++    }
++    if due && (host.dirty || prompt != host.last_prompt || open_panels != host.last_panels) {
++        messages.push(ServerMessage::State {
++            state: MatchState::capture(&game, &market, &deck),
++            prompt: prompt.clone(),
++            panels: open_panels.clone(),
++        });
++        host.dirty = false;
++        host.last_prompt = prompt;
++        host.last_panels = open_panels;
++        host.sync_timer.reset();
++    }
++    let mut dropped = Vec::new();
++    for (idx, client) in host.clients.iter_mut().enumerate() {
++        if client.joined()
++            && messages
++                .iter()
++                .any(|message| client.connection.send(message).is_err())
++        {
++            info!("Lost a remote player");
++            dropped.push(idx);
++        }
++    }
++    drop_clients(&mut host, dropped, &mut settings, Some(&mut game));
++}
++
++/// Connects to a host and introduces the player, or asks to watch.
++pub fn join_host(request: &ConnectRequest) -> Result<NetClient, NetError> {
++    let address = request
++        .address
++        .to_socket_addrs()?
++        .next()
++        .ok_or(NetError::Closed)?;
++    let stream =
++        TcpStream::connect_timeout(&address, Duration::from_secs(CONNECT_TIMEOUT_SECONDS))?;
++    let mut connection = Connection::new(stream)?;
++    if request.spectate {
++        connection.send(&ClientMessage::Watch {
++            code: request.code.clone(),
++        })?;
++    } else {
++        connection.send(&ClientMessage::Hello {
++            name: request.name.clone(),
++            code: request.code.clone(),
++        })?;
++    }
++    Ok(NetClient {
++        address,
++        connection: Some(connection),
++        seat: None,
++        spectator: request.spectate,
++        ready: false,
++        status: format!("Connected to {}, joining the room", address),
++        lobby: None,
++        prompt: String::new(),
++        panels: Vec::new(),
++    })
++}
++
++/// Applies what the host sends: the starting position, state updates and game
++/// events for the log.
++#[allow(clippy::too_many_arguments)]
++pub fn client_receive(
++    mut commands: Commands,
++    mut client: ResMut<NetClient>,
++    mut next_state: ResMut<NextState<AppState>>,
++    mut game: Option<ResMut<Game>>,
++    mut market: Option<ResMut<StockMarket>>,
++    mut deck: Option<ResMut<VentureDeck>>,
++    mut ui_state: ResMut<UiState>,
++    mut bankrupt_events: EventWriter<PlayerBankrupt>,
++) {
++    let Some(connection) = client.connection.as_mut() else {
++        return;
++    };
++    let messages = match connection.receive::<ServerMessage>() {
++        Ok(messages) => messages,
++        Err(err) => {
++            client.connection = None;
++            client.status = format!("Disconnected from the host: {}", err);
++            client.prompt = client.status.clone();
++            return;
++        }
++    };
++    // A match that starts in this batch is built here and inserted at the end.
++    let mut starting: Option<(Game, StockMarket, VentureDeck)> = None;
++    for message in messages {
++        match message {
++            ServerMessage::Welcome { seat } => {
++                client.seat = Some(seat);
++                client.status = "Press R when you are ready".to_string();
++            }
++            ServerMessage::Watching => {
++                client.status = "Watching: waiting for the host to start".to_string();
++            }
++            ServerMessage::Lobby(lobby) => client.lobby = Some(lobby),
++            ServerMessage::Refused { reason } => {
++                client.connection = None;
++                client.status = format!("The host refused the connection: {}", reason);
++            }
++            ServerMessage::Start { save } => match save.restore() {
++                Ok((game, market, deck, _)) => starting = Some((game, market, deck)),
++                Err(err) => {
++                    client.connection = None;
++                    client.status = format!("Could not start the match: {}", err);
++                }
++            },
++            ServerMessage::State {
++                state,
++                prompt,
++                panels,
++            } => {
++                client.prompt = prompt;
++                client.panels = panels;
++                let result = match (&mut starting, &mut game, &mut market, &mut deck) {
++                    (Some((game, market, deck)), ..) => state.apply(game, market, deck),
++                    (None, Some(game), Some(market), Some(deck)) => {
++                        sync_match_state(state, game, market, deck, &mut bankrupt_events)
++                    }
++                    _ => Ok(()),
++                };
++                if let Err(err) = result {
++                    error!("Could not apply the host's state: {}", err);
++                }
++            }
++            ServerMessage::Events(events) => {
++                for event in &events {
++                    if let GameEvent::Rolled { player, faces } = event {
++                        ui_state.last_rolls.insert(*player, faces.clone());
++                    }
++                }
++                match (&mut starting, &mut game) {
++                    (Some((game, ..)), _) => game.pending_events.extend(events),
++                    (None, Some(game)) => game.pending_events.extend(events),
++                    _ => {}
++                }
++            }
++        }
++    }
++    if let Some((game, market, deck)) = starting {
++        commands.insert_resource(game);
++        commands.insert_resource(market);
++        commands.insert_resource(deck);
++        next_state.set(AppState::Remote);
++    }
++}
++
++/// Relays the actions pressed this frame, through this player's own
++/// bindings, to the host. Spectators keep theirs to move the camera.
++pub fn client_send_actions(actions: Res<ActionInput>, mut client: ResMut<NetClient>) {
++    if client.spectator {
++        return;
++    }
++    let pressed: Vec<Action> = actions
++        .get_just_pressed()
++        .filter(|action| action.relayed())
++        .collect();
++    if pressed.is_empty() {
++        return;
++    }
++    let Some(connection) = client.connection.as_mut() else {
++        return;
++    };
++    if let Err(err) = connection.send(&ClientMessage::Actions(pressed)) {
++        client.connection = None;
++        client.prompt = format!("Disconnected from the host: {}", err);
++    }
++}
++
++/// R tells the host you are ready to start, or no longer ready.
++pub fn lobby_input(actions: Res<ActionInput>, mut client: ResMut<NetClient>) {
++    if !actions.just_pressed(Action::Ready) || client.seat.is_none() {
++        return;
++    }
++    let ready = !client.ready;
++    let Some(connection) = client.connection.as_mut() else {
++        return;
++    };
++    if connection.send(&ClientMessage::Ready(ready)).is_ok() {
++        client.ready = ready;
++        client.status = if ready {
++            "Ready: waiting for the host to start (R to cancel)".to_string()
++        } else {
++            "Press R when you are ready".to_string()
++        };
++    }
++}
++
++pub fn update_join_screen(client: Res<NetClient>, mut texts: Query<&mut Text, With<SetupText>>) {
++    let mut content = String::from("Itadaki Street\n\n");
++    if let Some(lobby) = &client.lobby {
++        content.push_str(&format!(
++            "Room {}\nBoard: {}\nTarget net worth: {}\nDifficulty: {}\n\n",
++            lobby.code,
++            lobby.board,
++            Money(lobby.target_net_worth),
++            lobby.difficulty.label()
++        ));
++        for (idx, seat) in lobby.seats.iter().enumerate() {
++            let you = if client.seat == Some(idx) {
++                " (you)"
++            } else {
++                ""
++            };
++            content.push_str(&format!(
++                "  {}. {} [{}]{}\n",
++                idx + 1,
++                seat.name,
++                seat.status.label(),
++                you
++            ));
++        }
++        if lobby.spectators > 0 {
++            content.push_str(&format!("  {} watching\n", lobby.spectators));
++        }
++        content.push('\n');
++    }
++    content.push_str(&client.status);
++    for mut text in texts.iter_mut() {
++        text.sections[0].value.clone_from(&content);
++    }
++}
++
++pub fn spawn_remote_panel(mut commands: Commands, asset_server: Res<AssetServer>) {
++    commands
++        .spawn((
++            NodeBundle {
++                style: Style {
++                    position_type: PositionType::Absolute,
++                    right: Val::Px(12.0),
++                    bottom: Val::Px(12.0),
++                    width: Val::Px(380.0),
++                    padding: UiRect::all(Val::Px(8.0)),
++                    display: Display::None,
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(Color::rgb(0.1, 0.1, 0.15)),
++                ..Default::default()
++            },
++            RemotePanel,
++        ))
++        .with_children(|panel| {
++            panel.spawn((
++                TextBundle::from_section(
++                    "",
++                    TextStyle {
++                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
++                        font_size: 15.0,
++                        color: Color::WHITE,
++                    },
++                ),
++                RemotePanelText,
++            ));
++        });
++}
++
++/// Shows the host's prompt and open panels in place of the local ones.
++pub fn update_remote_panels(
++    client: Res<NetClient>,
++    mut prompt_text: Query<&mut Text, With<PromptText>>,
++    mut panels: Query<&mut Style, With<RemotePanel>>,
++    mut panel_text: Query<&mut Text, (With<RemotePanelText>, Without<PromptText>)>,
++) {
++    if let Ok(mut text) = prompt_text.get_single_mut() {
++        text.sections[0].value = if client.spectator && client.connection.is_some() {
++            format!("Watching -- {}", client.prompt)
++        } else {
++            client.prompt.clone()
++        };
++    }
++    if let Ok(mut style) = panels.get_single_mut() {
++        style.display = if client.panels.is_empty() {
++            Display::None
++        } else {
++            Display::Flex
++        };
++    }
++    if let Ok(mut text) = panel_text.get_single_mut() {
++        text.sections[0].value = client.panels.join("\n\n");
++    }
++}
++
++#[cfg(test)]
++mod tests {
++    use super::*;
++
++    fn pair() -> (Connection, Connection) {
++        let listener = TcpListener::bind("127.0.0.1:0").expect("a local port is free");
++        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
++        let (host, _) = listener.accept().unwrap();
++        (
++            Connection::new(host).unwrap(),
++            Connection::new(client).unwrap(),
++        )
++    }
++
++    /// Receives on `connection`, flushing `other`, until `count` messages
++    /// have come in.
++    fn receive_all(
++        connection: &mut Connection,
++        other: &mut Connection,
++        count: usize,
++    ) -> Result<Vec<String>, NetError> {
++        let mut messages = Vec::new();
++        for _ in 0..10_000 {
++            other.flush()?;
++            messages.extend(connection.receive::<String>()?);
++            if messages.len() >= count {
++                return Ok(messages);
++            }
++            std::thread::sleep(Duration::from_millis(1));
++        }
++        panic!("only {} of {} messages arrived", messages.len(), count);
++    }
++
++    #[test]
++    fn large_messages_queue_instead_of_blocking() {
++        let (mut host, mut client) = pair();
++        let big = "x".repeat(MAX_LINE_BYTES / 2);
++        // More than a socket buffer usually holds, sent before anyone reads.
++        for _ in 0..4 {
++            host.send(&big).expect("sending never blocks");
++        }
++        let messages = receive_all(&mut client, &mut host, 4).expect("the messages arrive");
++        assert!(messages.iter().all(|message| *message == big));
++    }
++
++    #[test]
++    fn overlong_lines_drop_the_peer() {
++        let (mut host, mut client) = pair();
++        host.send(&"x".repeat(MAX_LINE_BYTES + 1)).unwrap();
++        assert!(matches!(
++            receive_all(&mut client, &mut host, 1),
++            Err(NetError::LineTooLong)
++        ));
++    }
++
++    /// A new match's start message leaves plenty of room under the line
++    /// limit for the history a long one carries.
++    #[test]
++    fn match_start_fits_in_a_line() {
++        for (name, definition) in crate::sim::load_board_files().expect("the board files load") {
++            let seats: Vec<Seat> = (0..4)
++                .map(|character| Seat {
++                    name: format!("Player {}", character + 1),
++                    kind: PlayerKind::Human,
++                    strategy: BotStrategy::Balanced,
++                    character,
++                })
++                .collect();
++            let game = Game::new(
++                &definition,
++                &seats,
++                TARGET_NET_WORTHS[3],
++                GameRules::default(),
++            );
++            let market = StockMarket::new(&game);
++            let save = SaveFile::capture(&game, &market, &VentureDeck::for_game(&game));
++            let line = ron::to_string(&ServerMessage::Start {
++                save: Box::new(save),
++            })
++            .unwrap();
++            assert!(
++                line.len() * 4 < MAX_LINE_BYTES,
++                "{}'s start message is {} bytes",
++                name,
++                line.len()
++            );
++        }
++    }
++}
@@ -0,0 +1,251 @@
+diff --git a/src/opening.rs b/src/opening.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..3ea311496a8c5fa8a5ead7a5c57e94b523103c50
+--- /dev/null
++++ b/src/opening.rs
+@@ -0,0 +1,245 @@
++//! The opening roll. Before the first turn everyone rolls a die and the
++//! highest roll goes first; players who tie roll again among themselves until
++//! each has a place. The order is kept in `Game::turn_order`, and a small
++//! panel plays the rolls back round by round before the match begins.
++
++use bevy::prelude::*;
++use rand::Rng;
++
++use crate::*;
++
++/// Seconds each round of the opening roll stays up before the next appears.
++pub const OPENING_ROUND_SECONDS: f32 = 1.2;
++
++/// Seconds the settled order stays up before the first turn.
++pub const OPENING_HOLD_SECONDS: f32 = 2.0;
++
++/// Every die rolled for the turn order, and the order it settled.
++#[derive(Debug, Clone, Default)]
++pub struct OpeningRolls {
++    /// Each round's rolls as (seat, face): everyone first, then each group
++    /// that tied.
++    pub rounds: Vec<Vec<(usize, usize)>>,
++    /// Seats from first to last.
++    pub order: Vec<usize>,
++}
++
++/// Rolls for the order of `seats` players.
++pub fn roll_for_order(seats: usize, rng: &mut impl Rng) -> OpeningRolls {
++    let mut rolls = OpeningRolls::default();
++    rolls.order = settle_order((0..seats).collect(), rng, &mut rolls.rounds);
++    rolls
++}
++
++/// Orders `group` by one roll each, highest first, settling ties with
++/// further rolls among the tied.
++fn settle_order(
++    group: Vec<usize>,
++    rng: &mut impl Rng,
++    rounds: &mut Vec<Vec<(usize, usize)>>,
++) -> Vec<usize> {
++    if group.len() < 2 {
++        return group;
++    }
++    let mut round: Vec<(usize, usize)> = group
++        .iter()
++        .map(|seat| (*seat, rng.gen_range(1..=6)))
++        .collect();
++    rounds.push(round.clone());
++    round.sort_by_key(|(seat, face)| (std::cmp::Reverse(*face), *seat));
++    let mut order = Vec::with_capacity(group.len());
++    for tied in round.chunk_by(|a, b| a.1 == b.1) {
++        let seats = tied.iter().map(|(seat, _)| *seat).collect();
++        order.extend(settle_order(seats, rng, rounds));
++    }
++    order
++}
++
++/// The opening roll being played back. Turns wait until it is over.
++#[derive(Resource)]
++pub struct OpeningCeremony {
++    pub rolls: OpeningRolls,
++    pub timer: Timer,
++}
++
++impl OpeningCeremony {
++    pub fn new(rolls: OpeningRolls) -> Self {
++        let seconds = rolls.rounds.len() as f32 * OPENING_ROUND_SECONDS + OPENING_HOLD_SECONDS;
++        Self {
++            rolls,
++            timer: Timer::from_seconds(seconds, TimerMode::Once),
++        }
++    }
++
++    /// Rounds revealed so far.
++    pub fn rounds_shown(&self) -> usize {
++        ((self.timer.elapsed_secs() / OPENING_ROUND_SECONDS) as usize + 1)
++            .min(self.rolls.rounds.len())
++    }
++}
++
++#[derive(Component)]
++pub struct OpeningPanel;
++
++#[derive(Component)]
++pub struct OpeningText;
++
++pub fn spawn_opening_panel(mut commands: Commands, asset_server: Res<AssetServer>) {
++    commands
++        .spawn((
++            NodeBundle {
++                style: Style {
++                    position_type: PositionType::Absolute,
++                    width: Val::Percent(100.0),
++                    height: Val::Percent(100.0),
++                    justify_content: JustifyContent::Center,
++                    align_items: AlignItems::Center,
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.5)),
++                z_index: ZIndex::Global(7),
++                ..Default::default()
++            },
++            OpeningPanel,
++        ))
++        .with_children(|overlay| {
++            overlay
++                .spawn(NodeBundle {
++                    style: Style {
++                        width: Val::Px(360.0),
++                        padding: UiRect::all(Val::Px(16.0)),
++                        ..Default::default()
++                    },
++                    background_color: BackgroundColor(Color::rgb(0.1, 0.1, 0.15)),
++                    ..Default::default()
++                })
++                .with_children(|panel| {
++                    panel.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: asset_server.load(DEFAULT_FONT),
++                                font_size: 20.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        OpeningText,
++                    ));
++                });
++        });
++}
++
++/// Plays the opening roll back a round at a time, then shows the order
++/// until the first turn starts. Confirm skips to the end.
++pub fn run_opening_ceremony(
++    mut commands: Commands,
++    time: Res<Time>,
++    actions: Res<ActionInput>,
++    game: Res<Game>,
++    mut ceremony: ResMut<OpeningCeremony>,
++    mut texts: Query<&mut Text, With<OpeningText>>,
++    panels: Query<Entity, With<OpeningPanel>>,
++) {
++    ceremony.timer.tick(time.delta());
++    if ceremony.timer.finished() || actions.just_pressed(Action::Confirm) {
++        close_opening(&mut commands, &panels);
++        return;
++    }
++    let shown = ceremony.rounds_shown();
++    let name = |seat: usize| game.players[seat].name.as_str();
++    let mut content = "Rolling for turn order\n".to_string();
++    for (idx, round) in ceremony.rolls.rounds[..shown].iter().enumerate() {
++        content.push('\n');
++        if idx > 0 {
++            content.push_str("Tie! ");
++        }
++        let rolls: Vec<String> = round
++            .iter()
++            .map(|(seat, face)| format!("{} {}", name(*seat), face))
++            .collect();
++        content.push_str(&rolls.join(", "));
++    }
++    if shown == ceremony.rolls.rounds.len() {
++        let order: Vec<&str> = ceremony
++            .rolls
++            .order
++            .iter()
++            .map(|seat| name(*seat))
++            .collect();
++        content.push_str(&format!("\n\nOrder: {}", order.join(", ")));
++    }
++    for mut text in texts.iter_mut() {
++        if text.sections[0].value != content {
++            text.sections[0].value = content.clone();
++        }
++    }
++}
++
++/// Takes the opening roll's panel down and lets the first turn begin.
++pub fn close_opening(commands: &mut Commands, panels: &Query<Entity, With<OpeningPanel>>) {
++    for panel in panels.iter() {
++        commands.entity(panel).despawn_recursive();
++    }
++    commands.remove_resource::<OpeningCeremony>();
++}
++
++/// Drops an opening roll still playing when the match is left.
++pub fn end_opening_ceremony(mut commands: Commands, panels: Query<Entity, With<OpeningPanel>>) {
++    close_opening(&mut commands, &panels);
++}
++
++#[cfg(test)]
++mod tests {
++    use super::*;
++    use rand::{SeedableRng, rngs::StdRng};
++
++    /// The first seed whose opening roll for `seats` has a tie-break round
++    /// among exactly `tied` players.
++    fn seed_with_tie(seats: usize, tied: usize) -> OpeningRolls {
++        (0..10_000)
++            .map(|seed| roll_for_order(seats, &mut StdRng::seed_from_u64(seed)))
++            .find(|rolls| rolls.rounds[1..].iter().any(|round| round.len() == tied))
++            .expect("some seed ties")
++    }
++
++    /// Checks that the order is a permutation of the seats that ranks each
++    /// round's rolls highest first.
++    fn assert_settled(rolls: &OpeningRolls, seats: usize) {
++        let mut order = rolls.order.clone();
++        order.sort_unstable();
++        assert_eq!(order, (0..seats).collect::<Vec<_>>());
++        for round in &rolls.rounds {
++            for (a, face_a) in round {
++                for (b, face_b) in round {
++                    let place = |seat| rolls.order.iter().position(|s| s == seat);
++                    if face_a > face_b {
++                        assert!(place(a) < place(b), "{:?}", rolls);
++                    }
++                }
++            }
++        }
++    }
++
++    #[test]
++    fn two_way_tie_is_rolled_off() {
++        let rolls = seed_with_tie(4, 2);
++        assert_eq!(rolls.rounds[0].len(), 4);
++        assert_settled(&rolls, 4);
++    }
++
++    #[test]
++    fn three_way_tie_is_rolled_off() {
++        let rolls = seed_with_tie(4, 3);
++        assert_settled(&rolls, 4);
++    }
++
++    #[test]
++    fn order_is_always_a_permutation_of_the_seats() {
++        for seats in 1..=4 {
++            for seed in 0..500 {
++                let rolls = roll_for_order(seats, &mut StdRng::seed_from_u64(seed));
++                assert_settled(&rolls, seats);
++            }
++        }
++    }
++}
@@ -0,0 +1,316 @@
+diff --git a/src/palette.rs b/src/palette.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..7602d3a03f38f1b47dfe193f15703bdbe4b2e24c
+--- /dev/null
++++ b/src/palette.rs
+@@ -0,0 +1,310 @@
++//! Colorblind-friendly drawing of the board. The settings menu can swap the
++//! tile and district colors for a palette that stays distinct under red-green
++//! or blue-yellow color blindness, and can lay patterns over the tiles so
++//! nothing depends on color alone: a symbol per tile kind (the district
++//! number on shops) and, on owned shops, stripes with the owner's seat
++//! number.
++
++use bevy::prelude::*;
++use serde::{Deserialize, Serialize};
++
++use crate::*;
++
++#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
++pub enum Palette {
++    /// The tile colors and the district colors the board file picks.
++    #[default]
++    Standard,
++    /// Safe for deuteranopia and protanopia, after Okabe and Ito.
++    RedGreen,
++    /// Safe for tritanopia.
++    BlueYellow,
++}
++
++/// Palettes in the order the settings menu cycles through them.
++pub const PALETTES: [Palette; 3] = [Palette::Standard, Palette::RedGreen, Palette::BlueYellow];
++
++/// Colors of one colorblind palette. Shops take the district's entry.
++pub struct PaletteColors {
++    pub bank: Color,
++    pub suit: Color,
++    pub suit_yourself: Color,
++    pub chance: Color,
++    pub take_a_break: Color,
++    pub tax: Color,
++    pub arcade: Color,
++    pub casino: Color,
++    pub warp: Color,
++    pub boon: Color,
++    pub toll: Color,
++    pub districts: [Color; MAX_DISTRICTS],
++}
++
++pub const RED_GREEN_COLORS: PaletteColors = PaletteColors {
++    bank: Color::rgb(0.94, 0.89, 0.26),
++    suit: Color::rgb(0.8, 0.47, 0.65),
++    suit_yourself: Color::rgb(0.9, 0.62, 0.0),
++    chance: Color::rgb(0.34, 0.71, 0.91),
++    take_a_break: Color::rgb(0.5, 0.5, 0.5),
++    tax: Color::rgb(0.84, 0.37, 0.0),
++    arcade: Color::rgb(0.55, 0.35, 0.6),
++    casino: Color::rgb(0.75, 0.65, 0.3),
++    warp: Color::rgb(0.0, 0.62, 0.45),
++    boon: Color::rgb(0.95, 0.8, 0.6),
++    toll: Color::rgb(0.35, 0.3, 0.25),
++    districts: [
++        Color::rgb(0.0, 0.45, 0.7),
++        Color::rgb(0.9, 0.62, 0.0),
++        Color::rgb(0.34, 0.71, 0.91),
++        Color::rgb(0.84, 0.37, 0.0),
++        Color::rgb(0.0, 0.62, 0.45),
++        Color::rgb(0.94, 0.89, 0.26),
++        Color::rgb(0.8, 0.47, 0.65),
++        Color::rgb(0.6, 0.6, 0.6),
++        Color::rgb(0.15, 0.2, 0.5),
++    ],
++};
++
++pub const BLUE_YELLOW_COLORS: PaletteColors = PaletteColors {
++    bank: Color::rgb(0.95, 0.95, 0.95),
++    suit: Color::rgb(0.6, 0.1, 0.35),
++    suit_yourself: Color::rgb(0.95, 0.55, 0.65),
++    chance: Color::rgb(0.0, 0.6, 0.6),
++    take_a_break: Color::rgb(0.45, 0.45, 0.45),
++    tax: Color::rgb(0.85, 0.15, 0.2),
++    arcade: Color::rgb(0.75, 0.35, 0.55),
++    casino: Color::rgb(0.55, 0.85, 0.85),
++    warp: Color::rgb(0.0, 0.35, 0.4),
++    boon: Color::rgb(0.98, 0.8, 0.85),
++    toll: Color::rgb(0.3, 0.15, 0.2),
++    districts: [
++        Color::rgb(0.85, 0.15, 0.2),
++        Color::rgb(0.0, 0.6, 0.6),
++        Color::rgb(0.95, 0.55, 0.65),
++        Color::rgb(0.35, 0.35, 0.35),
++        Color::rgb(0.6, 0.1, 0.35),
++        Color::rgb(0.55, 0.85, 0.85),
++        Color::rgb(0.7, 0.7, 0.7),
++        Color::rgb(0.4, 0.0, 0.1),
++        Color::rgb(0.0, 0.35, 0.4),
++    ],
++};
++
++impl Palette {
++    /// String table key of the palette's name.
++    pub fn label_key(self) -> &'static str {
++        match self {
++            Palette::Standard => "palette.standard",
++            Palette::RedGreen => "palette.red_green",
++            Palette::BlueYellow => "palette.blue_yellow",
++        }
++    }
++
++    /// The palette `delta` places on, wrapping around.
++    pub fn step(self, delta: i32) -> Self {
++        let idx = PALETTES
++            .iter()
++            .position(|palette| *palette == self)
++            .unwrap_or(0);
++        PALETTES[(idx as i32 + delta).rem_euclid(PALETTES.len() as i32) as usize]
++    }
++
++    /// The palette's colors, or `None` for the standard ones.
++    pub fn colors(self) -> Option<&'static PaletteColors> {
++        match self {
++            Palette::Standard => None,
++            Palette::RedGreen => Some(&RED_GREEN_COLORS),
++            Palette::BlueYellow => Some(&BLUE_YELLOW_COLORS),
++        }
++    }
++
++    /// Color of `district`'s shops in this palette.
++    pub fn district_color(self, game: &Game, district: DistrictId) -> Color {
++        match self.colors() {
++            Some(colors) => colors.districts[district % colors.districts.len()],
++            None => game.districts[district].color,
++        }
++    }
++
++    /// Fill color of tile `index` in this palette.
++    pub fn tile_color(self, game: &Game, index: usize) -> Color {
++        let Some(colors) = self.colors() else {
++            return tile_color(game, index);
++        };
++        match game.board[index].kind {
++            TileKind::Bank => colors.bank,
++            TileKind::Property { district, .. } => self.district_color(game, district),
++            TileKind::Suit(_) => colors.suit,
++            TileKind::SuitYourself => colors.suit_yourself,
++            TileKind::Chance => colors.chance,
++            TileKind::TakeABreak => colors.take_a_break,
++            TileKind::TaxOffice => colors.tax,
++            TileKind::Arcade => colors.arcade,
++            TileKind::Casino => colors.casino,
++            TileKind::Warp { .. } => colors.warp,
++            TileKind::Boon => colors.boon,
++            TileKind::TollGate { .. } => colors.toll,
++        }
++    }
++}
++
++/// Part of a tile's pattern overlay, redrawn whenever the overlay changes.
++#[derive(Component)]
++pub struct TilePattern;
++
++/// Horizontal stripes across an owned shop.
++pub const PATTERN_STRIPES: usize = 4;
++
++/// Opacity of the stripes over an owned shop.
++pub const PATTERN_STRIPE_ALPHA: f32 = 0.45;
++
++/// Symbol in the corner of tile `index` naming its kind: the district number
++/// for shops.
++pub fn pattern_symbol(game: &Game, index: usize) -> String {
++    match &game.board[index].kind {
++        TileKind::Bank => "$".to_string(),
++        TileKind::Property { district, .. } => (district + 1).to_string(),
++        TileKind::Suit(suit) => suit.icon().to_string(),
++        TileKind::SuitYourself => "*".to_string(),
++        TileKind::Chance => "?".to_string(),
++        TileKind::TakeABreak => "Z".to_string(),
++        TileKind::TaxOffice => "%".to_string(),
++        TileKind::Arcade => "\u{266b}".to_string(),
++        TileKind::Casino => "7".to_string(),
++        TileKind::Warp { .. } => "\u{bb}".to_string(),
++        TileKind::Boon => "+".to_string(),
++        TileKind::TollGate { .. } => "#".to_string(),
++    }
++}
++
++/// Recolors tiles and minimap shops in the chosen palette when it changes
++/// or the board is respawned. Tiles drawn with atlas art take the palette as
++/// their tint.
++#[allow(clippy::type_complexity)]
++pub fn apply_palette(
++    settings: Res<Settings>,
++    game: Res<Game>,
++    mut tiles: Query<(Ref<TileEntity>, &mut Sprite, Has<TextureAtlas>)>,
++    mut minimap_tiles: Query<(Ref<MinimapTile>, &mut Sprite), Without<TileEntity>>,
++) {
++    let palette = settings.palette;
++    let added = tiles.iter().any(|(tile, ..)| tile.is_added())
++        || minimap_tiles.iter().any(|(tile, _)| tile.is_added());
++    if !settings.is_changed() && !added {
++        return;
++    }
++    for (tile, mut sprite, has_art) in tiles.iter_mut() {
++        let color = if has_art {
++            tile_tint(&game, tile.0, palette)
++        } else {
++            palette.tile_color(&game, tile.0)
++        };
++        if sprite.color != color {
++            sprite.color = color;
++        }
++    }
++    for (tile, mut sprite) in minimap_tiles.iter_mut() {
++        let color = minimap_tile_color(&game, tile.0, palette);
++        if sprite.color != color {
++            sprite.color = color;
++        }
++    }
++}
++
++/// Redraws the pattern overlays when they are switched on or off, the board
++/// is respawned, a shop changes hands or the glyph atlas is built. The
++/// symbol and seat number are drawn from the glyph atlas once it is ready.
++pub fn update_tile_patterns(
++    mut commands: Commands,
++    settings: Res<Settings>,
++    game: Res<Game>,
++    glyphs: Option<Res<GlyphAtlas>>,
++    mut ownership_events: EventReader<OwnershipChanged>,
++    tiles: Query<(Entity, Ref<TileEntity>)>,
++    patterns: Query<Entity, With<TilePattern>>,
++) {
++    let owners_changed = ownership_events.read().count() > 0;
++    let added = tiles.iter().any(|(_, tile)| tile.is_added());
++    let glyphs_ready = glyphs.as_ref().is_some_and(|glyphs| glyphs.is_added());
++    if !settings.is_changed() && !owners_changed && !added && !glyphs_ready {
++        return;
++    }
++    for pattern in patterns.iter() {
++        commands.entity(pattern).despawn_recursive();
++    }
++    if !settings.patterns {
++        return;
++    }
++    let symbol_corner = Vec2::new(-TILE_SIZE / 2.0 + 7.0, TILE_SIZE / 2.0 - 8.0);
++    let owner_corner = Vec2::new(TILE_SIZE / 2.0 - 8.0, TILE_SIZE / 2.0 - 8.0);
++    // Atlas sprites are scaled here the way `apply_text_size` scales board
++    // text.
++    let glyph_scale = settings.text_scale() * settings.ui_scale;
++    let marker =
++        |parent: &mut ChildBuilder, text: String, font_size: f32, color: Color, corner: Vec2| {
++            let transform = Transform::from_translation(corner.extend(1.6));
++            match glyphs
++                .as_ref()
++                .and_then(|glyphs| glyphs.sprite(&text, font_size * glyph_scale, color, transform))
++            {
++                Some(sprite) => {
++                    parent.spawn((sprite, TilePattern));
++                }
++                None => {
++                    parent.spawn((
++                        Text2dBundle {
++                            text: Text::from_section(
++                                text,
++                                TextStyle {
++                                    font_size,
++                                    color,
++                                    ..Default::default()
++                                },
++                            ),
++                            transform,
++                            ..Default::default()
++                        },
++                        TilePattern,
++                    ));
++                }
++            }
++        };
++    for (entity, tile) in tiles.iter() {
++        commands.entity(entity).with_children(|parent| {
++            marker(
++                parent,
++                pattern_symbol(&game, tile.0),
++                12.0,
++                Color::WHITE,
++                symbol_corner,
++            );
++            let Some(owner) = game.owner_of(tile.0) else {
++                return;
++            };
++            let stripe_color = player_color(&game, owner).with_a(PATTERN_STRIPE_ALPHA);
++            let spacing = TILE_SIZE / PATTERN_STRIPES as f32;
++            for stripe in 0..PATTERN_STRIPES {
++                let y = -TILE_SIZE / 2.0 + spacing * (stripe as f32 + 0.5);
++                parent.spawn((
++                    SpriteBundle {
++                        sprite: Sprite {
++                            color: stripe_color,
++                            custom_size: Some(Vec2::new(TILE_SIZE, 3.0)),
++                            ..Default::default()
++                        },
++                        transform: Transform::from_xyz(0.0, y, 0.5),
++                        ..Default::default()
++                    },
++                    TilePattern,
++                ));
++            }
++            marker(
++                parent,
++                (owner + 1).to_string(),
++                11.0,
++                Color::BLACK,
++                owner_corner,
++            );
++        });
++    }
++}
@@ -0,0 +1,269 @@
+diff --git a/src/pause.rs b/src/pause.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..063a073480a624eb328c7b2232b3c07054f93dee
+--- /dev/null
++++ b/src/pause.rs
+@@ -0,0 +1,263 @@
++//! The pause menu. Escape stops the match where it stands: the game clock
++//! halts, so the bot thinking timer and every animation freeze, and the turn
++//! systems stop running. The menu offers Resume, Settings, Save and Quit to
++//! Title.
++
++use bevy::prelude::*;
++
++use crate::*;
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq)]
++pub enum PauseOption {
++    Resume,
++    Settings,
++    Save,
++    QuitToTitle,
++}
++
++impl PauseOption {
++    /// The option's key in the string tables.
++    pub fn key(self) -> &'static str {
++        match self {
++            PauseOption::Resume => "pause.resume",
++            PauseOption::Settings => "pause.settings",
++            PauseOption::Save => "pause.save",
++            PauseOption::QuitToTitle => "pause.quit_to_title",
++        }
++    }
++}
++
++/// The pause menu's options, top to bottom.
++pub const PAUSE_OPTIONS: [PauseOption; 4] = [
++    PauseOption::Resume,
++    PauseOption::Settings,
++    PauseOption::Save,
++    PauseOption::QuitToTitle,
++];
++
++/// State of the pause menu while the match is paused.
++#[derive(Resource, Default)]
++pub struct PauseMenu {
++    pub open: bool,
++    pub cursor: usize,
++    /// How the last save went.
++    pub message: String,
++}
++
++#[derive(Component)]
++pub struct PausePanel;
++
++#[derive(Component)]
++pub struct PauseText;
++
++/// Run condition: false while the pause menu, the developer console, a
++/// tutorial prompt, the opening roll or a hot-seat handover holds up the
++/// match.
++pub fn play_allowed(
++    menu: Res<PauseMenu>,
++    console: Res<DevConsole>,
++    tutorial: Option<Res<Tutorial>>,
++    opening: Option<Res<OpeningCeremony>>,
++    handover: Option<Res<Handover>>,
++) -> bool {
++    !menu.open
++        && !console.open
++        && tutorial_allows_play(tutorial)
++        && opening.is_none()
++        && handover.is_none()
++}
++
++pub fn spawn_pause_panel(mut commands: Commands, asset_server: Res<AssetServer>) {
++    commands
++        .spawn((
++            NodeBundle {
++                style: Style {
++                    position_type: PositionType::Absolute,
++                    width: Val::Percent(100.0),
++                    height: Val::Percent(100.0),
++                    display: Display::None,
++                    justify_content: JustifyContent::Center,
++                    align_items: AlignItems::Center,
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.5)),
++                z_index: ZIndex::Global(8),
++                ..Default::default()
++            },
++            PausePanel,
++        ))
++        .with_children(|overlay| {
++            overlay
++                .spawn(NodeBundle {
++                    style: Style {
++                        width: Val::Px(280.0),
++                        padding: UiRect::all(Val::Px(16.0)),
++                        ..Default::default()
++                    },
++                    background_color: BackgroundColor(Color::rgb(0.1, 0.1, 0.15)),
++                    ..Default::default()
++                })
++                .with_children(|panel| {
++                    panel.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
++                                font_size: 20.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        PauseText,
++                    ));
++                });
++        });
++}
++
++/// Escape pauses the match, unless it is closing a shop's details. While
++/// paused, Up/Down pick an option, Enter takes it and Escape resumes; the
++/// menu takes every action so nothing behind it reacts. Settings opens over
++/// the menu and hands control back when it closes.
++#[allow(clippy::too_many_arguments)]
++pub fn pause_menu_input(
++    mut actions: ResMut<ActionInput>,
++    mut menu: ResMut<PauseMenu>,
++    mut settings_menu: ResMut<SettingsMenu>,
++    selected: Res<SelectedTile>,
++    mut game: ResMut<Game>,
++    market: Res<StockMarket>,
++    deck: Res<VentureDeck>,
++    rng: Res<MatchRng>,
++    phase: Res<State<TurnPhase>>,
++    mut next_state: ResMut<NextState<AppState>>,
++) {
++    if !menu.open {
++        if actions.just_pressed(Action::Back) && selected.0.is_none() {
++            menu.open = true;
++            menu.cursor = 0;
++            menu.message.clear();
++            actions.reset_all();
++        }
++        return;
++    }
++    if settings_menu.open {
++        return;
++    }
++    if actions.just_pressed(Action::Up) {
++        menu.cursor = (menu.cursor + PAUSE_OPTIONS.len() - 1) % PAUSE_OPTIONS.len();
++    }
++    if actions.just_pressed(Action::Down) {
++        menu.cursor = (menu.cursor + 1) % PAUSE_OPTIONS.len();
++    }
++    if actions.just_pressed(Action::Back) {
++        menu.open = false;
++    } else if actions.just_pressed(Action::Confirm) {
++        match PAUSE_OPTIONS[menu.cursor] {
++            PauseOption::Resume => menu.open = false,
++            PauseOption::Settings => settings_menu.open = true,
++            PauseOption::Save => {
++                let event = quick_save(&game, &market, &deck, &rng, *phase.get());
++                menu.message = event.describe(&game);
++                game.pending_events.push(event);
++            }
++            PauseOption::QuitToTitle => {
++                menu.open = false;
++                next_state.set(AppState::Title);
++            }
++        }
++    }
++    actions.reset_all();
++}
++
++/// Halts the game clock while the match is held up, and starts it again
++/// after.
++pub fn hold_game_clock(
++    menu: Res<PauseMenu>,
++    console: Res<DevConsole>,
++    tutorial: Option<Res<Tutorial>>,
++    mut time: ResMut<Time<Virtual>>,
++) {
++    let held = menu.open || console.open || !tutorial_allows_play(tutorial);
++    if held != time.is_paused() {
++        if held {
++            time.pause();
++        } else {
++            time.unpause();
++        }
++    }
++}
++
++/// Shows the pause menu, stepping aside while the settings menu is open over
++/// it.
++pub fn update_pause_panel(
++    menu: Res<PauseMenu>,
++    settings_menu: Res<SettingsMenu>,
++    locale: Res<Locale>,
++    mut panels: Query<&mut Style, With<PausePanel>>,
++    mut texts: Query<&mut Text, With<PauseText>>,
++) {
++    if !menu.is_changed() && !settings_menu.is_changed() && !locale.is_changed() {
++        return;
++    }
++    for mut style in panels.iter_mut() {
++        style.display = if menu.open && !settings_menu.open {
++            Display::Flex
++        } else {
++            Display::None
++        };
++    }
++    let mut content = format!("{}\n\n", locale.get("pause.header"));
++    for (idx, option) in PAUSE_OPTIONS.iter().enumerate() {
++        let marker = if idx == menu.cursor { "> " } else { "  " };
++        content.push_str(&format!("{}{}\n", marker, locale.get(option.key())));
++    }
++    if !menu.message.is_empty() {
++        content.push_str(&format!("\n{}", menu.message));
++    }
++    for mut text in texts.iter_mut() {
++        text.sections[0].value.clone_from(&content);
++    }
++}
++
++/// Clears away what is left of a match quit from the pause menu before the
++/// title screen takes over: the board, tokens, minimap and sidebar panels,
++/// dice and effects still in flight, the turn's phase, and the match, its
++/// market and RNG themselves, so a host's next lobby is open to joiners again and
++/// systems that remember the last match see the next one as new.
++#[allow(clippy::type_complexity)]
++pub fn clear_match_view(
++    mut commands: Commands,
++    board_entities: BoardEntities,
++    transient: Query<
++        Entity,
++        Or<(
++            With<Die>,
++            With<CashPopup>,
++            With<Sparkle>,
++            With<PhaseBanner>,
++            With<TutorialPanel>,
++        )>,
++    >,
++    panels: Query<Entity, With<PlayerPanels>>,
++    mut minimap: Query<&mut Camera, With<MinimapCamera>>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut next_arcade: ResMut<NextState<ArcadeGame>>,
++) {
++    for entity in board_entities.iter().chain(transient.iter()) {
++        commands.entity(entity).despawn_recursive();
++    }
++    for entity in panels.iter() {
++        commands.entity(entity).despawn_descendants();
++    }
++    for mut camera in minimap.iter_mut() {
++        camera.is_active = false;
++    }
++    commands.insert_resource(UiState::default());
++    commands.insert_resource(TradeDesk::default());
++    commands.insert_resource(MoveState::default());
++    commands.insert_resource(SelectedTile::default());
++    commands.remove_resource::<Game>();
++    commands.remove_resource::<StockMarket>();
++    commands.remove_resource::<MatchRng>();
++    next_phase.set(TurnPhase::RollDice);
++    next_arcade.set(ArcadeGame::Closed);
++}
@@ -0,0 +1,329 @@
+diff --git a/src/player.rs b/src/player.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..ae30a0cc6959993ae42b9d763b1777a8a57f6b9d
+--- /dev/null
++++ b/src/player.rs
+@@ -0,0 +1,323 @@
++//! Players and their holdings: cash, shops, stocks, suits and bank loans.
++
++use bevy::prelude::*;
++use serde::{Deserialize, Serialize};
++use std::collections::{HashMap, HashSet};
++
++use crate::*;
++
++/// Cash each player starts with under Normal rules.
++pub const STARTING_CASH: i32 = 2500;
++
++/// Step by which a loan is taken out or repaid.
++pub const LOAN_STEP: i32 = 100;
++
++/// Players may only borrow while their cash is below this.
++pub const LOAN_CASH_CEILING: i32 = 300;
++
++/// Share of a player's assets the bank will lend against, in percent.
++pub const LOAN_LIMIT_PERCENT: i32 = 50;
++
++/// Interest added to outstanding debt each lap, in percent.
++pub const LOAN_INTEREST_PERCENT: i32 = 10;
++
++/// Most dice a player can roll, however high their level.
++pub const MAX_DICE: usize = 5;
++
++/// Someone to play as. Their color marks their token, sidebar panel and shop
++/// flags.
++#[derive(Debug, Clone, Copy)]
++pub struct Character {
++    pub name: &'static str,
++    pub color: Color,
++}
++
++/// Characters offered on the setup screen. Their portraits and tokens are
++/// the cells of `CHARACTER_ATLAS_PATH`, in the same order.
++pub const CHARACTERS: [Character; 8] = [
++    Character {
++        name: "Mira",
++        color: Color::rgb(0.9, 0.2, 0.9),
++    },
++    Character {
++        name: "Bruno",
++        color: Color::rgb(0.95, 0.55, 0.15),
++    },
++    Character {
++        name: "Kiko",
++        color: Color::rgb(0.15, 0.75, 0.7),
++    },
++    Character {
++        name: "Dax",
++        color: Color::rgb(0.95, 0.8, 0.2),
++    },
++    Character {
++        name: "Lena",
++        color: Color::rgb(0.5, 0.85, 0.25),
++    },
++    Character {
++        name: "Orin",
++        color: Color::rgb(0.3, 0.4, 0.95),
++    },
++    Character {
++        name: "Pip",
++        color: Color::rgb(0.9, 0.25, 0.25),
++    },
++    Character {
++        name: "Sable",
++        color: Color::rgb(0.6, 0.3, 0.9),
++    },
++];
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
++pub enum PlayerKind {
++    #[default]
++    Human,
++    Bot,
++}
++
++impl PlayerKind {
++    /// Who makes the decisions of a player of this kind.
++    pub fn decider(self) -> Decider {
++        match self {
++            PlayerKind::Human => Decider::Person,
++            PlayerKind::Bot => Decider::Brain,
++        }
++    }
++}
++
++/// What makes a player's decisions on their turn: the person at the
++/// controls, or the `BotBrain` of their strategy.
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
++pub enum Decider {
++    #[default]
++    Person,
++    Brain,
++}
++
++/// One entry in the match roster picked on the setup screen.
++#[derive(Debug, Clone)]
++pub struct Seat {
++    pub name: String,
++    pub kind: PlayerKind,
++    /// How the seat plays when it is a bot.
++    pub strategy: BotStrategy,
++    /// Index into `CHARACTERS`.
++    pub character: usize,
++}
++
++#[derive(Debug, Default, Clone)]
++pub struct PlayerState {
++    pub name: String,
++    pub kind: PlayerKind,
++    /// Picks the `BotBrain` the player uses while a bot or on autopilot.
++    pub strategy: BotStrategy,
++    /// Makes the player's decisions. Always the brain for a bot; a human on
++    /// autopilot has it swapped in at the start of their turn.
++    pub decider: Decider,
++    /// The human wants their brain to play their turns for them.
++    pub autopilot: bool,
++    /// Index into `CHARACTERS`: the portrait, token and color the player
++    /// picked.
++    pub character: usize,
++    /// Name of the profile whose career stats the match counts towards.
++    pub profile: Option<String>,
++    pub cash: Money,
++    /// Shares held per district.
++    pub stocks: HashMap<DistrictId, i32>,
++    /// What the player paid for the shares they hold in each district, or
++    /// received for those they sold short (as a negative amount).
++    pub cost_basis: HashMap<DistrictId, i32>,
++    pub properties: HashSet<usize>,
++    pub suits: HashSet<Suit>,
++    /// Suit Yourself cards, each of which can stand in for any missing suit.
++    pub suit_cards: u32,
++    pub position: usize,
++    pub level: u32,
++    /// Eliminated after failing to cover a debt; skipped in the turn order.
++    pub bankrupt: bool,
++    /// Venture card effect: this player's shops charge double until their next turn.
++    pub double_fees: bool,
++    /// Landed on a Take a Break tile; skips their next turn.
++    pub on_break: bool,
++    /// Times the player has come back round to the bank.
++    pub laps: u32,
++    /// Outstanding bank loan, including accrued interest.
++    pub debt: Money,
++    /// Turns a bot waits before proposing another trade.
++    pub trade_cooldown: u32,
++}
++
++impl PlayerState {
++    /// The brain making the player's decisions, or `None` while the person
++    /// at the controls makes them.
++    pub fn brain(&self) -> Option<&'static dyn BotBrain> {
++        (self.decider == Decider::Brain).then(|| self.strategy.brain())
++    }
++
++    /// Swaps a human's brain in or out to match `autopilot`. Done only as
++    /// their turn starts, so a turn is never left half played by each.
++    pub fn settle_decider(&mut self) {
++        if self.kind == PlayerKind::Human {
++            self.decider = if self.autopilot {
++                Decider::Brain
++            } else {
++                Decider::Person
++            };
++        }
++    }
++
++    pub fn net_worth(&self, board: &[Tile], market: &StockMarket) -> i32 {
++        (self.cash + self.property_value(board) + self.stock_value(market) - self.debt).get()
++    }
++
++    /// How much more the bank will lend: `LOAN_LIMIT_PERCENT` of the player's
++    /// shops and stocks, less what they already owe. Nothing while they still
++    /// hold `LOAN_CASH_CEILING` or more in cash.
++    pub fn loan_available(&self, board: &[Tile], market: &StockMarket) -> i32 {
++        if self.cash >= LOAN_CASH_CEILING {
++            return 0;
++        }
++        let collateral = self.property_value(board) + self.stock_value(market);
++        (Money(collateral.percent(LOAN_LIMIT_PERCENT)) - self.debt).available()
++    }
++
++    /// Borrows up to `amount` from the bank, returning what was actually lent.
++    pub fn borrow(&mut self, amount: i32, board: &[Tile], market: &StockMarket) -> i32 {
++        let lent = amount.min(self.loan_available(board, market));
++        self.cash += lent;
++        self.debt += lent;
++        lent
++    }
++
++    /// Pays back up to `amount` of the loan from cash, returning what was repaid.
++    pub fn repay(&mut self, amount: i32) -> i32 {
++        let repaid = amount.min(self.debt.get()).min(self.cash.available());
++        self.cash -= repaid;
++        self.debt -= repaid;
++        repaid
++    }
++
++    /// Up to two letters for the player, from the first letters of the
++    /// words in their name: "Mario Kart" is "MK", "Yoshi" is "Y".
++    pub fn initials(&self) -> String {
++        self.name
++            .split_whitespace()
++            .filter_map(|word| word.chars().next())
++            .flat_map(char::to_uppercase)
++            .take(2)
++            .collect()
++    }
++
++    pub fn property_value(&self, board: &[Tile]) -> Money {
++        self.properties
++            .iter()
++            .map(|index| Money(board[*index].value()))
++            .sum()
++    }
++
++    /// Value of the player's shares at current prices. Short positions count
++    /// against it, at what buying the shares back would cost.
++    pub fn stock_value(&self, market: &StockMarket) -> Money {
++        self.stocks
++            .iter()
++            .map(|(district, shares)| Money::times(*shares, market.price(*district)))
++            .sum()
++    }
++
++    /// Adds `shares` of `district` to the player's holding (negative to take
++    /// them away), bought for `cost` in all (negative when sold). Growing a
++    /// position adds `cost` to its basis, shrinking it keeps the average price
++    /// paid, and a position flipped between long and short starts afresh at
++    /// the trade's price.
++    pub fn add_shares(&mut self, district: DistrictId, shares: i32, cost: i32) {
++        let held = self.stocks.get(&district).copied().unwrap_or(0);
++        let basis = self.cost_basis.get(&district).copied().unwrap_or(0);
++        let total = held + shares;
++        let basis = if total == 0 {
++            0
++        } else if held == 0 || (held.signum() == total.signum() && total.abs() > held.abs()) {
++            basis + cost
++        } else if held.signum() == total.signum() {
++            basis * total / held
++        } else {
++            total * cost / shares
++        };
++        if total == 0 {
++            self.stocks.remove(&district);
++            self.cost_basis.remove(&district);
++        } else {
++            self.stocks.insert(district, total);
++            self.cost_basis.insert(district, basis);
++        }
++    }
++
++    /// The part of the cost basis in `district` that `shares` of the holding
++    /// carry.
++    pub fn basis_of(&self, district: DistrictId, shares: i32) -> i32 {
++        let held = self.stocks.get(&district).copied().unwrap_or(0);
++        if held == 0 {
++            return 0;
++        }
++        self.cost_basis.get(&district).copied().unwrap_or(0) * shares / held
++    }
++
++    /// What buying back every share the player has sold short would cost.
++    pub fn short_value(&self, market: &StockMarket) -> Money {
++        self.stocks
++            .iter()
++            .filter(|(_, shares)| **shares < 0)
++            .map(|(district, shares)| Money::times(-shares, market.price(*district)))
++            .sum()
++    }
++
++    /// Cash the player must hold against their short positions before the
++    /// bank calls them in.
++    pub fn margin_required(&self, market: &StockMarket) -> i32 {
++        self.short_value(market).percent(100 + SHORT_MARGIN_PERCENT)
++    }
++
++    /// Dice rolled each turn: one more for every two levels reached.
++    pub fn dice_count(&self) -> usize {
++        (1 + self.level as usize / 2).min(MAX_DICE)
++    }
++
++    /// Number of shops this player owns in `district`.
++    pub fn shops_in_district(&self, district: DistrictId, board: &[Tile]) -> usize {
++        self.properties
++            .iter()
++            .filter(|index| {
++                matches!(board[**index].kind, TileKind::Property { district: d, .. } if d == district)
++            })
++            .count()
++    }
++
++    pub fn missing_suits(&self) -> Vec<Suit> {
++        SUITS
++            .into_iter()
++            .filter(|suit| !self.suits.contains(suit))
++            .collect()
++    }
++
++    /// Spends Suit Yourself cards on missing suits, one card per suit.
++    pub fn use_suit_cards(&mut self) {
++        for suit in self.missing_suits() {
++            if self.suit_cards == 0 {
++                break;
++            }
++            self.suit_cards -= 1;
++            self.suits.insert(suit);
++        }
++    }
++}
++
++/// The player's shops in board order.
++pub fn owned_shops(player_idx: usize, game: &Game) -> Vec<usize> {
++    let mut shops: Vec<usize> = game.players[player_idx]
++        .properties
++        .iter()
++        .copied()
++        .collect();
++    shops.sort_unstable();
++    shops
++}
@@ -0,0 +1,165 @@
+diff --git a/src/profile.rs b/src/profile.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..c2eb7a801f0481d60ce09226302ede113ed5550b
+--- /dev/null
++++ b/src/profile.rs
+@@ -0,0 +1,159 @@
++//! Local player profiles and their career stats. The active profile, picked
++//! on the setup screen, goes with the first human seat into each match;
++//! when the match ends its games, wins, best net worth and characters played
++//! are added up. Profiles are stored as RON beside the settings.
++
++use bevy::prelude::*;
++use serde::{Deserialize, Serialize};
++use std::path::PathBuf;
++
++use crate::*;
++
++/// Name of the profiles file inside the config folder.
++pub const PROFILES_FILE: &str = "profiles.ron";
++
++#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
++#[serde(default)]
++pub struct Profile {
++    pub name: String,
++    pub games: u32,
++    pub wins: u32,
++    /// Best final net worth over every match played.
++    pub highest_net_worth: i32,
++    /// Matches played as each character, indexed like `CHARACTERS`.
++    pub character_games: Vec<u32>,
++}
++
++impl Profile {
++    pub fn new(name: String) -> Self {
++        Self {
++            name,
++            ..Default::default()
++        }
++    }
++
++    /// The character played most often, the earliest in the roster on a tie.
++    pub fn favorite_character(&self) -> Option<usize> {
++        let most = self
++            .character_games
++            .iter()
++            .copied()
++            .max()
++            .filter(|most| *most > 0)?;
++        self.character_games.iter().position(|games| *games == most)
++    }
++
++    /// Adds a finished match played as `player`.
++    pub fn record(&mut self, player: &PlayerState, net_worth: i32, won: bool) {
++        self.games += 1;
++        self.wins += won as u32;
++        self.highest_net_worth = if self.games == 1 {
++            net_worth
++        } else {
++            self.highest_net_worth.max(net_worth)
++        };
++        if self.character_games.len() <= player.character {
++            self.character_games.resize(player.character + 1, 0);
++        }
++        self.character_games[player.character] += 1;
++    }
++
++    pub fn describe(&self) -> String {
++        let favorite = self
++            .favorite_character()
++            .and_then(|character| CHARACTERS.get(character))
++            .map_or("-", |character| character.name);
++        let best = if self.games == 0 {
++            "-".to_string()
++        } else {
++            Money(self.highest_net_worth).to_string()
++        };
++        format!(
++            "{}: {} games, {} wins, best net worth {}, favorite character {}",
++            self.name, self.games, self.wins, best, favorite
++        )
++    }
++}
++
++#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
++#[serde(default)]
++pub struct Profiles {
++    pub profiles: Vec<Profile>,
++    /// Index into `profiles` of the one that plays the next match, if any.
++    pub active: Option<usize>,
++}
++
++impl Profiles {
++    /// Where the profiles live: next to the settings file.
++    pub fn path() -> PathBuf {
++        Settings::path().with_file_name(PROFILES_FILE)
++    }
++
++    pub fn active_profile(&self) -> Option<&Profile> {
++        self.active.and_then(|idx| self.profiles.get(idx))
++    }
++
++    /// Steps the active profile `delta` places through no profile and then
++    /// each profile in turn.
++    pub fn cycle(&mut self, delta: isize) {
++        let len = self.profiles.len() as isize + 1;
++        let current = self.active.map_or(0, |idx| idx as isize + 1);
++        self.active = match (current + delta).rem_euclid(len) {
++            0 => None,
++            idx => Some(idx as usize - 1),
++        };
++    }
++
++    /// Adds the finished `game` to the profile of every player who had one.
++    pub fn record(&mut self, game: &Game, market: &StockMarket) {
++        for (idx, player) in game.players.iter().enumerate() {
++            let Some(name) = &player.profile else {
++                continue;
++            };
++            let Some(profile) = self
++                .profiles
++                .iter_mut()
++                .find(|profile| &profile.name == name)
++            else {
++                continue;
++            };
++            profile.record(
++                player,
++                player.net_worth(&game.board, market),
++                game.winner == Some(idx),
++            );
++        }
++    }
++
++    pub fn save(&self) {
++        let path = Self::path();
++        if let Err(err) = write_ron(self, &path) {
++            error!("Could not write {}: {}", path.display(), err);
++        }
++    }
++}
++
++/// Reads the saved profiles, if any.
++pub fn load_profiles(mut profiles: ResMut<Profiles>) {
++    let path = Profiles::path();
++    if !stored(&path) {
++        return;
++    }
++    match read_ron(&path) {
++        Ok(loaded) => *profiles = loaded,
++        Err(err) => error!("Could not read {}: {}", path.display(), err),
++    }
++}
++
++/// Adds the match just finished to its players' profiles and saves them.
++pub fn record_career_stats(
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    mut profiles: ResMut<Profiles>,
++) {
++    if game.players.iter().all(|player| player.profile.is_none()) {
++        return;
++    }
++    profiles.record(&game, &market);
++    profiles.save();
++}
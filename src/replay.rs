@@ -0,0 +1,321 @@
+diff --git a/src/replay.rs b/src/replay.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..7a15dd1365c9988fcadeab4e2a91b8d3e0ab0ce7
+--- /dev/null
++++ b/src/replay.rs
+@@ -0,0 +1,315 @@
++//! Match replays: the starting save, RNG included, plus every turn's logged
++//! actions, game events and the state it left behind. Matches are recorded
++//! while they are played and can be stepped through again with
++//! `--replay <path>`.
++
++use bevy::prelude::*;
++use serde::{Deserialize, Serialize};
++use std::path::{Path, PathBuf};
++
++use crate::*;
++
++/// Where replays are written when a match ends or on F6.
++pub const REPLAY_PATH: &str = "replays/latest.replay.ron";
++
++/// Playback speeds stepped through with `+`/`-`.
++pub const REPLAY_SPEEDS: [f32; 6] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
++
++/// Seconds each turn stays on screen at 1x speed.
++pub const REPLAY_TURN_SECONDS: f32 = 1.5;
++
++#[derive(Debug, Serialize, Deserialize)]
++pub struct Replay {
++    pub start: SaveFile,
++    pub turns: Vec<ReplayTurn>,
++}
++
++#[derive(Debug, Clone, Serialize, Deserialize)]
++pub struct ReplayTurn {
++    /// What the turn's `ActionLog` recorded, replayed through
++    /// `engine::apply`. Replays from before actions were logged have none.
++    #[serde(default)]
++    pub actions: Vec<LoggedAction>,
++    /// Rolls, purchases, trades, card draws and the rest, in order.
++    pub events: Vec<GameEvent>,
++    /// The match once the turn has passed to the next player.
++    pub state: MatchState,
++}
++
++impl Replay {
++    /// Rebuilds the match as it stood before the first recorded turn, along
++    /// with the playback that steps through the rest.
++    pub fn restore(self) -> Result<(Game, StockMarket, VentureDeck, ReplayPlayback), SaveError> {
++        let (game, market, deck, rng) = self.start.restore()?;
++        Ok((game, market, deck, ReplayPlayback::new(self.turns, rng)))
++    }
++
++    pub fn read(path: &Path) -> Result<Self, SaveError> {
++        read_ron(path)
++    }
++
++    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
++        write_ron(self, path)
++    }
++}
++
++/// The replay of the match being played.
++#[derive(Resource)]
++pub struct ReplayRecorder {
++    pub replay: Replay,
++    /// Events of the turn in progress.
++    pub events: Vec<GameEvent>,
++    /// How much of the `ActionLog` earlier turns have taken.
++    pub logged: usize,
++}
++
++impl ReplayRecorder {
++    pub fn start(game: &Game, market: &StockMarket, deck: &VentureDeck, rng: &MatchRng) -> Self {
++        Self {
++            replay: Replay {
++                start: SaveFile::capture(game, market, deck).with_rng(rng),
++                turns: Vec::new(),
++            },
++            events: Vec::new(),
++            logged: 0,
++        }
++    }
++}
++
++/// Replay file named by `--replay`; it plays instead of the setup screen.
++#[derive(Resource)]
++pub struct ReplayRequest(pub PathBuf);
++
++/// Progress through a replay being watched.
++#[derive(Resource)]
++pub struct ReplayPlayback {
++    pub turns: Vec<ReplayTurn>,
++    /// The match's RNG, carried from turn to turn as the actions replay.
++    pub rng: MatchRng,
++    pub next_turn: usize,
++    /// Index into `REPLAY_SPEEDS`.
++    pub speed: usize,
++    pub paused: bool,
++    pub timer: Timer,
++}
++
++impl ReplayPlayback {
++    pub fn new(turns: Vec<ReplayTurn>, rng: MatchRng) -> Self {
++        Self {
++            turns,
++            rng,
++            next_turn: 0,
++            speed: 2,
++            paused: false,
++            timer: Timer::from_seconds(REPLAY_TURN_SECONDS, TimerMode::Repeating),
++        }
++    }
++
++    pub fn finished(&self) -> bool {
++        self.next_turn >= self.turns.len()
++    }
++}
++
++pub fn start_recording(
++    mut commands: Commands,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    deck: Res<VentureDeck>,
++    rng: Res<MatchRng>,
++) {
++    commands.insert_resource(ReplayRecorder::start(&game, &market, &deck, &rng));
++}
++
++/// Collects the game events of the turn in progress. Save and undo notices
++/// are left out, since they say nothing about the match itself.
++pub fn record_replay_events(
++    mut game_events: EventReader<GameEvent>,
++    mut recorder: ResMut<ReplayRecorder>,
++) {
++    for event in game_events.read() {
++        if !matches!(
++            event,
++            GameEvent::Saved { .. }
++                | GameEvent::Loaded { .. }
++                | GameEvent::SaveFailed { .. }
++                | GameEvent::UndidTurn { .. }
++        ) {
++            recorder.events.push(event.clone());
++        }
++    }
++}
++
++/// Closes the turn that just ended with its logged actions and a snapshot of
++/// the match.
++pub fn record_replay_turn(
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    deck: Res<VentureDeck>,
++    log: Res<ActionLog>,
++    mut recorder: ResMut<ReplayRecorder>,
++) {
++    let events = std::mem::take(&mut recorder.events);
++    let actions = log.0.get(recorder.logged..).unwrap_or_default().to_vec();
++    recorder.logged = log.0.len();
++    recorder.replay.turns.push(ReplayTurn {
++        actions,
++        events,
++        state: MatchState::capture(&game, &market, &deck),
++    });
++}
++
++/// F6 writes the replay so far to `REPLAY_PATH`.
++pub fn save_replay_controls(
++    actions: Res<ActionInput>,
++    recorder: Res<ReplayRecorder>,
++    mut game: ResMut<Game>,
++) {
++    if !actions.just_pressed(Action::SaveReplay) {
++        return;
++    }
++    let event = match recorder.replay.write(Path::new(REPLAY_PATH)) {
++        Ok(()) => GameEvent::Saved {
++            path: REPLAY_PATH.to_string(),
++        },
++        Err(err) => GameEvent::SaveFailed {
++            reason: err.to_string(),
++        },
++    };
++    game.pending_events.push(event);
++}
++
++/// Writes the finished match's replay to `REPLAY_PATH`.
++pub fn write_replay(recorder: Res<ReplayRecorder>) {
++    match recorder.replay.write(Path::new(REPLAY_PATH)) {
++        Ok(()) => info!("Replay saved to {}", REPLAY_PATH),
++        Err(err) => error!("Could not save the replay: {}", err),
++    }
++}
++
++/// Steps through the recorded turns: `Space` pauses, `+`/`-` change speed and
++/// `Right` advances one turn. Each turn replays its logged actions through
++/// `engine::apply` with the match's RNG and its events into the log, then
++/// settles the match on the recorded snapshot, which also carries what the
++/// log leaves out: the venture deck and the bots' own stock-phase moves.
++#[allow(clippy::too_many_arguments)]
++pub fn replay_playback(
++    time: Res<Time>,
++    actions: Res<ActionInput>,
++    mut playback: ResMut<ReplayPlayback>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut deck: ResMut<VentureDeck>,
++    mut ui_state: ResMut<UiState>,
++    mut bankrupt_events: EventWriter<PlayerBankrupt>,
++) {
++    if actions.just_pressed(Action::Pause) {
++        playback.paused = !playback.paused;
++    }
++    if actions.just_pressed(Action::Increase) {
++        playback.speed = (playback.speed + 1).min(REPLAY_SPEEDS.len() - 1);
++    }
++    if actions.just_pressed(Action::Decrease) {
++        playback.speed = playback.speed.saturating_sub(1);
++    }
++    let speed = REPLAY_SPEEDS[playback.speed];
++    let stepped = actions.just_pressed(Action::Right);
++    let due = !playback.paused
++        && playback
++            .timer
++            .tick(time.delta().mul_f32(speed))
++            .just_finished();
++    if !(stepped || due) || playback.finished() {
++        return;
++    }
++
++    let turn = playback.turns[playback.next_turn].clone();
++    playback.next_turn += 1;
++    for logged in turn.actions {
++        // The recorded events below already say what the actions did.
++        let _ = engine::apply(logged.action, &mut game, &mut market, &mut playback.rng);
++    }
++    for event in &turn.events {
++        if let GameEvent::Rolled { player, faces } = event {
++            ui_state.last_rolls.insert(*player, faces.clone());
++        }
++    }
++    game.pending_events.extend(turn.events);
++
++    if let Err(err) = sync_match_state(
++        turn.state,
++        &mut game,
++        &mut market,
++        &mut deck,
++        &mut bankrupt_events,
++    ) {
++        error!("Replay stopped at turn {}: {}", playback.next_turn, err);
++        playback.next_turn = playback.turns.len();
++    }
++}
++
++/// Jumps a match that is only being watched to `state`, noting changed owners
++/// for the ownership markers and announcing new bankruptcies so their tokens
++/// leave the board.
++pub fn sync_match_state(
++    state: MatchState,
++    game: &mut Game,
++    market: &mut StockMarket,
++    deck: &mut VentureDeck,
++    bankrupt_events: &mut EventWriter<PlayerBankrupt>,
++) -> Result<(), SaveError> {
++    let owners: Vec<Option<usize>> = (0..game.board.len())
++        .map(|tile| game.owner_of(tile))
++        .collect();
++    let was_bankrupt: Vec<bool> = game.players.iter().map(|player| player.bankrupt).collect();
++    state.apply(game, market, deck)?;
++    for (tile, owner) in owners.into_iter().enumerate() {
++        let now = game.owner_of(tile);
++        if now != owner {
++            game.note_ownership(tile, now);
++        }
++    }
++    for (player, was_bankrupt) in was_bankrupt.into_iter().enumerate() {
++        if !was_bankrupt
++            && game
++                .players
++                .get(player)
++                .is_some_and(|player| player.bankrupt)
++        {
++            bankrupt_events.send(PlayerBankrupt { player });
++        }
++    }
++    Ok(())
++}
++
++/// Replaces the turn prompt with the playback position and controls.
++pub fn update_replay_prompt(
++    playback: Res<ReplayPlayback>,
++    game: Res<Game>,
++    mut prompt_text: Query<&mut Text, With<PromptText>>,
++) {
++    let Ok(mut text) = prompt_text.get_single_mut() else {
++        return;
++    };
++    let position = format!(
++        "Replay: turn {}/{} at {}x",
++        playback.next_turn,
++        playback.turns.len(),
++        REPLAY_SPEEDS[playback.speed]
++    );
++    text.sections[0].value = if playback.finished() {
++        match game.winner {
++            Some(winner) => format!(
++                "{} -- finished, {} won",
++                position, game.players[winner].name
++            ),
++            None => format!("{} -- finished", position),
++        }
++    } else if playback.paused {
++        format!(
++            "{} -- paused (Space resumes, Right steps one turn)",
++            position
++        )
++    } else {
++        format!("{} (Space pauses, +/- change speed)", position)
++    };
++}
@@ -0,0 +1,47 @@
+diff --git a/src/rng.rs b/src/rng.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..734ff8cb582474c80445a0f04a5e97c0b7a8b43e
+--- /dev/null
++++ b/src/rng.rs
+@@ -0,0 +1,41 @@
++//! The match's own random number generator. Dice, shuffles and every other
++//! chance in a match draw from it rather than the thread RNG, and saves carry
++//! its state, so a resumed match rolls exactly what the original would have.
++
++use bevy::prelude::*;
++use rand::{RngCore, SeedableRng};
++use rand_chacha::ChaCha12Rng;
++use serde::{Deserialize, Serialize};
++
++#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
++pub struct MatchRng(ChaCha12Rng);
++
++impl MatchRng {
++    /// The same seed always plays out the same way.
++    pub fn seeded(seed: u64) -> Self {
++        Self(ChaCha12Rng::seed_from_u64(seed))
++    }
++
++    /// A fresh generator for a new match.
++    pub fn from_entropy() -> Self {
++        Self(ChaCha12Rng::from_entropy())
++    }
++}
++
++impl RngCore for MatchRng {
++    fn next_u32(&mut self) -> u32 {
++        self.0.next_u32()
++    }
++
++    fn next_u64(&mut self) -> u64 {
++        self.0.next_u64()
++    }
++
++    fn fill_bytes(&mut self, dest: &mut [u8]) {
++        self.0.fill_bytes(dest)
++    }
++
++    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
++        self.0.try_fill_bytes(dest)
++    }
++}
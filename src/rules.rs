@@ -0,0 +1,246 @@
+diff --git a/src/rules.rs b/src/rules.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..4775f65643ddd19411951b5f79ffe816fc3e4838
+--- /dev/null
++++ b/src/rules.rs
+@@ -0,0 +1,240 @@
++//! Match rules: the Easy, Normal and Hard presets, the economy and bot
++//! parameters they set, and house-rule toggles. The rules start out as
++//! `RULES_PATH` says, if it exists, and each `Game` carries its own copy, so
++//! saves, replays and bot rollouts play by the rules the match started with.
++
++use bevy::prelude::*;
++use rand::Rng;
++use serde::{Deserialize, Serialize};
++use std::path::Path;
++
++use crate::*;
++
++/// Settings file read at startup, relative to the working directory. Fields
++/// left out keep their Normal values.
++pub const RULES_PATH: &str = "rules.ron";
++
++/// Presets offered on the setup screen, easiest first.
++pub const DIFFICULTIES: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
++pub enum Difficulty {
++    Easy,
++    #[default]
++    Normal,
++    Hard,
++}
++
++impl Difficulty {
++    pub fn label(self) -> &'static str {
++        match self {
++            Difficulty::Easy => "Easy",
++            Difficulty::Normal => "Normal",
++            Difficulty::Hard => "Hard",
++        }
++    }
++
++    pub fn from_name(name: &str) -> Option<Self> {
++        DIFFICULTIES
++            .into_iter()
++            .find(|difficulty| difficulty.label().eq_ignore_ascii_case(name.trim()))
++    }
++}
++
++/// Rules picked on the setup screen for the next match; `Game::new` copies
++/// them into the match it builds.
++#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
++#[serde(default)]
++pub struct GameRules {
++    pub difficulty: Difficulty,
++    pub starting_cash: i32,
++    /// Salary paid on every promotion, before level pay.
++    pub salary_base: i32,
++    pub salary_per_level: i32,
++    /// Scales the venture cards that cost the drawer money, in percent.
++    pub venture_penalty_percent: i32,
++    /// Laps ahead bots project their net worth when ranking purchases.
++    pub bot_lookahead_laps: i32,
++    /// Chance, in percent, that a bot passes up a purchase or trade it
++    /// would otherwise make.
++    pub bot_blunder_percent: u32,
++    pub house: HouseRules,
++}
++
++/// Paid for landing on a suit already held, under `DuplicateSuit::CashBonus`.
++pub const DUPLICATE_SUIT_BONUS: i32 = 50;
++
++/// What landing on a suit the player already holds gives them.
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
++pub enum DuplicateSuit {
++    /// Nothing: the suit is already held.
++    Nothing,
++    /// `DUPLICATE_SUIT_BONUS` from the bank.
++    #[default]
++    CashBonus,
++    /// A Suit Yourself card in its place.
++    SuitCard,
++}
++
++/// Extra salary for a promotion made by landing exactly on the bank, in
++/// percent, under `BankLanding::SalaryBonus`.
++pub const BANK_LANDING_SALARY_PERCENT: i32 = 20;
++
++/// What landing exactly on the bank gives, on top of what passing it does.
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
++pub enum BankLanding {
++    /// Nothing more than passing.
++    Nothing,
++    /// `BANK_LANDING_SALARY_PERCENT` more salary if the visit promotes.
++    #[default]
++    SalaryBonus,
++    /// One missing suit, which may complete the set in time to promote.
++    FreeSuit,
++}
++
++/// Optional rules that hold whatever the difficulty.
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
++#[serde(default)]
++pub struct HouseRules {
++    /// Shops a player passes on or can't afford go to auction.
++    pub auctions_on_decline: bool,
++    /// Owning a whole district doubles its fees.
++    pub double_fee_on_monopoly: bool,
++    /// Cash never drops below zero: fees take only what the payer has, and
++    /// other shortfalls are written off rather than forcing sales or
++    /// bankruptcy.
++    pub no_negative_cash: bool,
++    pub duplicate_suits: DuplicateSuit,
++    /// Suits are collected by passing over their tiles, not only by landing.
++    pub suits_on_pass: bool,
++    pub bank_landing: BankLanding,
++    /// Players may sell shares they don't hold, owing them back to the bank
++    /// at whatever the price is when they buy them back.
++    pub short_selling: bool,
++}
++
++impl Default for HouseRules {
++    fn default() -> Self {
++        Self {
++            auctions_on_decline: true,
++            double_fee_on_monopoly: true,
++            no_negative_cash: false,
++            duplicate_suits: DuplicateSuit::default(),
++            suits_on_pass: true,
++            bank_landing: BankLanding::default(),
++            short_selling: false,
++        }
++    }
++}
++
++impl HouseRules {
++    /// The toggles that differ from the standard rules, for the setup screen.
++    pub fn changes(&self) -> Vec<&'static str> {
++        let mut changes = Vec::new();
++        if !self.auctions_on_decline {
++            changes.push("no auctions");
++        }
++        if !self.double_fee_on_monopoly {
++            changes.push("no monopoly double fees");
++        }
++        if self.no_negative_cash {
++            changes.push("no negative cash");
++        }
++        if !self.suits_on_pass {
++            changes.push("suits only on landing");
++        }
++        if self.short_selling {
++            changes.push("short selling");
++        }
++        match self.bank_landing {
++            BankLanding::Nothing => changes.push("no bank landing bonus"),
++            BankLanding::SalaryBonus => {}
++            BankLanding::FreeSuit => changes.push("free suit on landing at the bank"),
++        }
++        match self.duplicate_suits {
++            DuplicateSuit::Nothing => changes.push("nothing for duplicate suits"),
++            DuplicateSuit::CashBonus => {}
++            DuplicateSuit::SuitCard => changes.push("duplicate suits become cards"),
++        }
++        changes
++    }
++}
++
++impl Default for GameRules {
++    fn default() -> Self {
++        Self::preset(Difficulty::default())
++    }
++}
++
++impl GameRules {
++    /// Easy gives everyone more cash and salary, softens venture card
++    /// penalties and makes bots short-sighted and error-prone; Hard does the
++    /// opposite.
++    pub fn preset(difficulty: Difficulty) -> Self {
++        match difficulty {
++            Difficulty::Easy => Self {
++                difficulty,
++                starting_cash: 3000,
++                salary_base: 300,
++                salary_per_level: 180,
++                venture_penalty_percent: 50,
++                bot_lookahead_laps: 1,
++                bot_blunder_percent: 25,
++                house: HouseRules::default(),
++            },
++            Difficulty::Normal => Self {
++                difficulty,
++                starting_cash: STARTING_CASH,
++                salary_base: SALARY_BASE,
++                salary_per_level: SALARY_PER_LEVEL,
++                venture_penalty_percent: 100,
++                bot_lookahead_laps: PROJECTION_LAPS,
++                bot_blunder_percent: 0,
++                house: HouseRules::default(),
++            },
++            Difficulty::Hard => Self {
++                difficulty,
++                starting_cash: 2000,
++                salary_base: 200,
++                salary_per_level: 120,
++                venture_penalty_percent: 150,
++                bot_lookahead_laps: 5,
++                bot_blunder_percent: 0,
++                house: HouseRules::default(),
++            },
++        }
++    }
++
++    /// Switches to `difficulty`'s preset, keeping the house rules.
++    pub fn apply_preset(&mut self, difficulty: Difficulty) {
++        *self = Self {
++            house: self.house,
++            ..Self::preset(difficulty)
++        };
++    }
++
++    /// `amount` of a venture card penalty, scaled by `venture_penalty_percent`.
++    pub fn venture_penalty(&self, amount: i32) -> i32 {
++        amount * self.venture_penalty_percent / 100
++    }
++
++    /// Rolls whether a bot fumbles the decision it is about to make.
++    pub fn bot_blunders(&self) -> bool {
++        rand::thread_rng().gen_ratio(self.bot_blunder_percent.min(100), 100)
++    }
++}
++
++/// Replaces the default rules with `RULES_PATH` when the file exists.
++pub fn load_rules(mut rules: ResMut<GameRules>) {
++    let path = Path::new(RULES_PATH);
++    if !stored(path) {
++        return;
++    }
++    match read_ron(path) {
++        Ok(loaded) => {
++            info!("Rules loaded from {}", RULES_PATH);
++            *rules = loaded;
++        }
++        Err(err) => error!("Could not read {}: {}", RULES_PATH, err),
++    }
++}
@@ -0,0 +1,474 @@
+diff --git a/src/save.rs b/src/save.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..c73203b041391adc4fe7baf43245efb43ca287da
+--- /dev/null
++++ b/src/save.rs
+@@ -0,0 +1,468 @@
++//! Saving and resuming matches: a RON snapshot of the board, players, stock
++//! market, turn order, venture deck and dice. Also the RON file helpers the rules,
++//! settings and replays share, which use local storage in the browser.
++
++use serde::{Deserialize, Serialize, de::DeserializeOwned};
++use std::{
++    collections::{BTreeMap, BTreeSet, HashMap},
++    path::Path,
++};
++
++use crate::*;
++
++/// Where F5 writes and F9 reads, relative to the working directory.
++pub const QUICKSAVE_PATH: &str = "saves/quicksave.ron";
++
++/// Bumped whenever the save layout changes incompatibly.
++pub const SAVE_VERSION: u32 = 3;
++
++#[derive(Debug, thiserror::Error)]
++pub enum SaveError {
++    #[error("could not access save file: {0}")]
++    Io(#[from] std::io::Error),
++    #[error("could not write save file: {0}")]
++    Encode(#[from] ron::Error),
++    #[error("could not parse save file: {0}")]
++    Decode(#[from] ron::error::SpannedError),
++    #[error("save file version {0} is not supported")]
++    Version(u32),
++    #[error("save file is inconsistent: {0}")]
++    Invalid(String),
++    #[error("browser storage is unavailable")]
++    Storage,
++}
++
++/// Everything needed to pick a match back up at the start of a turn, down to
++/// the dice it will roll next.
++#[derive(Debug, Serialize, Deserialize)]
++pub struct SaveFile {
++    pub version: u32,
++    pub board: BoardDefinition,
++    pub target_net_worth: i32,
++    #[serde(default)]
++    pub rules: GameRules,
++    pub state: MatchState,
++    /// The match's RNG. Left out of the snapshots remote players start from,
++    /// which never roll, and of older saves; restoring those starts a fresh
++    /// one.
++    #[serde(default)]
++    pub rng: Option<MatchRng>,
++}
++
++/// The parts of a match that change from turn to turn.
++#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
++pub struct MatchState {
++    /// Capital invested in each tile, by tile index.
++    pub capital: Vec<i32>,
++    pub players: Vec<SavedPlayer>,
++    pub current_turn: usize,
++    /// Seats in turn order. Saves from before the opening roll leave it out
++    /// and play in seat order.
++    #[serde(default)]
++    pub turn_order: Vec<usize>,
++    pub district_shop_count: BTreeMap<String, usize>,
++    pub winner: Option<usize>,
++    pub market: SavedMarket,
++    /// Titles of the venture cards left to draw; the last one comes up next.
++    pub venture_pile: Vec<String>,
++}
++
++#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
++pub struct SavedPlayer {
++    pub name: String,
++    pub kind: PlayerKind,
++    #[serde(default)]
++    pub strategy: BotStrategy,
++    /// Missing from saves made before autopilot, where it follows `kind`.
++    #[serde(default)]
++    pub decider: Option<Decider>,
++    #[serde(default)]
++    pub autopilot: bool,
++    #[serde(alias = "color")]
++    pub character: usize,
++    #[serde(default)]
++    pub profile: Option<String>,
++    pub cash: Money,
++    pub stocks: BTreeMap<String, i32>,
++    #[serde(default)]
++    pub cost_basis: BTreeMap<String, i32>,
++    pub properties: BTreeSet<usize>,
++    pub suits: Vec<Suit>,
++    pub suit_cards: u32,
++    pub position: usize,
++    pub level: u32,
++    pub bankrupt: bool,
++    pub double_fees: bool,
++    pub on_break: bool,
++    pub laps: u32,
++    pub debt: Money,
++    #[serde(default)]
++    pub trade_cooldown: u32,
++}
++
++#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
++pub struct SavedMarket {
++    pub prices: BTreeMap<String, i32>,
++    pub shares_outstanding: BTreeMap<String, i32>,
++    pub sentiment: BTreeMap<String, f32>,
++    #[serde(default)]
++    pub calm_turns: u32,
++}
++
++fn by_name<T: Copy>(map: &HashMap<DistrictId, T>, districts: &[District]) -> BTreeMap<String, T> {
++    map.iter()
++        .map(|(district, value)| (districts[*district].name.clone(), *value))
++        .collect()
++}
++
++/// Keys a saved map by the board's own district names again.
++fn by_district<T>(
++    map: BTreeMap<String, T>,
++    districts: &[District],
++) -> Result<HashMap<DistrictId, T>, SaveError> {
++    map.into_iter()
++        .map(|(name, value)| {
++            let district = districts
++                .iter()
++                .find(|district| district.name == name)
++                .ok_or_else(|| SaveError::Invalid(format!("unknown district \"{}\"", name)))?;
++            Ok((district.id, value))
++        })
++        .collect()
++}
++
++impl SaveFile {
++    pub fn capture(game: &Game, market: &StockMarket, deck: &VentureDeck) -> Self {
++        Self {
++            version: SAVE_VERSION,
++            board: game.board_definition(),
++            target_net_worth: game.target_net_worth,
++            rules: game.rules.clone(),
++            state: MatchState::capture(game, market, deck),
++            rng: None,
++        }
++    }
++
++    /// Carries `rng` along, so the restored match rolls what this one would.
++    pub fn with_rng(mut self, rng: &MatchRng) -> Self {
++        self.rng = Some(rng.clone());
++        self
++    }
++
++    /// Rebuilds the match resources from the saved board and state.
++    pub fn restore(self) -> Result<(Game, StockMarket, VentureDeck, MatchRng), SaveError> {
++        if self.version != SAVE_VERSION {
++            return Err(SaveError::Version(self.version));
++        }
++        self.board
++            .validate()
++            .map_err(|err| SaveError::Invalid(err.to_string()))?;
++        let mut game = Game::new(&self.board, &[], self.target_net_worth, self.rules);
++        let mut market = StockMarket::default();
++        let mut deck = VentureDeck::for_game(&game);
++        self.state.apply(&mut game, &mut market, &mut deck)?;
++        let rng = self.rng.unwrap_or_else(MatchRng::from_entropy);
++        Ok((game, market, deck, rng))
++    }
++
++    pub fn read(path: &Path) -> Result<Self, SaveError> {
++        read_ron(path)
++    }
++
++    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
++        write_ron(self, path)
++    }
++}
++
++impl MatchState {
++    pub fn capture(game: &Game, market: &StockMarket, deck: &VentureDeck) -> Self {
++        let players = game
++            .players
++            .iter()
++            .map(|player| {
++                let mut suits: Vec<Suit> = player.suits.iter().copied().collect();
++                suits.sort_by_key(|suit| *suit as u8);
++                SavedPlayer {
++                    name: player.name.clone(),
++                    kind: player.kind,
++                    strategy: player.strategy,
++                    decider: Some(player.decider),
++                    autopilot: player.autopilot,
++                    character: player.character,
++                    profile: player.profile.clone(),
++                    cash: player.cash,
++                    stocks: by_name(&player.stocks, &game.districts),
++                    cost_basis: by_name(&player.cost_basis, &game.districts),
++                    properties: player.properties.iter().copied().collect(),
++                    suits,
++                    suit_cards: player.suit_cards,
++                    position: player.position,
++                    level: player.level,
++                    bankrupt: player.bankrupt,
++                    double_fees: player.double_fees,
++                    on_break: player.on_break,
++                    laps: player.laps,
++                    debt: player.debt,
++                    trade_cooldown: player.trade_cooldown,
++                }
++            })
++            .collect();
++        Self {
++            capital: game.board.iter().map(|tile| tile.capital).collect(),
++            players,
++            current_turn: game.current_turn,
++            turn_order: game.turn_order.clone(),
++            district_shop_count: by_name(&game.district_shop_count, &game.districts),
++            winner: game.winner,
++            market: SavedMarket {
++                prices: by_name(&market.prices, &game.districts),
++                shares_outstanding: by_name(&market.shares_outstanding, &game.districts),
++                sentiment: by_name(&market.sentiment, &game.districts),
++                calm_turns: market.calm_turns,
++            },
++            venture_pile: deck
++                .draw_pile
++                .iter()
++                .map(|card| card.name(game).to_string())
++                .collect(),
++        }
++    }
++
++    /// Overwrites the match with this state, checking first that every index
++    /// and district refers to something on `game`'s board. Nothing changes if
++    /// the check fails.
++    pub fn apply(
++        self,
++        game: &mut Game,
++        market: &mut StockMarket,
++        deck: &mut VentureDeck,
++    ) -> Result<(), SaveError> {
++        if self.capital.len() != game.board.len() {
++            return Err(SaveError::Invalid(format!(
++                "{} capital entries for {} tiles",
++                self.capital.len(),
++                game.board.len()
++            )));
++        }
++        if self.players.is_empty() || self.current_turn >= self.players.len() {
++            return Err(SaveError::Invalid("no player has the turn".to_string()));
++        }
++        let turn_order = if self.turn_order.is_empty() {
++            (0..self.players.len()).collect()
++        } else {
++            self.turn_order
++        };
++        if !is_turn_order(&turn_order, self.players.len()) {
++            return Err(SaveError::Invalid(
++                "the turn order doesn't list every player once".to_string(),
++            ));
++        }
++
++        let mut players = Vec::new();
++        for saved in self.players {
++            let on_board = |tile: usize| tile < game.board.len();
++            if !on_board(saved.position) || !saved.properties.iter().copied().all(on_board) {
++                return Err(SaveError::Invalid(format!(
++                    "{} refers to a tile off the board",
++                    saved.name
++                )));
++            }
++            players.push(PlayerState {
++                name: saved.name,
++                kind: saved.kind,
++                strategy: saved.strategy,
++                decider: saved.decider.unwrap_or(saved.kind.decider()),
++                autopilot: saved.autopilot,
++                character: saved.character,
++                profile: saved.profile,
++                cash: saved.cash,
++                stocks: by_district(saved.stocks, &game.districts)?,
++                cost_basis: by_district(saved.cost_basis, &game.districts)?,
++                properties: saved.properties.into_iter().collect(),
++                suits: saved.suits.into_iter().collect(),
++                suit_cards: saved.suit_cards,
++                position: saved.position,
++                level: saved.level,
++                bankrupt: saved.bankrupt,
++                double_fees: saved.double_fees,
++                on_break: saved.on_break,
++                laps: saved.laps,
++                debt: saved.debt,
++                trade_cooldown: saved.trade_cooldown,
++            });
++        }
++        let restored_market = StockMarket {
++            prices: by_district(self.market.prices, &game.districts)?,
++            shares_outstanding: by_district(self.market.shares_outstanding, &game.districts)?,
++            sentiment: by_district(self.market.sentiment, &game.districts)?,
++            calm_turns: self.market.calm_turns,
++            pending_changes: Vec::new(),
++        };
++        let district_shop_count = by_district(self.district_shop_count, &game.districts)?;
++        let mut draw_pile = Vec::new();
++        for title in &self.venture_pile {
++            let card = VENTURE_CARDS
++                .iter()
++                .copied()
++                .find(|card| card.title == title)
++                .or_else(|| {
++                    game.board_cards
++                        .iter()
++                        .position(|card| card.title == *title)
++                        .map(VentureCard::board_card)
++                })
++                .ok_or_else(|| SaveError::Invalid(format!("unknown venture card \"{}\"", title)))?;
++            draw_pile.push(card);
++        }
++
++        for (tile, capital) in game.board.iter_mut().zip(&self.capital) {
++            tile.capital = *capital;
++        }
++        game.players = players;
++        game.current_turn = self.current_turn;
++        game.turn_order = turn_order;
++        game.district_shop_count = district_shop_count;
++        game.winner = self.winner;
++        *market = restored_market;
++        deck.draw_pile = draw_pile;
++        deck.active = None;
++        Ok(())
++    }
++}
++
++pub fn read_ron<T: DeserializeOwned>(path: &Path) -> Result<T, SaveError> {
++    Ok(ron::from_str(&read_text(path)?)?)
++}
++
++/// Writes `value` as pretty RON.
++pub fn write_ron<T: Serialize>(value: &T, path: &Path) -> Result<(), SaveError> {
++    let text = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())?;
++    write_text(path, &text)
++}
++
++/// Whether something has been written to `path`.
++#[cfg(not(target_arch = "wasm32"))]
++pub fn stored(path: &Path) -> bool {
++    path.exists()
++}
++
++#[cfg(not(target_arch = "wasm32"))]
++fn read_text(path: &Path) -> Result<String, SaveError> {
++    Ok(std::fs::read_to_string(path)?)
++}
++
++/// Writes the file, creating its parent folder if needed.
++#[cfg(not(target_arch = "wasm32"))]
++fn write_text(path: &Path, text: &str) -> Result<(), SaveError> {
++    if let Some(dir) = path.parent() {
++        std::fs::create_dir_all(dir)?;
++    }
++    std::fs::write(path, text)?;
++    Ok(())
++}
++
++/// In the browser there is no file system, so files live in the page's local
++/// storage, keyed by path.
++#[cfg(target_arch = "wasm32")]
++fn local_storage() -> Result<web_sys::Storage, SaveError> {
++    web_sys::window()
++        .and_then(|window| window.local_storage().ok().flatten())
++        .ok_or(SaveError::Storage)
++}
++
++#[cfg(target_arch = "wasm32")]
++pub fn stored(path: &Path) -> bool {
++    read_text(path).is_ok()
++}
++
++#[cfg(target_arch = "wasm32")]
++fn read_text(path: &Path) -> Result<String, SaveError> {
++    local_storage()?
++        .get_item(&path.to_string_lossy())
++        .map_err(|_| SaveError::Storage)?
++        .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound).into())
++}
++
++#[cfg(target_arch = "wasm32")]
++fn write_text(path: &Path, text: &str) -> Result<(), SaveError> {
++    local_storage()?
++        .set_item(&path.to_string_lossy(), text)
++        .map_err(|_| SaveError::Storage)
++}
++
++/// Reads a save file and rebuilds the match it holds.
++pub fn load_game(path: &Path) -> Result<(Game, StockMarket, VentureDeck, MatchRng), SaveError> {
++    SaveFile::read(path)?.restore()
++}
++
++#[cfg(test)]
++mod tests {
++    use super::*;
++    use crate::sim::load_board_files;
++    use rand::Rng;
++
++    /// A match a few moves in: a shop bought, shares held, cash moved and
++    /// dice already rolled.
++    fn match_in_progress() -> (Game, StockMarket, VentureDeck, MatchRng) {
++        let boards = load_board_files().expect("the board files load");
++        let seats: Vec<Seat> = (0..3)
++            .map(|character| Seat {
++                name: format!("Player {}", character + 1),
++                kind: PlayerKind::Bot,
++                strategy: BotStrategy::Balanced,
++                character,
++            })
++            .collect();
++        let mut game = Game::new(
++            &boards[0].1,
++            &seats,
++            TARGET_NET_WORTHS[1],
++            GameRules::default(),
++        );
++        let mut market = StockMarket::new(&game);
++        let mut deck = VentureDeck::for_game(&game);
++        let mut rng = MatchRng::seeded(7);
++        let (shop, district) = (0..game.board.len())
++            .find_map(|tile| match game.board[tile].kind {
++                TileKind::Property { district, .. } => Some((tile, district)),
++                _ => None,
++            })
++            .expect("the board has shops");
++        buy_property(shop, 1, &mut game, &mut market);
++        buy_stock(2, district, 20, &mut game, &mut market).expect("the buy goes through");
++        game.players[0].cash -= 350;
++        game.players[0].position = rng.gen_range(0..game.board.len());
++        game.current_turn = 2;
++        deck.draw();
++        (game, market, deck, rng)
++    }
++
++    fn to_text(save: &SaveFile) -> String {
++        ron::to_string(save).expect("the save encodes")
++    }
++
++    #[test]
++    fn save_and_restore_round_trip() {
++        let (game, market, deck, mut rng) = match_in_progress();
++        let text = to_text(&SaveFile::capture(&game, &market, &deck).with_rng(&rng));
++        let save: SaveFile = ron::from_str(&text).expect("the save decodes");
++        let (game, market, deck, mut restored) = save.restore().expect("the save restores");
++        assert_eq!(
++            to_text(&SaveFile::capture(&game, &market, &deck).with_rng(&restored)),
++            text
++        );
++        let rolls =
++            |rng: &mut MatchRng| -> Vec<usize> { (0..20).map(|_| rng.gen_range(1..=6)).collect() };
++        assert_eq!(rolls(&mut restored), rolls(&mut rng));
++    }
++
++    #[test]
++    fn other_versions_are_refused() {
++        let (game, market, deck, rng) = match_in_progress();
++        let mut save = SaveFile::capture(&game, &market, &deck).with_rng(&rng);
++        save.version = SAVE_VERSION - 1;
++        assert!(
++            matches!(save.restore(), Err(SaveError::Version(version)) if version == SAVE_VERSION - 1)
++        );
++    }
++}
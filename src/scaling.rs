@@ -0,0 +1,71 @@
+diff --git a/src/scaling.rs b/src/scaling.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..58be5436ead023cf0e4802c26ecb84ca3e309749
+--- /dev/null
++++ b/src/scaling.rs
+@@ -0,0 +1,65 @@
++//! UI scale and large text. The settings menu scales the whole interface,
++//! sidebar and panels through Bevy's `UiScale` and board text such as tile
++//! labels through its font size, and large-text mode enlarges every piece of
++//! text on top of that.
++
++use bevy::prelude::*;
++
++use crate::*;
++
++/// Smallest and largest interface scale the settings menu allows.
++pub const UI_SCALE_RANGE: (f32, f32) = (0.75, 2.0);
++
++/// How much larger text is drawn in large-text mode.
++pub const LARGE_TEXT_SCALE: f32 = 1.3;
++
++/// The font size each section of a text was spawned with, which the scaled
++/// sizes are worked out from.
++#[derive(Component)]
++pub struct BaseFontSizes(pub Vec<f32>);
++
++/// Sets the interface scale whenever the settings change.
++pub fn apply_ui_scale(settings: Res<Settings>, mut ui_scale: ResMut<UiScale>) {
++    if settings.is_changed() && ui_scale.0 != settings.ui_scale {
++        ui_scale.0 = settings.ui_scale;
++    }
++}
++
++/// Scales text as it is spawned, and all text again whenever the settings
++/// change. Board text is scaled by the UI scale as well, since `UiScale`
++/// only reaches the interface.
++#[allow(clippy::type_complexity)]
++pub fn apply_text_size(
++    mut commands: Commands,
++    settings: Res<Settings>,
++    mut new_texts: Query<(Entity, &mut Text, Has<Node>), Without<BaseFontSizes>>,
++    mut texts: Query<(&mut Text, &BaseFontSizes, Has<Node>)>,
++) {
++    let scale = |is_ui: bool| {
++        let ui_scale = if is_ui { 1.0 } else { settings.ui_scale };
++        settings.text_scale() * ui_scale
++    };
++    for (entity, mut text, is_ui) in new_texts.iter_mut() {
++        let sizes: Vec<f32> = text
++            .sections
++            .iter()
++            .map(|section| section.style.font_size)
++            .collect();
++        let scale = scale(is_ui);
++        if scale != 1.0 {
++            for section in text.sections.iter_mut() {
++                section.style.font_size *= scale;
++            }
++        }
++        commands.entity(entity).insert(BaseFontSizes(sizes));
++    }
++    if !settings.is_changed() {
++        return;
++    }
++    for (mut text, base, is_ui) in texts.iter_mut() {
++        let scale = scale(is_ui);
++        for (section, size) in text.sections.iter_mut().zip(&base.0) {
++            section.style.font_size = size * scale;
++        }
++    }
++}
@@ -0,0 +1,576 @@
+diff --git a/src/script.rs b/src/script.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..ad511a0aa0877f95496be4216f5a4a7bb25f66f6
+--- /dev/null
++++ b/src/script.rs
+@@ -0,0 +1,570 @@
++//! Board scripts. A board file can give any tile, and any venture card it
++//! adds to the deck, a short script such as `pay 5% of leader's cash to
++//! leader`, so a custom board brings its own effects without a new build.
++//!
++//! The language is small on purpose. A script is a few statements separated
++//! by `;` or new lines, each moving gold between the player it runs for, the
++//! bank and the other players:
++//!
++//! - `gain AMOUNT` and `pay AMOUNT` take from or give to the bank;
++//! - `pay AMOUNT to WHO` and `collect AMOUNT from WHO` trade with `leader`,
++//!   `last` or `each` other player.
++//!
++//! An AMOUNT is a number of gold, or a share such as `10% of my cash` or
++//! `5% of leader's worth`. The leader and last are picked by net worth among
++//! the players still standing. Scripts can't loop, read anything but these
++//! numbers or touch anything but cash, and their length and amounts are
++//! capped, so a board file can't hang or break a match.
++
++use std::cmp::Reverse;
++
++use crate::*;
++
++/// Most statements a script can hold.
++pub const MAX_SCRIPT_STATEMENTS: usize = 8;
++
++/// Longest script, in characters.
++pub const MAX_SCRIPT_CHARS: usize = 240;
++
++/// Largest fixed amount a statement can move.
++pub const MAX_SCRIPT_AMOUNT: i32 = 10_000;
++
++/// Why a script can't be run.
++#[derive(Debug, Clone, PartialEq, thiserror::Error)]
++pub enum ScriptError {
++    #[error("the script is empty")]
++    Empty,
++    #[error("a script can be at most {} characters", MAX_SCRIPT_CHARS)]
++    TooLong,
++    #[error("a script can have at most {} statements", MAX_SCRIPT_STATEMENTS)]
++    TooManyStatements,
++    #[error("can't make sense of \"{0}\"")]
++    Unknown(String),
++    #[error("{0} isn't an amount from 0 to {}", MAX_SCRIPT_AMOUNT)]
++    BadAmount(String),
++    #[error("{0} isn't a percentage from 0% to 100%")]
++    BadPercent(String),
++    #[error("\"{0}\" isn't a player a script can name")]
++    BadParty(String),
++}
++
++/// Who a statement names.
++#[derive(Debug, Clone, Copy, PartialEq, Eq)]
++pub enum Party {
++    /// The player the script runs for.
++    Me,
++    /// The standing player with the highest net worth.
++    Leader,
++    /// The standing player with the lowest net worth.
++    Last,
++    /// Every other standing player.
++    Each,
++}
++
++/// What a percentage is taken of.
++#[derive(Debug, Clone, Copy, PartialEq, Eq)]
++pub enum Measure {
++    Cash,
++    NetWorth,
++}
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq)]
++pub enum Amount {
++    Fixed(i32),
++    Percent {
++        percent: i32,
++        of: Party,
++        measure: Measure,
++    },
++}
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq)]
++pub enum Statement {
++    /// Gold from the bank.
++    Gain(Amount),
++    /// Gold to the bank.
++    Pay(Amount),
++    /// Gold to each player named.
++    PayTo(Amount, Party),
++    /// Gold from each player named.
++    CollectFrom(Amount, Party),
++}
++
++/// A parsed script, keeping the text it was written as so the board can be
++/// saved back out.
++#[derive(Debug, Clone, PartialEq)]
++pub struct Script {
++    source: String,
++    statements: Vec<Statement>,
++}
++
++impl Script {
++    pub fn source(&self) -> &str {
++        &self.source
++    }
++
++    /// What the script does, a sentence per statement.
++    pub fn describe(&self) -> String {
++        let sentences: Vec<String> = self
++            .statements
++            .iter()
++            .map(|statement| match statement {
++                Statement::Gain(amount) => format!("Collect {} from the bank.", amount.describe()),
++                Statement::Pay(amount) => format!("Pay {} to the bank.", amount.describe()),
++                Statement::PayTo(amount, party) => {
++                    format!("Pay {} to {}.", amount.describe(), party.describe())
++                }
++                Statement::CollectFrom(amount, party) => {
++                    format!("Collect {} from {}.", amount.describe(), party.describe())
++                }
++            })
++            .collect();
++        sentences.join(" ")
++    }
++}
++
++impl Party {
++    fn describe(self) -> &'static str {
++        match self {
++            Party::Me => "yourself",
++            Party::Leader => "the leader",
++            Party::Last => "the player in last place",
++            Party::Each => "every other player",
++        }
++    }
++}
++
++impl Amount {
++    fn describe(self) -> String {
++        match self {
++            Amount::Fixed(amount) => Money(amount).to_string(),
++            Amount::Percent {
++                percent,
++                of,
++                measure,
++            } => {
++                let owner = match of {
++                    Party::Me => "your",
++                    Party::Leader => "the leader's",
++                    Party::Last => "last place's",
++                    Party::Each => "each other player's",
++                };
++                let measure = match measure {
++                    Measure::Cash => "cash",
++                    Measure::NetWorth => "net worth",
++                };
++                format!("{}% of {} {}", percent, owner, measure)
++            }
++        }
++    }
++}
++
++/// Parses `source`. Keywords are matched regardless of case, and a trailing
++/// full stop on a statement is ignored.
++pub fn parse_script(source: &str) -> Result<Script, ScriptError> {
++    if source.chars().count() > MAX_SCRIPT_CHARS {
++        return Err(ScriptError::TooLong);
++    }
++    let lowered = source.to_lowercase();
++    let statements = lowered
++        .split([';', '\n'])
++        .map(|statement| statement.trim().trim_end_matches('.'))
++        .filter(|statement| !statement.is_empty())
++        .map(parse_statement)
++        .collect::<Result<Vec<_>, _>>()?;
++    if statements.is_empty() {
++        return Err(ScriptError::Empty);
++    }
++    if statements.len() > MAX_SCRIPT_STATEMENTS {
++        return Err(ScriptError::TooManyStatements);
++    }
++    Ok(Script {
++        source: source.trim().to_string(),
++        statements,
++    })
++}
++
++fn parse_statement(statement: &str) -> Result<Statement, ScriptError> {
++    let words: Vec<&str> = statement.split_whitespace().collect();
++    let split_at = |keyword: &str| {
++        words
++            .iter()
++            .rposition(|word| *word == keyword)
++            .map(|idx| (&words[1..idx], &words[idx + 1..]))
++    };
++    match words.as_slice() {
++        ["gain", amount @ ..] => Ok(Statement::Gain(parse_amount(amount, statement)?)),
++        ["pay", ..] => match split_at("to") {
++            Some((amount, who)) => Ok(Statement::PayTo(
++                parse_amount(amount, statement)?,
++                parse_payee(who)?,
++            )),
++            None => Ok(Statement::Pay(parse_amount(&words[1..], statement)?)),
++        },
++        ["collect", ..] => match split_at("from") {
++            Some((amount, who)) => Ok(Statement::CollectFrom(
++                parse_amount(amount, statement)?,
++                parse_payee(who)?,
++            )),
++            None => Err(ScriptError::Unknown(statement.to_string())),
++        },
++        _ => Err(ScriptError::Unknown(statement.to_string())),
++    }
++}
++
++/// A player gold can go to or come from: never the one the script runs for.
++fn parse_payee(words: &[&str]) -> Result<Party, ScriptError> {
++    match words {
++        ["leader"] | ["the", "leader"] => Ok(Party::Leader),
++        ["last"] => Ok(Party::Last),
++        ["each"] | ["everyone"] => Ok(Party::Each),
++        _ => Err(ScriptError::BadParty(words.join(" "))),
++    }
++}
++
++fn parse_amount(words: &[&str], statement: &str) -> Result<Amount, ScriptError> {
++    match words {
++        [amount] => {
++            let digits = amount.strip_suffix('g').unwrap_or(amount);
++            match digits.parse::<i32>() {
++                Ok(amount) if (0..=MAX_SCRIPT_AMOUNT).contains(&amount) => {
++                    Ok(Amount::Fixed(amount))
++                }
++                _ => Err(ScriptError::BadAmount(amount.to_string())),
++            }
++        }
++        [percent, "of", rest @ ..] => {
++            let digits = percent
++                .strip_suffix('%')
++                .ok_or_else(|| ScriptError::BadPercent(percent.to_string()))?;
++            let percent = match digits.parse::<i32>() {
++                Ok(value) if (0..=100).contains(&value) => value,
++                _ => return Err(ScriptError::BadPercent(percent.to_string())),
++            };
++            let rest = rest.strip_prefix(&["the"]).unwrap_or(rest);
++            let (of, measure) = match rest {
++                [owner, measure @ ..] => (*owner, measure.join(" ")),
++                [] => return Err(ScriptError::Unknown(statement.to_string())),
++            };
++            let of = match of {
++                "my" => Party::Me,
++                "leader's" => Party::Leader,
++                "last's" => Party::Last,
++                _ => return Err(ScriptError::BadParty(of.to_string())),
++            };
++            let measure = match measure.as_str() {
++                "cash" => Measure::Cash,
++                "worth" | "net worth" => Measure::NetWorth,
++                _ => return Err(ScriptError::Unknown(statement.to_string())),
++            };
++            Ok(Amount::Percent {
++                percent,
++                of,
++                measure,
++            })
++        }
++        _ => Err(ScriptError::Unknown(statement.to_string())),
++    }
++}
++
++/// Runs `script` for `player_idx`. Amounts are worked out as each statement
++/// starts, so a later statement sees what the earlier ones moved.
++pub fn run_script(script: &Script, player_idx: usize, game: &mut Game, market: &StockMarket) {
++    for statement in &script.statements {
++        match *statement {
++            Statement::Gain(amount) => {
++                let amount = amount_for(amount, player_idx, game, market);
++                game.players[player_idx].cash += amount;
++            }
++            Statement::Pay(amount) => {
++                let amount = amount_for(amount, player_idx, game, market);
++                game.players[player_idx].cash -= amount;
++            }
++            Statement::PayTo(amount, party) => {
++                let amount = amount_for(amount, player_idx, game, market);
++                for other in others(party, player_idx, game, market) {
++                    game.players[player_idx].cash -= amount;
++                    game.players[other].cash += amount;
++                }
++            }
++            Statement::CollectFrom(amount, party) => {
++                let amount = amount_for(amount, player_idx, game, market);
++                for other in others(party, player_idx, game, market) {
++                    game.players[other].cash -= amount;
++                    game.players[player_idx].cash += amount;
++                }
++            }
++        }
++    }
++}
++
++fn amount_for(amount: Amount, player_idx: usize, game: &Game, market: &StockMarket) -> i32 {
++    match amount {
++        Amount::Fixed(amount) => amount,
++        Amount::Percent {
++            percent,
++            of,
++            measure,
++        } => {
++            let Some(&seat) = named(of, player_idx, game, market).first() else {
++                return 0;
++            };
++            let player = &game.players[seat];
++            let base = match measure {
++                Measure::Cash => player.cash,
++                Measure::NetWorth => Money(player.net_worth(&game.board, market)),
++            };
++            base.max(Money::ZERO).percent(percent)
++        }
++    }
++}
++
++/// The standing players `party` names other than `player_idx`.
++fn others(party: Party, player_idx: usize, game: &Game, market: &StockMarket) -> Vec<usize> {
++    named(party, player_idx, game, market)
++        .into_iter()
++        .filter(|seat| *seat != player_idx)
++        .collect()
++}
++
++/// The seats `party` names, as seen by `player_idx`. Ties for leader or last
++/// go to the lower seat.
++fn named(party: Party, player_idx: usize, game: &Game, market: &StockMarket) -> Vec<usize> {
++    let standing = (0..game.players.len()).filter(|seat| !game.players[*seat].bankrupt);
++    let worth = |seat: &usize| game.players[*seat].net_worth(&game.board, market);
++    match party {
++        Party::Me => vec![player_idx],
++        Party::Leader => standing
++            .max_by_key(|seat| (worth(seat), Reverse(*seat)))
++            .into_iter()
++            .collect(),
++        Party::Last => standing
++            .min_by_key(|seat| (worth(seat), *seat))
++            .into_iter()
++            .collect(),
++        Party::Each => standing.filter(|seat| *seat != player_idx).collect(),
++    }
++}
++
++#[cfg(test)]
++mod tests {
++    use super::*;
++    use crate::invariants::total_cash;
++    use crate::sim::load_board_files;
++
++    fn statements(source: &str) -> Vec<Statement> {
++        parse_script(source).expect("the script parses").statements
++    }
++
++    /// A fresh match on the first shipped board with a player per amount of
++    /// cash, so the leader and last place are picked by cash alone.
++    fn new_match(cash: &[i32]) -> (Game, StockMarket) {
++        let boards = load_board_files().expect("the board files load");
++        let seats: Vec<Seat> = (0..cash.len())
++            .map(|character| Seat {
++                name: format!("Player {}", character + 1),
++                kind: PlayerKind::Bot,
++                strategy: BotStrategy::Balanced,
++                character,
++            })
++            .collect();
++        let mut game = Game::new(
++            &boards[0].1,
++            &seats,
++            TARGET_NET_WORTHS[1],
++            GameRules::default(),
++        );
++        for (player, cash) in game.players.iter_mut().zip(cash) {
++            player.cash = Money(*cash);
++        }
++        let market = StockMarket::new(&game);
++        (game, market)
++    }
++
++    fn run(source: &str, cash: &[i32]) -> Vec<i32> {
++        let (mut game, market) = new_match(cash);
++        let script = parse_script(source).expect("the script parses");
++        run_script(&script, 0, &mut game, &market);
++        game.players
++            .iter()
++            .map(|player| player.cash.get())
++            .collect()
++    }
++
++    #[test]
++    fn parses_each_statement_form() {
++        assert_eq!(
++            statements("gain 50"),
++            vec![Statement::Gain(Amount::Fixed(50))]
++        );
++        assert_eq!(
++            statements("Pay 20G."),
++            vec![Statement::Pay(Amount::Fixed(20))]
++        );
++        assert_eq!(
++            statements("pay 30 to the leader; collect 40 from last\ncollect 5 from everyone"),
++            vec![
++                Statement::PayTo(Amount::Fixed(30), Party::Leader),
++                Statement::CollectFrom(Amount::Fixed(40), Party::Last),
++                Statement::CollectFrom(Amount::Fixed(5), Party::Each),
++            ]
++        );
++    }
++
++    #[test]
++    fn parses_percent_amounts() {
++        assert_eq!(
++            statements(
++                "pay 10% of my cash to each; collect 5% of the leader's net worth from leader"
++            ),
++            vec![
++                Statement::PayTo(
++                    Amount::Percent {
++                        percent: 10,
++                        of: Party::Me,
++                        measure: Measure::Cash,
++                    },
++                    Party::Each
++                ),
++                Statement::CollectFrom(
++                    Amount::Percent {
++                        percent: 5,
++                        of: Party::Leader,
++                        measure: Measure::NetWorth,
++                    },
++                    Party::Leader
++                ),
++            ]
++        );
++        assert_eq!(
++            statements("gain 100% of last's worth"),
++            vec![Statement::Gain(Amount::Percent {
++                percent: 100,
++                of: Party::Last,
++                measure: Measure::NetWorth,
++            })]
++        );
++    }
++
++    #[test]
++    fn limits_are_inclusive() {
++        let longest = format!("gain 1;{}", " ".repeat(MAX_SCRIPT_CHARS - 7));
++        assert!(parse_script(&longest).is_ok());
++        assert_eq!(
++            parse_script(&format!("{} ", longest)),
++            Err(ScriptError::TooLong)
++        );
++
++        let most = ["gain 1"; MAX_SCRIPT_STATEMENTS].join(";");
++        assert!(parse_script(&most).is_ok());
++        assert_eq!(
++            parse_script(&format!("{};gain 1", most)),
++            Err(ScriptError::TooManyStatements)
++        );
++
++        assert!(parse_script(&format!("gain {}", MAX_SCRIPT_AMOUNT)).is_ok());
++        assert_eq!(
++            parse_script(&format!("gain {}", MAX_SCRIPT_AMOUNT + 1)),
++            Err(ScriptError::BadAmount((MAX_SCRIPT_AMOUNT + 1).to_string()))
++        );
++    }
++
++    #[test]
++    fn reports_each_error() {
++        assert_eq!(parse_script(" ;\n. "), Err(ScriptError::Empty));
++        assert_eq!(
++            parse_script(&"x".repeat(MAX_SCRIPT_CHARS + 1)),
++            Err(ScriptError::TooLong)
++        );
++        assert_eq!(
++            parse_script(&["pay 1"; MAX_SCRIPT_STATEMENTS + 1].join("\n")),
++            Err(ScriptError::TooManyStatements)
++        );
++        assert_eq!(
++            parse_script("gain 5; dance"),
++            Err(ScriptError::Unknown("dance".to_string()))
++        );
++        assert_eq!(
++            parse_script("collect 5"),
++            Err(ScriptError::Unknown("collect 5".to_string()))
++        );
++        assert_eq!(
++            parse_script("pay -5"),
++            Err(ScriptError::BadAmount("-5".to_string()))
++        );
++        assert_eq!(
++            parse_script("gain lots"),
++            Err(ScriptError::BadAmount("lots".to_string()))
++        );
++        assert_eq!(
++            parse_script("gain 101% of my cash"),
++            Err(ScriptError::BadPercent("101%".to_string()))
++        );
++        assert_eq!(
++            parse_script("gain 10 of my cash"),
++            Err(ScriptError::BadPercent("10".to_string()))
++        );
++        assert_eq!(
++            parse_script("pay 5 to the bank"),
++            Err(ScriptError::BadParty("the bank".to_string()))
++        );
++        assert_eq!(
++            parse_script("gain 5% of your cash"),
++            Err(ScriptError::BadParty("your".to_string()))
++        );
++    }
++
++    #[test]
++    fn moves_cash_with_the_bank_and_players() {
++        assert_eq!(run("gain 50", &[100, 200, 300]), vec![150, 200, 300]);
++        assert_eq!(run("pay 50", &[100, 200, 300]), vec![50, 200, 300]);
++        assert_eq!(
++            run("pay 10% of my cash", &[1000, 200, 300]),
++            vec![900, 200, 300]
++        );
++        assert_eq!(
++            run("pay 30 to leader", &[100, 200, 300]),
++            vec![70, 200, 330]
++        );
++        assert_eq!(
++            run("collect 30 from last", &[500, 200, 300]),
++            vec![530, 170, 300]
++        );
++        assert_eq!(
++            run("collect 10% of leader's cash from each", &[100, 200, 300]),
++            vec![160, 170, 270]
++        );
++    }
++
++    #[test]
++    fn trading_with_each_player_conserves_cash() {
++        for source in [
++            "pay 25 to each",
++            "collect 25 from each",
++            "pay 50% of my cash to each",
++            "collect 10% of the leader's worth from each",
++        ] {
++            let (mut game, market) = new_match(&[400, 1000, 50, 700]);
++            let before = total_cash(&game);
++            let script = parse_script(source).expect("the script parses");
++            for player_idx in 0..game.players.len() {
++                run_script(&script, player_idx, &mut game, &market);
++            }
++            assert_eq!(total_cash(&game), before, "{}", source);
++        }
++    }
++
++    #[test]
++    fn describes_every_statement() {
++        let script = parse_script(
++            "gain 1000; pay 5% of last's cash to each; collect 1% of leader's worth from last",
++        )
++        .expect("the script parses");
++        assert_eq!(
++            script.describe(),
++            "Collect 1,000G from the bank. \
++             Pay 5% of last place's cash to every other player. \
++             Collect 1% of the leader's net worth from the player in last place."
++        );
++    }
++}
@@ -0,0 +1,220 @@
+diff --git a/src/settings.rs b/src/settings.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..f0b3368c31c7816e5e1a1315f1880eda19917eb8
+--- /dev/null
++++ b/src/settings.rs
+@@ -0,0 +1,214 @@
++//! Player preferences kept between sessions: volumes, animation speed, camera
++//! sensitivity, language, colorblind palette and patterns, UI scale and text
++//! size, and key bindings. They are stored as RON in the platform's
++//! config folder, read at startup and written whenever the settings menu
++//! closes.
++
++use bevy::prelude::*;
++use serde::{Deserialize, Serialize};
++use std::path::PathBuf;
++
++use crate::*;
++
++/// Name of the settings file inside the config folder.
++pub const SETTINGS_FILE: &str = "settings.ron";
++
++/// Sliders at the top of the settings menu, from the volumes to large text.
++pub const SETTINGS_SLIDERS: usize = 10;
++
++/// Rows of the settings menu, in order: the sliders, then one row per action.
++pub const SETTINGS_ROWS: usize = SETTINGS_SLIDERS + ACTIONS.len();
++
++#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
++#[serde(default)]
++pub struct Settings {
++    /// Master volume from 0 to 1, which scales both channels below.
++    pub volume: f32,
++    /// Background music volume from 0 to 1.
++    pub music_volume: f32,
++    /// Sound effects volume from 0 to 1.
++    pub effects_volume: f32,
++    /// Scales how fast tokens walk and dice tumble; 2 plays them at double
++    /// speed.
++    pub animation_speed: f32,
++    /// Scales keyboard panning speed and mouse-wheel zoom steps.
++    pub camera_sensitivity: f32,
++    pub language: Language,
++    /// Colors the board is drawn in.
++    pub palette: Palette,
++    /// Lays symbols and ownership stripes over the tiles.
++    pub patterns: bool,
++    /// Scales the sidebar, panels and board text; 2 draws them at double
++    /// size.
++    pub ui_scale: f32,
++    /// Draws every piece of text larger.
++    pub large_text: bool,
++    pub bindings: InputMap,
++}
++
++impl Default for Settings {
++    fn default() -> Self {
++        Self {
++            volume: 0.8,
++            music_volume: 0.6,
++            effects_volume: 1.0,
++            animation_speed: 1.0,
++            camera_sensitivity: 1.0,
++            language: Language::default(),
++            palette: Palette::default(),
++            patterns: false,
++            ui_scale: 1.0,
++            large_text: false,
++            bindings: InputMap::default(),
++        }
++    }
++}
++
++impl Settings {
++    /// Where the settings live: the platform's config folder when there is
++    /// one, otherwise the working directory.
++    pub fn path() -> PathBuf {
++        directories::ProjectDirs::from("", "", "Itadaki Street")
++            .map(|dirs| dirs.config_dir().join(SETTINGS_FILE))
++            .unwrap_or_else(|| PathBuf::from(SETTINGS_FILE))
++    }
++
++    /// Moves setting `row` one `delta` step, clamped to its range. Action
++    /// rows are changed by rebinding instead.
++    pub fn adjust(&mut self, row: usize, delta: i32) {
++        let step = delta as f32;
++        match row {
++            0 => self.volume = (self.volume + step * 0.1).clamp(0.0, 1.0),
++            1 => self.music_volume = (self.music_volume + step * 0.1).clamp(0.0, 1.0),
++            2 => self.effects_volume = (self.effects_volume + step * 0.1).clamp(0.0, 1.0),
++            3 => self.animation_speed = (self.animation_speed + step * 0.25).clamp(0.5, 3.0),
++            4 => self.camera_sensitivity = (self.camera_sensitivity + step * 0.25).clamp(0.25, 3.0),
++            5 => self.language = self.language.step(delta),
++            6 => self.palette = self.palette.step(delta),
++            7 => self.patterns = !self.patterns,
++            8 => {
++                let (min, max) = UI_SCALE_RANGE;
++                self.ui_scale = (self.ui_scale + step * 0.25).clamp(min, max);
++            }
++            9 => self.large_text = !self.large_text,
++            _ => {}
++        }
++    }
++
++    /// How much larger than spawned text is drawn, before the UI scale.
++    pub fn text_scale(&self) -> f32 {
++        if self.large_text {
++            LARGE_TEXT_SCALE
++        } else {
++            1.0
++        }
++    }
++
++    /// How loud music plays, after the master volume.
++    pub fn music_level(&self) -> f32 {
++        self.volume * self.music_volume
++    }
++
++    /// How loud sound effects play, after the master volume.
++    pub fn effects_level(&self) -> f32 {
++        self.volume * self.effects_volume
++    }
++
++    pub fn save(&self) {
++        let path = Self::path();
++        if let Err(err) = write_ron(self, &path) {
++            error!("Could not write {}: {}", path.display(), err);
++        }
++    }
++}
++
++/// State of the settings menu while it is open.
++#[derive(Resource, Default)]
++pub struct SettingsMenu {
++    pub open: bool,
++    pub cursor: usize,
++    /// The highlighted action takes the next key or button pressed.
++    pub rebinding: bool,
++}
++
++/// Replaces the default settings with the saved ones, if any.
++pub fn load_settings(mut settings: ResMut<Settings>) {
++    let path = Settings::path();
++    if !stored(&path) {
++        return;
++    }
++    match read_ron(&path) {
++        Ok(loaded) => *settings = loaded,
++        Err(err) => error!("Could not read {}: {}", path.display(), err),
++    }
++}
++
++/// The Settings action opens the menu. Up/Down pick a row, Left/Right
++/// change a slider, Confirm on an action rebinds it to the next key or
++/// gamepad button pressed (Back cancels) and Erase restores its default keys.
++/// Back or Settings closes the menu and saves. While the menu is open it takes
++/// every action, so nothing else reacts to them.
++pub fn settings_menu_input(
++    keyboard: Res<ButtonInput<KeyCode>>,
++    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
++    mut actions: ResMut<ActionInput>,
++    mut menu: ResMut<SettingsMenu>,
++    mut settings: ResMut<Settings>,
++) {
++    let action = menu
++        .cursor
++        .checked_sub(SETTINGS_SLIDERS)
++        .map(|idx| ACTIONS[idx]);
++    if menu.rebinding {
++        let pressed = keyboard
++            .get_just_pressed()
++            .next()
++            .map(|key| Binding::Key(*key))
++            .or_else(|| {
++                gamepad_buttons
++                    .get_just_pressed()
++                    .next()
++                    .map(|button| Binding::Gamepad(button.button_type))
++            });
++        if actions.just_pressed(Action::Back) {
++            menu.rebinding = false;
++        } else if let (Some(binding), Some(action)) = (pressed, action) {
++            settings.bindings.rebind(action, binding);
++            menu.rebinding = false;
++        }
++        actions.reset_all();
++        return;
++    }
++    let toggled =
++        actions.just_pressed(Action::Settings) || (menu.open && actions.just_pressed(Action::Back));
++    if !menu.open && !toggled {
++        return;
++    }
++    if toggled {
++        menu.open = !menu.open;
++        if !menu.open {
++            settings.save();
++        }
++    } else {
++        if actions.just_pressed(Action::Up) {
++            menu.cursor = (menu.cursor + SETTINGS_ROWS - 1) % SETTINGS_ROWS;
++        }
++        if actions.just_pressed(Action::Down) {
++            menu.cursor = (menu.cursor + 1) % SETTINGS_ROWS;
++        }
++        if actions.just_pressed(Action::Left) {
++            settings.adjust(menu.cursor, -1);
++        }
++        if actions.just_pressed(Action::Right) {
++            settings.adjust(menu.cursor, 1);
++        }
++        if let Some(action) = action {
++            if actions.just_pressed(Action::Confirm) {
++                menu.rebinding = true;
++            } else if actions.just_pressed(Action::Erase) {
++                settings.bindings.reset(action);
++            }
++        }
++    }
++    actions.reset_all();
++}
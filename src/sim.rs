@@ -0,0 +1,453 @@
+diff --git a/src/sim.rs b/src/sim.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..c1089b4f3df160b9d9fd7f254aa5fa70aeb3fbf7
+--- /dev/null
++++ b/src/sim.rs
+@@ -0,0 +1,447 @@
++//! Headless simulation for balancing: plays bot-only matches back to back
++//! without rendering and reports win rates, game length and final net worth.
++
++use std::path::PathBuf;
++
++use crate::*;
++
++/// Turns after which a simulated match is abandoned without a winner.
++pub const DEFAULT_MAX_TURNS: usize = 2000;
++
++#[derive(Debug, thiserror::Error)]
++pub enum SimulationError {
++    #[error("{0}")]
++    Usage(String),
++    #[error(transparent)]
++    Board(#[from] BoardLoadError),
++    #[error("no board files found in {0}")]
++    NoBoards(String),
++    #[error("no board named \"{0}\"")]
++    UnknownBoard(String),
++    #[error("board \"{0}\" isn't playable: {1}")]
++    Unplayable(String, BoardLayoutError),
++}
++
++/// Options for `--headless`, read from the command line.
++#[derive(Debug, Clone)]
++pub struct SimulationConfig {
++    pub games: usize,
++    /// Board name or file stem; the first board file when `None`.
++    pub board: Option<String>,
++    pub bots: usize,
++    /// Strategies handed out to the seats in turn.
++    pub strategies: Vec<BotStrategy>,
++    pub target_net_worth: i32,
++    pub difficulty: Difficulty,
++    pub max_turns: usize,
++}
++
++impl Default for SimulationConfig {
++    fn default() -> Self {
++        Self {
++            games: 100,
++            board: None,
++            bots: MAX_PLAYERS,
++            strategies: vec![BotStrategy::default()],
++            target_net_worth: TARGET_NET_WORTHS[1],
++            difficulty: Difficulty::default(),
++            max_turns: DEFAULT_MAX_TURNS,
++        }
++    }
++}
++
++pub const USAGE: &str = "usage: itadaki-street --headless [--games N] [--board NAME] [--bots 2-4] [--strategies cautious,balanced,aggressive,hard] [--target G] [--difficulty easy|normal|hard] [--max-turns N]";
++
++impl SimulationConfig {
++    pub fn from_args(args: &[String]) -> Result<Self, SimulationError> {
++        let mut config = Self::default();
++        let mut args = args.iter();
++        while let Some(arg) = args.next() {
++            if arg == "--headless" {
++                continue;
++            }
++            let mut value = || {
++                args.next().ok_or_else(|| {
++                    SimulationError::Usage(format!("{} needs a value\n{}", arg, USAGE))
++                })
++            };
++            let number = |value: &String| {
++                value.parse::<usize>().map_err(|_| {
++                    SimulationError::Usage(format!("{} is not a number\n{}", value, USAGE))
++                })
++            };
++            match arg.as_str() {
++                "--games" => config.games = number(value()?)?,
++                "--board" => config.board = Some(value()?.clone()),
++                "--bots" => config.bots = number(value()?)?.clamp(MIN_PLAYERS, MAX_PLAYERS),
++                "--strategies" => {
++                    config.strategies = value()?
++                        .split(',')
++                        .map(|name| {
++                            BotStrategy::from_name(name).ok_or_else(|| {
++                                SimulationError::Usage(format!(
++                                    "unknown strategy {}\n{}",
++                                    name, USAGE
++                                ))
++                            })
++                        })
++                        .collect::<Result<_, _>>()?;
++                }
++                "--target" => config.target_net_worth = number(value()?)? as i32,
++                "--difficulty" => {
++                    let name = value()?;
++                    config.difficulty = Difficulty::from_name(name).ok_or_else(|| {
++                        SimulationError::Usage(format!("unknown difficulty {}\n{}", name, USAGE))
++                    })?;
++                }
++                "--max-turns" => config.max_turns = number(value()?)?,
++                _ => {
++                    return Err(SimulationError::Usage(format!(
++                        "unknown option {}\n{}",
++                        arg, USAGE
++                    )));
++                }
++            }
++        }
++        Ok(config)
++    }
++}
++
++/// How one simulated match ended.
++#[derive(Debug, Clone)]
++pub struct MatchResult {
++    /// `None` if the match hit the turn limit.
++    pub winner: Option<usize>,
++    pub turns: usize,
++    pub net_worths: Vec<i32>,
++}
++
++/// Reads every `*.board.ron` file in the assets boards folder, sorted by file
++/// name, the same set the setup screen offers.
++pub fn load_board_files() -> Result<Vec<(String, BoardDefinition)>, SimulationError> {
++    let root =
++        std::env::var_os("CARGO_MANIFEST_DIR").map_or_else(|| PathBuf::from("."), PathBuf::from);
++    let folder = root.join("assets").join(BOARDS_FOLDER);
++    let mut paths: Vec<PathBuf> = std::fs::read_dir(&folder)
++        .map_err(BoardLoadError::from)?
++        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
++        .filter(|path| path.to_string_lossy().ends_with(".board.ron"))
++        .collect();
++    paths.sort();
++    if paths.is_empty() {
++        return Err(SimulationError::NoBoards(folder.display().to_string()));
++    }
++    paths
++        .into_iter()
++        .map(|path| {
++            let bytes = std::fs::read(&path).map_err(BoardLoadError::from)?;
++            let definition: BoardDefinition =
++                ron::de::from_bytes(&bytes).map_err(BoardLoadError::from)?;
++            let stem = path
++                .file_name()
++                .map(|name| {
++                    name.to_string_lossy()
++                        .trim_end_matches(".board.ron")
++                        .to_string()
++                })
++                .unwrap_or_default();
++            if let Err(err) = definition.validate() {
++                return Err(SimulationError::Unplayable(stem, err));
++            }
++            Ok((stem, definition))
++        })
++        .collect()
++}
++
++/// Runs `config.games` matches and prints aggregate statistics.
++pub fn run_headless(config: &SimulationConfig) -> Result<(), SimulationError> {
++    let boards = load_board_files()?;
++    let definition = match &config.board {
++        None => &boards[0].1,
++        Some(wanted) => boards
++            .iter()
++            .find(|(stem, definition)| {
++                stem.eq_ignore_ascii_case(wanted) || definition.name.eq_ignore_ascii_case(wanted)
++            })
++            .map(|(_, definition)| definition)
++            .ok_or_else(|| SimulationError::UnknownBoard(wanted.clone()))?,
++    };
++
++    let results: Vec<MatchResult> = (0..config.games)
++        .map(|_| simulate_match(definition, config))
++        .collect();
++    print_report(definition, config, &results);
++    Ok(())
++}
++
++/// Plays one bot-only match to completion or to the turn limit.
++pub fn simulate_match(definition: &BoardDefinition, config: &SimulationConfig) -> MatchResult {
++    let seats: Vec<Seat> = (0..config.bots)
++        .zip('A'..)
++        .map(|(character, letter)| Seat {
++            name: format!("Bot {}", letter),
++            kind: PlayerKind::Bot,
++            strategy: config.strategies[character % config.strategies.len()],
++            character,
++        })
++        .collect();
++    let mut game = Game::new(
++        definition,
++        &seats,
++        config.target_net_worth,
++        GameRules::preset(config.difficulty),
++    );
++    let mut rng = MatchRng::from_entropy();
++    game.set_turn_order(roll_for_order(seats.len(), &mut rng).order);
++    let mut market = StockMarket::new(&game);
++    let mut deck = VentureDeck::for_game(&game);
++
++    let mut turns = 0;
++    while game.winner.is_none() && turns < config.max_turns {
++        advance_match(&mut game, &mut market, &mut deck, &mut rng);
++        turns += 1;
++    }
++    MatchResult {
++        winner: game.winner,
++        turns,
++        net_worths: game
++            .players
++            .iter()
++            .map(|player| player.net_worth(&game.board, &market))
++            .collect(),
++    }
++}
++
++/// Plays the active bot's turn, crowns the last player standing and passes
++/// the turn on.
++pub fn advance_match(
++    game: &mut Game,
++    market: &mut StockMarket,
++    deck: &mut VentureDeck,
++    rng: &mut MatchRng,
++) {
++    play_turn(game, market, deck, rng);
++    let _ = apply(TurnAction::EndTurn, game, market, rng);
++    if cfg!(debug_assertions) {
++        assert_valid(game, market);
++    }
++}
++
++/// Where the simulated turn goes next, mirroring `TurnPhase`.
++enum Step {
++    Roll,
++    Move(usize),
++    Resolve { warped: bool },
++    Stock,
++}
++
++/// Plays the active bot's whole turn the way the Bevy systems would, without
++/// timers or animation. Everything but the bot's own stock phase goes
++/// through the same `engine::apply` the systems use; the events it returns
++/// go unheard.
++fn play_turn(
++    game: &mut Game,
++    market: &mut StockMarket,
++    deck: &mut VentureDeck,
++    rng: &mut MatchRng,
++) {
++    let current = game.current_turn;
++    let mut step = Step::Roll;
++    loop {
++        step = match step {
++            Step::Roll => {
++                let rolled = apply(TurnAction::Roll, game, market, rng)
++                    .unwrap_or_default()
++                    .into_iter()
++                    .find_map(|event| match event {
++                        GameEvent::Rolled { faces, .. } => Some(faces.iter().sum()),
++                        _ => None,
++                    });
++                Step::Move(rolled.unwrap_or_default())
++            }
++            Step::Move(steps) => {
++                walk(current, steps, game, market, rng);
++                Step::Resolve { warped: false }
++            }
++            Step::Resolve { warped } => {
++                let tile_index = game.players[current].position;
++                let kind = game.board[tile_index].kind.clone();
++                match kind {
++                    TileKind::Chance => {
++                        let card = deck.draw();
++                        let outcome = venture_outcome(card.effect, game);
++                        let _ = apply(TurnAction::Venture(card.effect), game, market, rng);
++                        if settle_all(game, market, rng) {
++                            break;
++                        }
++                        match outcome {
++                            VentureOutcome::Done => Step::Stock,
++                            VentureOutcome::RollAgain => Step::Roll,
++                            VentureOutcome::Move(steps) => Step::Move(steps),
++                            VentureOutcome::Resolve => Step::Resolve { warped: false },
++                        }
++                    }
++                    TileKind::Arcade => {
++                        let prize = TurnAction::ArcadePrize(bot_arcade_prize());
++                        let _ = apply(prize, game, market, rng);
++                        Step::Stock
++                    }
++                    TileKind::Casino => {
++                        if let Some(bet) = bot_casino_bet(current, game) {
++                            let _ = apply(bet, game, market, rng);
++                        }
++                        Step::Stock
++                    }
++                    TileKind::Warp { destination } if !warped => {
++                        let warp = TurnAction::Teleport {
++                            player: current,
++                            tile: destination,
++                        };
++                        let _ = apply(warp, game, market, rng);
++                        Step::Resolve { warped: true }
++                    }
++                    _ => {
++                        let _ = apply(TurnAction::Land, game, market, rng);
++                        if settle_all(game, market, rng) {
++                            break;
++                        }
++                        if let Some(tile) = game.declined_shop.take() {
++                            run_auction(tile, current, game, market, rng);
++                        }
++                        Step::Stock
++                    }
++                }
++            }
++            Step::Stock => {
++                // Every seat is a bot, so proposals are answered on the spot.
++                if let Some(offer) = bot_propose_trade(current, game, market)
++                    && bot_accepts_trade(&offer, game, market) == Some(true)
++                {
++                    let _ = apply(TurnAction::Trade(offer), game, market, rng);
++                }
++                bot_stock_phase(current, game, market);
++                break;
++            }
++        };
++    }
++    // Nothing listens for the queued UI events in a headless run.
++    game.pending_salaries.clear();
++    game.pending_events.clear();
++    game.pending_ownership.clear();
++    game.pending_investments.clear();
++    market.pending_changes.clear();
++}
++
++/// Moves a bot `steps` tiles, branching at junctions and applying the tiles
++/// and the bank it passes, as `move_player` does.
++fn walk(
++    player_idx: usize,
++    steps: usize,
++    game: &mut Game,
++    market: &mut StockMarket,
++    rng: &mut MatchRng,
++) {
++    for remaining in (0..steps).rev() {
++        let here = game.players[player_idx].position;
++        let exits = game.board[here].next.clone();
++        let exit = match exits.len() {
++            1 => exits[0],
++            _ => bot_branch(player_idx, &exits, remaining + 1, game, market),
++        };
++        let _ = apply(
++            TurnAction::Step {
++                to: exit,
++                remaining,
++            },
++            game,
++            market,
++            rng,
++        );
++    }
++}
++
++/// Settles every player in debt; returns whether the active player went
++/// bankrupt.
++fn settle_all(game: &mut Game, market: &mut StockMarket, rng: &mut MatchRng) -> bool {
++    let _ = apply(TurnAction::SettleDebts, game, market, rng);
++    game.players[game.current_turn].bankrupt
++}
++
++/// Bids bots up to their ceilings in turn order until nobody will raise, then
++/// sells the shop to the leader.
++fn run_auction(
++    tile: usize,
++    declined_by: usize,
++    game: &mut Game,
++    market: &mut StockMarket,
++    rng: &mut MatchRng,
++) {
++    let TileKind::Property { price, .. } = game.board[tile].kind else {
++        return;
++    };
++    let mut auction = Auction::open(tile, declined_by, price);
++    while let Some(bidder) = (0..game.players.len()).find(|idx| {
++        auction.can_bid(*idx, game)
++            && brain_of(*idx, game).bid_ceiling(*idx, tile, game) >= auction.next_bid()
++    }) {
++        auction.high_bid = auction.next_bid();
++        auction.leader = Some(bidder);
++    }
++    if let Some(buyer) = auction.leader {
++        let sale = TurnAction::AuctionSale {
++            tile,
++            buyer,
++            bid: auction.high_bid,
++        };
++        let _ = apply(sale, game, market, rng);
++    }
++}
++
++fn print_report(definition: &BoardDefinition, config: &SimulationConfig, results: &[MatchResult]) {
++    let games = results.len().max(1) as f32;
++    let average_turns = results.iter().map(|result| result.turns).sum::<usize>() as f32 / games;
++    let unfinished = results
++        .iter()
++        .filter(|result| result.winner.is_none())
++        .count();
++    println!(
++        "Simulated {} games on \"{}\" ({} bots, target {}, {})",
++        results.len(),
++        definition.name,
++        config.bots,
++        Money(config.target_net_worth),
++        config.difficulty.label()
++    );
++    println!(
++        "Average length: {:.1} turns ({:.1} rounds)",
++        average_turns,
++        average_turns / config.bots as f32
++    );
++    println!(
++        "Unfinished after {} turns: {}",
++        config.max_turns, unfinished
++    );
++    println!(
++        "{:<8} {:<11} {:>6} {:>9} {:>15}",
++        "Seat", "Strategy", "Wins", "Win rate", "Avg net worth"
++    );
++    for (seat, letter) in (0..config.bots).zip('A'..) {
++        let wins = results
++            .iter()
++            .filter(|result| result.winner == Some(seat))
++            .count();
++        let net_worth = results
++            .iter()
++            .map(|result| result.net_worths[seat] as f32)
++            .sum::<f32>()
++            / games;
++        println!(
++            "{:<8} {:<11} {:>6} {:>8.1}% {:>15}",
++            format!("Bot {}", letter),
++            config.strategies[seat % config.strategies.len()].label(),
++            wins,
++            100.0 * wins as f32 / games,
++            Money(net_worth.round() as i32)
++        );
++    }
++}
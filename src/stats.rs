@@ -0,0 +1,401 @@
+diff --git a/src/stats.rs b/src/stats.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..5c06982362c7cb4e747af09d4094dbe9327d3e6e
+--- /dev/null
++++ b/src/stats.rs
+@@ -0,0 +1,395 @@
++//! Match statistics. `MatchStats` follows a match from its first turn,
++//! sampling every player's net worth as each turn starts and tallying fees,
++//! shop purchases and dice rolls from the game events. When someone wins,
++//! the stats screen lays it all out: a net worth graph, a table of fees and
++//! shops, the biggest single fee, and each player's spread of roll totals.
++
++use bevy::prelude::*;
++
++use crate::*;
++
++/// Most points drawn per player on the net worth graph; longer matches are
++/// thinned out evenly.
++pub const GRAPH_POINTS: usize = 80;
++
++pub const GRAPH_WIDTH: f32 = 680.0;
++
++pub const GRAPH_HEIGHT: f32 = 200.0;
++
++/// Height of the tallest bar in a roll distribution.
++pub const ROLL_BAR_HEIGHT: f32 = 36.0;
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq)]
++pub struct BiggestFee {
++    pub payer: usize,
++    pub owner: usize,
++    pub tile: usize,
++    pub fee: i32,
++}
++
++/// Figures gathered over a match, by player index.
++#[derive(Resource, Debug, Clone, Default)]
++pub struct MatchStats {
++    /// Each player's net worth at the start of every turn, plus once more
++    /// when the match ends.
++    pub net_worth: Vec<Vec<i32>>,
++    pub fees_paid: Vec<i32>,
++    pub fees_received: Vec<i32>,
++    pub shops_bought: Vec<u32>,
++    pub biggest_fee: Option<BiggestFee>,
++    /// How often each roll total came up, indexed by the total.
++    pub roll_totals: Vec<Vec<u32>>,
++    /// Dice thrown and the sum of their faces, for average rolls.
++    pub dice_thrown: Vec<u32>,
++    pub pips: Vec<u32>,
++}
++
++impl MatchStats {
++    pub fn new(game: &Game, market: &StockMarket) -> Self {
++        let players = game.players.len();
++        let mut stats = Self {
++            net_worth: vec![Vec::new(); players],
++            fees_paid: vec![0; players],
++            fees_received: vec![0; players],
++            shops_bought: vec![0; players],
++            biggest_fee: None,
++            roll_totals: vec![Vec::new(); players],
++            dice_thrown: vec![0; players],
++            pips: vec![0; players],
++        };
++        stats.sample(game, market);
++        stats
++    }
++
++    /// Adds everyone's current net worth to the graph.
++    pub fn sample(&mut self, game: &Game, market: &StockMarket) {
++        for (history, player) in self.net_worth.iter_mut().zip(&game.players) {
++            history.push(player.net_worth(&game.board, market));
++        }
++    }
++
++    /// Tallies one game event.
++    pub fn record(&mut self, event: &GameEvent) {
++        match event {
++            GameEvent::Rolled { player, faces } => {
++                let total = faces.iter().sum::<usize>();
++                let counts = &mut self.roll_totals[*player];
++                if counts.len() <= total {
++                    counts.resize(total + 1, 0);
++                }
++                counts[total] += 1;
++                self.dice_thrown[*player] += faces.len() as u32;
++                self.pips[*player] += total as u32;
++            }
++            GameEvent::BoughtShop { player, .. } => self.shops_bought[*player] += 1,
++            GameEvent::PaidFee {
++                payer,
++                owner,
++                tile,
++                fee,
++            } => {
++                self.fees_paid[*payer] += fee;
++                self.fees_received[*owner] += fee;
++                if self.biggest_fee.is_none_or(|biggest| *fee > biggest.fee) {
++                    self.biggest_fee = Some(BiggestFee {
++                        payer: *payer,
++                        owner: *owner,
++                        tile: *tile,
++                        fee: *fee,
++                    });
++                }
++            }
++            _ => {}
++        }
++    }
++
++    /// Average face a player rolled, or `None` before their first roll.
++    pub fn average_roll(&self, player: usize) -> Option<f32> {
++        (self.dice_thrown[player] > 0)
++            .then(|| self.pips[player] as f32 / self.dice_thrown[player] as f32)
++    }
++
++    /// The player whose dice came up highest on average.
++    pub fn luckiest(&self) -> Option<usize> {
++        (0..self.dice_thrown.len())
++            .filter_map(|player| self.average_roll(player).map(|average| (player, average)))
++            .max_by(|a, b| a.1.total_cmp(&b.1))
++            .map(|(player, _)| player)
++    }
++}
++
++/// Marks the end-of-match stats overlay.
++#[derive(Component)]
++pub struct StatsScreen;
++
++/// Starts a fresh set of stats for the match just begun.
++pub fn start_match_stats(mut commands: Commands, game: Res<Game>, market: Res<StockMarket>) {
++    commands.insert_resource(MatchStats::new(&game, &market));
++}
++
++/// Tallies each game event, and samples net worth as every turn starts.
++pub fn track_match_stats(
++    mut stats: ResMut<MatchStats>,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    mut game_events: EventReader<GameEvent>,
++) {
++    for event in game_events.read() {
++        if let GameEvent::Rolled { .. } = event {
++            stats.sample(&game, &market);
++        }
++        stats.record(event);
++    }
++}
++
++/// Takes the final net worth sample and covers the board with the stats
++/// screen.
++pub fn spawn_stats_screen(
++    mut commands: Commands,
++    asset_server: Res<AssetServer>,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    mut stats: ResMut<MatchStats>,
++) {
++    stats.sample(&game, &market);
++    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
++    let text = |size: f32, color: Color| TextStyle {
++        font: font.clone(),
++        font_size: size,
++        color,
++    };
++    let title = match game.winner {
++        Some(winner) => format!(
++            "{} wins with {} net worth!",
++            game.players[winner].name,
++            Money(game.players[winner].net_worth(&game.board, &market))
++        ),
++        None => "Match over".to_string(),
++    };
++
++    commands
++        .spawn((
++            NodeBundle {
++                style: Style {
++                    position_type: PositionType::Absolute,
++                    width: Val::Percent(100.0),
++                    height: Val::Percent(100.0),
++                    justify_content: JustifyContent::Center,
++                    align_items: AlignItems::Center,
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.6)),
++                z_index: ZIndex::Global(10),
++                ..Default::default()
++            },
++            StatsScreen,
++        ))
++        .with_children(|overlay| {
++            overlay
++                .spawn(NodeBundle {
++                    style: Style {
++                        flex_direction: FlexDirection::Column,
++                        padding: UiRect::all(Val::Px(16.0)),
++                        row_gap: Val::Px(10.0),
++                        ..Default::default()
++                    },
++                    background_color: BackgroundColor(Color::rgb(0.1, 0.1, 0.15)),
++                    ..Default::default()
++                })
++                .with_children(|screen| {
++                    screen.spawn(TextBundle::from_section(title, text(24.0, BANK_COLOR)));
++                    screen.spawn(TextBundle::from_section(
++                        "Net worth by turn",
++                        text(16.0, Color::WHITE),
++                    ));
++                    spawn_net_worth_graph(screen, &game, &stats);
++                    for idx in 0..game.players.len() {
++                        screen.spawn(TextBundle::from_section(
++                            describe_player_stats(&game, &stats, idx),
++                            text(15.0, player_color(&game, idx)),
++                        ));
++                    }
++                    let mut summary = match stats.biggest_fee {
++                        Some(biggest) => format!(
++                            "Biggest fee: {} paid {} {} at {}",
++                            game.players[biggest.payer].name,
++                            game.players[biggest.owner].name,
++                            Money(biggest.fee),
++                            game.tile_label(biggest.tile)
++                        ),
++                        None => "No fees were paid".to_string(),
++                    };
++                    if let Some(luckiest) = stats.luckiest() {
++                        summary.push_str(&format!(
++                            "\nLuckiest roller: {} ({:.2} per die)",
++                            game.players[luckiest].name,
++                            stats.average_roll(luckiest).unwrap_or_default()
++                        ));
++                    }
++                    screen.spawn(TextBundle::from_section(summary, text(15.0, Color::WHITE)));
++                    screen.spawn(TextBundle::from_section(
++                        "Roll totals",
++                        text(16.0, Color::WHITE),
++                    ));
++                    spawn_roll_distributions(screen, &game, &stats, &font);
++                    screen.spawn(TextBundle::from_section(
++                        "Enter hides this screen",
++                        text(13.0, Color::rgba(1.0, 1.0, 1.0, 0.6)),
++                    ));
++                });
++        });
++}
++
++/// A line of fee, shop and roll figures for player `idx`.
++pub fn describe_player_stats(game: &Game, stats: &MatchStats, idx: usize) -> String {
++    let average = match stats.average_roll(idx) {
++        Some(average) => format!("{:.2}", average),
++        None => "-".to_string(),
++    };
++    format!(
++        "{}: paid {} in fees, received {}, bought {} shops, average die {}",
++        game.players[idx].name,
++        Money(stats.fees_paid[idx]),
++        Money(stats.fees_received[idx]),
++        stats.shops_bought[idx],
++        average
++    )
++}
++
++/// Plots each player's net worth history as a line of dots in their color,
++/// scaled between the lowest and highest values of the match.
++fn spawn_net_worth_graph(parent: &mut ChildBuilder, game: &Game, stats: &MatchStats) {
++    let values = stats.net_worth.iter().flatten();
++    let low = values.clone().copied().min().unwrap_or_default().min(0);
++    let high = values.copied().max().unwrap_or_default().max(low + 1);
++    let samples = stats.net_worth.first().map_or(0, Vec::len);
++    let step = samples.div_ceil(GRAPH_POINTS).max(1);
++    let columns = samples.div_ceil(step).max(2) - 1;
++    parent
++        .spawn(NodeBundle {
++            style: Style {
++                width: Val::Px(GRAPH_WIDTH),
++                height: Val::Px(GRAPH_HEIGHT),
++                ..Default::default()
++            },
++            background_color: BackgroundColor(Color::rgba(1.0, 1.0, 1.0, 0.05)),
++            ..Default::default()
++        })
++        .with_children(|graph| {
++            for (idx, history) in stats.net_worth.iter().enumerate() {
++                let color = player_color(game, idx);
++                for (column, value) in history.iter().step_by(step).enumerate() {
++                    let x = column as f32 / columns as f32;
++                    let y = (value - low) as f32 / (high - low) as f32;
++                    graph.spawn(NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            left: Val::Px(x * (GRAPH_WIDTH - 4.0)),
++                            bottom: Val::Px(y * (GRAPH_HEIGHT - 4.0)),
++                            width: Val::Px(4.0),
++                            height: Val::Px(4.0),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(color),
++                        ..Default::default()
++                    });
++                }
++            }
++        });
++}
++
++/// A small bar chart per player of how often each roll total came up.
++fn spawn_roll_distributions(
++    parent: &mut ChildBuilder,
++    game: &Game,
++    stats: &MatchStats,
++    font: &Handle<Font>,
++) {
++    let highest = stats
++        .roll_totals
++        .iter()
++        .map(Vec::len)
++        .max()
++        .unwrap_or_default();
++    parent
++        .spawn(NodeBundle {
++            style: Style {
++                column_gap: Val::Px(16.0),
++                ..Default::default()
++            },
++            ..Default::default()
++        })
++        .with_children(|row| {
++            for (idx, counts) in stats.roll_totals.iter().enumerate() {
++                let most = counts.iter().copied().max().unwrap_or_default().max(1);
++                row.spawn(NodeBundle {
++                    style: Style {
++                        flex_direction: FlexDirection::Column,
++                        row_gap: Val::Px(2.0),
++                        ..Default::default()
++                    },
++                    ..Default::default()
++                })
++                .with_children(|chart| {
++                    chart.spawn(TextBundle::from_section(
++                        game.players[idx].name.clone(),
++                        TextStyle {
++                            font: font.clone(),
++                            font_size: 13.0,
++                            color: player_color(game, idx),
++                        },
++                    ));
++                    chart
++                        .spawn(NodeBundle {
++                            style: Style {
++                                height: Val::Px(ROLL_BAR_HEIGHT),
++                                align_items: AlignItems::FlexEnd,
++                                column_gap: Val::Px(1.0),
++                                ..Default::default()
++                            },
++                            ..Default::default()
++                        })
++                        .with_children(|bars| {
++                            for total in 1..highest {
++                                let count = counts.get(total).copied().unwrap_or_default();
++                                bars.spawn(NodeBundle {
++                                    style: Style {
++                                        width: Val::Px(5.0),
++                                        height: Val::Px(
++                                            ROLL_BAR_HEIGHT * count as f32 / most as f32,
++                                        ),
++                                        ..Default::default()
++                                    },
++                                    background_color: BackgroundColor(player_color(game, idx)),
++                                    ..Default::default()
++                                });
++                            }
++                        });
++                    chart.spawn(TextBundle::from_section(
++                        format!("1-{}", highest.saturating_sub(1)),
++                        TextStyle {
++                            font: font.clone(),
++                            font_size: 11.0,
++                            color: Color::rgba(1.0, 1.0, 1.0, 0.6),
++                        },
++                    ));
++                });
++            }
++        });
++}
++
++/// Enter hides the stats screen to show the final board, and brings it back.
++pub fn toggle_stats_screen(
++    actions: Res<ActionInput>,
++    mut screens: Query<&mut Style, With<StatsScreen>>,
++) {
++    if !actions.just_pressed(Action::Confirm) {
++        return;
++    }
++    for mut style in screens.iter_mut() {
++        style.display = match style.display {
++            Display::None => Display::Flex,
++            _ => Display::None,
++        };
++    }
++}
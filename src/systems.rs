@@ -0,0 +1,2761 @@
+diff --git a/src/systems.rs b/src/systems.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..d0d016a845f5cb7dbb23993cce3f3156217647b9
+--- /dev/null
++++ b/src/systems.rs
+@@ -0,0 +1,2755 @@
++//! The turn state machine and the Bevy systems that drive each phase of play.
++
++use bevy::{asset::LoadedFolder, prelude::*};
++use rand::{Rng, seq::SliceRandom};
++use serde::{Deserialize, Serialize};
++use std::{
++    collections::{HashMap, HashSet, VecDeque},
++    path::{Path, PathBuf},
++    time::Duration,
++};
++
++use crate::*;
++
++/// Net worth goals offered on the setup screen.
++pub const TARGET_NET_WORTHS: [i32; 4] = [5000, 8000, 10000, 15000];
++
++pub const MIN_PLAYERS: usize = 2;
++
++pub const MAX_PLAYERS: usize = 4;
++
++/// Longest name that can be typed for a seat on the setup screen.
++pub const MAX_NAME_LENGTH: usize = 12;
++
++/// The action a seat bids with when several humans are in an auction: its
++/// seat number.
++pub fn seat_bid(seat: usize) -> Action {
++    Action::Pick(seat as u8 + 1)
++}
++
++/// Step by which the trade panel adjusts the cash in an offer.
++pub const TRADE_CASH_STEP: i32 = 50;
++
++#[derive(Resource)]
++pub struct TurnTimer(pub Timer);
++
++/// How much faster the game clock runs while the fast-forward key is held on
++/// a bot's turn.
++pub const FAST_FORWARD_SPEED: f32 = 4.0;
++
++/// Paces picked on the setup screen, slowest last.
++pub const BOT_PACINGS: [BotPacing; 3] =
++    [BotPacing::Instant, BotPacing::Normal, BotPacing::Cinematic];
++
++/// How quickly bot turns play out.
++#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
++pub enum BotPacing {
++    Instant,
++    #[default]
++    Normal,
++    Cinematic,
++}
++
++impl BotPacing {
++    pub fn label(self) -> &'static str {
++        match self {
++            BotPacing::Instant => "Instant",
++            BotPacing::Normal => "Normal",
++            BotPacing::Cinematic => "Cinematic",
++        }
++    }
++
++    /// Seconds a bot waits before rolling, the `TurnTimer` duration.
++    pub fn thinking_seconds(self) -> f32 {
++        match self {
++            BotPacing::Instant => 0.0,
++            BotPacing::Normal => 2.0,
++            BotPacing::Cinematic => 3.0,
++        }
++    }
++
++    /// How fast the game clock, and with it every animation, runs on a bot's
++    /// turn.
++    pub fn speed(self) -> f32 {
++        match self {
++            BotPacing::Instant => 6.0,
++            BotPacing::Normal => 1.0,
++            BotPacing::Cinematic => 0.75,
++        }
++    }
++}
++
++/// Top-level flow: wait for the board assets, show the title screen, pick the
++/// match settings, then play until someone wins. `Replay` watches a recorded
++/// match instead.
++#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
++pub enum AppState {
++    #[default]
++    Loading,
++    Title,
++    Setup,
++    InGame,
++    GameOver,
++    Replay,
++    Joining,
++    Remote,
++    Editor,
++}
++
++/// Choices made on the setup screen before the match is built.
++#[derive(Resource)]
++pub struct MatchSettings {
++    pub board: usize,
++    pub target: usize,
++    pub seats: Vec<Seat>,
++    /// Seats whose name was typed in; the rest follow the roster defaults.
++    pub renamed: Vec<bool>,
++    /// Setup row the keys currently adjust: board, target, player count, bot
++    /// pacing, decision timer, difficulty, profile, tutorial, then one row
++    /// per seat.
++    pub focus: usize,
++    /// The profile screen is showing in place of the setup rows.
++    pub viewing_profiles: bool,
++    /// Seats opened for players joining over the network when hosting.
++    pub remote: Vec<bool>,
++    pub pacing: BotPacing,
++    /// Time limit on each human decision.
++    pub decision_timer: DecisionTimer,
++    /// Play the match as a tutorial for the first human seat.
++    pub tutorial: bool,
++}
++
++/// Setup row picking the active profile.
++pub const SETUP_PROFILE_ROW: usize = 6;
++
++/// Setup row turning tutorial mode on or off.
++pub const SETUP_TUTORIAL_ROW: usize = 7;
++
++/// Setup rows above the first seat.
++pub const SETUP_SEAT_ROW: usize = 8;
++
++impl Default for MatchSettings {
++    fn default() -> Self {
++        let mut settings = Self {
++            board: 0,
++            target: 1,
++            seats: Vec::new(),
++            renamed: Vec::new(),
++            focus: 0,
++            viewing_profiles: false,
++            remote: Vec::new(),
++            pacing: BotPacing::default(),
++            decision_timer: DecisionTimer::default(),
++            tutorial: false,
++        };
++        settings.add_seat(PlayerKind::Human);
++        settings.add_seat(PlayerKind::Bot);
++        settings.add_seat(PlayerKind::Bot);
++        settings
++    }
++}
++
++impl MatchSettings {
++    pub fn rows(&self) -> usize {
++        SETUP_SEAT_ROW + self.seats.len()
++    }
++
++    /// Adds a seat with the first character nobody else is playing.
++    pub fn add_seat(&mut self, kind: PlayerKind) {
++        let character = (0..CHARACTERS.len())
++            .find(|character| self.seats.iter().all(|seat| seat.character != *character))
++            .unwrap_or(0);
++        self.seats.push(Seat {
++            name: String::new(),
++            kind,
++            strategy: BotStrategy::default(),
++            character,
++        });
++        self.renamed.push(false);
++        self.remote.push(false);
++        self.refresh_default_names();
++    }
++
++    /// Names seats nobody typed a name for: the first human is "Hero", other
++    /// humans are numbered by seat and bots are lettered in order.
++    pub fn refresh_default_names(&mut self) {
++        let mut humans = 0;
++        let mut bots = 'A'..;
++        for (idx, seat) in self.seats.iter_mut().enumerate() {
++            let default = match seat.kind {
++                PlayerKind::Human if self.remote[idx] => "Open seat".to_string(),
++                PlayerKind::Human => {
++                    humans += 1;
++                    if humans == 1 {
++                        "Hero".to_string()
++                    } else {
++                        format!("Player {}", idx + 1)
++                    }
++                }
++                PlayerKind::Bot => format!("Bot {}", bots.next().unwrap_or('?')),
++            };
++            if !self.renamed[idx] {
++                seat.name = default;
++            }
++        }
++    }
++
++    /// Moves the seat `delta` steps through the character roster, skipping
++    /// characters other seats already play.
++    pub fn cycle_character(&mut self, seat: usize, delta: isize) {
++        let len = CHARACTERS.len() as isize;
++        let mut character = self.seats[seat].character as isize;
++        for _ in 0..len {
++            character = (character + delta).rem_euclid(len);
++            let taken = self
++                .seats
++                .iter()
++                .enumerate()
++                .any(|(idx, other)| idx != seat && other.character == character as usize);
++            if !taken {
++                self.seats[seat].character = character as usize;
++                return;
++            }
++        }
++    }
++}
++
++/// Save file named by `--load`; the match it holds starts in place of the
++/// setup screen.
++#[derive(Resource)]
++pub struct LoadRequest(pub PathBuf);
++
++pub fn load_boards(mut commands: Commands, asset_server: Res<AssetServer>) {
++    let source = if cfg!(target_arch = "wasm32") {
++        BoardSource::Files(
++            WEB_BOARDS
++                .iter()
++                .map(|file| asset_server.load(format!("{}/{}", BOARDS_FOLDER, file)))
++                .collect(),
++        )
++    } else {
++        BoardSource::Folder(asset_server.load_folder(BOARDS_FOLDER))
++    };
++    commands.insert_resource(source);
++}
++
++/// Waits for every board to load, then opens the setup screen,
++/// or resumes the saved match or replay if one was requested.
++#[allow(clippy::too_many_arguments)]
++pub fn collect_boards(
++    mut commands: Commands,
++    source: Res<BoardSource>,
++    folders: Res<Assets<LoadedFolder>>,
++    boards: Res<Assets<BoardDefinition>>,
++    load_request: Option<Res<LoadRequest>>,
++    replay_request: Option<Res<ReplayRequest>>,
++    connect_request: Option<Res<ConnectRequest>>,
++    host: Option<Res<NetHost>>,
++    mut next_state: ResMut<NextState<AppState>>,
++) {
++    let mut library: Vec<Handle<BoardDefinition>> = match &*source {
++        BoardSource::Folder(folder) => {
++            let Some(loaded) = folders.get(folder) else {
++                return;
++            };
++            loaded
++                .handles
++                .iter()
++                .filter_map(|handle| handle.clone().try_typed().ok())
++                .collect()
++        }
++        BoardSource::Files(files) => files.clone(),
++    };
++    if library.iter().any(|handle| boards.get(handle).is_none()) {
++        return;
++    }
++    // Boards that can't be played are left out rather than failing mid-match.
++    library.retain(|handle| {
++        let Some(board) = boards.get(handle) else {
++            return false;
++        };
++        let problems = board.problems();
++        for problem in &problems {
++            error!("Skipping board \"{}\": {}", board.name, problem);
++        }
++        problems.is_empty()
++    });
++    if library.is_empty() {
++        error!("No boards found in assets/{}", BOARDS_FOLDER);
++        return;
++    }
++    library.sort_by_key(|handle| boards.get(handle).map(|board| board.name.clone()));
++    commands.insert_resource(BoardLibrary(library));
++
++    if let Some(request) = load_request {
++        commands.remove_resource::<LoadRequest>();
++        match load_game(&request.0) {
++            Ok((mut game, market, deck, rng)) => {
++                info!(
++                    "Resuming \"{}\" from {}",
++                    game.board_name,
++                    request.0.display()
++                );
++                game.pending_events.push(GameEvent::Loaded {
++                    path: request.0.display().to_string(),
++                });
++                commands.insert_resource(rng);
++                commands.insert_resource(market);
++                commands.insert_resource(deck);
++                commands.insert_resource(game);
++                next_state.set(AppState::InGame);
++                return;
++            }
++            Err(err) => error!("Could not load {}: {}", request.0.display(), err),
++        }
++    }
++    if let Some(request) = replay_request {
++        commands.remove_resource::<ReplayRequest>();
++        match Replay::read(&request.0).and_then(Replay::restore) {
++            Ok((game, market, deck, playback)) => {
++                info!(
++                    "Replaying {} turns on \"{}\"",
++                    playback.turns.len(),
++                    game.board_name
++                );
++                commands.insert_resource(market);
++                commands.insert_resource(deck);
++                commands.insert_resource(game);
++                commands.insert_resource(playback);
++                next_state.set(AppState::Replay);
++                return;
++            }
++            Err(err) => error!("Could not load {}: {}", request.0.display(), err),
++        }
++    }
++    if let Some(request) = connect_request {
++        commands.remove_resource::<ConnectRequest>();
++        match join_host(&request) {
++            Ok(client) => {
++                info!("Joined {}", client.address);
++                commands.insert_resource(client);
++                next_state.set(AppState::Joining);
++                return;
++            }
++            Err(err) => error!("Could not connect to {}: {}", request.address, err),
++        }
++    }
++    // A host goes straight to its lobby.
++    next_state.set(if host.is_some() {
++        AppState::Setup
++    } else {
++        AppState::Title
++    });
++}
++
++/// Up/Down pick a setting, Left/Right change it, Enter builds the match. On
++/// a seat row Tab switches between human and bot and typing renames it. On
++/// the profile row Tab opens the profile screen, and typing renames the
++/// active profile or, with none picked, starts a new one.
++#[allow(clippy::too_many_arguments)]
++pub fn setup_screen_input(
++    mut commands: Commands,
++    actions: Res<ActionInput>,
++    mut typed: EventReader<ReceivedCharacter>,
++    mut settings: ResMut<MatchSettings>,
++    mut profiles: ResMut<Profiles>,
++    library: Res<BoardLibrary>,
++    boards: Res<Assets<BoardDefinition>>,
++    host: Option<Res<NetHost>>,
++    mut rules: ResMut<GameRules>,
++    mut next_state: ResMut<NextState<AppState>>,
++) {
++    let rows = settings.rows();
++    if actions.just_pressed(Action::Up) {
++        settings.focus = (settings.focus + rows - 1) % rows;
++    }
++    if actions.just_pressed(Action::Down) {
++        settings.focus = (settings.focus + 1) % rows;
++    }
++    let delta: isize = if actions.just_pressed(Action::Right) {
++        1
++    } else if actions.just_pressed(Action::Left) {
++        -1
++    } else {
++        0
++    };
++    if delta != 0 {
++        let step =
++            |value: usize, len: usize| (value as isize + delta).rem_euclid(len as isize) as usize;
++        match settings.focus {
++            0 => settings.board = step(settings.board, library.0.len()),
++            1 => settings.target = step(settings.target, TARGET_NET_WORTHS.len()),
++            2 => {
++                let count = step(
++                    settings.seats.len() - MIN_PLAYERS,
++                    MAX_PLAYERS - MIN_PLAYERS + 1,
++                ) + MIN_PLAYERS;
++                // Seats remote players have taken stay on the roster.
++                let taken = host.as_ref().is_some_and(|host| {
++                    (count..settings.seats.len()).any(|seat| host.client_at(seat).is_some())
++                });
++                if !taken {
++                    while settings.seats.len() < count {
++                        settings.add_seat(PlayerKind::Bot);
++                    }
++                    settings.seats.truncate(count);
++                    settings.renamed.truncate(count);
++                    settings.remote.truncate(count);
++                    settings.refresh_default_names();
++                }
++            }
++            3 => {
++                let pacing = BOT_PACINGS
++                    .iter()
++                    .position(|pacing| *pacing == settings.pacing)
++                    .unwrap_or(1);
++                settings.pacing = BOT_PACINGS[step(pacing, BOT_PACINGS.len())];
++            }
++            4 => {
++                let timer = DECISION_TIMERS
++                    .iter()
++                    .position(|timer| *timer == settings.decision_timer)
++                    .unwrap_or(0);
++                settings.decision_timer = DECISION_TIMERS[step(timer, DECISION_TIMERS.len())];
++            }
++            5 => {
++                let difficulty = DIFFICULTIES
++                    .iter()
++                    .position(|difficulty| *difficulty == rules.difficulty)
++                    .unwrap_or(1);
++                rules.apply_preset(DIFFICULTIES[step(difficulty, DIFFICULTIES.len())]);
++            }
++            SETUP_PROFILE_ROW => profiles.cycle(delta),
++            SETUP_TUTORIAL_ROW => settings.tutorial = !settings.tutorial,
++            row => settings.cycle_character(row - SETUP_SEAT_ROW, delta),
++        }
++    }
++
++    if let Some(seat) = settings.focus.checked_sub(SETUP_SEAT_ROW) {
++        let settings = &mut *settings;
++        // Tab goes from human through each bot strategy and back. When
++        // hosting it also opens the seat to remote players, and seats they
++        // have taken are left alone.
++        if actions.just_pressed(Action::Cycle)
++            && host
++                .as_ref()
++                .is_none_or(|host| host.client_at(seat).is_none())
++        {
++            let hosting = host.is_some();
++            let next_strategy = BOT_STRATEGIES
++                .iter()
++                .position(|strategy| *strategy == settings.seats[seat].strategy)
++                .and_then(|idx| BOT_STRATEGIES.get(idx + 1).copied());
++            let roster = &mut settings.seats[seat];
++            match (roster.kind, settings.remote[seat]) {
++                (PlayerKind::Human, false) => {
++                    roster.kind = PlayerKind::Bot;
++                    roster.strategy = BOT_STRATEGIES[0];
++                }
++                (PlayerKind::Bot, _) => match next_strategy {
++                    Some(strategy) => roster.strategy = strategy,
++                    None => {
++                        roster.kind = PlayerKind::Human;
++                        settings.remote[seat] = hosting;
++                    }
++                },
++                (PlayerKind::Human, true) => settings.remote[seat] = false,
++            }
++            settings.refresh_default_names();
++        }
++        if settings.remote[seat] {
++            typed.clear();
++        }
++        // The first key typed over a default name replaces it.
++        let edit = |settings: &mut MatchSettings| {
++            if !std::mem::replace(&mut settings.renamed[seat], true) {
++                settings.seats[seat].name.clear();
++            }
++        };
++        if actions.just_pressed(Action::Erase) && !settings.remote[seat] {
++            edit(settings);
++            settings.seats[seat].name.pop();
++        }
++        for event in typed.read() {
++            for character in event.char.chars().filter(|c| !c.is_control()) {
++                edit(settings);
++                if settings.seats[seat].name.chars().count() < MAX_NAME_LENGTH {
++                    settings.seats[seat].name.push(character);
++                }
++            }
++        }
++    } else if settings.focus == SETUP_PROFILE_ROW {
++        if actions.just_pressed(Action::Cycle) {
++            settings.viewing_profiles = !settings.viewing_profiles;
++        }
++        if actions.just_pressed(Action::Erase)
++            && let Some(active) = profiles.active
++        {
++            profiles.profiles[active].name.pop();
++        }
++        for event in typed.read() {
++            for character in event.char.chars().filter(|c| !c.is_control()) {
++                let active = match profiles.active {
++                    Some(active) => active,
++                    None => {
++                        profiles.profiles.push(Profile::new(String::new()));
++                        profiles.profiles.len() - 1
++                    }
++                };
++                profiles.active = Some(active);
++                let name = &mut profiles.profiles[active].name;
++                if name.chars().count() < MAX_NAME_LENGTH {
++                    name.push(character);
++                }
++            }
++        }
++    } else {
++        typed.clear();
++    }
++    if actions.just_pressed(Action::Back) && !settings.viewing_profiles && host.is_none() {
++        next_state.set(AppState::Title);
++        return;
++    }
++    if settings.focus != SETUP_PROFILE_ROW || actions.just_pressed(Action::Back) {
++        settings.viewing_profiles = false;
++    }
++
++    // Typing a name on a player or profile row takes the editor's key too.
++    if actions.just_pressed(Action::Editor)
++        && host.is_none()
++        && settings.focus < SETUP_PROFILE_ROW
++        && let Some(definition) = boards.get(&library.0[settings.board])
++    {
++        commands.insert_resource(BoardEditor::new(definition));
++        next_state.set(AppState::Editor);
++        return;
++    }
++
++    if !actions.just_pressed(Action::Confirm) {
++        return;
++    }
++    let Some(definition) = boards.get(&library.0[settings.board]) else {
++        return;
++    };
++    if host.as_ref().is_some_and(|host| !host.all_ready()) {
++        return;
++    }
++    for idx in 0..settings.seats.len() {
++        if settings.seats[idx].name.trim().is_empty() {
++            settings.renamed[idx] = false;
++        }
++    }
++    // Bots take the open seats nobody has joined.
++    for seat in 0..settings.seats.len() {
++        if seat_status(&settings, host.as_deref(), seat) == SeatStatus::Open {
++            settings.seats[seat].kind = PlayerKind::Bot;
++        }
++    }
++    settings.refresh_default_names();
++    info!("Starting on board \"{}\"", definition.name);
++    let mut game = Game::new(
++        definition,
++        &settings.seats,
++        TARGET_NET_WORTHS[settings.target],
++        rules.clone(),
++    );
++    // The active profile plays the first human seat at this machine, and the
++    // tutorial teaches it.
++    let profile = profiles
++        .active_profile()
++        .map(|profile| profile.name.clone())
++        .filter(|name| !name.trim().is_empty());
++    let local_seat = (0..settings.seats.len())
++        .find(|seat| settings.seats[*seat].kind == PlayerKind::Human && !settings.remote[*seat]);
++    if let Some(seat) = local_seat {
++        game.players[seat].profile = profile;
++    }
++    profiles.save();
++    match local_seat.filter(|_| settings.tutorial) {
++        Some(seat) => commands.insert_resource(Tutorial::new(seat)),
++        None => commands.remove_resource::<Tutorial>(),
++    }
++    let mut rng = MatchRng::from_entropy();
++    let opening = roll_for_order(game.players.len(), &mut rng);
++    game.set_turn_order(opening.order.clone());
++    commands.insert_resource(OpeningCeremony::new(opening));
++    commands.insert_resource(rng);
++    commands.insert_resource(StockMarket::new(&game));
++    commands.insert_resource(VentureDeck::for_game(&game));
++    commands.insert_resource(game);
++    next_state.set(AppState::InGame);
++}
++
++/// Phases of a single player's turn. Each phase hands off to the next through
++/// `NextState<TurnPhase>`, so decisions can pause the flow and new phases can be
++/// slotted in without touching the others.
++#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
++pub enum TurnPhase {
++    #[default]
++    RollDice,
++    Moving,
++    ResolveTile,
++    ShopDecision,
++    Auction,
++    StockPhase,
++    VentureCard,
++    Arcade,
++    Casino,
++    Warping,
++    EndTurn,
++}
++
++/// Seconds a token takes per tile at normal animation speed.
++pub const MOVE_STEP_SECONDS: f32 = 0.25;
++
++/// Remaining steps of the active player's roll.
++#[derive(Resource)]
++pub struct MoveState {
++    pub steps_remaining: usize,
++    pub step_timer: Timer,
++    /// The human is standing on a junction and must pick an exit.
++    pub awaiting_direction: bool,
++    /// The human reached the bank and is asked whether to play Suit Yourself cards.
++    pub awaiting_suit_cards: bool,
++}
++
++impl Default for MoveState {
++    fn default() -> Self {
++        Self {
++            steps_remaining: 0,
++            step_timer: Timer::from_seconds(MOVE_STEP_SECONDS, TimerMode::Repeating),
++            awaiting_direction: false,
++            awaiting_suit_cards: false,
++        }
++    }
++}
++
++pub fn setup_board(mut commands: Commands, game: Res<Game>) {
++    spawn_board(&mut commands, &game);
++}
++
++/// Spawns the tile sprites, with labels and ownership markers, a token for
++/// every player still in the game, and the minimap.
++pub fn spawn_board(commands: &mut Commands, game: &Game) {
++    for tile in &game.board {
++        let color = tile_color(game, tile.index);
++        let label = game.tile_label(tile.index);
++
++        commands
++            .spawn(SpriteBundle {
++                sprite: Sprite {
++                    color,
++                    custom_size: Some(Vec2::splat(TILE_SIZE)),
++                    ..Default::default()
++                },
++                transform: Transform::from_translation(tile.position.extend(0.0)),
++                ..Default::default()
++            })
++            .insert(TileEntity(tile.index))
++            .with_children(|parent| {
++                parent.spawn((
++                    Text2dBundle {
++                        text: Text::from_section(
++                            label.clone(),
++                            TextStyle {
++                                font_size: 14.0,
++                                color: Color::WHITE,
++                                ..Default::default()
++                            },
++                        ),
++                        transform: Transform::from_xyz(0.0, 0.0, 1.0),
++                        ..Default::default()
++                    },
++                    TileLabel(tile.index),
++                ));
++                if let Some(owner) = game.owner_of(tile.index) {
++                    parent.spawn(ownership_marker(player_color(game, owner)));
++                }
++            });
++    }
++
++    for (idx, player) in game.players.iter().enumerate() {
++        if player.bankrupt {
++            continue;
++        }
++        let position = game.board[player.position].position;
++        commands
++            .spawn(SpriteBundle {
++                sprite: Sprite {
++                    color: player_color(game, idx),
++                    custom_size: Some(Vec2::splat(20.0)),
++                    ..Default::default()
++                },
++                transform: Transform::from_translation(position.extend(2.0)),
++                ..Default::default()
++            })
++            .insert((PlayerToken(idx), TokenOffset::default()));
++    }
++    spawn_minimap(commands, game);
++}
++
++/// Tile sprites, player tokens, the minimap and the district map labels,
++/// which are drawn from the match state.
++#[allow(clippy::type_complexity)]
++pub type BoardEntities<'w, 's> = Query<
++    'w,
++    's,
++    Entity,
++    Or<(
++        With<TileEntity>,
++        With<PlayerToken>,
++        With<MinimapBackdrop>,
++        With<MinimapTile>,
++        With<MinimapToken>,
++        With<DistrictMapLabel>,
++    )>,
++>;
++
++/// Redraws the board and sidebar for `game` and clears turn-local UI state,
++/// after a load or an undo has replaced the match wholesale.
++pub fn rebuild_match_view(
++    commands: &mut Commands,
++    board_entities: &BoardEntities,
++    panels: &Query<Entity, With<PlayerPanels>>,
++    asset_server: &AssetServer,
++    tracker: &mut CashTracker,
++    game: &Game,
++) {
++    for entity in board_entities.iter() {
++        commands.entity(entity).despawn_recursive();
++    }
++    spawn_board(commands, game);
++    if let Ok(container) = panels.get_single() {
++        build_player_panels(commands, container, asset_server, game, tracker);
++    }
++    commands.insert_resource(UiState::default());
++    commands.insert_resource(TradeDesk::default());
++    commands.insert_resource(MoveState::default());
++    commands.insert_resource(SelectedTile::default());
++}
++
++/// Saves the match to `QUICKSAVE_PATH`, which can only be done at the start
++/// of a turn, and says how it went.
++pub fn quick_save(
++    game: &Game,
++    market: &StockMarket,
++    deck: &VentureDeck,
++    rng: &MatchRng,
++    phase: TurnPhase,
++) -> GameEvent {
++    if phase != TurnPhase::RollDice {
++        return GameEvent::SaveFailed {
++            reason: "wait for the start of a turn".to_string(),
++        };
++    }
++    match SaveFile::capture(game, market, deck)
++        .with_rng(rng)
++        .write(Path::new(QUICKSAVE_PATH))
++    {
++        Ok(()) => GameEvent::Saved {
++            path: QUICKSAVE_PATH.to_string(),
++        },
++        Err(err) => GameEvent::SaveFailed {
++            reason: err.to_string(),
++        },
++    }
++}
++
++/// F5 saves the match to `QUICKSAVE_PATH` at the start of a turn; F9 loads
++/// it back at the start of a turn or during the stock phase, rebuilding the
++/// board and sidebar for the restored players.
++#[allow(clippy::too_many_arguments)]
++pub fn quick_save_load(
++    mut commands: Commands,
++    actions: Res<ActionInput>,
++    phase: Res<State<TurnPhase>>,
++    mut game: ResMut<Game>,
++    market: Res<StockMarket>,
++    deck: Res<VentureDeck>,
++    rng: Res<MatchRng>,
++    board_entities: BoardEntities,
++    panels: Query<Entity, With<PlayerPanels>>,
++    asset_server: Res<AssetServer>,
++    mut tracker: ResMut<CashTracker>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++) {
++    let path = Path::new(QUICKSAVE_PATH);
++    if actions.just_pressed(Action::QuickSave) {
++        let event = quick_save(&game, &market, &deck, &rng, *phase.get());
++        game.pending_events.push(event);
++        return;
++    }
++    if !actions.just_pressed(Action::QuickLoad)
++        || !matches!(phase.get(), TurnPhase::RollDice | TurnPhase::StockPhase)
++    {
++        return;
++    }
++    let (mut loaded, market, deck, rng) = match load_game(path) {
++        Ok(resources) => resources,
++        Err(err) => {
++            game.pending_events.push(GameEvent::SaveFailed {
++                reason: err.to_string(),
++            });
++            return;
++        }
++    };
++    loaded.pending_events.push(GameEvent::Loaded {
++        path: QUICKSAVE_PATH.to_string(),
++    });
++    rebuild_match_view(
++        &mut commands,
++        &board_entities,
++        &panels,
++        &asset_server,
++        &mut tracker,
++        &loaded,
++    );
++    commands.insert_resource(ReplayRecorder::start(&loaded, &market, &deck, &rng));
++    commands.insert_resource(UndoHistory::start(&loaded, &market, &deck, &rng));
++    commands.insert_resource(ActionLog::default());
++    commands.insert_resource(market);
++    commands.insert_resource(deck);
++    commands.insert_resource(rng);
++    *game = loaded;
++    next_phase.set(TurnPhase::RollDice);
++}
++
++/// Turn-start snapshots kept for undo.
++pub const UNDO_DEPTH: usize = 8;
++
++/// Snapshots of the match as each recent turn began, with the RNG as it
++/// stood, newest last. The newest is the turn about to be played, so undo
++/// restores the one before it.
++#[derive(Resource, Default)]
++pub struct UndoHistory(pub VecDeque<(MatchState, MatchRng)>);
++
++impl UndoHistory {
++    pub fn start(game: &Game, market: &StockMarket, deck: &VentureDeck, rng: &MatchRng) -> Self {
++        let mut history = Self::default();
++        history.record(game, market, deck, rng);
++        history
++    }
++
++    pub fn record(
++        &mut self,
++        game: &Game,
++        market: &StockMarket,
++        deck: &VentureDeck,
++        rng: &MatchRng,
++    ) {
++        self.0
++            .push_back((MatchState::capture(game, market, deck), rng.clone()));
++        if self.0.len() > UNDO_DEPTH {
++            self.0.pop_front();
++        }
++    }
++
++    /// The start of the turn that just ended, if a human played it.
++    pub fn undoable(&self) -> Option<&(MatchState, MatchRng)> {
++        let previous = self.0.iter().nth_back(1)?;
++        let (state, _) = previous;
++        let player = state.players.get(state.current_turn)?;
++        let decider = player.decider.unwrap_or(player.kind.decider());
++        (decider == Decider::Person).then_some(previous)
++    }
++}
++
++/// An action taken through the `ActionLog`, and by whom.
++#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
++pub struct LoggedAction {
++    pub player: usize,
++    pub action: TurnAction,
++}
++
++/// Every action the systems have applied to the match since it started or
++/// was loaded, oldest first. Entries are only ever appended, except that
++/// undo drops the turn it takes back. The replay recorder copies each turn's
++/// actions out as the turn ends.
++#[derive(Resource, Default)]
++pub struct ActionLog(pub Vec<LoggedAction>);
++
++impl ActionLog {
++    /// Applies `action` for the active player through `engine::apply`,
++    /// recording it unless the rules refuse it.
++    pub fn apply(
++        &mut self,
++        action: TurnAction,
++        game: &mut Game,
++        market: &mut StockMarket,
++        rng: &mut MatchRng,
++    ) -> Result<Vec<GameEvent>, &'static str> {
++        let player = game.current_turn;
++        let events = engine::apply(action.clone(), game, market, rng)?;
++        self.0.push(LoggedAction { player, action });
++        Ok(events)
++    }
++
++    /// Drops the actions of the last finished turn.
++    pub fn undo_turn(&mut self) {
++        let is_end = |logged: &LoggedAction| logged.action == TurnAction::EndTurn;
++        if let Some(end) = self.0.iter().rposition(is_end) {
++            let start = self.0[..end]
++                .iter()
++                .rposition(is_end)
++                .map_or(0, |idx| idx + 1);
++            self.0.truncate(start);
++        }
++    }
++}
++
++pub fn start_undo_history(
++    mut commands: Commands,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    deck: Res<VentureDeck>,
++    rng: Res<MatchRng>,
++) {
++    commands.insert_resource(UndoHistory::start(&game, &market, &deck, &rng));
++}
++
++pub fn start_action_log(mut commands: Commands) {
++    commands.insert_resource(ActionLog::default());
++}
++
++/// Snapshots the turn that is about to begin.
++pub fn record_undo_point(
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    deck: Res<VentureDeck>,
++    rng: Res<MatchRng>,
++    mut history: ResMut<UndoHistory>,
++) {
++    history.record(&game, &market, &deck, &rng);
++}
++
++/// U takes back the turn a human just played, as long as the next player has
++/// not rolled yet: dice already tumbling count as rolled. The RNG goes back
++/// with the rest of the match, so the turn rolls the same dice again, and the
++/// undone turn is dropped from the action log and the replay.
++#[allow(clippy::too_many_arguments)]
++pub fn undo_turn(
++    mut commands: Commands,
++    actions: Res<ActionInput>,
++    dice: Query<(), With<Die>>,
++    mut history: ResMut<UndoHistory>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut recorder: ResMut<ReplayRecorder>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut deck: ResMut<VentureDeck>,
++    board_entities: BoardEntities,
++    panels: Query<Entity, With<PlayerPanels>>,
++    asset_server: Res<AssetServer>,
++    mut tracker: ResMut<CashTracker>,
++    mut timer: ResMut<TurnTimer>,
++) {
++    if !actions.just_pressed(Action::Undo) || !dice.is_empty() {
++        return;
++    }
++    let Some((state, turn_rng)) = history.undoable().cloned() else {
++        return;
++    };
++    if let Err(err) = state.apply(&mut game, &mut market, &mut deck) {
++        error!("Could not undo the last turn: {}", err);
++        return;
++    }
++    *rng = turn_rng;
++    history.0.pop_back();
++    log.undo_turn();
++    recorder.replay.turns.pop();
++    recorder.events.clear();
++    recorder.logged = log.0.len();
++    let player = game.current_turn;
++    game.pending_events.push(GameEvent::UndidTurn { player });
++    rebuild_match_view(
++        &mut commands,
++        &board_entities,
++        &panels,
++        &asset_server,
++        &mut tracker,
++        &game,
++    );
++    timer.0.reset();
++}
++
++/// Q puts the human whose turn it is on autopilot, so their brain plays for
++/// them while they are away, or takes them off it. The swap waits for the
++/// start of their next turn unless they haven't rolled yet.
++pub fn toggle_autopilot(
++    actions: Res<ActionInput>,
++    phase: Res<State<TurnPhase>>,
++    mut game: ResMut<Game>,
++    mut timer: ResMut<TurnTimer>,
++) {
++    if !actions.just_pressed(Action::Autopilot) || game.winner.is_some() {
++        return;
++    }
++    let current = game.current_turn;
++    let player = &mut game.players[current];
++    if player.kind != PlayerKind::Human {
++        return;
++    }
++    player.autopilot = !player.autopilot;
++    if *phase.get() == TurnPhase::RollDice {
++        player.settle_decider();
++        timer.0.reset();
++    }
++}
++
++/// Sets the bots' thinking delay from the chosen pacing and runs the game
++/// clock at the pacing's speed on bot turns, times `FAST_FORWARD_SPEED` while
++/// the fast-forward key is held. Auctions keep to the pacing's normal clock
++/// unless fast-forwarded, so humans still get time to bid.
++pub fn pace_bots(
++    actions: Res<ActionInput>,
++    settings: Res<MatchSettings>,
++    game: Res<Game>,
++    phase: Res<State<TurnPhase>>,
++    mut timer: ResMut<TurnTimer>,
++    mut time: ResMut<Time<Virtual>>,
++) {
++    let thinking = Duration::from_secs_f32(settings.pacing.thinking_seconds());
++    if timer.0.duration() != thinking {
++        timer.0.set_duration(thinking);
++    }
++    let mut speed = 1.0;
++    if game.players[game.current_turn].decider == Decider::Brain {
++        if *phase.get() != TurnPhase::Auction {
++            speed = settings.pacing.speed();
++        }
++        if actions.pressed(Action::FastForward) {
++            speed *= FAST_FORWARD_SPEED;
++        }
++    }
++    if time.relative_speed() != speed {
++        time.set_relative_speed(speed);
++    }
++}
++
++/// Puts the game clock back to real time once the match is over.
++pub fn reset_game_speed(mut time: ResMut<Time<Virtual>>) {
++    time.set_relative_speed(1.0);
++    time.unpause();
++}
++
++/// Rolls the die for whoever is active: bots after the `TurnTimer` thinking
++/// delay, humans when they press Space or click the Roll button. The faces
++/// are rolled through the `ActionLog` and announced once the dice settle.
++#[allow(clippy::too_many_arguments)]
++pub fn roll_dice(
++    time: Res<Time>,
++    actions: Res<ActionInput>,
++    roll_buttons: Query<&Interaction, (Changed<Interaction>, With<RollButton>)>,
++    mut timer: ResMut<TurnTimer>,
++    mut commands: Commands,
++    dice: Query<(), With<Die>>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    if game.players.is_empty() || !dice.is_empty() {
++        return;
++    }
++
++    let wants_roll = match game.players[game.current_turn].decider {
++        Decider::Brain => timer.0.tick(time.delta()).just_finished(),
++        Decider::Person => {
++            actions.just_pressed(Action::Roll)
++                || roll_buttons
++                    .iter()
++                    .any(|interaction| *interaction == Interaction::Pressed)
++        }
++    };
++    if !wants_roll {
++        return;
++    }
++
++    let current = game.current_turn;
++    let origin = game.board[game.players[current].position].position;
++    let mut faces = Vec::new();
++    for event in log
++        .apply(TurnAction::Roll, &mut game, &mut market, &mut rng)
++        .unwrap_or_default()
++    {
++        match event {
++            GameEvent::Rolled { faces: rolled, .. } => faces = rolled,
++            event => {
++                game_events.send(event);
++            }
++        }
++    }
++    let count = faces.len();
++    for (slot, face) in faces.into_iter().enumerate() {
++        let offset = (slot as f32 - (count - 1) as f32 / 2.0) * 34.0;
++        spawn_die(
++            &mut commands,
++            current,
++            slot,
++            face,
++            origin + Vec2::new(offset, TILE_SIZE),
++        );
++    }
++}
++
++/// A die tumbling above the roller's token. Once `tumble` finishes it settles
++/// on `value` and lingers briefly so everyone can read it; when every die of
++/// the roll has settled, `DiceRolled` is sent.
++#[derive(Component)]
++pub struct Die {
++    pub player: usize,
++    /// Position of this die within the roll, left to right.
++    pub slot: usize,
++    pub value: usize,
++    pub tumble: Timer,
++    pub face_flip: Timer,
++    pub linger: Timer,
++}
++
++#[derive(Component)]
++pub struct DieFace;
++
++/// Sent when all dice of a roll settle; starts the roller's move.
++#[derive(Event, Debug, Clone)]
++pub struct DiceRolled {
++    pub player: usize,
++    /// Each die's face, left to right.
++    pub faces: Vec<usize>,
++    /// Sum of the faces.
++    pub value: usize,
++}
++
++pub fn spawn_die(
++    commands: &mut Commands,
++    player: usize,
++    slot: usize,
++    value: usize,
++    position: Vec2,
++) {
++    commands
++        .spawn((
++            SpriteBundle {
++                sprite: Sprite {
++                    color: Color::WHITE,
++                    custom_size: Some(Vec2::splat(28.0)),
++                    ..Default::default()
++                },
++                transform: Transform::from_translation(position.extend(5.0)),
++                ..Default::default()
++            },
++            Die {
++                player,
++                slot,
++                value,
++                tumble: Timer::from_seconds(1.0, TimerMode::Once),
++                face_flip: Timer::from_seconds(0.08, TimerMode::Repeating),
++                linger: Timer::from_seconds(1.0, TimerMode::Once),
++            },
++        ))
++        .with_children(|die| {
++            die.spawn((
++                Text2dBundle {
++                    text: Text::from_section(
++                        "?",
++                        TextStyle {
++                            font_size: 22.0,
++                            color: Color::BLACK,
++                            ..Default::default()
++                        },
++                    ),
++                    transform: Transform::from_xyz(0.0, 0.0, 1.0),
++                    ..Default::default()
++                },
++                DieFace,
++            ));
++        });
++}
++
++pub fn animate_dice(
++    time: Res<Time>,
++    settings: Res<Settings>,
++    mut commands: Commands,
++    mut dice: Query<(Entity, &mut Die, &mut Transform, &Children)>,
++    mut faces: Query<&mut Text, With<DieFace>>,
++    mut rolled_events: EventWriter<DiceRolled>,
++) {
++    let delta = time.delta().mul_f32(settings.animation_speed);
++    let mut settled = false;
++    for (entity, mut die, mut transform, children) in dice.iter_mut() {
++        if !die.tumble.finished() {
++            die.tumble.tick(delta);
++            let face = if die.tumble.just_finished() {
++                transform.rotation = Quat::IDENTITY;
++                settled = true;
++                Some(die.value)
++            } else {
++                transform.rotate_z(delta.as_secs_f32() * 12.0);
++                die.face_flip
++                    .tick(delta)
++                    .just_finished()
++                    .then(|| rand::thread_rng().gen_range(1..=6))
++            };
++            if let Some(face) = face {
++                for child in children.iter() {
++                    if let Ok(mut text) = faces.get_mut(*child) {
++                        text.sections[0].value = face.to_string();
++                    }
++                }
++            }
++        } else if die.linger.tick(delta).finished() {
++            commands.entity(entity).despawn_recursive();
++        }
++    }
++
++    if !settled || dice.iter().any(|(_, die, ..)| !die.tumble.finished()) {
++        return;
++    }
++    let mut rolled: Vec<&Die> = dice.iter().map(|(_, die, ..)| die).collect();
++    rolled.sort_by_key(|die| die.slot);
++    if let Some(first) = rolled.first() {
++        let faces: Vec<usize> = rolled.iter().map(|die| die.value).collect();
++        rolled_events.send(DiceRolled {
++            player: first.player,
++            value: faces.iter().sum(),
++            faces,
++        });
++    }
++}
++
++/// Hands a settled roll to the movement phase.
++pub fn start_move(
++    mut rolled_events: EventReader<DiceRolled>,
++    mut move_state: ResMut<MoveState>,
++    mut ui_state: ResMut<UiState>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    for event in rolled_events.read() {
++        game_events.send(GameEvent::Rolled {
++            player: event.player,
++            faces: event.faces.clone(),
++        });
++        ui_state
++            .last_rolls
++            .insert(event.player, event.faces.clone());
++        move_state.steps_remaining = event.value;
++        move_state.step_timer.reset();
++        next_phase.set(TurnPhase::Moving);
++    }
++}
++
++/// Walks the active player one tile per `MoveState::step_timer` tick, paced
++/// by the animation speed setting, until the roll is used up. Each tile
++/// walked over on the way applies its passing effect.
++#[allow(clippy::too_many_arguments)]
++pub fn move_player(
++    time: Res<Time>,
++    settings: Res<Settings>,
++    actions: Res<ActionInput>,
++    mut move_state: ResMut<MoveState>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    let current = game.current_turn;
++    let here = game.players[current].position;
++    let step = Duration::from_secs_f32(MOVE_STEP_SECONDS / settings.animation_speed);
++    if move_state.step_timer.duration() != step {
++        move_state.step_timer.set_duration(step);
++    }
++    let action = if move_state.awaiting_suit_cards {
++        let play = actions.just_pressed(Action::Yes);
++        if !play && !actions.just_pressed(Action::No) {
++            return;
++        }
++        move_state.awaiting_suit_cards = false;
++        TurnAction::SuitCards {
++            play,
++            remaining: move_state.steps_remaining,
++        }
++    } else if move_state.awaiting_direction {
++        let Some(exit) = exit_for_arrow(&actions, here, &game) else {
++            return;
++        };
++        move_state.awaiting_direction = false;
++        move_state.steps_remaining -= 1;
++        TurnAction::Step {
++            to: exit,
++            remaining: move_state.steps_remaining,
++        }
++    } else if move_state.steps_remaining > 0
++        && move_state.step_timer.tick(time.delta()).just_finished()
++    {
++        let exits = game.board[here].next.clone();
++        let exit = match (exits.len(), game.players[current].decider) {
++            (1, _) => exits[0],
++            (_, Decider::Brain) => {
++                bot_branch(current, &exits, move_state.steps_remaining, &game, &market)
++            }
++            (_, Decider::Person) => {
++                move_state.awaiting_direction = true;
++                return;
++            }
++        };
++        move_state.steps_remaining -= 1;
++        TurnAction::Step {
++            to: exit,
++            remaining: move_state.steps_remaining,
++        }
++    } else if move_state.steps_remaining == 0 {
++        next_phase.set(TurnPhase::ResolveTile);
++        return;
++    } else {
++        return;
++    };
++
++    game_events.send_batch(
++        log.apply(action.clone(), &mut game, &mut market, &mut rng)
++            .unwrap_or_default(),
++    );
++    if let TurnAction::Step { to, .. } = action
++        && game.bank_index() == Some(to)
++        && awaits_suit_cards(current, &game)
++    {
++        move_state.awaiting_suit_cards = true;
++        return;
++    }
++    if move_state.steps_remaining == 0 {
++        next_phase.set(TurnPhase::ResolveTile);
++    }
++}
++
++#[allow(clippy::too_many_arguments)]
++pub fn resolve_tile(
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut bankrupt_events: EventWriter<PlayerBankrupt>,
++    mut deck: ResMut<VentureDeck>,
++    mut warp: ResMut<WarpState>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    let current = game.current_turn;
++    let tile_index = game.players[current].position;
++    let tile_kind = game.board[tile_index].kind.clone();
++    let warped = std::mem::take(&mut warp.arrived);
++    let interactive_phase = match tile_kind {
++        TileKind::Chance => {
++            let card = deck.draw();
++            let title = card.name(&game).to_string();
++            game.pending_events.push(GameEvent::DrewVenture {
++                player: current,
++                title,
++            });
++            deck.active = Some(card);
++            Some(TurnPhase::VentureCard)
++        }
++        TileKind::Arcade => Some(TurnPhase::Arcade),
++        TileKind::Casino => Some(TurnPhase::Casino),
++        TileKind::Warp { .. } if !warped => Some(TurnPhase::Warping),
++        _ => None,
++    };
++    if let Some(phase) = interactive_phase {
++        next_phase.set(phase);
++        return;
++    }
++    game_events.send_batch(
++        log.apply(TurnAction::Land, &mut game, &mut market, &mut rng)
++            .unwrap_or_default(),
++    );
++
++    if settle_debts(
++        &mut log,
++        &mut game,
++        &mut market,
++        &mut rng,
++        &mut game_events,
++        &mut bankrupt_events,
++    ) {
++        game.pending_purchase = None;
++        next_phase.set(TurnPhase::EndTurn);
++        return;
++    }
++
++    next_phase.set(if game.pending_purchase.is_some() {
++        TurnPhase::ShopDecision
++    } else if game.declined_shop.is_some() {
++        TurnPhase::Auction
++    } else {
++        TurnPhase::StockPhase
++    });
++}
++
++/// Waits for the human to answer the purchase prompt with Y or N.
++#[allow(clippy::too_many_arguments)]
++pub fn shop_decision(
++    actions: Res<ActionInput>,
++    buttons: Query<(&Interaction, &DialogButton), Changed<Interaction>>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    if game.pending_purchase.is_none() {
++        next_phase.set(TurnPhase::StockPhase);
++        return;
++    }
++    let clicked = |wanted: DialogButton| {
++        buttons
++            .iter()
++            .any(|(interaction, button)| *interaction == Interaction::Pressed && *button == wanted)
++    };
++    let (action, phase) = if actions.just_pressed(Action::Yes) || clicked(DialogButton::Yes) {
++        (TurnAction::BuyShop, TurnPhase::StockPhase)
++    } else if actions.just_pressed(Action::No) || clicked(DialogButton::No) {
++        (TurnAction::DeclineShop, TurnPhase::Auction)
++    } else {
++        return;
++    };
++    game_events.send_batch(
++        log.apply(action, &mut game, &mut market, &mut rng)
++            .unwrap_or_default(),
++    );
++    next_phase.set(phase);
++}
++
++/// Bots play their stock phase at once, unless they put a trade to a human,
++/// who answers it through `answer_bot_offer`; humans trade through
++/// `stock_trading` until they end the turn.
++#[allow(clippy::too_many_arguments)]
++pub fn stock_phase(
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut desk: ResMut<TradeDesk>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    desk.clear();
++    let current = game.current_turn;
++    if game.players[current].decider == Decider::Brain {
++        let offer = bot_propose_trade(current, &mut game, &market);
++        if let Some(offer) = &offer
++            && let Some(accepts) = bot_accepts_trade(offer, &game, &market)
++        {
++            let traded = accepts
++                && log
++                    .apply(
++                        TurnAction::Trade(offer.clone()),
++                        &mut game,
++                        &mut market,
++                        &mut rng,
++                    )
++                    .map(|events| game_events.send_batch(events))
++                    .is_ok();
++            if !traded {
++                game.pending_events.push(GameEvent::RefusedTrade {
++                    from: offer.from,
++                    to: offer.to,
++                });
++            }
++        } else if let Some(offer) = offer {
++            desk.message = format!(
++                "{}, {} offers {} for {}. Accept? (Y/N)",
++                game.players[offer.to].name,
++                game.players[current].name,
++                offer.give.describe(&game),
++                offer.take.describe(&game)
++            );
++            desk.counter = Some(offer);
++            return;
++        }
++        bot_stock_phase(current, &mut game, &mut market);
++        next_phase.set(TurnPhase::EndTurn);
++    }
++}
++
++/// Y/N from the human a bot put a trade to; the bot then finishes its stock
++/// phase.
++#[allow(clippy::too_many_arguments)]
++pub fn answer_bot_offer(
++    actions: Res<ActionInput>,
++    mut desk: ResMut<TradeDesk>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    let current = game.current_turn;
++    if game.players[current].decider != Decider::Brain {
++        return;
++    }
++    let Some(offer) = desk.counter.clone() else {
++        return;
++    };
++    let accepted = if actions.just_pressed(Action::Yes) {
++        log.apply(
++            TurnAction::Trade(offer.clone()),
++            &mut game,
++            &mut market,
++            &mut rng,
++        )
++        .map(|events| game_events.send_batch(events))
++        .is_ok()
++    } else if actions.just_pressed(Action::No) {
++        false
++    } else {
++        return;
++    };
++    if !accepted {
++        game.pending_events.push(GameEvent::RefusedTrade {
++            from: offer.from,
++            to: offer.to,
++        });
++    }
++    desk.clear();
++    bot_stock_phase(current, &mut game, &mut market);
++    next_phase.set(TurnPhase::EndTurn);
++}
++
++/// Main menu controls for the human during the stock phase: [ and ] pick one
++/// of their shops, as do previous/next row while no other panel is open, and
++/// X sells it back to the bank. The menu's buttons do the same, and its
++/// Invest button puts `INVEST_STEP` into the shop.
++#[allow(clippy::too_many_arguments)]
++pub fn sell_shop_controls(
++    actions: Res<ActionInput>,
++    buttons: MenuClicks,
++    mut ui_state: ResMut<UiState>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    let current = game.current_turn;
++    if !ui_state.menu_open || game.players[current].decider != Decider::Person {
++        return;
++    }
++    let shops = owned_shops(current, &game);
++    if shops.is_empty() {
++        return;
++    }
++    let rows = !ui_state.stocks_open && !ui_state.trade_open;
++    if actions.just_pressed(Action::PrevShop)
++        || (rows && actions.just_pressed(Action::PrevRow))
++        || menu_clicked(&buttons, MenuButton::PrevShop)
++    {
++        ui_state.sell_cursor = ui_state.sell_cursor.saturating_sub(1);
++    }
++    if actions.just_pressed(Action::NextShop)
++        || (rows && actions.just_pressed(Action::NextRow))
++        || menu_clicked(&buttons, MenuButton::NextShop)
++    {
++        ui_state.sell_cursor += 1;
++    }
++    ui_state.sell_cursor = ui_state.sell_cursor.min(shops.len() - 1);
++    if menu_clicked(&buttons, MenuButton::Invest) {
++        let tile_index = shops[ui_state.sell_cursor];
++        let action = TurnAction::Invest { tile: tile_index };
++        ui_state.menu_message = match log.apply(action, &mut game, &mut market, &mut rng) {
++            Ok(events) => {
++                game_events.send_batch(events);
++                format!(
++                    "{} now holds {} of capital",
++                    game.tile_label(tile_index),
++                    Money(game.board[tile_index].capital)
++                )
++            }
++            Err(err) => err.to_string(),
++        };
++    }
++    if actions.just_pressed(Action::SellShop) || menu_clicked(&buttons, MenuButton::SellShop) {
++        let tile_index = shops[ui_state.sell_cursor];
++        let cash_before = game.players[current].cash;
++        let action = TurnAction::SellShop { tile: tile_index };
++        ui_state.menu_message = match log.apply(action, &mut game, &mut market, &mut rng) {
++            Ok(events) => {
++                game_events.send_batch(events);
++                format!(
++                    "Sold {} to the bank for {}",
++                    game.tile_label(tile_index),
++                    game.players[current].cash - cash_before
++                )
++            }
++            Err(err) => err.to_string(),
++        };
++    }
++}
++
++/// Main menu loan controls for the human during the stock phase: L borrows
++/// `LOAN_STEP` from the bank and P repays the same amount, as do the menu's
++/// Borrow and Repay buttons.
++#[allow(clippy::too_many_arguments)]
++pub fn loan_controls(
++    actions: Res<ActionInput>,
++    buttons: MenuClicks,
++    mut ui_state: ResMut<UiState>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    let current = game.current_turn;
++    if !ui_state.menu_open || game.players[current].decider != Decider::Person {
++        return;
++    }
++    let cash_before = game.players[current].cash;
++    if actions.just_pressed(Action::Borrow) || menu_clicked(&buttons, MenuButton::Borrow) {
++        game_events.send_batch(
++            log.apply(
++                TurnAction::Borrow(LOAN_STEP),
++                &mut game,
++                &mut market,
++                &mut rng,
++            )
++            .unwrap_or_default(),
++        );
++        let lent = game.players[current].cash - cash_before;
++        ui_state.menu_message = if lent > 0 {
++            format!("Borrowed {} from the bank", lent)
++        } else if cash_before >= LOAN_CASH_CEILING {
++            format!(
++                "The bank only lends below {} cash",
++                Money(LOAN_CASH_CEILING)
++            )
++        } else {
++            "The bank won't lend you any more".to_string()
++        };
++    } else if actions.just_pressed(Action::Repay) || menu_clicked(&buttons, MenuButton::Repay) {
++        game_events.send_batch(
++            log.apply(
++                TurnAction::Repay(LOAN_STEP),
++                &mut game,
++                &mut market,
++                &mut rng,
++            )
++            .unwrap_or_default(),
++        );
++        let repaid = cash_before - game.players[current].cash;
++        ui_state.menu_message = if repaid > 0 {
++            format!("Repaid {} of your loan", repaid)
++        } else {
++            "Nothing to repay".to_string()
++        };
++    }
++}
++
++/// Stock panel controls for the human during the stock phase: digits (or
++/// previous/next row) pick a district, +/- size the order in blocks of `MIN_SHARE_ORDER`, Enter (or
++/// the panel's Buy/Sell stock buttons) places it and Space (or the menu's End
++/// turn button) ends the turn.
++#[allow(clippy::too_many_arguments)]
++pub fn stock_trading(
++    actions: Res<ActionInput>,
++    buttons: MenuClicks,
++    mut ui_state: ResMut<UiState>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    if actions.just_pressed(Action::Roll) || menu_clicked(&buttons, MenuButton::EndTurn) {
++        ui_state.stock_order = 0;
++        ui_state.stock_message.clear();
++        next_phase.set(TurnPhase::EndTurn);
++        return;
++    }
++    if !ui_state.stocks_open {
++        return;
++    }
++
++    let districts = game.district_ids();
++    if let Some(idx) = actions.just_picked(districts.len()) {
++        ui_state.stock_cursor = idx;
++        ui_state.stock_order = 0;
++    }
++    if !districts.is_empty() {
++        let step = if actions.just_pressed(Action::PrevRow) {
++            districts.len() - 1
++        } else {
++            usize::from(actions.just_pressed(Action::NextRow))
++        };
++        if step != 0 {
++            ui_state.stock_cursor = (ui_state.stock_cursor + step) % districts.len();
++            ui_state.stock_order = 0;
++        }
++    }
++    if actions.just_pressed(Action::Increase) {
++        ui_state.stock_order += MIN_SHARE_ORDER;
++    }
++    if actions.just_pressed(Action::Decrease) {
++        ui_state.stock_order -= MIN_SHARE_ORDER;
++    }
++    // The Buy/Sell stock buttons place the order's size, at least one block,
++    // whichever way they say.
++    let side = if menu_clicked(&buttons, MenuButton::BuyStock) {
++        Some(1)
++    } else if menu_clicked(&buttons, MenuButton::SellStock) {
++        Some(-1)
++    } else {
++        None
++    };
++    if let Some(side) = side {
++        ui_state.stock_order = side * ui_state.stock_order.abs().max(MIN_SHARE_ORDER);
++    }
++    let placed = actions.just_pressed(Action::Confirm) || side.is_some();
++    if !placed || ui_state.stock_order == 0 {
++        return;
++    }
++
++    let Some(&district) = districts.get(ui_state.stock_cursor) else {
++        return;
++    };
++    let current = game.current_turn;
++    let order = ui_state.stock_order;
++    let cash_before = game.players[current].cash;
++    let result = if order > 0 {
++        let action = TurnAction::BuyStock {
++            district,
++            shares: order,
++        };
++        log.apply(action, &mut game, &mut market, &mut rng)
++            .map(|events| {
++                game_events.send_batch(events);
++                format!(
++                    "Bought {} {} shares for {}",
++                    order,
++                    game.district_name(district),
++                    cash_before - game.players[current].cash
++                )
++            })
++    } else {
++        let action = TurnAction::SellStock {
++            district,
++            shares: -order,
++        };
++        log.apply(action, &mut game, &mut market, &mut rng)
++            .map(|events| {
++                game_events.send_batch(events);
++                let short = game.players[current]
++                    .stocks
++                    .get(&district)
++                    .is_some_and(|held| *held < 0);
++                format!(
++                    "Sold {} {} shares{} for {}",
++                    -order,
++                    game.district_name(district),
++                    if short { " short" } else { "" },
++                    game.players[current].cash - cash_before
++                )
++            })
++    };
++    ui_state.stock_message = result.unwrap_or_else(|err| err.to_string());
++    ui_state.stock_order = 0;
++}
++
++/// A row of the trade panel.
++#[derive(Debug, Clone, Copy)]
++pub enum TradeLine {
++    Cash,
++    Stock(DistrictId),
++    Shop(usize),
++}
++
++/// The human's trade draft. Cash and share amounts are signed: positive hands
++/// them to the partner, negative asks for them.
++#[derive(Resource, Default)]
++pub struct TradeDesk {
++    pub partner: Option<usize>,
++    pub cursor: usize,
++    pub cash: i32,
++    pub stocks: HashMap<DistrictId, i32>,
++    pub shops: HashSet<usize>,
++    /// Offer waiting on Y/N: a bot's counter-offer for the active player, the
++    /// active player's offer to a human partner, or a bot's offer to a human.
++    pub counter: Option<TradeProposal>,
++    pub message: String,
++}
++
++impl TradeDesk {
++    pub fn clear(&mut self) {
++        *self = Self {
++            partner: self.partner,
++            ..Default::default()
++        };
++    }
++
++    pub fn lines(&self, game: &Game) -> Vec<TradeLine> {
++        let mut lines = vec![TradeLine::Cash];
++        lines.extend(game.district_ids().into_iter().map(TradeLine::Stock));
++        let Some(partner) = self.partner else {
++            return lines;
++        };
++        let mut shops: Vec<usize> = game.players[game.current_turn]
++            .properties
++            .union(&game.players[partner].properties)
++            .copied()
++            .collect();
++        shops.sort_unstable();
++        lines.extend(shops.into_iter().map(TradeLine::Shop));
++        lines
++    }
++
++    pub fn proposal(&self, game: &Game) -> Option<TradeProposal> {
++        let from = game.current_turn;
++        let to = self.partner?;
++        let mut give = TradeOffer::default();
++        let mut take = TradeOffer::default();
++        if self.cash > 0 {
++            give.cash = self.cash;
++        } else {
++            take.cash = -self.cash;
++        }
++        for (district, shares) in &self.stocks {
++            match shares.signum() {
++                1 => give.stocks.insert(*district, *shares),
++                -1 => take.stocks.insert(*district, -shares),
++                _ => None,
++            };
++        }
++        for index in &self.shops {
++            if game.players[from].properties.contains(index) {
++                give.shops.push(*index);
++            } else if game.players[to].properties.contains(index) {
++                take.shops.push(*index);
++            }
++        }
++        give.shops.sort_unstable();
++        take.shops.sort_unstable();
++        Some(TradeProposal {
++            from,
++            to,
++            give,
++            take,
++        })
++    }
++}
++
++/// Trade panel controls for the human during the stock phase: Tab picks the
++/// partner, , and . move between rows, +/- adjust the row, Enter sends the
++/// offer and Y/N answer a counter-offer, or a human partner's offer in
++/// hot-seat play.
++#[allow(clippy::too_many_arguments)]
++pub fn trade_controls(
++    actions: Res<ActionInput>,
++    ui_state: Res<UiState>,
++    mut desk: ResMut<TradeDesk>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    if !ui_state.trade_open || game.players[game.current_turn].decider != Decider::Person {
++        return;
++    }
++
++    if let Some(counter) = desk.counter.clone() {
++        // A human partner answers the offer itself; otherwise it is a bot's
++        // counter-offer for the active player to answer.
++        let partner = &game.players[counter.to];
++        let (accepted, declined) = match partner.decider {
++            Decider::Person => (
++                format!("{} accepted the trade", partner.name),
++                format!("{} rejected the offer", partner.name),
++            ),
++            Decider::Brain => (
++                "Counter-offer accepted, trade done".to_string(),
++                "Counter-offer declined".to_string(),
++            ),
++        };
++        if actions.just_pressed(Action::Yes) {
++            let result = log.apply(TurnAction::Trade(counter), &mut game, &mut market, &mut rng);
++            desk.clear();
++            desk.message = match result {
++                Ok(events) => {
++                    game_events.send_batch(events);
++                    accepted
++                }
++                Err(err) => err.to_string(),
++            };
++        } else if actions.just_pressed(Action::No) {
++            desk.counter = None;
++            desk.message = declined;
++        }
++        return;
++    }
++
++    let current = game.current_turn;
++    if actions.just_pressed(Action::Cycle) || desk.partner.is_none() {
++        let start = desk.partner.unwrap_or(current);
++        desk.clear();
++        desk.partner = (1..game.players.len())
++            .map(|offset| (start + offset) % game.players.len())
++            .find(|idx| *idx != current && !game.players[*idx].bankrupt);
++    }
++    let Some(partner) = desk.partner else {
++        return;
++    };
++    if actions.just_pressed(Action::Erase) {
++        desk.clear();
++    }
++
++    let lines = desk.lines(&game);
++    if actions.just_pressed(Action::PrevRow) {
++        desk.cursor = desk.cursor.saturating_sub(1);
++    }
++    if actions.just_pressed(Action::NextRow) {
++        desk.cursor = (desk.cursor + 1).min(lines.len() - 1);
++    }
++    desk.cursor = desk.cursor.min(lines.len() - 1);
++
++    let step = if actions.just_pressed(Action::Increase) {
++        1
++    } else if actions.just_pressed(Action::Decrease) {
++        -1
++    } else {
++        0
++    };
++    if step != 0 {
++        match lines[desk.cursor] {
++            TradeLine::Cash => desk.cash += step * TRADE_CASH_STEP,
++            TradeLine::Stock(district) => {
++                let shares = desk.stocks.entry(district).or_default();
++                *shares += step * MIN_SHARE_ORDER;
++                if *shares == 0 {
++                    desk.stocks.remove(&district);
++                }
++            }
++            TradeLine::Shop(index) if step > 0 => {
++                desk.shops.insert(index);
++            }
++            TradeLine::Shop(index) => {
++                desk.shops.remove(&index);
++            }
++        }
++    }
++
++    if !actions.just_pressed(Action::Confirm) {
++        return;
++    }
++    let Some(proposal) = desk.proposal(&game) else {
++        return;
++    };
++    if !can_hand_over(current, &proposal.give, &game) {
++        desk.message = "You don't hold everything in your offer".to_string();
++        return;
++    }
++    let name = game.players[partner].name.clone();
++    let Some(brain) = game.players[partner].brain() else {
++        desk.message = format!(
++            "{}, accept {}'s offer? (Y/N)",
++            name, game.players[current].name
++        );
++        desk.counter = Some(proposal);
++        return;
++    };
++    match brain.evaluate_trade(&proposal, &game, &market) {
++        TradeResponse::Accept => {
++            let result = log.apply(
++                TurnAction::Trade(proposal),
++                &mut game,
++                &mut market,
++                &mut rng,
++            );
++            desk.clear();
++            desk.message = match result {
++                Ok(events) => {
++                    game_events.send_batch(events);
++                    format!("{} accepted the trade", name)
++                }
++                Err(err) => err.to_string(),
++            };
++        }
++        TradeResponse::Reject => desk.message = format!("{} rejected the offer", name),
++        TradeResponse::Counter(counter) => {
++            desk.message = format!(
++                "{} wants {} more. Accept? (Y/N)",
++                name,
++                Money(counter.give.cash - proposal.give.cash)
++            );
++            desk.counter = Some(counter);
++        }
++    }
++}
++
++#[allow(clippy::too_many_arguments)]
++pub fn end_turn(
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut timer: ResMut<TurnTimer>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut next_state: ResMut<NextState<AppState>>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    game_events.send_batch(
++        log.apply(TurnAction::EndTurn, &mut game, &mut market, &mut rng)
++            .unwrap_or_default(),
++    );
++    if game.winner.is_some() {
++        next_state.set(AppState::GameOver);
++        return;
++    }
++    timer.0.reset();
++    next_phase.set(TurnPhase::RollDice);
++}
++
++/// Warp animation: the token shrinks away, reappears on the destination tile
++/// and grows back, then the destination tile resolves.
++#[derive(Resource)]
++pub struct WarpState {
++    pub timer: Timer,
++    pub teleported: bool,
++    /// The player just arrived by warp, so a warp tile there must not fire again.
++    pub arrived: bool,
++}
++
++impl Default for WarpState {
++    fn default() -> Self {
++        Self {
++            timer: Timer::from_seconds(0.6, TimerMode::Once),
++            teleported: false,
++            arrived: false,
++        }
++    }
++}
++
++#[allow(clippy::too_many_arguments)]
++pub fn warp_player(
++    time: Res<Time>,
++    mut warp: ResMut<WarpState>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut tokens: Query<(&mut Transform, &PlayerToken, &TokenOffset)>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    let current = game.current_turn;
++    let here = game.players[current].position;
++    warp.timer.tick(time.delta());
++    let progress = warp.timer.fraction();
++    if progress >= 0.5
++        && !warp.teleported
++        && let TileKind::Warp { destination } = game.board[here].kind
++    {
++        warp.teleported = true;
++        let action = TurnAction::Teleport {
++            player: current,
++            tile: destination,
++        };
++        game_events.send_batch(
++            log.apply(action, &mut game, &mut market, &mut rng)
++                .unwrap_or_default(),
++        );
++        let target = game.board[destination].position;
++        for (mut transform, token, offset) in tokens.iter_mut() {
++            if token.0 == current {
++                transform.translation = (target + offset.0).extend(2.0);
++            }
++        }
++    }
++    // Shrinks to nothing at the midpoint and grows back by the end.
++    let scale = ((progress - 0.5).abs() * 2.0).max(0.05);
++    for (mut transform, token, _) in tokens.iter_mut() {
++        if token.0 == current {
++            transform.scale = Vec3::splat(if warp.timer.finished() { 1.0 } else { scale });
++        }
++    }
++    if warp.timer.finished() {
++        warp.timer.reset();
++        warp.teleported = false;
++        warp.arrived = true;
++        next_phase.set(TurnPhase::ResolveTile);
++    }
++}
++
++/// Board direction of each arrow action.
++pub const ARROWS: [(Action, Vec2); 4] = [
++    (Action::Up, Vec2::Y),
++    (Action::Down, Vec2::NEG_Y),
++    (Action::Left, Vec2::NEG_X),
++    (Action::Right, Vec2::X),
++];
++
++/// Board direction `action` points in, if it is an arrow.
++pub fn arrow_direction(action: Action) -> Option<Vec2> {
++    ARROWS
++        .into_iter()
++        .find(|(arrow, _)| *arrow == action)
++        .map(|(_, direction)| direction)
++}
++
++/// Exit from `from` whose direction best matches the direction pressed, if
++/// any.
++pub fn exit_for_arrow(actions: &ActionInput, from: usize, game: &Game) -> Option<usize> {
++    let (_, wanted) = ARROWS
++        .into_iter()
++        .find(|(action, _)| actions.just_pressed(*action))?;
++    exit_toward(from, wanted, game)
++}
++
++/// Exit from `from` best lined up with `wanted`, if any is within 60 degrees
++/// of it.
++pub fn exit_toward(from: usize, wanted: Vec2, game: &Game) -> Option<usize> {
++    let origin = game.board[from].position;
++    game.board[from]
++        .next
++        .iter()
++        .copied()
++        .map(|exit| {
++            (
++                exit,
++                (game.board[exit].position - origin)
++                    .normalize_or_zero()
++                    .dot(wanted),
++            )
++        })
++        .filter(|(_, alignment)| *alignment > 0.5)
++        .max_by(|a, b| a.1.total_cmp(&b.1))
++        .map(|(exit, _)| exit)
++}
++
++/// Shows the drawn venture card until the human presses Enter/Space, or the
++/// bot's thinking delay runs out, then applies it.
++#[allow(clippy::too_many_arguments)]
++pub fn venture_card(
++    time: Res<Time>,
++    actions: Res<ActionInput>,
++    mut timer: ResMut<TurnTimer>,
++    mut deck: ResMut<VentureDeck>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut move_state: ResMut<MoveState>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut game_events: EventWriter<GameEvent>,
++    mut bankrupt_events: EventWriter<PlayerBankrupt>,
++) {
++    let current = game.current_turn;
++    let confirmed = match game.players[current].decider {
++        Decider::Brain => timer.0.tick(time.delta()).just_finished(),
++        Decider::Person => actions.any_just_pressed([Action::Confirm, Action::Roll]),
++    };
++    if !confirmed {
++        return;
++    }
++    let Some(card) = deck.active.take() else {
++        next_phase.set(TurnPhase::StockPhase);
++        return;
++    };
++
++    let outcome = venture_outcome(card.effect, &game);
++    game_events.send_batch(
++        log.apply(
++            TurnAction::Venture(card.effect),
++            &mut game,
++            &mut market,
++            &mut rng,
++        )
++        .unwrap_or_default(),
++    );
++    if settle_debts(
++        &mut log,
++        &mut game,
++        &mut market,
++        &mut rng,
++        &mut game_events,
++        &mut bankrupt_events,
++    ) {
++        next_phase.set(TurnPhase::EndTurn);
++        return;
++    }
++    timer.0.reset();
++    next_phase.set(match outcome {
++        VentureOutcome::Done => TurnPhase::StockPhase,
++        VentureOutcome::RollAgain => TurnPhase::RollDice,
++        VentureOutcome::Move(steps) => {
++            move_state.steps_remaining = steps;
++            move_state.step_timer.reset();
++            TurnPhase::Moving
++        }
++        VentureOutcome::Resolve => TurnPhase::ResolveTile,
++    });
++}
++
++/// Mini-game being played at an arcade tile. Each game runs in its own state
++/// while `TurnPhase::Arcade` is active; `Closed` the rest of the time.
++#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
++pub enum ArcadeGame {
++    #[default]
++    Closed,
++    DiceDuel,
++    CardMemory,
++    Roulette,
++}
++
++/// Result of the finished mini-game, shown until the human leaves the arcade.
++#[derive(Resource, Default)]
++pub struct ArcadeOutcome(pub Option<String>);
++
++/// Sends humans to a random mini-game. Bots skip the game and take a random
++/// roulette prize.
++#[allow(clippy::too_many_arguments)]
++pub fn enter_arcade(
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut game_events: EventWriter<GameEvent>,
++    mut outcome: ResMut<ArcadeOutcome>,
++    mut next_game: ResMut<NextState<ArcadeGame>>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut panels: Query<&mut Style, With<ArcadePanel>>,
++) {
++    let current = game.current_turn;
++    outcome.0 = None;
++    if game.players[current].decider == Decider::Brain {
++        pay_arcade_prize(
++            bot_arcade_prize(),
++            &mut game,
++            &mut market,
++            &mut log,
++            &mut rng,
++            &mut game_events,
++        );
++        next_phase.set(TurnPhase::StockPhase);
++        return;
++    }
++    let games = [
++        ArcadeGame::DiceDuel,
++        ArcadeGame::CardMemory,
++        ArcadeGame::Roulette,
++    ];
++    next_game.set(
++        *games
++            .choose(&mut rand::thread_rng())
++            .expect("arcade has games"),
++    );
++    for mut style in panels.iter_mut() {
++        style.display = Display::Flex;
++    }
++}
++
++/// Pays `reward` to the active player through the `ActionLog` and says what
++/// they won.
++fn pay_arcade_prize(
++    reward: ArcadeReward,
++    game: &mut Game,
++    market: &mut StockMarket,
++    log: &mut ActionLog,
++    rng: &mut MatchRng,
++    game_events: &mut EventWriter<GameEvent>,
++) -> String {
++    let line = describe_arcade_reward(reward, game.current_turn, game);
++    game_events.send_batch(
++        log.apply(TurnAction::ArcadePrize(reward), game, market, rng)
++            .unwrap_or_default(),
++    );
++    line
++}
++
++/// Once a mini-game has finished, Enter closes the arcade and resumes the turn.
++pub fn leave_arcade(
++    actions: Res<ActionInput>,
++    outcome: Res<ArcadeOutcome>,
++    mut next_game: ResMut<NextState<ArcadeGame>>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++) {
++    if outcome.0.is_some() && actions.just_pressed(Action::Confirm) {
++        next_game.set(ArcadeGame::Closed);
++        next_phase.set(TurnPhase::StockPhase);
++    }
++}
++
++/// Dice duel: the player and the house each roll a die; beating the house
++/// pays 150G and a tie pays 50G.
++#[derive(Resource, Default)]
++pub struct DiceDuel {
++    pub rolls: Option<(usize, usize)>,
++}
++
++pub fn start_dice_duel(mut commands: Commands) {
++    commands.insert_resource(DiceDuel::default());
++}
++
++#[allow(clippy::too_many_arguments)]
++pub fn dice_duel(
++    actions: Res<ActionInput>,
++    mut duel: ResMut<DiceDuel>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut game_events: EventWriter<GameEvent>,
++    mut outcome: ResMut<ArcadeOutcome>,
++    mut texts: Query<&mut Text, With<ArcadeText>>,
++) {
++    if duel.rolls.is_none() && actions.just_pressed(Action::Roll) {
++        let mut dice = rand::thread_rng();
++        let (player, house) = (dice.gen_range(1..=6), dice.gen_range(1..=6));
++        duel.rolls = Some((player, house));
++        let reward = match player.cmp(&house) {
++            std::cmp::Ordering::Greater => ArcadeReward::Cash(150),
++            std::cmp::Ordering::Equal => ArcadeReward::Cash(50),
++            std::cmp::Ordering::Less => ArcadeReward::Nothing,
++        };
++        outcome.0 = Some(pay_arcade_prize(
++            reward,
++            &mut game,
++            &mut market,
++            &mut log,
++            &mut rng,
++            &mut game_events,
++        ));
++    }
++    let body = match (duel.rolls, &outcome.0) {
++        (Some((player, house)), Some(result)) => format!(
++            "You rolled {}, the house rolled {}.\n{}\n\nPress Enter to continue",
++            player, house, result
++        ),
++        _ => "Beat the house's die: win 150G, tie for 50G.\n\nPress Space to roll".to_string(),
++    };
++    set_arcade_text(&mut texts, format!("ARCADE: Dice Duel\n\n{}", body));
++}
++
++/// Card memory: six face-down cards hide three pairs of suits. Each matched
++/// pair awards its suit; the player gets three attempts.
++#[derive(Resource)]
++pub struct CardMemory {
++    pub cards: Vec<Suit>,
++    pub matched: Vec<bool>,
++    /// Cards turned over in the current attempt.
++    pub picks: Vec<usize>,
++    pub attempts_left: u32,
++    pub log: Vec<String>,
++}
++
++pub fn start_card_memory(mut commands: Commands) {
++    let mut rng = rand::thread_rng();
++    let mut suits = SUITS;
++    suits.shuffle(&mut rng);
++    let mut cards: Vec<Suit> = suits[..3].iter().flat_map(|suit| [*suit, *suit]).collect();
++    cards.shuffle(&mut rng);
++    commands.insert_resource(CardMemory {
++        matched: vec![false; cards.len()],
++        cards,
++        picks: Vec::new(),
++        attempts_left: 3,
++        log: Vec::new(),
++    });
++}
++
++#[allow(clippy::too_many_arguments)]
++pub fn card_memory(
++    actions: Res<ActionInput>,
++    mut memory: ResMut<CardMemory>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut game_events: EventWriter<GameEvent>,
++    mut outcome: ResMut<ArcadeOutcome>,
++    mut texts: Query<&mut Text, With<ArcadeText>>,
++) {
++    if outcome.0.is_none() {
++        if memory.picks.len() == 2 {
++            // A mismatched pair stays face up until the next key press.
++            if actions.get_just_pressed().next().is_some() {
++                memory.picks.clear();
++            }
++        } else if let Some(card) = actions.just_picked(memory.cards.len())
++            && !memory.matched[card]
++            && !memory.picks.contains(&card)
++        {
++            memory.picks.push(card);
++            if let [first, second] = memory.picks[..] {
++                memory.attempts_left -= 1;
++                if memory.cards[first] == memory.cards[second] {
++                    memory.matched[first] = true;
++                    memory.matched[second] = true;
++                    memory.picks.clear();
++                    let reward = ArcadeReward::Suit(memory.cards[first]);
++                    let line = pay_arcade_prize(
++                        reward,
++                        &mut game,
++                        &mut market,
++                        &mut log,
++                        &mut rng,
++                        &mut game_events,
++                    );
++                    memory.log.push(line);
++                }
++                if memory.attempts_left == 0 || memory.matched.iter().all(|matched| *matched) {
++                    outcome.0 = Some(if memory.log.is_empty() {
++                        "No pairs found.".to_string()
++                    } else {
++                        memory.log.join("\n")
++                    });
++                }
++            }
++        }
++    }
++
++    let cards: String = memory
++        .cards
++        .iter()
++        .enumerate()
++        .map(|(idx, suit)| {
++            if memory.matched[idx] || memory.picks.contains(&idx) {
++                format!("{}:[{}] ", idx + 1, suit.icon())
++            } else {
++                format!("{}:[?] ", idx + 1)
++            }
++        })
++        .collect();
++    let footer = match &outcome.0 {
++        Some(result) => format!("{}\n\nPress Enter to continue", result),
++        None => format!(
++            "Press 1-6 to turn cards over. Attempts left: {}\n{}",
++            memory.attempts_left,
++            memory.log.join("\n")
++        ),
++    };
++    set_arcade_text(
++        &mut texts,
++        format!("ARCADE: Card Memory\n\n{}\n\n{}", cards, footer),
++    );
++}
++
++/// Roulette: one spin of `ROULETTE_SLOTS`. The wheel ticks through the slots
++/// while `spin` runs, then pays out where it stops.
++#[derive(Resource)]
++pub struct Roulette {
++    pub spin: Option<Timer>,
++    pub tick: Timer,
++    pub slot: usize,
++}
++
++pub fn start_roulette(mut commands: Commands) {
++    commands.insert_resource(Roulette {
++        spin: None,
++        tick: Timer::from_seconds(0.08, TimerMode::Repeating),
++        slot: rand::thread_rng().gen_range(0..ROULETTE_SLOTS.len()),
++    });
++}
++
++#[allow(clippy::too_many_arguments)]
++pub fn roulette(
++    time: Res<Time>,
++    actions: Res<ActionInput>,
++    mut wheel: ResMut<Roulette>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut game_events: EventWriter<GameEvent>,
++    mut outcome: ResMut<ArcadeOutcome>,
++    mut texts: Query<&mut Text, With<ArcadeText>>,
++) {
++    if wheel.spin.is_none() && outcome.0.is_none() && actions.just_pressed(Action::Roll) {
++        let seconds = rand::thread_rng().gen_range(1.5..2.5);
++        wheel.spin = Some(Timer::from_seconds(seconds, TimerMode::Once));
++    }
++    if wheel.spin.is_some() {
++        if wheel.tick.tick(time.delta()).just_finished() {
++            wheel.slot = (wheel.slot + 1) % ROULETTE_SLOTS.len();
++        }
++        let stopped = wheel
++            .spin
++            .as_mut()
++            .is_some_and(|spin| spin.tick(time.delta()).just_finished());
++        if stopped {
++            wheel.spin = None;
++            outcome.0 = Some(pay_arcade_prize(
++                ROULETTE_SLOTS[wheel.slot],
++                &mut game,
++                &mut market,
++                &mut log,
++                &mut rng,
++                &mut game_events,
++            ));
++        }
++    }
++
++    let slots: Vec<String> = ROULETTE_SLOTS
++        .iter()
++        .enumerate()
++        .map(|(idx, reward)| {
++            if idx == wheel.slot {
++                format!("> {} <", describe_slot(*reward))
++            } else {
++                describe_slot(*reward)
++            }
++        })
++        .collect();
++    let footer = match (&outcome.0, &wheel.spin) {
++        (Some(result), _) => format!("{}\n\nPress Enter to continue", result),
++        (None, Some(_)) => "Spinning...".to_string(),
++        (None, None) => "Press Space to spin".to_string(),
++    };
++    set_arcade_text(
++        &mut texts,
++        format!("ARCADE: Roulette\n\n{}\n\n{}", slots.join(" | "), footer),
++    );
++}
++
++/// Bots bet a tenth of their cash on a coin-flip side, and only once they have
++/// a comfortable bankroll; humans get the casino panel.
++#[allow(clippy::too_many_arguments)]
++pub fn enter_casino(
++    mut commands: Commands,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut panels: Query<&mut Style, With<CasinoPanel>>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    let current = game.current_turn;
++    let cash = game.players[current].cash;
++    if game.players[current].decider == Decider::Brain {
++        if let Some(action) = bot_casino_bet(current, &game) {
++            game_events.send_batch(
++                log.apply(action, &mut game, &mut market, &mut rng)
++                    .unwrap_or_default(),
++            );
++        }
++        next_phase.set(TurnPhase::StockPhase);
++        return;
++    }
++    commands.insert_resource(CasinoVisit {
++        stake: CASINO_STAKE_STEP.min(cash.available()),
++        wagered: 0,
++        last_result: String::new(),
++    });
++    for mut style in panels.iter_mut() {
++        style.display = Display::Flex;
++    }
++}
++
++/// Casino controls: +/- size the stake, H or L bets high or low, Enter leaves.
++#[allow(clippy::too_many_arguments)]
++pub fn casino(
++    actions: Res<ActionInput>,
++    visit: Option<ResMut<CasinoVisit>>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut texts: Query<&mut Text, With<CasinoText>>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    let Some(mut visit) = visit else {
++        return;
++    };
++    let current = game.current_turn;
++    if actions.just_pressed(Action::Confirm) {
++        next_phase.set(TurnPhase::StockPhase);
++        return;
++    }
++    if actions.just_pressed(Action::Increase) {
++        visit.stake += CASINO_STAKE_STEP;
++    }
++    if actions.just_pressed(Action::Decrease) {
++        visit.stake -= CASINO_STAKE_STEP;
++    }
++    let max_stake = visit.max_stake(game.players[current].cash);
++    visit.stake = visit.stake.clamp(0, max_stake);
++
++    let bet = if actions.just_pressed(Action::BetHigh) {
++        Some(true)
++    } else if actions.just_pressed(Action::BetLow) {
++        Some(false)
++    } else {
++        None
++    };
++    if let Some(bet_high) = bet
++        && visit.stake > 0
++    {
++        let stake = visit.stake;
++        let action = TurnAction::HighLow { bet_high, stake };
++        let events = log
++            .apply(action, &mut game, &mut market, &mut rng)
++            .unwrap_or_default();
++        if let Some(result) = events
++            .iter()
++            .find(|event| matches!(event, GameEvent::PlayedHighLow { .. }))
++        {
++            visit.last_result = result.describe(&game);
++        }
++        game_events.send_batch(events);
++        visit.wagered += stake;
++        let max_stake = visit.max_stake(game.players[current].cash);
++        visit.stake = visit.stake.min(max_stake);
++    }
++
++    for mut text in texts.iter_mut() {
++        text.sections[0].value = format!(
++            "CASINO: High or Low\nTwo dice: 8-12 is high, 2-6 is low, 7 goes to the house.\nWinning bets pay double.\n\nStake: {} (+/-)\nWagered this visit: {} / {}\nCash: {}\n\n{}\n\nH: bet high  L: bet low  Enter: leave",
++            Money(visit.stake),
++            Money(visit.wagered),
++            Money(CASINO_VISIT_CAP),
++            game.players[current].cash,
++            visit.last_result,
++        );
++    }
++}
++
++pub fn hide_casino_panel(mut commands: Commands, mut panels: Query<&mut Style, With<CasinoPanel>>) {
++    commands.remove_resource::<CasinoVisit>();
++    for mut style in panels.iter_mut() {
++        style.display = Display::None;
++    }
++}
++
++/// Opens bidding on the declined shop at half its price, or skips straight to
++/// the stock phase when nobody can afford the opening bid.
++pub fn start_auction(
++    mut commands: Commands,
++    mut game: ResMut<Game>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut panels: Query<&mut Style, With<AuctionPanel>>,
++) {
++    let Some(tile) = game.declined_shop.take() else {
++        next_phase.set(TurnPhase::StockPhase);
++        return;
++    };
++    let TileKind::Property { price, .. } = game.board[tile].kind else {
++        next_phase.set(TurnPhase::StockPhase);
++        return;
++    };
++    let auction = Auction::open(tile, game.current_turn, price);
++    if !(0..game.players.len()).any(|idx| auction.can_bid(idx, &game)) {
++        next_phase.set(TurnPhase::StockPhase);
++        return;
++    }
++    for mut style in panels.iter_mut() {
++        style.display = Display::Flex;
++    }
++    commands.insert_resource(auction);
++}
++
++/// Runs the bidding: humans raise with B or the Bid button, or with their seat
++/// number when more than one human can bid; bots raise while the shop is under
++/// their ceiling, and the shop is sold when the countdown expires.
++#[allow(clippy::too_many_arguments)]
++pub fn auction(
++    time: Res<Time>,
++    actions: Res<ActionInput>,
++    bid_buttons: Query<&Interaction, (Changed<Interaction>, With<BidButton>)>,
++    mut commands: Commands,
++    auction: Option<ResMut<Auction>>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut next_phase: ResMut<NextState<TurnPhase>>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    let Some(mut auction) = auction else {
++        return;
++    };
++
++    let shared_bid = actions.just_pressed(Action::Bid)
++        || bid_buttons
++            .iter()
++            .any(|interaction| *interaction == Interaction::Pressed);
++    let human_bidders = (0..game.players.len())
++        .filter(|idx| game.players[*idx].decider == Decider::Person && auction.can_bid(*idx, &game))
++        .count();
++    let bots_ready = auction.bot_timer.tick(time.delta()).just_finished();
++    let bidder = (0..game.players.len())
++        .filter(|idx| auction.can_bid(*idx, &game))
++        .find(|idx| match game.players[*idx].brain() {
++            None => (shared_bid && human_bidders == 1) || actions.just_pressed(seat_bid(*idx)),
++            Some(brain) => {
++                bots_ready && brain.bid_ceiling(*idx, auction.tile, &game) >= auction.next_bid()
++            }
++        });
++    if let Some(bidder) = bidder {
++        auction.high_bid = auction.next_bid();
++        auction.leader = Some(bidder);
++        auction.countdown.reset();
++        return;
++    }
++
++    if !auction.countdown.tick(time.delta()).finished() {
++        return;
++    }
++    if let Some(buyer) = auction.leader {
++        let sale = TurnAction::AuctionSale {
++            tile: auction.tile,
++            buyer,
++            bid: auction.high_bid,
++        };
++        game_events.send_batch(
++            log.apply(sale, &mut game, &mut market, &mut rng)
++                .unwrap_or_default(),
++        );
++    }
++    commands.remove_resource::<Auction>();
++    next_phase.set(TurnPhase::StockPhase);
++}
++
++/// Publishes the market's queued price moves as `StockPriceChanged` events.
++pub fn publish_price_changes(
++    mut market: ResMut<StockMarket>,
++    mut price_events: EventWriter<StockPriceChanged>,
++) {
++    if !market.pending_changes.is_empty() {
++        price_events.send_batch(market.pending_changes.drain(..));
++    }
++}
++
++pub fn publish_ownership_changes(
++    mut game: ResMut<Game>,
++    mut ownership_events: EventWriter<OwnershipChanged>,
++) {
++    if !game.pending_ownership.is_empty() {
++        ownership_events.send_batch(game.pending_ownership.drain(..));
++    }
++}
++
++pub fn publish_investment_changes(
++    mut game: ResMut<Game>,
++    mut investment_events: EventWriter<InvestmentChanged>,
++) {
++    if !game.pending_investments.is_empty() {
++        investment_events.send_batch(game.pending_investments.drain(..));
++    }
++}
++
++pub fn publish_game_events(mut game: ResMut<Game>, mut game_events: EventWriter<GameEvent>) {
++    if !game.pending_events.is_empty() {
++        game_events.send_batch(game.pending_events.drain(..));
++    }
++}
++
++pub fn publish_salaries(mut game: ResMut<Game>, mut salary_events: EventWriter<SalaryPaid>) {
++    if !game.pending_salaries.is_empty() {
++        salary_events.send_batch(game.pending_salaries.drain(..));
++    }
++}
@@ -0,0 +1,193 @@
+diff --git a/src/title.rs b/src/title.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..ad5f4e22740f99f6a622f5ddfb5d2a0e73fb70a9
+--- /dev/null
++++ b/src/title.rs
+@@ -0,0 +1,187 @@
++//! The title screen and its main menu: New Game goes on to the setup screen,
++//! Load Game resumes the quick save, Board Editor opens the editor on the
++//! first board, Settings opens the settings menu over the title, and Quit
++//! closes the game. No match state exists until one is started from here.
++
++use bevy::{app::AppExit, prelude::*};
++use std::path::Path;
++
++use crate::*;
++
++#[derive(Debug, Clone, Copy, PartialEq, Eq)]
++pub enum TitleOption {
++    NewGame,
++    LoadGame,
++    BoardEditor,
++    Settings,
++    Quit,
++}
++
++impl TitleOption {
++    /// The option's key in the string tables.
++    pub fn key(self) -> &'static str {
++        match self {
++            TitleOption::NewGame => "title.new_game",
++            TitleOption::LoadGame => "title.load_game",
++            TitleOption::BoardEditor => "title.board_editor",
++            TitleOption::Settings => "title.settings",
++            TitleOption::Quit => "title.quit",
++        }
++    }
++}
++
++/// The main menu's options, top to bottom.
++pub const TITLE_OPTIONS: [TitleOption; 5] = [
++    TitleOption::NewGame,
++    TitleOption::LoadGame,
++    TitleOption::BoardEditor,
++    TitleOption::Settings,
++    TitleOption::Quit,
++];
++
++#[derive(Resource, Default)]
++pub struct TitleMenu {
++    pub cursor: usize,
++    /// Why the last Load Game failed, if it did.
++    pub message: String,
++}
++
++#[derive(Component)]
++pub struct TitleScreen;
++
++#[derive(Component)]
++pub struct TitleText;
++
++pub fn spawn_title_screen(
++    mut commands: Commands,
++    asset_server: Res<AssetServer>,
++    mut menu: ResMut<TitleMenu>,
++) {
++    menu.message.clear();
++    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
++    commands
++        .spawn((
++            NodeBundle {
++                style: Style {
++                    position_type: PositionType::Absolute,
++                    width: Val::Percent(100.0),
++                    height: Val::Percent(100.0),
++                    flex_direction: FlexDirection::Column,
++                    justify_content: JustifyContent::Center,
++                    align_items: AlignItems::Center,
++                    row_gap: Val::Px(32.0),
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(BOARD_COLOR),
++                z_index: ZIndex::Global(10),
++                ..Default::default()
++            },
++            TitleScreen,
++        ))
++        .with_children(|screen| {
++            screen.spawn((
++                TextBundle::from_section(
++                    "",
++                    TextStyle {
++                        font: font.clone(),
++                        font_size: 64.0,
++                        color: BANK_COLOR,
++                    },
++                ),
++                Localized::new(&["title.name"]),
++            ));
++            screen.spawn((
++                TextBundle::from_section(
++                    "",
++                    TextStyle {
++                        font,
++                        font_size: 28.0,
++                        color: Color::WHITE,
++                    },
++                ),
++                TitleText,
++            ));
++        });
++}
++
++pub fn despawn_title_screen(mut commands: Commands, screens: Query<Entity, With<TitleScreen>>) {
++    for entity in screens.iter() {
++        commands.entity(entity).despawn_recursive();
++    }
++}
++
++/// Up/Down pick an option and Enter takes it. The settings menu takes the
++/// keys while it is open over the title.
++#[allow(clippy::too_many_arguments)]
++pub fn title_input(
++    mut commands: Commands,
++    actions: Res<ActionInput>,
++    mut menu: ResMut<TitleMenu>,
++    mut settings_menu: ResMut<SettingsMenu>,
++    settings: Res<MatchSettings>,
++    library: Res<BoardLibrary>,
++    boards: Res<Assets<BoardDefinition>>,
++    mut next_state: ResMut<NextState<AppState>>,
++    mut exit: EventWriter<AppExit>,
++) {
++    if settings_menu.open {
++        return;
++    }
++    if actions.just_pressed(Action::Up) {
++        menu.cursor = (menu.cursor + TITLE_OPTIONS.len() - 1) % TITLE_OPTIONS.len();
++    }
++    if actions.just_pressed(Action::Down) {
++        menu.cursor = (menu.cursor + 1) % TITLE_OPTIONS.len();
++    }
++    if !actions.just_pressed(Action::Confirm) {
++        return;
++    }
++    match TITLE_OPTIONS[menu.cursor] {
++        TitleOption::NewGame => next_state.set(AppState::Setup),
++        TitleOption::LoadGame => match load_game(Path::new(QUICKSAVE_PATH)) {
++            Ok((mut game, market, deck, rng)) => {
++                game.pending_events.push(GameEvent::Loaded {
++                    path: QUICKSAVE_PATH.to_string(),
++                });
++                commands.remove_resource::<Tutorial>();
++                commands.insert_resource(rng);
++                commands.insert_resource(market);
++                commands.insert_resource(deck);
++                commands.insert_resource(game);
++                next_state.set(AppState::InGame);
++            }
++            Err(err) => menu.message = format!("Could not load {}: {}", QUICKSAVE_PATH, err),
++        },
++        TitleOption::BoardEditor => {
++            if let Some(definition) = boards.get(&library.0[settings.board]) {
++                commands.insert_resource(BoardEditor::new(definition));
++                next_state.set(AppState::Editor);
++            }
++        }
++        TitleOption::Settings => settings_menu.open = true,
++        TitleOption::Quit => {
++            exit.send(AppExit);
++        }
++    }
++}
++
++pub fn update_title_screen(
++    menu: Res<TitleMenu>,
++    locale: Res<Locale>,
++    mut texts: Query<&mut Text, With<TitleText>>,
++) {
++    if !menu.is_changed() && !locale.is_changed() {
++        return;
++    }
++    let mut content = String::new();
++    for (idx, option) in TITLE_OPTIONS.iter().enumerate() {
++        let marker = if idx == menu.cursor { "> " } else { "  " };
++        content.push_str(&format!("{}{}\n", marker, locale.get(option.key())));
++    }
++    if !menu.message.is_empty() {
++        content.push_str(&format!("\n{}", menu.message));
++    }
++    for mut text in texts.iter_mut() {
++        text.sections[0].value.clone_from(&content);
++    }
++}
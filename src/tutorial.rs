@@ -0,0 +1,276 @@
+diff --git a/src/tutorial.rs b/src/tutorial.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..aad8c335e691474c7a136ebd27f28825b80aec37
+--- /dev/null
++++ b/src/tutorial.rs
+@@ -0,0 +1,270 @@
++//! Tutorial mode for a first match. A fixed list of steps walks one human
++//! player through rolling, buying shops, suits, the bank and the stock
++//! phase. Each step waits for its trigger, shows a prompt and is done once
++//! its goal is met. While a step waits for Enter, the turn stands still, so
++//! bots never play past a prompt.
++
++use bevy::prelude::*;
++
++use crate::*;
++
++/// What makes a tutorial step show up.
++#[derive(Debug, Clone, Copy, PartialEq, Eq)]
++pub enum TutorialTrigger {
++    /// As soon as the match starts.
++    Start,
++    /// The tutorial player is about to roll.
++    RollTurn,
++    /// The tutorial player is offered a vacant shop.
++    ShopOffer,
++    /// The tutorial player picked up a suit.
++    Suit,
++    /// The tutorial player reached their stock phase.
++    StockPhase,
++    /// The tutorial player paid a fee at a rival's shop.
++    PaidFee,
++    /// A rival paid the tutorial player a fee.
++    EarnedFee,
++    /// The tutorial player came back round to the bank.
++    Lap,
++}
++
++/// What finishes a tutorial step.
++#[derive(Debug, Clone, Copy, PartialEq, Eq)]
++pub enum TutorialGoal {
++    /// Pressing Enter; play waits until then.
++    Continue,
++    /// The tutorial player rolling the dice.
++    Roll,
++    /// The turn moving on from this phase.
++    LeavePhase(TurnPhase),
++}
++
++pub struct TutorialStep {
++    pub trigger: TutorialTrigger,
++    pub goal: TutorialGoal,
++    pub text: &'static str,
++}
++
++pub const TUTORIAL_STEPS: [TutorialStep; 9] = [
++    TutorialStep {
++        trigger: TutorialTrigger::Start,
++        goal: TutorialGoal::Continue,
++        text: "Welcome to Itadaki Street! Buy shops around the board and charge your rivals a fee whenever they land on one.",
++    },
++    TutorialStep {
++        trigger: TutorialTrigger::Start,
++        goal: TutorialGoal::Continue,
++        text: "Your net worth is your cash plus your shops and stocks. Reach the target net worth, then get back to the bank to win.",
++    },
++    TutorialStep {
++        trigger: TutorialTrigger::RollTurn,
++        goal: TutorialGoal::Roll,
++        text: "It's your turn. Press Space or click Roll to roll the dice.",
++    },
++    TutorialStep {
++        trigger: TutorialTrigger::ShopOffer,
++        goal: TutorialGoal::LeavePhase(TurnPhase::ShopDecision),
++        text: "Nobody owns this shop yet. Press Y to buy it, or N to pass and let the other players bid for it at auction.",
++    },
++    TutorialStep {
++        trigger: TutorialTrigger::Suit,
++        goal: TutorialGoal::Continue,
++        text: "You picked up a suit! Collect all four, then pass the bank to level up and collect a salary.",
++    },
++    TutorialStep {
++        trigger: TutorialTrigger::StockPhase,
++        goal: TutorialGoal::LeavePhase(TurnPhase::StockPhase),
++        text: "This is your stock phase. Press S to buy shares in a district, whose price rises as its shops grow, or Space to end your turn.",
++    },
++    TutorialStep {
++        trigger: TutorialTrigger::PaidFee,
++        goal: TutorialGoal::Continue,
++        text: "You landed on a rival's shop and paid its fee. Fees climb when one player owns several shops in a district.",
++    },
++    TutorialStep {
++        trigger: TutorialTrigger::EarnedFee,
++        goal: TutorialGoal::Continue,
++        text: "A rival landed on your shop and paid you its fee!",
++    },
++    TutorialStep {
++        trigger: TutorialTrigger::Lap,
++        goal: TutorialGoal::Continue,
++        text: "You came back round to the bank and collected the lap bonus. Bring all four suits next time for a promotion.",
++    },
++];
++
++/// Progress through the tutorial, present only in tutorial matches.
++#[derive(Resource, Debug, Clone)]
++pub struct Tutorial {
++    /// The human player being taught.
++    pub player: usize,
++    /// Steps whose trigger has happened, by index into `TUTORIAL_STEPS`.
++    pub triggered: Vec<bool>,
++    pub done: Vec<bool>,
++}
++
++impl Tutorial {
++    pub fn new(player: usize) -> Self {
++        Self {
++            player,
++            triggered: vec![false; TUTORIAL_STEPS.len()],
++            done: vec![false; TUTORIAL_STEPS.len()],
++        }
++    }
++
++    /// The step on screen: the first one triggered and not yet done.
++    pub fn current(&self) -> Option<usize> {
++        (0..TUTORIAL_STEPS.len()).find(|step| self.triggered[*step] && !self.done[*step])
++    }
++
++    /// Whether the step on screen holds up play until Enter is pressed.
++    pub fn waiting(&self) -> bool {
++        self.current()
++            .is_some_and(|step| TUTORIAL_STEPS[step].goal == TutorialGoal::Continue)
++    }
++
++    /// Marks the steps `trigger` starts.
++    pub fn trigger(&mut self, trigger: TutorialTrigger) {
++        for (step, triggered) in TUTORIAL_STEPS.iter().zip(self.triggered.iter_mut()) {
++            *triggered |= step.trigger == trigger;
++        }
++    }
++}
++
++#[derive(Component)]
++pub struct TutorialPanel;
++
++#[derive(Component)]
++pub struct TutorialText;
++
++/// Run condition: false while a tutorial prompt is waiting for Enter.
++pub fn tutorial_allows_play(tutorial: Option<Res<Tutorial>>) -> bool {
++    tutorial.is_none_or(|tutorial| !tutorial.waiting())
++}
++
++pub fn spawn_tutorial_panel(mut commands: Commands, asset_server: Res<AssetServer>) {
++    commands
++        .spawn((
++            NodeBundle {
++                style: Style {
++                    position_type: PositionType::Absolute,
++                    left: Val::Percent(32.0),
++                    bottom: Val::Px(12.0),
++                    width: Val::Px(460.0),
++                    display: Display::None,
++                    flex_direction: FlexDirection::Column,
++                    padding: UiRect::all(Val::Px(10.0)),
++                    border: UiRect::all(Val::Px(2.0)),
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(Color::rgba(0.05, 0.05, 0.12, 0.92)),
++                border_color: BorderColor(BANK_COLOR),
++                z_index: ZIndex::Global(5),
++                ..Default::default()
++            },
++            TutorialPanel,
++        ))
++        .with_children(|panel| {
++            panel.spawn((
++                TextBundle::from_section(
++                    "",
++                    TextStyle {
++                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
++                        font_size: 17.0,
++                        color: Color::WHITE,
++                    },
++                ),
++                TutorialText,
++            ));
++        });
++}
++
++/// Enter finishes a prompt that is waiting for it. Until then the prompt
++/// takes every action, as the settings menu does.
++pub fn tutorial_input(mut actions: ResMut<ActionInput>, mut tutorial: ResMut<Tutorial>) {
++    if !tutorial.waiting() {
++        return;
++    }
++    if actions.just_pressed(Action::Confirm)
++        && let Some(step) = tutorial.current()
++    {
++        tutorial.done[step] = true;
++    }
++    actions.reset_all();
++}
++
++/// Triggers and finishes steps from the turn phase and game events, and
++/// shows the current prompt.
++pub fn run_tutorial(
++    mut tutorial: ResMut<Tutorial>,
++    game: Res<Game>,
++    phase: Res<State<TurnPhase>>,
++    mut game_events: EventReader<GameEvent>,
++    mut panels: Query<&mut Style, With<TutorialPanel>>,
++    mut texts: Query<&mut Text, With<TutorialText>>,
++) {
++    let player = tutorial.player;
++    let their_turn = game.current_turn == player;
++    tutorial.trigger(TutorialTrigger::Start);
++    match phase.get() {
++        TurnPhase::RollDice if their_turn => tutorial.trigger(TutorialTrigger::RollTurn),
++        TurnPhase::ShopDecision if their_turn && game.pending_purchase.is_some() => {
++            tutorial.trigger(TutorialTrigger::ShopOffer)
++        }
++        TurnPhase::StockPhase if their_turn => tutorial.trigger(TutorialTrigger::StockPhase),
++        _ => {}
++    }
++    let mut rolled = false;
++    for event in game_events.read() {
++        match event {
++            GameEvent::Rolled { player: roller, .. } if *roller == player => rolled = true,
++            GameEvent::CollectedSuit {
++                player: collector, ..
++            } if *collector == player => tutorial.trigger(TutorialTrigger::Suit),
++            GameEvent::PaidFee { payer, .. } if *payer == player => {
++                tutorial.trigger(TutorialTrigger::PaidFee)
++            }
++            GameEvent::PaidFee { owner, .. } if *owner == player => {
++                tutorial.trigger(TutorialTrigger::EarnedFee)
++            }
++            GameEvent::CompletedLap { player: lapper, .. } if *lapper == player => {
++                tutorial.trigger(TutorialTrigger::Lap)
++            }
++            _ => {}
++        }
++    }
++    // A step whose moment has passed by the time it comes up is skipped.
++    while let Some(step) = tutorial.current() {
++        let finished = match TUTORIAL_STEPS[step].goal {
++            TutorialGoal::Continue => false,
++            TutorialGoal::Roll => rolled,
++            TutorialGoal::LeavePhase(waiting_in) => *phase.get() != waiting_in || !their_turn,
++        };
++        if !finished {
++            break;
++        }
++        tutorial.done[step] = true;
++    }
++
++    let current = tutorial.current();
++    for mut style in panels.iter_mut() {
++        style.display = if current.is_some() {
++            Display::Flex
++        } else {
++            Display::None
++        };
++    }
++    let Some(step) = current else {
++        return;
++    };
++    let mut prompt = TUTORIAL_STEPS[step].text.to_string();
++    if tutorial.waiting() {
++        prompt.push_str("\n\nPress Enter to continue");
++    }
++    for mut text in texts.iter_mut() {
++        if text.sections[0].value != prompt {
++            text.sections[0].value.clone_from(&prompt);
++        }
++    }
++}
@@ -0,0 +1,4428 @@
+diff --git a/src/ui.rs b/src/ui.rs
+new file mode 100644
+index 0000000000000000000000000000000000000000..434a5eca2a625af93a21e7c4623572b7cf1f4d86
+--- /dev/null
++++ b/src/ui.rs
+@@ -0,0 +1,4422 @@
++//! Rendering and interface: the sidebar HUD, panels, dialogs, tooltips, the
++//! setup screen and board token visuals.
++
++use bevy::{
++    input::mouse::MouseWheel,
++    prelude::*,
++    window::{PrimaryWindow, WindowResized},
++};
++use rand::Rng;
++use std::collections::{HashMap, HashSet, VecDeque};
++
++use crate::*;
++
++pub const BOARD_COLOR: Color = Color::rgb(0.15, 0.15, 0.25);
++
++pub const BANK_COLOR: Color = Color::rgb(0.9, 0.8, 0.25);
++
++pub const PROPERTY_COLOR: Color = Color::rgb(0.25, 0.7, 0.45);
++
++pub const SUIT_COLOR: Color = Color::rgb(0.6, 0.25, 0.6);
++
++pub const CHANCE_COLOR: Color = Color::rgb(0.25, 0.55, 0.9);
++
++pub const SUIT_YOURSELF_COLOR: Color = Color::rgb(0.85, 0.45, 0.2);
++
++pub const BREAK_COLOR: Color = Color::rgb(0.45, 0.45, 0.5);
++
++pub const TAX_COLOR: Color = Color::rgb(0.75, 0.2, 0.2);
++
++pub const ARCADE_COLOR: Color = Color::rgb(0.9, 0.35, 0.65);
++
++pub const CASINO_COLOR: Color = Color::rgb(0.8, 0.65, 0.1);
++
++pub const WARP_COLOR: Color = Color::rgb(0.3, 0.85, 0.85);
++
++pub const BOON_COLOR: Color = Color::rgb(0.95, 0.7, 0.55);
++
++pub const TOLL_COLOR: Color = Color::rgb(0.5, 0.35, 0.25);
++
++#[allow(dead_code)]
++#[derive(Component)]
++pub struct TileEntity(pub usize);
++
++#[derive(Component)]
++pub struct PlayerToken(pub usize);
++
++/// Where a token sits relative to its tile's center, so tokens sharing a
++/// tile don't hide one another.
++#[derive(Component, Default)]
++pub struct TokenOffset(pub Vec2);
++
++/// Distance from the tile's center of tokens sharing it.
++pub const TOKEN_RING_RADIUS: f32 = TILE_SIZE * 0.25;
++
++/// Text drawn on a board tile.
++#[derive(Component)]
++pub struct TileLabel(pub usize);
++
++/// Owner-colored flag in the corner of an owned shop tile.
++#[derive(Component)]
++pub struct OwnershipMarker;
++
++#[derive(Resource, Default)]
++pub struct UiState {
++    pub menu_open: bool,
++    pub stocks_open: bool,
++    /// District highlighted in the stock panel.
++    pub stock_cursor: usize,
++    /// Pending order size; positive buys, negative sells.
++    pub stock_order: i32,
++    pub stock_message: String,
++    pub trade_open: bool,
++    pub leaderboard_open: bool,
++    pub portfolio_open: bool,
++    pub district_map_open: bool,
++    /// Shop highlighted in the main menu's sell list.
++    pub sell_cursor: usize,
++    pub menu_message: String,
++    pub last_price_change: HashMap<DistrictId, i32>,
++    /// Each district's most recent prices, oldest first, for the portfolio
++    /// sparklines.
++    pub price_history: HashMap<DistrictId, Vec<i32>>,
++    /// Die faces of each player's most recent roll, by player index.
++    pub last_rolls: HashMap<usize, Vec<usize>>,
++    /// The screen has been touched, so the main buttons are enlarged.
++    pub touch_mode: bool,
++}
++
++#[derive(Component)]
++pub struct SetupScreen;
++
++#[derive(Component)]
++pub struct SetupText;
++
++pub fn spawn_setup_screen(mut commands: Commands, asset_server: Res<AssetServer>) {
++    commands
++        .spawn((
++            NodeBundle {
++                style: Style {
++                    position_type: PositionType::Absolute,
++                    width: Val::Percent(100.0),
++                    height: Val::Percent(100.0),
++                    justify_content: JustifyContent::Center,
++                    align_items: AlignItems::Center,
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(BOARD_COLOR),
++                z_index: ZIndex::Global(10),
++                ..Default::default()
++            },
++            SetupScreen,
++        ))
++        .with_children(|screen| {
++            screen.spawn((
++                TextBundle::from_section(
++                    "",
++                    TextStyle {
++                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
++                        font_size: 24.0,
++                        color: Color::WHITE,
++                    },
++                ),
++                SetupText,
++            ));
++        });
++}
++
++pub fn update_setup_screen(
++    settings: Res<MatchSettings>,
++    profiles: Res<Profiles>,
++    rules: Res<GameRules>,
++    library: Res<BoardLibrary>,
++    boards: Res<Assets<BoardDefinition>>,
++    host: Option<Res<NetHost>>,
++    mut texts: Query<&mut Text, With<SetupText>>,
++) {
++    let board_name = boards
++        .get(&library.0[settings.board])
++        .map_or("?", |board| board.name.as_str());
++    let mut rows = vec![
++        format!("Board: < {} >", board_name),
++        format!(
++            "Target net worth: < {} >",
++            Money(TARGET_NET_WORTHS[settings.target])
++        ),
++        format!("Players: < {} >", settings.seats.len()),
++        format!("Bot pacing: < {} >", settings.pacing.label()),
++        format!("Decision timer: < {} >", settings.decision_timer.label()),
++        format!("Difficulty: < {} >", rules.difficulty.label()),
++        format!(
++            "Profile: < {} >",
++            profiles
++                .active_profile()
++                .map_or("None", |profile| profile.name.as_str())
++        ),
++        format!(
++            "Tutorial: < {} >",
++            if settings.tutorial { "On" } else { "Off" }
++        ),
++    ];
++    let profile_seat = profiles.active.and_then(|_| {
++        (0..settings.seats.len())
++            .find(|seat| settings.seats[*seat].kind == PlayerKind::Human && !settings.remote[*seat])
++    });
++    for (idx, seat) in settings.seats.iter().enumerate() {
++        rows.push(format!(
++            "  {}. {}{} [{}] < {} >",
++            idx + 1,
++            seat.name,
++            if profile_seat == Some(idx) {
++                " (profile)"
++            } else {
++                ""
++            },
++            match seat_status(&settings, host.as_deref(), idx) {
++                SeatStatus::Bot => format!("{} bot", seat.strategy.label()),
++                status => status.label().to_string(),
++            },
++            CHARACTERS[seat.character].name
++        ));
++    }
++    let mut content = String::from("Itadaki Street\n\n");
++    if let Some(host) = &host {
++        content.push_str(&format!("Hosting room {} on port {}", host.code, host.port));
++        match host.spectators() {
++            0 => content.push_str("\n\n"),
++            watching => content.push_str(&format!(", {} watching\n\n", watching)),
++        }
++    }
++    if settings.viewing_profiles {
++        content.push_str("Profiles\n\n");
++        if profiles.profiles.is_empty() {
++            content.push_str("  No profiles yet\n");
++        }
++        for (idx, profile) in profiles.profiles.iter().enumerate() {
++            let marker = if profiles.active == Some(idx) {
++                "> "
++            } else {
++                "  "
++            };
++            content.push_str(&format!("{}{}\n", marker, profile.describe()));
++        }
++        content.push_str("\nLeft/Right pick the active profile, Tab or Escape goes back");
++        for mut text in texts.iter_mut() {
++            text.sections[0].value.clone_from(&content);
++        }
++        return;
++    }
++    for (idx, row) in rows.iter().enumerate() {
++        let marker = if idx == settings.focus { "> " } else { "  " };
++        content.push_str(&format!("{}{}\n", marker, row));
++    }
++    content.push_str("\nUp/Down choose, Left/Right change, Enter to start");
++    if settings.focus == SETUP_PROFILE_ROW {
++        content.push_str(
++            "\nOn the profile: Tab shows every profile's career stats, type to rename it or to start a new one",
++        );
++    }
++    if host.is_none() && settings.focus < SETUP_PROFILE_ROW {
++        content.push_str("\nE edits a copy of the board in the board editor");
++    }
++    if settings.focus >= SETUP_SEAT_ROW {
++        if host.is_some() {
++            content.push_str(
++                "\nOn a player: Tab cycles human, bot strategies and open, type to rename",
++            );
++        } else {
++            content.push_str("\nOn a player: Tab cycles human and bot strategies, type to rename");
++        }
++    }
++    let changes = rules.house.changes();
++    if !changes.is_empty() {
++        content.push_str(&format!("\nHouse rules: {}", changes.join(", ")));
++    }
++    if host.as_ref().is_some_and(|host| !host.all_ready()) {
++        content.push_str("\nWaiting for remote players to ready up");
++    }
++    for mut text in texts.iter_mut() {
++        text.sections[0].value.clone_from(&content);
++    }
++}
++
++pub fn despawn_setup_screen(mut commands: Commands, screens: Query<Entity, With<SetupScreen>>) {
++    for entity in screens.iter() {
++        commands.entity(entity).despawn_recursive();
++    }
++}
++
++/// The camera looking at the board, which the UI is drawn over.
++#[derive(Component)]
++pub struct MainCamera;
++
++pub fn setup_camera(mut commands: Commands) {
++    commands.spawn((
++        Camera2dBundle {
++            transform: Transform::from_xyz(0.0, 0.0, 999.0),
++            projection: OrthographicProjection {
++                scale: 1.0,
++                ..Default::default()
++            },
++            ..Default::default()
++        },
++        MainCamera,
++        bevy::ui::IsDefaultUiCamera,
++    ));
++}
++
++pub fn character_color(character: usize) -> Color {
++    CHARACTERS[character % CHARACTERS.len()].color
++}
++
++/// Fill color of tile `index`; shops take their district's color.
++pub fn tile_color(game: &Game, index: usize) -> Color {
++    match game.board[index].kind {
++        TileKind::Bank => BANK_COLOR,
++        TileKind::Property { district, .. } => game.districts[district].color,
++        TileKind::Suit(_) => SUIT_COLOR,
++        TileKind::SuitYourself => SUIT_YOURSELF_COLOR,
++        TileKind::Chance => CHANCE_COLOR,
++        TileKind::TakeABreak => BREAK_COLOR,
++        TileKind::TaxOffice => TAX_COLOR,
++        TileKind::Arcade => ARCADE_COLOR,
++        TileKind::Casino => CASINO_COLOR,
++        TileKind::Warp { .. } => WARP_COLOR,
++        TileKind::Boon => BOON_COLOR,
++        TileKind::TollGate { .. } => TOLL_COLOR,
++    }
++}
++
++/// The color of the character the player picked on the setup screen.
++pub fn player_color(game: &Game, player: usize) -> Color {
++    character_color(game.players[player].character)
++}
++
++#[derive(Component)]
++pub struct UiRoot;
++
++#[derive(Component)]
++pub struct InfoText;
++
++/// Sidebar container holding one `PlayerPanelText` block per player.
++#[derive(Component)]
++pub struct PlayerPanels;
++
++#[derive(Component)]
++pub struct PlayerPanelText(pub usize);
++
++/// Floating "+120G" / "-80G" label beside a player's panel, shown for
++/// `CASH_DELTA_SECONDS` after their cash changes.
++#[derive(Component)]
++pub struct CashDelta {
++    pub player: usize,
++    pub timer: Timer,
++}
++
++/// One of the four suit icons under a player's portrait. It pulses when the
++/// suit is picked up or spent.
++#[derive(Component)]
++pub struct SuitIcon {
++    pub player: usize,
++    pub suit: Suit,
++    pub pulse: Timer,
++}
++
++pub const SUIT_ICON_SIZE: f32 = 13.0;
++
++/// Color of a suit icon while the player is missing that suit.
++pub const SUIT_MISSING_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.2);
++
++pub const SUIT_PULSE_COLOR: Color = Color::rgb(1.0, 0.85, 0.3);
++
++pub const SUIT_PULSE_SECONDS: f32 = 0.8;
++
++/// A bit of glitter flying out from a suit pickup or promotion.
++#[derive(Component)]
++pub struct Sparkle {
++    pub velocity: Vec2,
++    pub timer: Timer,
++}
++
++pub const SPARKLE_SECONDS: f32 = 0.7;
++
++/// Sparkles in one burst.
++pub const SPARKLE_COUNT: usize = 14;
++
++/// A timer that starts out finished, for effects that wait to be triggered.
++pub fn finished_timer(seconds: f32) -> Timer {
++    let mut timer = Timer::from_seconds(seconds, TimerMode::Once);
++    timer.tick(timer.duration());
++    timer
++}
++
++/// Floating "+120G" / "-80G" text over a player's token, rising and fading
++/// for `CASH_DELTA_SECONDS` after their cash changes.
++#[derive(Component)]
++pub struct CashPopup(pub Timer);
++
++/// How far a cash popup rises over its life, in world units.
++pub const CASH_POPUP_RISE: f32 = 36.0;
++
++pub const CASH_GAIN_COLOR: Color = Color::rgb(0.4, 0.95, 0.45);
++
++pub const CASH_LOSS_COLOR: Color = Color::rgb(1.0, 0.4, 0.35);
++
++/// How long a cash delta stays on screen while it drifts up and fades.
++pub const CASH_DELTA_SECONDS: f32 = 1.5;
++
++/// How long a phase banner stays on screen, sliding in and fading out.
++pub const PHASE_BANNER_SECONDS: f32 = 1.8;
++
++/// Seconds a phase banner takes to slide in.
++pub const PHASE_BANNER_SLIDE: f32 = 0.3;
++
++/// Seconds a phase banner takes to fade out at the end.
++pub const PHASE_BANNER_FADE: f32 = 0.6;
++
++pub const PHASE_BANNER_COLOR: Color = Color::rgba(0.05, 0.05, 0.12, 0.8);
++
++/// A short-lived strip announcing a turn or phase.
++#[derive(Component)]
++pub struct PhaseBanner(pub Timer);
++
++/// How long a market boom or crash banner stays on screen.
++pub const MARKET_BANNER_SECONDS: f32 = 3.5;
++
++/// Banner colors for a market boom and a crash; their alpha matches
++/// `PHASE_BANNER_COLOR` so they fade out the same way.
++pub const MARKET_BOOM_COLOR: Color = Color::rgba(0.05, 0.35, 0.12, 0.8);
++pub const MARKET_CRASH_COLOR: Color = Color::rgba(0.45, 0.05, 0.05, 0.8);
++
++/// A phase banner announcing a market boom or crash. It sits below the phase
++/// banners and outlasts them, so the next turn's banner doesn't cut it off.
++#[derive(Component)]
++pub struct MarketBanner;
++
++#[derive(Component)]
++pub struct MenuPanel;
++
++#[derive(Component)]
++pub struct MenuText;
++
++#[derive(Component)]
++pub struct StockPanel;
++
++#[derive(Component)]
++pub struct StockText;
++
++#[derive(Component)]
++pub struct TradePanel;
++
++#[derive(Component)]
++pub struct TradeText;
++
++#[derive(Component)]
++pub struct SettingsPanel;
++
++#[derive(Component)]
++pub struct LeaderboardPanel;
++
++/// The row of the leaderboard showing the player ranked `.0`, counting from
++/// the leader at 0.
++#[derive(Component)]
++pub struct LeaderboardRow(pub usize);
++
++#[derive(Component)]
++pub struct LeaderboardLabel(pub usize);
++
++/// One of the three parts of a leaderboard bar: cash, shops or stock.
++#[derive(Component)]
++pub struct LeaderboardSegment {
++    pub rank: usize,
++    pub part: usize,
++}
++
++/// Colors of a leaderboard bar's cash, shops and stock segments.
++pub const LEADERBOARD_COLORS: [Color; 3] = [
++    Color::rgb(0.95, 0.8, 0.3),
++    Color::rgb(0.35, 0.75, 0.45),
++    Color::rgb(0.35, 0.6, 0.95),
++];
++
++pub const LEADERBOARD_BAR_HEIGHT: f32 = 12.0;
++
++#[derive(Component)]
++pub struct PortfolioPanel;
++
++#[derive(Component)]
++pub struct PortfolioText;
++
++/// Price moves each district keeps for its sparkline.
++pub const PRICE_HISTORY_LEN: usize = 16;
++
++/// Bars of a sparkline, lowest first.
++pub const SPARK_BARS: [char; 8] = [
++    '\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}', '\u{2588}',
++];
++
++#[derive(Component)]
++pub struct SettingsText;
++
++#[derive(Component)]
++pub struct PromptText;
++
++#[derive(Component)]
++pub struct VenturePanel;
++
++#[derive(Component)]
++pub struct PurchaseDialog;
++
++#[derive(Component)]
++pub struct PurchaseText;
++
++/// Yes/No buttons in the purchase dialog.
++#[derive(Component, Clone, Copy, PartialEq, Eq)]
++pub enum DialogButton {
++    Yes,
++    No,
++}
++
++#[derive(Component)]
++pub struct VentureText;
++
++/// Banner announcing a promotion and its salary; hides itself when the timer
++/// ends.
++#[derive(Component)]
++pub struct PromotionBanner(pub Timer);
++
++#[derive(Component)]
++pub struct PromotionText;
++
++#[derive(Component)]
++pub struct ArcadePanel;
++
++#[derive(Component)]
++pub struct ArcadeText;
++
++#[derive(Component)]
++pub struct CasinoPanel;
++
++#[derive(Component)]
++pub struct CasinoText;
++
++#[derive(Component)]
++pub struct TileTooltip;
++
++#[derive(Component)]
++pub struct TileTooltipText;
++
++/// Tile the player clicked on, shown in the shop detail panel until they click
++/// elsewhere or press Escape.
++#[derive(Resource, Default)]
++pub struct SelectedTile(pub Option<usize>);
++
++#[derive(Component)]
++pub struct ShopDetailPanel;
++
++#[derive(Component)]
++pub struct ShopDetailText;
++
++/// Row holding the invest/sell buttons, shown only to the owner in their
++/// stock phase.
++#[derive(Component)]
++pub struct ShopDetailActions;
++
++#[derive(Component, Clone, Copy, PartialEq, Eq)]
++pub enum ShopDetailButton {
++    Invest,
++    Sell,
++}
++
++#[derive(Component)]
++pub struct AuctionPanel;
++
++#[derive(Component)]
++pub struct AuctionText;
++
++#[derive(Component)]
++pub struct BidButton;
++
++#[derive(Component)]
++pub struct RollButton;
++
++/// Buttons of the main menu and its stock panel, and the sidebar button
++/// that opens the menu, so a match can be played with the mouse alone. Each
++/// does what its key does; Invest puts `INVEST_STEP` into the chosen shop,
++/// and Buy/Sell stock place the stock panel's order either way round.
++#[derive(Component, Clone, Copy, PartialEq, Eq)]
++pub enum MenuButton {
++    Open,
++    Stocks,
++    Trade,
++    Borrow,
++    Repay,
++    PrevShop,
++    NextShop,
++    Invest,
++    SellShop,
++    BuyStock,
++    SellStock,
++    EndTurn,
++}
++
++/// Menu buttons whose interaction changed this frame.
++pub type MenuClicks<'w, 's> =
++    Query<'w, 's, (&'static Interaction, &'static MenuButton), Changed<Interaction>>;
++
++/// Whether `wanted` was clicked this frame.
++pub fn menu_clicked(buttons: &MenuClicks, wanted: MenuButton) -> bool {
++    buttons
++        .iter()
++        .any(|(interaction, button)| *interaction == Interaction::Pressed && *button == wanted)
++}
++
++/// A button that presses its action when clicked, so the system reading that
++/// action's keys handles the click too.
++#[derive(Component, Clone, Copy, PartialEq, Eq)]
++pub struct ActionButton(pub Action);
++
++/// Presses the action of each `ActionButton` clicked this frame.
++pub fn press_action_buttons(
++    buttons: Query<(&Interaction, &ActionButton), Changed<Interaction>>,
++    mut actions: ResMut<ActionInput>,
++) {
++    for (interaction, button) in buttons.iter() {
++        if *interaction == Interaction::Pressed {
++            actions.press(button.0);
++        }
++    }
++}
++
++/// Buttons under the turn prompt, each shown only while the choice it
++/// answers is open: a junction's directions, the Suit Yourself question and
++/// a bot's trade offer to the human.
++#[derive(Component, Clone, Copy, PartialEq, Eq)]
++pub enum PromptButton {
++    Junction,
++    SuitCards,
++    BotOffer,
++}
++
++/// Trade panel buttons for drafting an offer, hidden while one waits on an
++/// answer.
++#[derive(Component)]
++pub struct TradeDraftButtons;
++
++/// Trade panel buttons answering the offer waiting on Y/N.
++#[derive(Component)]
++pub struct TradeAnswerButtons;
++
++/// Arcade panel buttons: those playing one of the mini-games, shown while it
++/// runs, and the one leaving once it is over.
++#[derive(Component, Clone, Copy, PartialEq, Eq)]
++pub enum ArcadeButton {
++    Play(ArcadeGame),
++    Leave,
++}
++
++/// Row of buttons along the bottom of the screen for what the active human
++/// can do right now.
++#[derive(Component)]
++pub struct ActionBar;
++
++#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
++pub enum ActionBarSlot {
++    Roll,
++    Buy,
++    Decline,
++    /// Invests in the shop selected on the board.
++    Invest,
++    Stocks,
++    EndTurn,
++}
++
++/// The action bar's buttons, left to right.
++pub const ACTION_BAR_SLOTS: [ActionBarSlot; 6] = [
++    ActionBarSlot::Roll,
++    ActionBarSlot::Buy,
++    ActionBarSlot::Decline,
++    ActionBarSlot::Invest,
++    ActionBarSlot::Stocks,
++    ActionBarSlot::EndTurn,
++];
++
++impl ActionBarSlot {
++    /// String table key of the button's label.
++    pub fn label_key(self) -> &'static str {
++        match self {
++            ActionBarSlot::Roll => "hud.roll",
++            ActionBarSlot::Buy => "dialog.buy",
++            ActionBarSlot::Decline => "dialog.pass",
++            ActionBarSlot::Invest => "shop.invest",
++            ActionBarSlot::Stocks => "menu.stocks",
++            ActionBarSlot::EndTurn => "menu.end_turn",
++        }
++    }
++}
++
++pub const ROLL_BUTTON_COLOR: Color = Color::rgb(0.2, 0.35, 0.6);
++
++pub const ROLL_BUTTON_HOVER_COLOR: Color = Color::rgb(0.3, 0.45, 0.75);
++
++pub const ROLL_BUTTON_PRESSED_COLOR: Color = Color::rgb(0.15, 0.25, 0.45);
++
++/// Rows of buttons under the main menu's text, with the string table key of
++/// each button's label.
++pub const MENU_BUTTON_ROWS: [&[(MenuButton, &str)]; 4] = [
++    &[
++        (MenuButton::Borrow, "menu.borrow"),
++        (MenuButton::Repay, "menu.repay"),
++    ],
++    &[
++        (MenuButton::PrevShop, "menu.prev_shop"),
++        (MenuButton::NextShop, "menu.next_shop"),
++    ],
++    &[
++        (MenuButton::Invest, "shop.invest"),
++        (MenuButton::SellShop, "menu.sell_shop"),
++    ],
++    &[
++        (MenuButton::Stocks, "menu.stocks"),
++        (MenuButton::Trade, "menu.trade"),
++        (MenuButton::EndTurn, "menu.end_turn"),
++    ],
++];
++
++/// Stock panel buttons picking the district and sizing the order, labelled
++/// with `MIN_SHARE_ORDER`; the Buy/Sell stock menu buttons follow them.
++pub const STOCK_ACTION_BUTTONS: [&[(Action, &str)]; 2] = [
++    &[
++        (Action::PrevRow, "stocks.prev_district"),
++        (Action::NextRow, "stocks.next_district"),
++    ],
++    &[
++        (Action::Decrease, "stocks.fewer"),
++        (Action::Increase, "stocks.more"),
++    ],
++];
++
++/// Rows of trade panel buttons for drafting an offer.
++pub const TRADE_DRAFT_BUTTONS: [&[(Action, &str)]; 3] = [
++    &[
++        (Action::Cycle, "trade.partner"),
++        (Action::Erase, "trade.clear"),
++    ],
++    &[
++        (Action::PrevRow, "trade.prev_row"),
++        (Action::NextRow, "trade.next_row"),
++    ],
++    &[
++        (Action::Decrease, "trade.less"),
++        (Action::Increase, "trade.more"),
++        (Action::Confirm, "trade.propose"),
++    ],
++];
++
++/// Buttons under the turn prompt, with the action each presses.
++pub const PROMPT_BUTTONS: [(PromptButton, Action, &str); 8] = [
++    (PromptButton::Junction, Action::Up, "junction.up"),
++    (PromptButton::Junction, Action::Down, "junction.down"),
++    (PromptButton::Junction, Action::Left, "junction.left"),
++    (PromptButton::Junction, Action::Right, "junction.right"),
++    (PromptButton::SuitCards, Action::Yes, "suit_cards.play"),
++    (PromptButton::SuitCards, Action::No, "suit_cards.keep"),
++    (PromptButton::BotOffer, Action::Yes, "trade.accept"),
++    (PromptButton::BotOffer, Action::No, "trade.decline"),
++];
++
++/// Rows of casino panel buttons, the stake ones labelled with
++/// `CASINO_STAKE_STEP`.
++pub const CASINO_BUTTONS: [&[(Action, &str)]; 2] = [
++    &[
++        (Action::Decrease, "casino.less"),
++        (Action::Increase, "casino.more"),
++    ],
++    &[
++        (Action::BetHigh, "casino.high"),
++        (Action::BetLow, "casino.low"),
++        (Action::Confirm, "casino.leave"),
++    ],
++];
++
++/// Cards laid out in the card memory mini-game.
++pub const MEMORY_CARDS: u8 = 6;
++
++/// Spawns a HUD button `width` pixels wide under `parent`, with `bundle`
++/// saying what it does and `label` as its text.
++fn spawn_button(
++    parent: &mut ChildBuilder,
++    font: &Handle<Font>,
++    width: f32,
++    bundle: impl Bundle,
++    label: Localized,
++) {
++    parent
++        .spawn((
++            ButtonBundle {
++                style: Style {
++                    width: Val::Px(width),
++                    height: Val::Px(30.0),
++                    justify_content: JustifyContent::Center,
++                    align_items: AlignItems::Center,
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(ROLL_BUTTON_COLOR),
++                ..Default::default()
++            },
++            bundle,
++        ))
++        .with_children(|button| {
++            button.spawn((
++                TextBundle::from_section(
++                    "",
++                    TextStyle {
++                        font: font.clone(),
++                        font_size: 14.0,
++                        color: Color::WHITE,
++                    },
++                ),
++                label,
++            ));
++        });
++}
++
++/// A row of buttons spaced like the menu's.
++fn button_row() -> NodeBundle {
++    NodeBundle {
++        style: Style {
++            flex_wrap: FlexWrap::Wrap,
++            column_gap: Val::Px(8.0),
++            row_gap: Val::Px(6.0),
++            ..Default::default()
++        },
++        ..Default::default()
++    }
++}
++
++pub fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
++    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
++    commands
++        .spawn((
++            NodeBundle {
++                style: Style {
++                    width: Val::Percent(100.0),
++                    height: Val::Percent(100.0),
++                    padding: UiRect::all(Val::Px(12.0)),
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(Color::NONE),
++                ..Default::default()
++            },
++            UiRoot,
++        ))
++        .with_children(|parent| {
++            parent
++                .spawn(NodeBundle {
++                    style: Style {
++                        width: Val::Percent(30.0),
++                        height: Val::Percent(100.0),
++                        flex_direction: FlexDirection::Column,
++                        row_gap: Val::Px(8.0),
++                        ..Default::default()
++                    },
++                    background_color: BackgroundColor(BOARD_COLOR.with_a(0.5)),
++                    ..Default::default()
++                })
++                .with_children(|sidebar| {
++                    sidebar.spawn((
++                        TextBundle {
++                            text: Text::from_section(
++                                "",
++                                TextStyle {
++                                    font: font.clone(),
++                                    font_size: 18.0,
++                                    color: Color::WHITE,
++                                },
++                            ),
++                            ..Default::default()
++                        },
++                        InfoText,
++                    ));
++                    sidebar.spawn((
++                        NodeBundle {
++                            style: Style {
++                                flex_direction: FlexDirection::Column,
++                                row_gap: Val::Px(6.0),
++                                ..Default::default()
++                            },
++                            ..Default::default()
++                        },
++                        PlayerPanels,
++                    ));
++                    sidebar.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 18.0,
++                                color: Color::rgb(1.0, 0.9, 0.4),
++                            },
++                        ),
++                        PromptText,
++                    ));
++                    sidebar.spawn(button_row()).with_children(|row| {
++                        for (button, action, key) in PROMPT_BUTTONS {
++                            spawn_button(
++                                row,
++                                &font,
++                                110.0,
++                                (ActionButton(action), button),
++                                Localized::new(&[key]),
++                            );
++                        }
++                    });
++                    sidebar
++                        .spawn((
++                            ButtonBundle {
++                                style: Style {
++                                    width: Val::Px(160.0),
++                                    height: Val::Px(36.0),
++                                    justify_content: JustifyContent::Center,
++                                    align_items: AlignItems::Center,
++                                    ..Default::default()
++                                },
++                                background_color: BackgroundColor(ROLL_BUTTON_COLOR),
++                                ..Default::default()
++                            },
++                            RollButton,
++                        ))
++                        .with_children(|button| {
++                            button.spawn((
++                                TextBundle::from_section(
++                                    "",
++                                    TextStyle {
++                                        font: font.clone(),
++                                        font_size: 18.0,
++                                        color: Color::WHITE,
++                                    },
++                                ),
++                                Localized::new(&["hud.roll"]),
++                            ));
++                        });
++                    sidebar
++                        .spawn((
++                            ButtonBundle {
++                                style: Style {
++                                    width: Val::Px(160.0),
++                                    height: Val::Px(30.0),
++                                    justify_content: JustifyContent::Center,
++                                    align_items: AlignItems::Center,
++                                    ..Default::default()
++                                },
++                                background_color: BackgroundColor(ROLL_BUTTON_COLOR),
++                                ..Default::default()
++                            },
++                            MenuButton::Open,
++                        ))
++                        .with_children(|button| {
++                            button.spawn((
++                                TextBundle::from_section(
++                                    "",
++                                    TextStyle {
++                                        font: font.clone(),
++                                        font_size: 16.0,
++                                        color: Color::WHITE,
++                                    },
++                                ),
++                                Localized::new(&["hud.menu"]),
++                            ));
++                        });
++                });
++
++            parent
++                .spawn(NodeBundle {
++                    style: Style {
++                        position_type: PositionType::Absolute,
++                        right: Val::Px(12.0),
++                        top: Val::Px(12.0),
++                        width: Val::Px(340.0),
++                        flex_direction: FlexDirection::Column,
++                        padding: UiRect::all(Val::Px(8.0)),
++                        row_gap: Val::Px(4.0),
++                        ..Default::default()
++                    },
++                    background_color: BackgroundColor(Color::rgba(0.08, 0.08, 0.12, 0.8)),
++                    ..Default::default()
++                })
++                .with_children(|log| {
++                    log.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 16.0,
++                                color: Color::rgb(1.0, 0.9, 0.4),
++                            },
++                        ),
++                        Localized::new(&["hud.event_log"]),
++                    ));
++                    log.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 14.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        EventLogText,
++                    ));
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            right: Val::Px(12.0),
++                            bottom: Val::Px(12.0),
++                            width: Val::Px(320.0),
++                            min_height: Val::Px(280.0),
++                            display: Display::None,
++                            flex_direction: FlexDirection::Column,
++                            padding: UiRect::all(Val::Px(8.0)),
++                            row_gap: Val::Px(8.0),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgb(0.1, 0.1, 0.15)),
++                        ..Default::default()
++                    },
++                    MenuPanel,
++                ))
++                .with_children(|menu| {
++                    menu.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 16.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        Localized::new(&["menu.header"]),
++                    ));
++                    menu.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 15.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        MenuText,
++                    ));
++                    for buttons in MENU_BUTTON_ROWS {
++                        menu.spawn(NodeBundle {
++                            style: Style {
++                                column_gap: Val::Px(8.0),
++                                ..Default::default()
++                            },
++                            ..Default::default()
++                        })
++                        .with_children(|row| {
++                            for (button, key) in buttons {
++                                let label = match button {
++                                    MenuButton::Borrow | MenuButton::Repay => {
++                                        Localized::new(&[key]).with_arg("amount", LOAN_STEP)
++                                    }
++                                    MenuButton::Invest => {
++                                        Localized::new(&[key]).with_arg("amount", INVEST_STEP)
++                                    }
++                                    _ => Localized::new(&[key]),
++                                };
++                                row.spawn((
++                                    ButtonBundle {
++                                        style: Style {
++                                            width: Val::Px(96.0),
++                                            height: Val::Px(30.0),
++                                            justify_content: JustifyContent::Center,
++                                            align_items: AlignItems::Center,
++                                            ..Default::default()
++                                        },
++                                        background_color: BackgroundColor(ROLL_BUTTON_COLOR),
++                                        ..Default::default()
++                                    },
++                                    *button,
++                                ))
++                                .with_children(|button| {
++                                    button.spawn((
++                                        TextBundle::from_section(
++                                            "",
++                                            TextStyle {
++                                                font: font.clone(),
++                                                font_size: 14.0,
++                                                color: Color::WHITE,
++                                            },
++                                        ),
++                                        label,
++                                    ));
++                                });
++                            }
++                        });
++                    }
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            left: Val::Px(0.0),
++                            right: Val::Px(0.0),
++                            bottom: Val::Px(12.0),
++                            justify_content: JustifyContent::Center,
++                            column_gap: Val::Px(8.0),
++                            ..Default::default()
++                        },
++                        ..Default::default()
++                    },
++                    ActionBar,
++                ))
++                .with_children(|bar| {
++                    for slot in ACTION_BAR_SLOTS {
++                        let label = match slot {
++                            ActionBarSlot::Invest => {
++                                Localized::new(&[slot.label_key()]).with_arg("amount", INVEST_STEP)
++                            }
++                            _ => Localized::new(&[slot.label_key()]),
++                        };
++                        let mut button = bar.spawn((
++                            ButtonBundle {
++                                style: Style {
++                                    width: Val::Px(130.0),
++                                    height: Val::Px(36.0),
++                                    display: Display::None,
++                                    justify_content: JustifyContent::Center,
++                                    align_items: AlignItems::Center,
++                                    ..Default::default()
++                                },
++                                background_color: BackgroundColor(ROLL_BUTTON_COLOR),
++                                ..Default::default()
++                            },
++                            slot,
++                        ));
++                        // Each slot is also the button that already does its
++                        // job elsewhere in the HUD, so the same systems
++                        // handle the click.
++                        match slot {
++                            ActionBarSlot::Roll => button.insert(RollButton),
++                            ActionBarSlot::Buy => button.insert(DialogButton::Yes),
++                            ActionBarSlot::Decline => button.insert(DialogButton::No),
++                            ActionBarSlot::Invest => button.insert(ShopDetailButton::Invest),
++                            ActionBarSlot::Stocks => button.insert(MenuButton::Stocks),
++                            ActionBarSlot::EndTurn => button.insert(MenuButton::EndTurn),
++                        };
++                        button.with_children(|button| {
++                            button.spawn((
++                                TextBundle::from_section(
++                                    "",
++                                    TextStyle {
++                                        font: font.clone(),
++                                        font_size: 16.0,
++                                        color: Color::WHITE,
++                                    },
++                                ),
++                                label,
++                            ));
++                        });
++                    }
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            left: Val::Px(12.0),
++                            bottom: Val::Px(12.0),
++                            width: Val::Px(360.0),
++                            min_height: Val::Px(300.0),
++                            display: Display::None,
++                            flex_direction: FlexDirection::Column,
++                            padding: UiRect::all(Val::Px(8.0)),
++                            row_gap: Val::Px(6.0),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgb(0.12, 0.1, 0.16)),
++                        ..Default::default()
++                    },
++                    StockPanel,
++                ))
++                .with_children(|stock| {
++                    stock.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 16.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        Localized::new(&["stocks.header"]),
++                    ));
++                    stock.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 15.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        StockText,
++                    ));
++                    for buttons in STOCK_ACTION_BUTTONS {
++                        stock.spawn(button_row()).with_children(|row| {
++                            for (action, key) in buttons {
++                                spawn_button(
++                                    row,
++                                    &font,
++                                    120.0,
++                                    ActionButton(*action),
++                                    Localized::new(&[key]).with_arg("shares", MIN_SHARE_ORDER),
++                                );
++                            }
++                        });
++                    }
++                    stock.spawn(button_row()).with_children(|row| {
++                        for (button, key) in [
++                            (MenuButton::BuyStock, "stocks.buy"),
++                            (MenuButton::SellStock, "stocks.sell"),
++                        ] {
++                            spawn_button(row, &font, 120.0, button, Localized::new(&[key]));
++                        }
++                    });
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            right: Val::Px(12.0),
++                            top: Val::Px(12.0),
++                            width: Val::Px(400.0),
++                            display: Display::None,
++                            flex_direction: FlexDirection::Column,
++                            padding: UiRect::all(Val::Px(8.0)),
++                            row_gap: Val::Px(6.0),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgb(0.12, 0.1, 0.16)),
++                        ..Default::default()
++                    },
++                    TradePanel,
++                ))
++                .with_children(|trade| {
++                    trade.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 16.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        Localized::new(&["trade.header"]),
++                    ));
++                    trade.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 15.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        TradeText,
++                    ));
++                    trade
++                        .spawn((
++                            NodeBundle {
++                                style: Style {
++                                    flex_direction: FlexDirection::Column,
++                                    row_gap: Val::Px(6.0),
++                                    ..Default::default()
++                                },
++                                ..Default::default()
++                            },
++                            TradeDraftButtons,
++                        ))
++                        .with_children(|draft| {
++                            for buttons in TRADE_DRAFT_BUTTONS {
++                                draft.spawn(button_row()).with_children(|row| {
++                                    for (action, key) in buttons {
++                                        spawn_button(
++                                            row,
++                                            &font,
++                                            120.0,
++                                            ActionButton(*action),
++                                            Localized::new(&[key]),
++                                        );
++                                    }
++                                });
++                            }
++                        });
++                    trade
++                        .spawn((button_row(), TradeAnswerButtons))
++                        .with_children(|row| {
++                            for (action, key) in
++                                [(Action::Yes, "trade.accept"), (Action::No, "trade.decline")]
++                            {
++                                spawn_button(
++                                    row,
++                                    &font,
++                                    120.0,
++                                    ActionButton(action),
++                                    Localized::new(&[key]),
++                                );
++                            }
++                        });
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            left: Val::Percent(35.0),
++                            top: Val::Percent(20.0),
++                            width: Val::Px(380.0),
++                            display: Display::None,
++                            flex_direction: FlexDirection::Column,
++                            padding: UiRect::all(Val::Px(12.0)),
++                            row_gap: Val::Px(6.0),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgb(0.1, 0.1, 0.15)),
++                        // Above the title screen, which it opens over.
++                        z_index: ZIndex::Global(11),
++                        ..Default::default()
++                    },
++                    SettingsPanel,
++                ))
++                .with_children(|settings| {
++                    settings.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 16.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        Localized::new(&["settings.header"]),
++                    ));
++                    settings.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 15.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        SettingsText,
++                    ));
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            left: Val::Percent(32.0),
++                            top: Val::Px(12.0),
++                            width: Val::Px(340.0),
++                            display: Display::None,
++                            flex_direction: FlexDirection::Column,
++                            padding: UiRect::all(Val::Px(8.0)),
++                            row_gap: Val::Px(6.0),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgba(0.08, 0.08, 0.12, 0.85)),
++                        ..Default::default()
++                    },
++                    LeaderboardPanel,
++                ))
++                .with_children(|leaderboard| {
++                    let style = |color| TextStyle {
++                        font: font.clone(),
++                        font_size: 14.0,
++                        color,
++                    };
++                    leaderboard.spawn((
++                        TextBundle::from_sections([
++                            TextSection::new(
++                                "",
++                                TextStyle {
++                                    font: font.clone(),
++                                    font_size: 16.0,
++                                    color: Color::WHITE,
++                                },
++                            ),
++                            TextSection::new("", style(LEADERBOARD_COLORS[0])),
++                            TextSection::new(" / ", style(Color::WHITE)),
++                            TextSection::new("", style(LEADERBOARD_COLORS[1])),
++                            TextSection::new(" / ", style(Color::WHITE)),
++                            TextSection::new("", style(LEADERBOARD_COLORS[2])),
++                        ]),
++                        Localized::new(&[
++                            "leaderboard.header",
++                            "leaderboard.cash",
++                            "",
++                            "leaderboard.shops",
++                            "",
++                            "leaderboard.stock",
++                        ]),
++                    ));
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            left: Val::Percent(32.0),
++                            bottom: Val::Px(12.0),
++                            width: Val::Px(420.0),
++                            display: Display::None,
++                            flex_direction: FlexDirection::Column,
++                            padding: UiRect::all(Val::Px(8.0)),
++                            row_gap: Val::Px(6.0),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgba(0.08, 0.08, 0.12, 0.85)),
++                        ..Default::default()
++                    },
++                    PortfolioPanel,
++                ))
++                .with_children(|portfolio| {
++                    portfolio.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 14.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        PortfolioText,
++                    ));
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            left: Val::Percent(40.0),
++                            top: Val::Percent(30.0),
++                            width: Val::Px(300.0),
++                            min_height: Val::Px(180.0),
++                            display: Display::None,
++                            flex_direction: FlexDirection::Column,
++                            justify_content: JustifyContent::Center,
++                            row_gap: Val::Px(10.0),
++                            padding: UiRect::all(Val::Px(12.0)),
++                            border: UiRect::all(Val::Px(3.0)),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgb(0.1, 0.2, 0.35)),
++                        border_color: BorderColor(CHANCE_COLOR),
++                        ..Default::default()
++                    },
++                    VenturePanel,
++                ))
++                .with_children(|card| {
++                    card.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 18.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        VentureText,
++                    ));
++                    spawn_button(
++                        card,
++                        &font,
++                        140.0,
++                        ActionButton(Action::Confirm),
++                        Localized::new(&["hud.continue"]),
++                    );
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            left: Val::Percent(36.0),
++                            top: Val::Percent(8.0),
++                            width: Val::Px(380.0),
++                            display: Display::None,
++                            flex_direction: FlexDirection::Column,
++                            align_items: AlignItems::Center,
++                            padding: UiRect::all(Val::Px(12.0)),
++                            border: UiRect::all(Val::Px(3.0)),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgb(0.3, 0.25, 0.08)),
++                        border_color: BorderColor(BANK_COLOR),
++                        z_index: ZIndex::Global(5),
++                        ..Default::default()
++                    },
++                    PromotionBanner(Timer::from_seconds(4.0, TimerMode::Once)),
++                ))
++                .with_children(|banner| {
++                    banner.spawn((
++                        TextBundle::from_sections([
++                            TextSection::new(
++                                "",
++                                TextStyle {
++                                    font: font.clone(),
++                                    font_size: 32.0,
++                                    color: BANK_COLOR,
++                                },
++                            ),
++                            TextSection::new(
++                                "",
++                                TextStyle {
++                                    font: font.clone(),
++                                    font_size: 18.0,
++                                    color: Color::WHITE,
++                                },
++                            ),
++                        ]),
++                        PromotionText,
++                    ));
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            left: Val::Percent(38.0),
++                            top: Val::Percent(28.0),
++                            width: Val::Px(340.0),
++                            display: Display::None,
++                            flex_direction: FlexDirection::Column,
++                            row_gap: Val::Px(10.0),
++                            padding: UiRect::all(Val::Px(12.0)),
++                            border: UiRect::all(Val::Px(3.0)),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgb(0.1, 0.25, 0.18)),
++                        border_color: BorderColor(PROPERTY_COLOR),
++                        z_index: ZIndex::Global(5),
++                        ..Default::default()
++                    },
++                    PurchaseDialog,
++                ))
++                .with_children(|dialog| {
++                    dialog.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 18.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        PurchaseText,
++                    ));
++                    dialog
++                        .spawn(NodeBundle {
++                            style: Style {
++                                column_gap: Val::Px(12.0),
++                                justify_content: JustifyContent::Center,
++                                ..Default::default()
++                            },
++                            ..Default::default()
++                        })
++                        .with_children(|row| {
++                            for (button, label) in [
++                                (DialogButton::Yes, "dialog.buy"),
++                                (DialogButton::No, "dialog.pass"),
++                            ] {
++                                row.spawn((
++                                    ButtonBundle {
++                                        style: Style {
++                                            width: Val::Px(120.0),
++                                            height: Val::Px(36.0),
++                                            justify_content: JustifyContent::Center,
++                                            align_items: AlignItems::Center,
++                                            ..Default::default()
++                                        },
++                                        background_color: BackgroundColor(ROLL_BUTTON_COLOR),
++                                        ..Default::default()
++                                    },
++                                    button,
++                                ))
++                                .with_children(|button| {
++                                    button.spawn((
++                                        TextBundle::from_section(
++                                            "",
++                                            TextStyle {
++                                                font: font.clone(),
++                                                font_size: 18.0,
++                                                color: Color::WHITE,
++                                            },
++                                        ),
++                                        Localized::new(&[label]),
++                                    ));
++                                });
++                            }
++                        });
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            left: Val::Percent(38.0),
++                            top: Val::Percent(28.0),
++                            width: Val::Px(340.0),
++                            display: Display::None,
++                            flex_direction: FlexDirection::Column,
++                            row_gap: Val::Px(10.0),
++                            padding: UiRect::all(Val::Px(12.0)),
++                            border: UiRect::all(Val::Px(3.0)),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgb(0.25, 0.12, 0.1)),
++                        border_color: BorderColor(BANK_COLOR),
++                        z_index: ZIndex::Global(5),
++                        ..Default::default()
++                    },
++                    AuctionPanel,
++                ))
++                .with_children(|panel| {
++                    panel.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 18.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        AuctionText,
++                    ));
++                    panel
++                        .spawn((
++                            ButtonBundle {
++                                style: Style {
++                                    width: Val::Px(160.0),
++                                    height: Val::Px(36.0),
++                                    align_self: AlignSelf::Center,
++                                    justify_content: JustifyContent::Center,
++                                    align_items: AlignItems::Center,
++                                    ..Default::default()
++                                },
++                                background_color: BackgroundColor(ROLL_BUTTON_COLOR),
++                                ..Default::default()
++                            },
++                            BidButton,
++                        ))
++                        .with_children(|button| {
++                            button.spawn((
++                                TextBundle::from_section(
++                                    "",
++                                    TextStyle {
++                                        font: font.clone(),
++                                        font_size: 18.0,
++                                        color: Color::WHITE,
++                                    },
++                                ),
++                                Localized::new(&["auction.bid"]),
++                            ));
++                        });
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            left: Val::Percent(36.0),
++                            top: Val::Percent(26.0),
++                            width: Val::Px(380.0),
++                            display: Display::None,
++                            flex_direction: FlexDirection::Column,
++                            row_gap: Val::Px(10.0),
++                            padding: UiRect::all(Val::Px(12.0)),
++                            border: UiRect::all(Val::Px(3.0)),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgb(0.2, 0.08, 0.2)),
++                        border_color: BorderColor(ARCADE_COLOR),
++                        z_index: ZIndex::Global(5),
++                        ..Default::default()
++                    },
++                    ArcadePanel,
++                ))
++                .with_children(|panel| {
++                    panel.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 18.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        ArcadeText,
++                    ));
++                    panel.spawn(button_row()).with_children(|row| {
++                        spawn_button(
++                            row,
++                            &font,
++                            120.0,
++                            (
++                                ActionButton(Action::Roll),
++                                ArcadeButton::Play(ArcadeGame::DiceDuel),
++                            ),
++                            Localized::new(&["arcade.roll"]),
++                        );
++                        spawn_button(
++                            row,
++                            &font,
++                            120.0,
++                            (
++                                ActionButton(Action::Roll),
++                                ArcadeButton::Play(ArcadeGame::Roulette),
++                            ),
++                            Localized::new(&["arcade.spin"]),
++                        );
++                        for number in 1..=MEMORY_CARDS {
++                            spawn_button(
++                                row,
++                                &font,
++                                70.0,
++                                (
++                                    ActionButton(Action::Pick(number)),
++                                    ArcadeButton::Play(ArcadeGame::CardMemory),
++                                ),
++                                Localized::new(&["arcade.card"]).with_arg("number", number),
++                            );
++                        }
++                        spawn_button(
++                            row,
++                            &font,
++                            140.0,
++                            (ActionButton(Action::Confirm), ArcadeButton::Leave),
++                            Localized::new(&["hud.continue"]),
++                        );
++                    });
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            left: Val::Percent(36.0),
++                            top: Val::Percent(26.0),
++                            width: Val::Px(380.0),
++                            display: Display::None,
++                            flex_direction: FlexDirection::Column,
++                            row_gap: Val::Px(10.0),
++                            padding: UiRect::all(Val::Px(12.0)),
++                            border: UiRect::all(Val::Px(3.0)),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgb(0.08, 0.2, 0.1)),
++                        border_color: BorderColor(CASINO_COLOR),
++                        z_index: ZIndex::Global(5),
++                        ..Default::default()
++                    },
++                    CasinoPanel,
++                ))
++                .with_children(|panel| {
++                    panel.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 18.0,
++                                color: Color::WHITE,
++                            },
++                        ),
++                        CasinoText,
++                    ));
++                    for buttons in CASINO_BUTTONS {
++                        panel.spawn(button_row()).with_children(|row| {
++                            for (action, key) in buttons {
++                                spawn_button(
++                                    row,
++                                    &font,
++                                    110.0,
++                                    ActionButton(*action),
++                                    Localized::new(&[key]).with_arg("amount", CASINO_STAKE_STEP),
++                                );
++                            }
++                        });
++                    }
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            display: Display::None,
++                            padding: UiRect::all(Val::Px(6.0)),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgba(0.05, 0.05, 0.1, 0.9)),
++                        z_index: ZIndex::Global(10),
++                        ..Default::default()
++                    },
++                    TileTooltip,
++                ))
++                .with_children(|tooltip| {
++                    let style = TextStyle {
++                        font: font.clone(),
++                        font_size: 15.0,
++                        color: Color::WHITE,
++                    };
++                    // Description, owner name (in their token color), then fee details.
++                    tooltip.spawn((
++                        TextBundle::from_sections([
++                            TextSection::new("", style.clone()),
++                            TextSection::new("", style.clone()),
++                            TextSection::new("", style),
++                        ]),
++                        TileTooltipText,
++                    ));
++                });
++
++            parent
++                .spawn((
++                    NodeBundle {
++                        style: Style {
++                            position_type: PositionType::Absolute,
++                            left: Val::Percent(38.0),
++                            bottom: Val::Px(12.0),
++                            width: Val::Px(360.0),
++                            display: Display::None,
++                            flex_direction: FlexDirection::Column,
++                            row_gap: Val::Px(10.0),
++                            padding: UiRect::all(Val::Px(12.0)),
++                            border: UiRect::all(Val::Px(3.0)),
++                            ..Default::default()
++                        },
++                        background_color: BackgroundColor(Color::rgb(0.1, 0.12, 0.2)),
++                        border_color: BorderColor(PROPERTY_COLOR),
++                        z_index: ZIndex::Global(5),
++                        ..Default::default()
++                    },
++                    // Lets `select_tile` ignore clicks that land on the panel.
++                    Interaction::default(),
++                    ShopDetailPanel,
++                ))
++                .with_children(|panel| {
++                    let style = TextStyle {
++                        font: font.clone(),
++                        font_size: 16.0,
++                        color: Color::WHITE,
++                    };
++                    panel.spawn((
++                        TextBundle::from_sections([
++                            TextSection::new("", style.clone()),
++                            TextSection::new("", style.clone()),
++                            TextSection::new("", style),
++                        ]),
++                        ShopDetailText,
++                    ));
++                    panel
++                        .spawn((
++                            NodeBundle {
++                                style: Style {
++                                    column_gap: Val::Px(12.0),
++                                    justify_content: JustifyContent::Center,
++                                    ..Default::default()
++                                },
++                                ..Default::default()
++                            },
++                            ShopDetailActions,
++                        ))
++                        .with_children(|row| {
++                            for (button, label) in [
++                                (
++                                    ShopDetailButton::Invest,
++                                    Localized::new(&["shop.invest"])
++                                        .with_arg("amount", INVEST_STEP),
++                                ),
++                                (ShopDetailButton::Sell, Localized::new(&["shop.sell"])),
++                            ] {
++                                row.spawn((
++                                    ButtonBundle {
++                                        style: Style {
++                                            width: Val::Px(140.0),
++                                            height: Val::Px(36.0),
++                                            justify_content: JustifyContent::Center,
++                                            align_items: AlignItems::Center,
++                                            ..Default::default()
++                                        },
++                                        background_color: BackgroundColor(ROLL_BUTTON_COLOR),
++                                        ..Default::default()
++                                    },
++                                    button,
++                                ))
++                                .with_children(|button| {
++                                    button.spawn((
++                                        TextBundle::from_section(
++                                            "",
++                                            TextStyle {
++                                                font: font.clone(),
++                                                font_size: 18.0,
++                                                color: Color::WHITE,
++                                            },
++                                        ),
++                                        label,
++                                    ));
++                                });
++                            }
++                        });
++                });
++        });
++}
++
++/// How fast holding a zoom action zooms, as a fraction of the scale per
++/// second.
++pub const ZOOM_SPEED: f32 = 1.2;
++
++/// How quickly the following camera closes on the active player's token, as
++/// a fraction of the remaining distance per second.
++pub const CAMERA_FOLLOW_RATE: f32 = 4.0;
++
++/// Whether the camera keeps the active player's token centred. Panning by
++/// hand switches to a free camera; the FollowCamera action switches back.
++#[derive(Resource)]
++pub struct CameraFollow(pub bool);
++
++impl Default for CameraFollow {
++    fn default() -> Self {
++        Self(true)
++    }
++}
++
++/// Pans the camera with the pan actions and the gamepad's left stick, and
++/// zooms with the mouse wheel and the zoom actions. While following, glides
++/// after the token of the player whose turn it is, including as it walks.
++#[allow(clippy::too_many_arguments)]
++pub fn camera_controls(
++    actions: Res<ActionInput>,
++    gamepads: Res<Gamepads>,
++    sticks: Res<Axis<GamepadAxis>>,
++    mut scroll_evr: EventReader<MouseWheel>,
++    mut query: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
++    tokens: Query<(&Transform, &PlayerToken), Without<MainCamera>>,
++    game: Res<Game>,
++    mut follow: ResMut<CameraFollow>,
++    time: Res<Time<Real>>,
++    settings: Res<Settings>,
++    settings_menu: Res<SettingsMenu>,
++) {
++    if settings_menu.open {
++        scroll_evr.clear();
++        return;
++    }
++    if actions.just_pressed(Action::FollowCamera) {
++        follow.0 = !follow.0;
++    }
++    for (mut transform, mut projection) in query.iter_mut() {
++        let mut direction = Vec3::ZERO;
++        if actions.pressed(Action::PanLeft) {
++            direction.x -= 1.0;
++        }
++        if actions.pressed(Action::PanRight) {
++            direction.x += 1.0;
++        }
++        if actions.pressed(Action::PanUp) {
++            direction.y += 1.0;
++        }
++        if actions.pressed(Action::PanDown) {
++            direction.y -= 1.0;
++        }
++        for gamepad in gamepads.iter() {
++            let stick = |axis| sticks.get(GamepadAxis::new(gamepad, axis)).unwrap_or(0.0);
++            direction.x += stick(GamepadAxisType::LeftStickX);
++            direction.y += stick(GamepadAxisType::LeftStickY);
++        }
++        let speed = 400.0 * settings.camera_sensitivity * time.delta_seconds();
++        transform.translation += direction.clamp_length_max(1.0) * speed;
++        if direction.length_squared() > 0.01 {
++            follow.0 = false;
++        }
++        if follow.0
++            && let Some((token, _)) = tokens
++                .iter()
++                .find(|(_, token)| token.0 == game.current_turn)
++        {
++            let t = (CAMERA_FOLLOW_RATE * time.delta_seconds()).min(1.0);
++            let target = token.translation.truncate();
++            let position = transform.translation.truncate().lerp(target, t);
++            transform.translation = position.extend(transform.translation.z);
++        }
++
++        let mut zoom = 0.0;
++        if actions.pressed(Action::ZoomIn) {
++            zoom += 1.0;
++        }
++        if actions.pressed(Action::ZoomOut) {
++            zoom -= 1.0;
++        }
++        let zoom = zoom * ZOOM_SPEED * settings.camera_sensitivity * time.delta_seconds();
++        projection.scale = (projection.scale * (1.0 - zoom)).clamp(0.5, 2.5);
++
++        for ev in scroll_evr.read() {
++            let zoom = ev.y * 0.1 * settings.camera_sensitivity;
++            projection.scale = (projection.scale * (1.0 - zoom)).clamp(0.5, 2.5);
++        }
++    }
++}
++
++/// Text of shop `index`'s label: its district and price while vacant; once
++/// owned, its owner's initials, how many times it has been invested in and
++/// the fee it charges right now.
++pub fn shop_label(game: &Game, index: usize, market: &StockMarket) -> Option<String> {
++    let tile = &game.board[index];
++    let TileKind::Property {
++        district, price, ..
++    } = tile.kind
++    else {
++        return None;
++    };
++    let district = game.district_name(district);
++    let (Some(owner), Some(fee)) = (game.owner_of(index), game.shop_fee(index, market)) else {
++        return Some(format!("{}\n{}", district, Money(price)));
++    };
++    let initials = game.players[owner].initials();
++    let level = tile.capital / INVEST_STEP;
++    Some(if level > 0 {
++        format!("{} {}+{}\nfee {}", district, initials, level, Money(fee))
++    } else {
++        format!("{} {}\nfee {}", district, initials, Money(fee))
++    })
++}
++
++/// Keeps shop labels showing their owner, investment and current fee. A
++/// shop changing hands or being invested in rewrites the labels of its whole
++/// district, whose fees move with it, as does a share price moving; a
++/// venture card doubling a player's fees rewrites their shops'. Everything
++/// is written when the board is respawned.
++#[allow(clippy::too_many_arguments)]
++pub fn update_tile_labels(
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    mut ownership_events: EventReader<OwnershipChanged>,
++    mut investment_events: EventReader<InvestmentChanged>,
++    mut price_events: EventReader<StockPriceChanged>,
++    mut double_fees: Local<Vec<bool>>,
++    mut labels: Query<(&mut Text, Ref<TileLabel>)>,
++) {
++    let district_of = |tile: usize| match game.board.get(tile).map(|tile| &tile.kind) {
++        Some(TileKind::Property { district, .. }) => Some(*district),
++        _ => None,
++    };
++    if game.is_added() {
++        double_fees.clear();
++    }
++    let mut districts: HashSet<DistrictId> = ownership_events
++        .read()
++        .map(|event| event.tile)
++        .chain(investment_events.read().map(|event| event.tile))
++        .filter_map(district_of)
++        .collect();
++    districts.extend(price_events.read().map(|event| event.district));
++    let mut owners: HashSet<usize> = HashSet::new();
++    let doubled: Vec<bool> = game
++        .players
++        .iter()
++        .map(|player| player.double_fees)
++        .collect();
++    if *double_fees != doubled {
++        owners.extend(
++            doubled
++                .iter()
++                .enumerate()
++                .filter(|(idx, doubled)| double_fees.get(*idx) != Some(doubled))
++                .map(|(idx, _)| idx),
++        );
++        *double_fees = doubled;
++    }
++    for (mut text, label) in labels.iter_mut() {
++        let stale = label.is_added()
++            || district_of(label.0).is_some_and(|district| districts.contains(&district))
++            || game
++                .owner_of(label.0)
++                .is_some_and(|owner| owners.contains(&owner));
++        if !stale {
++            continue;
++        }
++        let Some(value) = shop_label(&game, label.0, &market) else {
++            continue;
++        };
++        // Rewriting a label relays it out, so only touch the ones that moved.
++        if text.sections[0].value != value {
++            text.sections[0].value = value;
++        }
++    }
++}
++
++/// How far past the edge of the view board text stays drawn, so labels
++/// don't pop in as a tile scrolls into sight.
++pub const BOARD_TEXT_CULL_MARGIN: f32 = TILE_SIZE;
++
++/// Zoomed out past this scale, tile labels and pattern markers are too small
++/// to read and are hidden altogether.
++pub const BOARD_TEXT_MAX_SCALE: f32 = 2.0;
++
++/// Hides tile labels and pattern markers outside the main camera's view, or
++/// all of them when zoomed too far out to read. Sprites are culled by the
++/// renderer already, but board text is laid out and queued for every tile,
++/// which on a 200-tile board costs more than drawing the board itself.
++#[allow(clippy::type_complexity)]
++pub fn cull_board_text(
++    cameras: Query<(Ref<GlobalTransform>, Ref<OrthographicProjection>), With<MainCamera>>,
++    // Labels and pattern glyphs, drawn as text or atlas sprites, but not the
++    // owner stripes, which stay legible at any zoom.
++    mut text: Query<
++        (Ref<GlobalTransform>, &mut Visibility),
++        (
++            Or<(With<TileLabel>, With<TilePattern>)>,
++            Or<(With<Text>, With<TextureAtlas>)>,
++        ),
++    >,
++) {
++    let Ok((camera, projection)) = cameras.get_single() else {
++        return;
++    };
++    let moved = camera.is_changed() || projection.is_changed();
++    let readable = projection.scale <= BOARD_TEXT_MAX_SCALE;
++    let view = Rect::from_center_size(
++        camera.translation().truncate(),
++        projection.area.size() + Vec2::splat(BOARD_TEXT_CULL_MARGIN * 2.0),
++    );
++    for (transform, mut visibility) in text.iter_mut() {
++        if !moved && !transform.is_changed() {
++            continue;
++        }
++        let shown = if readable && view.contains(transform.translation().truncate()) {
++            Visibility::Inherited
++        } else {
++            Visibility::Hidden
++        };
++        if *visibility != shown {
++            *visibility = shown;
++        }
++    }
++}
++
++/// Finds the tile sprite under the mouse cursor, returning the cursor's
++/// window position and the tile's index.
++pub fn tile_under_cursor(
++    windows: &Query<&Window, With<PrimaryWindow>>,
++    cameras: &Query<(&Camera, &GlobalTransform), With<MainCamera>>,
++    tiles: &Query<(&TileEntity, &GlobalTransform, &Sprite)>,
++) -> Option<(Vec2, usize)> {
++    let cursor = windows.get_single().ok()?.cursor_position()?;
++    tile_at(cursor, cameras, tiles).map(|tile| (cursor, tile))
++}
++
++/// Index of the tile drawn at window position `point`, if any.
++pub fn tile_at(
++    point: Vec2,
++    cameras: &Query<(&Camera, &GlobalTransform), With<MainCamera>>,
++    tiles: &Query<(&TileEntity, &GlobalTransform, &Sprite)>,
++) -> Option<usize> {
++    let (camera, camera_transform) = cameras.get_single().ok()?;
++    let world = camera.viewport_to_world_2d(camera_transform, point)?;
++    tiles.iter().find_map(|(tile, transform, sprite)| {
++        let half_size = sprite.custom_size.unwrap_or(Vec2::splat(TILE_SIZE)) / 2.0;
++        let offset = (world - transform.translation().truncate()).abs();
++        (offset.x <= half_size.x && offset.y <= half_size.y).then_some(tile.0)
++    })
++}
++
++/// Describes the board tile under the mouse cursor: a shop's district, price,
++/// owner, current fee, capital and how heavily its owner has invested in the
++/// district.
++#[allow(clippy::type_complexity)]
++pub fn tile_tooltip(
++    windows: Query<&Window, With<PrimaryWindow>>,
++    cameras: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
++    tiles: Query<(&TileEntity, &GlobalTransform, &Sprite)>,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    mut tooltips: Query<&mut Style, With<TileTooltip>>,
++    mut texts: Query<&mut Text, With<TileTooltipText>>,
++) {
++    let Ok(mut style) = tooltips.get_single_mut() else {
++        return;
++    };
++    let Some((cursor, tile_index)) = tile_under_cursor(&windows, &cameras, &tiles) else {
++        style.display = Display::None;
++        return;
++    };
++
++    let (description, owner, details) = describe_tile(tile_index, &game, &market);
++    for mut text in texts.iter_mut() {
++        text.sections[0].value = description.clone();
++        text.sections[1].value =
++            owner.map_or(String::new(), |owner| game.players[owner].name.clone());
++        text.sections[1].style.color =
++            owner.map_or(Color::WHITE, |owner| player_color(&game, owner));
++        text.sections[2].value = details.clone();
++    }
++    style.left = Val::Px(cursor.x + 16.0);
++    style.top = Val::Px(cursor.y + 16.0);
++    style.display = Display::Flex;
++}
++
++/// A tile's description in three parts: the text before the owner's name, the
++/// owner (shown in their token color), and the text after it, which ends
++/// with what any script on the tile does.
++pub fn describe_tile(
++    tile_index: usize,
++    game: &Game,
++    market: &StockMarket,
++) -> (String, Option<usize>, String) {
++    let tile = &game.board[tile_index];
++    let (header, owner, mut details) = match tile.kind {
++        TileKind::Property {
++            district,
++            price,
++            base_fee,
++        } => {
++            let name = game.district_name(district);
++            let header = format!("{} shop ({})\nOwner: ", name, Money(price));
++            let stock = format!("Stock: {} per share", Money(market.price(district)));
++            match game.owner_of(tile.index) {
++                Some(owner) => (
++                    header,
++                    Some(owner),
++                    format!(
++                        "\nFee: {} (base {}, x{:.2} ownership, x{:.2} district value)\nCapital invested: {}\nInvestment: {} of {} {} shops\n{}",
++                        Money(game.shop_fee(tile.index, market).unwrap_or(base_fee)),
++                        Money(base_fee),
++                        game.fee_multiplier(tile.index),
++                        market.value_multiplier(district),
++                        Money(tile.capital),
++                        game.players[owner].shops_in_district(district, &game.board),
++                        game.district_size(district),
++                        name,
++                        stock,
++                    ),
++                ),
++                None => (
++                    header,
++                    None,
++                    format!("vacant\nBase fee: {}\n{}", Money(base_fee), stock),
++                ),
++            }
++        }
++        _ => (game.tile_label(tile_index), None, String::new()),
++    };
++    if let Some(script) = &tile.script {
++        details.push_str(&format!("\nScript: {}", script.describe()));
++    }
++    (header, owner, details)
++}
++
++/// Left-clicking or tapping a tile selects it for the shop detail panel;
++/// clicking or tapping empty board space or pressing Back clears the
++/// selection. A touch that moved further than `TAP_SLOP` was a drag, not a
++/// tap.
++#[allow(clippy::too_many_arguments)]
++pub fn select_tile(
++    mouse: Res<ButtonInput<MouseButton>>,
++    touches: Res<Touches>,
++    actions: Res<ActionInput>,
++    windows: Query<&Window, With<PrimaryWindow>>,
++    cameras: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
++    tiles: Query<(&TileEntity, &GlobalTransform, &Sprite)>,
++    interactions: Query<&Interaction>,
++    minimap: Query<&Camera, With<MinimapCamera>>,
++    mut selected: ResMut<SelectedTile>,
++) {
++    if actions.just_pressed(Action::Back) {
++        selected.0 = None;
++        return;
++    }
++    if interactions
++        .iter()
++        .any(|interaction| *interaction != Interaction::None)
++    {
++        return;
++    }
++    if let Ok(window) = windows.get_single()
++        && let Some(point) = window.cursor_position().or_else(|| {
++            touches
++                .iter_just_released()
++                .next()
++                .map(|touch| touch.position())
++        })
++        && over_minimap(point, window, &minimap)
++    {
++        return;
++    }
++    if mouse.just_pressed(MouseButton::Left) {
++        selected.0 = tile_under_cursor(&windows, &cameras, &tiles).map(|(_, tile)| tile);
++    } else if let Some(tap) = touches
++        .iter_just_released()
++        .find(|touch| touch.distance().length() <= TAP_SLOP)
++    {
++        selected.0 = tile_at(tap.position(), &cameras, &tiles);
++    }
++}
++
++/// Space left around the board when it is framed, which is also how far past
++/// its edge the camera may be panned, in world units.
++pub const CAMERA_MARGIN: f32 = TILE_SIZE * 2.0;
++
++/// The zoom at which the whole board and its margin fit in `window`.
++pub fn fit_scale(bounds: Rect, window: &Window) -> f32 {
++    let size = bounds.size() + Vec2::splat(CAMERA_MARGIN * 2.0);
++    (size.x / window.width())
++        .max(size.y / window.height())
++        .clamp(0.5, 2.5)
++}
++
++/// Centres the camera on the board and zooms to fit it as a match opens.
++pub fn frame_board(
++    game: Res<Game>,
++    windows: Query<&Window, With<PrimaryWindow>>,
++    mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
++) {
++    let Ok(window) = windows.get_single() else {
++        return;
++    };
++    let bounds = game.bounds();
++    for (mut transform, mut projection) in cameras.iter_mut() {
++        transform.translation = bounds.center().extend(transform.translation.z);
++        projection.scale = fit_scale(bounds, window);
++    }
++}
++
++/// Refits the zoom to the board whenever the window is resized.
++pub fn refit_on_resize(
++    mut resized: EventReader<WindowResized>,
++    game: Res<Game>,
++    windows: Query<&Window, With<PrimaryWindow>>,
++    mut cameras: Query<&mut OrthographicProjection, With<MainCamera>>,
++) {
++    if resized.read().last().is_none() {
++        return;
++    }
++    let Ok(window) = windows.get_single() else {
++        return;
++    };
++    for mut projection in cameras.iter_mut() {
++        projection.scale = fit_scale(game.bounds(), window);
++    }
++}
++
++/// Keeps the centre of the view within `CAMERA_MARGIN` of the board, so it
++/// can't be panned off into empty space.
++pub fn clamp_camera(game: Res<Game>, mut cameras: Query<&mut Transform, With<MainCamera>>) {
++    let bounds = game.bounds();
++    let margin = Vec2::splat(CAMERA_MARGIN);
++    for mut transform in cameras.iter_mut() {
++        let centre = transform
++            .translation
++            .truncate()
++            .clamp(bounds.min - margin, bounds.max + margin);
++        transform.translation = centre.extend(transform.translation.z);
++    }
++}
++
++/// How far, in pixels, a touch may move and still count as a tap.
++pub const TAP_SLOP: f32 = 12.0;
++
++/// Height of the roll, purchase and bid buttons once the screen has been
++/// touched.
++pub const TAP_TARGET_HEIGHT: f32 = 64.0;
++
++/// One finger drags the board around, leaving follow mode, and two fingers
++/// pinch to zoom.
++pub fn touch_camera(
++    touches: Res<Touches>,
++    mut follow: ResMut<CameraFollow>,
++    mut query: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
++) {
++    let held: Vec<_> = touches.iter().collect();
++    for (mut transform, mut projection) in query.iter_mut() {
++        match held[..] {
++            [touch] => {
++                let delta = touch.delta() * projection.scale;
++                if delta != Vec2::ZERO {
++                    follow.0 = false;
++                }
++                transform.translation.x -= delta.x;
++                transform.translation.y += delta.y;
++            }
++            [first, second, ..] => {
++                let before = first
++                    .previous_position()
++                    .distance(second.previous_position());
++                let now = first.position().distance(second.position());
++                if before > 0.0 && now > 0.0 {
++                    projection.scale = (projection.scale * before / now).clamp(0.5, 2.5);
++                }
++            }
++            [] => {}
++        }
++    }
++}
++
++/// Switches to touch mode on the first touch, enlarging the roll, purchase
++/// and bid buttons to `TAP_TARGET_HEIGHT` so they are easy to hit.
++#[allow(clippy::type_complexity)]
++pub fn enlarge_tap_targets(
++    touches: Res<Touches>,
++    mut ui_state: ResMut<UiState>,
++    mut buttons: Query<&mut Style, Or<(With<RollButton>, With<DialogButton>, With<BidButton>)>>,
++) {
++    if ui_state.touch_mode || touches.iter_just_pressed().next().is_none() {
++        return;
++    }
++    ui_state.touch_mode = true;
++    for mut style in buttons.iter_mut() {
++        style.height = Val::Px(TAP_TARGET_HEIGHT);
++        if let Val::Px(width) = style.width {
++            style.width = Val::Px(width * 1.5);
++        }
++    }
++}
++
++/// Shows the action bar's buttons for what the active human may do right
++/// now, going by the engine's `legal_actions`, and hides the rest: nothing
++/// at all on a bot's turn or outside a match being played. Invest is offered
++/// for the shop selected on the board.
++#[allow(clippy::too_many_arguments)]
++pub fn update_action_bar(
++    app_state: Res<State<AppState>>,
++    phase: Res<State<TurnPhase>>,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    selected: Res<SelectedTile>,
++    mut buttons: Query<(&mut Style, &ActionBarSlot)>,
++) {
++    let changed = app_state.is_changed()
++        || phase.is_changed()
++        || game.is_changed()
++        || market.is_changed()
++        || selected.is_changed();
++    if !changed {
++        return;
++    }
++    let phase = *phase.get();
++    let playing = *app_state.get() == AppState::InGame
++        && game.winner.is_none()
++        && game.players[game.current_turn].decider == Decider::Person;
++    let legal = if playing {
++        legal_actions(&game, &market, phase)
++    } else {
++        Vec::new()
++    };
++    for (mut style, slot) in buttons.iter_mut() {
++        let shown = match slot {
++            ActionBarSlot::Roll => playing && phase == TurnPhase::RollDice,
++            ActionBarSlot::Buy => legal.contains(&TurnAction::BuyShop),
++            ActionBarSlot::Decline => legal.contains(&TurnAction::DeclineShop),
++            ActionBarSlot::Invest => selected
++                .0
++                .is_some_and(|tile| legal.contains(&TurnAction::Invest { tile })),
++            ActionBarSlot::Stocks => legal.iter().any(|action| {
++                matches!(
++                    action,
++                    TurnAction::BuyStock { .. } | TurnAction::SellStock { .. }
++                )
++            }),
++            ActionBarSlot::EndTurn => legal.contains(&TurnAction::EndTurn),
++        };
++        let display = if shown { Display::Flex } else { Display::None };
++        if style.display != display {
++            style.display = display;
++        }
++    }
++}
++
++/// Whether the active human may invest in or sell `tile_index` right now.
++pub fn can_manage_shop(tile_index: usize, game: &Game, phase: TurnPhase) -> bool {
++    let current = game.current_turn;
++    phase == TurnPhase::StockPhase
++        && game.players[current].decider == Decider::Person
++        && game.owner_of(tile_index) == Some(current)
++}
++
++/// Invest and sell buttons in the shop detail panel.
++#[allow(clippy::too_many_arguments)]
++pub fn shop_detail_actions(
++    buttons: Query<(&Interaction, &ShopDetailButton), Changed<Interaction>>,
++    selected: Res<SelectedTile>,
++    phase: Res<State<TurnPhase>>,
++    mut game: ResMut<Game>,
++    mut market: ResMut<StockMarket>,
++    mut log: ResMut<ActionLog>,
++    mut rng: ResMut<MatchRng>,
++    mut ui_state: ResMut<UiState>,
++    mut game_events: EventWriter<GameEvent>,
++) {
++    let Some(tile_index) = selected.0 else {
++        return;
++    };
++    if !can_manage_shop(tile_index, &game, *phase.get()) {
++        return;
++    }
++    let current = game.current_turn;
++    for (interaction, button) in buttons.iter() {
++        if *interaction != Interaction::Pressed {
++            continue;
++        }
++        let label = game.tile_label(tile_index);
++        let cash_before = game.players[current].cash;
++        ui_state.menu_message = match button {
++            ShopDetailButton::Invest => {
++                match log.apply(
++                    TurnAction::Invest { tile: tile_index },
++                    &mut game,
++                    &mut market,
++                    &mut rng,
++                ) {
++                    Ok(events) => {
++                        game_events.send_batch(events);
++                        format!(
++                            "{} now holds {} of capital",
++                            label,
++                            Money(game.board[tile_index].capital)
++                        )
++                    }
++                    Err(err) => err.to_string(),
++                }
++            }
++            ShopDetailButton::Sell => {
++                match log.apply(
++                    TurnAction::SellShop { tile: tile_index },
++                    &mut game,
++                    &mut market,
++                    &mut rng,
++                ) {
++                    Ok(events) => {
++                        game_events.send_batch(events);
++                        format!(
++                            "Sold {} to the bank for {}",
++                            label,
++                            game.players[current].cash - cash_before
++                        )
++                    }
++                    Err(err) => err.to_string(),
++                }
++            }
++        };
++    }
++}
++
++#[allow(clippy::type_complexity, clippy::too_many_arguments)]
++pub fn update_shop_detail_panel(
++    selected: Res<SelectedTile>,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    ui_state: Res<UiState>,
++    phase: Res<State<TurnPhase>>,
++    mut panels: Query<&mut Style, With<ShopDetailPanel>>,
++    mut actions: Query<&mut Style, (With<ShopDetailActions>, Without<ShopDetailPanel>)>,
++    mut texts: Query<&mut Text, With<ShopDetailText>>,
++) {
++    let Ok(mut panel) = panels.get_single_mut() else {
++        return;
++    };
++    let Some(tile_index) = selected.0 else {
++        panel.display = Display::None;
++        return;
++    };
++    panel.display = Display::Flex;
++    let manageable = can_manage_shop(tile_index, &game, *phase.get());
++    for mut style in actions.iter_mut() {
++        style.display = if manageable {
++            Display::Flex
++        } else {
++            Display::None
++        };
++    }
++    let (description, owner, mut details) = describe_tile(tile_index, &game, &market);
++    if let TileKind::Property { .. } = game.board[tile_index].kind {
++        details.push_str(&format!(
++            "\nRoom for {} more capital\nBank buy-back: {}",
++            Money(game.board[tile_index].capital_room()),
++            Money(game.board[tile_index].value() * SHOP_RESALE_PERCENT / 100)
++        ));
++    }
++    if manageable && !ui_state.menu_message.is_empty() {
++        details.push_str(&format!("\n\n{}", ui_state.menu_message));
++    }
++    details.push_str("\n\nEsc or click elsewhere to close");
++    for mut text in texts.iter_mut() {
++        text.sections[0].value = description.clone();
++        text.sections[1].value =
++            owner.map_or(String::new(), |owner| game.players[owner].name.clone());
++        text.sections[1].style.color =
++            owner.map_or(Color::WHITE, |owner| player_color(&game, owner));
++        text.sections[2].value = details.clone();
++    }
++}
++
++#[allow(clippy::type_complexity)]
++pub fn toggle_menu(
++    actions: Res<ActionInput>,
++    buttons: MenuClicks,
++    mut ui_state: ResMut<UiState>,
++    mut menus: Query<&mut Style, With<MenuPanel>>,
++    mut stocks: Query<&mut Style, (With<StockPanel>, Without<MenuPanel>)>,
++    mut trades: Query<&mut Style, (With<TradePanel>, Without<MenuPanel>, Without<StockPanel>)>,
++) {
++    if actions.just_pressed(Action::Menu) || menu_clicked(&buttons, MenuButton::Open) {
++        ui_state.menu_open = !ui_state.menu_open;
++    }
++    if actions.just_pressed(Action::Stocks) || menu_clicked(&buttons, MenuButton::Stocks) {
++        ui_state.stocks_open = !ui_state.stocks_open;
++        ui_state.trade_open &= !ui_state.stocks_open;
++        ui_state.menu_open = ui_state.menu_open || ui_state.stocks_open;
++    }
++    if actions.just_pressed(Action::Trade) || menu_clicked(&buttons, MenuButton::Trade) {
++        ui_state.trade_open = !ui_state.trade_open;
++        ui_state.stocks_open &= !ui_state.trade_open;
++    }
++
++    for mut style in menus.iter_mut() {
++        style.display = if ui_state.menu_open {
++            Display::Flex
++        } else {
++            Display::None
++        };
++    }
++    for mut style in stocks.iter_mut() {
++        style.display = if ui_state.stocks_open {
++            Display::Flex
++        } else {
++            Display::None
++        };
++    }
++    for mut style in trades.iter_mut() {
++        style.display = if ui_state.trade_open {
++            Display::Flex
++        } else {
++            Display::None
++        };
++    }
++}
++
++/// Shows or hides the net worth leaderboard. It only looks on, so it can be
++/// opened while watching a replay or someone else's turn too.
++pub fn toggle_leaderboard(
++    actions: Res<ActionInput>,
++    mut ui_state: ResMut<UiState>,
++    mut panels: Query<&mut Style, With<LeaderboardPanel>>,
++) {
++    if actions.just_pressed(Action::Leaderboard) {
++        ui_state.leaderboard_open = !ui_state.leaderboard_open;
++    }
++    for mut style in panels.iter_mut() {
++        style.display = if ui_state.leaderboard_open {
++            Display::Flex
++        } else {
++            Display::None
++        };
++    }
++}
++
++/// Players ranked by net worth, richest first, with their cash, shop and
++/// stock values. Negative cash counts as nothing on the bar.
++pub fn leaderboard_standings(game: &Game, market: &StockMarket) -> Vec<(usize, [i32; 3])> {
++    let mut standings: Vec<(usize, [i32; 3])> = game
++        .players
++        .iter()
++        .enumerate()
++        .map(|(idx, player)| {
++            (
++                idx,
++                [
++                    player.cash.available(),
++                    player.property_value(&game.board).get(),
++                    player.stock_value(market).available(),
++                ],
++            )
++        })
++        .collect();
++    standings.sort_by_key(|(idx, _)| {
++        std::cmp::Reverse(game.players[*idx].net_worth(&game.board, market))
++    });
++    standings
++}
++
++/// Keeps the leaderboard's bars in step with every player's cash, shops and
++/// stock, reordering rows as the standings change. Bars are scaled to the
++/// largest total on the board.
++#[allow(clippy::too_many_arguments, clippy::type_complexity)]
++pub fn update_leaderboard(
++    mut commands: Commands,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    ui_state: Res<UiState>,
++    asset_server: Res<AssetServer>,
++    panels: Query<Entity, With<LeaderboardPanel>>,
++    mut rows: Query<(Entity, &LeaderboardRow, &mut BorderColor)>,
++    mut labels: Query<(&mut Text, &LeaderboardLabel)>,
++    mut segments: Query<(&mut Style, &LeaderboardSegment)>,
++) {
++    if !ui_state.leaderboard_open {
++        return;
++    }
++    let Ok(panel) = panels.get_single() else {
++        return;
++    };
++    if rows.iter().count() != game.players.len() {
++        for (entity, _, _) in rows.iter() {
++            commands.entity(entity).despawn_recursive();
++        }
++        let font = asset_server.load("fonts/FiraSans-Bold.ttf");
++        commands.entity(panel).with_children(|leaderboard| {
++            for rank in 0..game.players.len() {
++                leaderboard
++                    .spawn((
++                        NodeBundle {
++                            style: Style {
++                                flex_direction: FlexDirection::Column,
++                                row_gap: Val::Px(2.0),
++                                padding: UiRect::left(Val::Px(6.0)),
++                                border: UiRect::left(Val::Px(4.0)),
++                                ..Default::default()
++                            },
++                            ..Default::default()
++                        },
++                        LeaderboardRow(rank),
++                    ))
++                    .with_children(|row| {
++                        row.spawn((
++                            TextBundle::from_section(
++                                "",
++                                TextStyle {
++                                    font: font.clone(),
++                                    font_size: 14.0,
++                                    color: Color::WHITE,
++                                },
++                            ),
++                            LeaderboardLabel(rank),
++                        ));
++                        row.spawn(NodeBundle {
++                            style: Style {
++                                width: Val::Percent(100.0),
++                                height: Val::Px(LEADERBOARD_BAR_HEIGHT),
++                                ..Default::default()
++                            },
++                            background_color: BackgroundColor(Color::rgba(1.0, 1.0, 1.0, 0.08)),
++                            ..Default::default()
++                        })
++                        .with_children(|bar| {
++                            for (part, color) in LEADERBOARD_COLORS.into_iter().enumerate() {
++                                bar.spawn((
++                                    NodeBundle {
++                                        style: Style {
++                                            height: Val::Percent(100.0),
++                                            width: Val::Percent(0.0),
++                                            ..Default::default()
++                                        },
++                                        background_color: BackgroundColor(color),
++                                        ..Default::default()
++                                    },
++                                    LeaderboardSegment { rank, part },
++                                ));
++                            }
++                        });
++                    });
++            }
++        });
++        return;
++    }
++
++    let standings = leaderboard_standings(&game, &market);
++    let scale = standings
++        .iter()
++        .map(|(_, parts)| parts.iter().sum::<i32>())
++        .max()
++        .unwrap_or_default()
++        .max(1) as f32;
++    for (_, row, mut border) in rows.iter_mut() {
++        if let Some((idx, _)) = standings.get(row.0) {
++            border.0 = player_color(&game, *idx);
++        }
++    }
++    for (mut text, label) in labels.iter_mut() {
++        let Some((idx, parts)) = standings.get(label.0) else {
++            continue;
++        };
++        let player = &game.players[*idx];
++        text.sections[0].value = format!(
++            "{}. {}  {}  ({} / {} / {})",
++            label.0 + 1,
++            player.name,
++            Money(player.net_worth(&game.board, &market)),
++            Money(parts[0]),
++            Money(parts[1]),
++            Money(parts[2]),
++        );
++    }
++    for (mut style, segment) in segments.iter_mut() {
++        let Some((_, parts)) = standings.get(segment.rank) else {
++            continue;
++        };
++        style.width = Val::Percent(100.0 * parts[segment.part] as f32 / scale);
++    }
++}
++
++pub fn update_menu_panel(
++    mut menu_text: Query<&mut Text, With<MenuText>>,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    ui_state: Res<UiState>,
++    phase: Res<State<TurnPhase>>,
++) {
++    let Ok(mut text) = menu_text.get_single_mut() else {
++        return;
++    };
++    let changed =
++        game.is_changed() || market.is_changed() || ui_state.is_changed() || phase.is_changed();
++    if !changed && !text.is_added() {
++        return;
++    }
++    let current = game.current_turn;
++    let player = &game.players[current];
++    let mut content = format!(
++        "Bank loan (L borrow, P repay): owe {}, {} available\n\n",
++        player.debt,
++        Money(player.loan_available(&game.board, &market))
++    );
++    let shops = owned_shops(current, &game);
++    content.push_str("Your shops ([ ] choose, X sells to the bank):\n");
++    if shops.is_empty() {
++        content.push_str("  no shops owned\n");
++    }
++    for (idx, tile_index) in shops.iter().enumerate() {
++        content.push_str(&format!(
++            "{} {} #{}: {}\n",
++            if idx == ui_state.sell_cursor {
++                ">"
++            } else {
++                " "
++            },
++            game.tile_label(*tile_index),
++            tile_index,
++            Money(game.board[*tile_index].value() * SHOP_RESALE_PERCENT / 100)
++        ));
++    }
++    if *phase.get() != TurnPhase::StockPhase {
++        content.push_str("(available in your stock phase)\n");
++    }
++    content.push_str(&ui_state.menu_message);
++    text.sections[0].value = content;
++}
++
++/// Rows of the settings menu shown at once; the list scrolls with the cursor.
++pub const SETTINGS_VISIBLE_ROWS: usize = 14;
++
++/// Shows the settings menu while it is open, with the highlighted row marked.
++pub fn update_settings_panel(
++    menu: Res<SettingsMenu>,
++    settings: Res<Settings>,
++    locale: Res<Locale>,
++    mut panels: Query<&mut Style, With<SettingsPanel>>,
++    mut texts: Query<&mut Text, With<SettingsText>>,
++) {
++    if !menu.is_changed() && !settings.is_changed() && !locale.is_changed() {
++        return;
++    }
++    for mut style in panels.iter_mut() {
++        style.display = if menu.open {
++            Display::Flex
++        } else {
++            Display::None
++        };
++    }
++    let Ok(mut text) = texts.get_single_mut() else {
++        return;
++    };
++    let percent = |value: f32| format!("{:.0}", value * 100.0);
++    let scale = |value: f32| format!("{:.2}", value);
++    let mut rows = vec![
++        locale.format("settings.volume", &[("value", &percent(settings.volume))]),
++        locale.format(
++            "settings.music",
++            &[("value", &percent(settings.music_volume))],
++        ),
++        locale.format(
++            "settings.effects",
++            &[("value", &percent(settings.effects_volume))],
++        ),
++        locale.format(
++            "settings.animation_speed",
++            &[("value", &scale(settings.animation_speed))],
++        ),
++        locale.format(
++            "settings.camera_sensitivity",
++            &[("value", &scale(settings.camera_sensitivity))],
++        ),
++        locale.format(
++            "settings.language",
++            &[("value", &settings.language.label())],
++        ),
++        locale.format(
++            "settings.palette",
++            &[("value", &locale.get(settings.palette.label_key()))],
++        ),
++        locale.format(
++            "settings.patterns",
++            &[(
++                "value",
++                &locale.get(if settings.patterns {
++                    "settings.on"
++                } else {
++                    "settings.off"
++                }),
++            )],
++        ),
++        locale.format("settings.ui_scale", &[("value", &scale(settings.ui_scale))]),
++        locale.format(
++            "settings.large_text",
++            &[(
++                "value",
++                &locale.get(if settings.large_text {
++                    "settings.on"
++                } else {
++                    "settings.off"
++                }),
++            )],
++        ),
++    ];
++    for (idx, action) in ACTIONS.iter().enumerate() {
++        let bound = if menu.rebinding && menu.cursor == SETTINGS_SLIDERS + idx {
++            locale.get("settings.rebinding").to_string()
++        } else {
++            let bindings = settings.bindings.bindings(*action);
++            let labels: Vec<String> = bindings.iter().map(|binding| binding.label()).collect();
++            if labels.is_empty() {
++                locale.get("settings.unbound").to_string()
++            } else {
++                labels.join(", ")
++            }
++        };
++        rows.push(format!("{}: {}", action.label(), bound));
++    }
++    let first = menu
++        .cursor
++        .saturating_sub(SETTINGS_VISIBLE_ROWS / 2)
++        .min(rows.len().saturating_sub(SETTINGS_VISIBLE_ROWS));
++    text.sections[0].value = rows
++        .iter()
++        .enumerate()
++        .skip(first)
++        .take(SETTINGS_VISIBLE_ROWS)
++        .map(|(idx, row)| {
++            let marker = if idx == menu.cursor { ">" } else { " " };
++            format!("{} {}\n", marker, row)
++        })
++        .collect();
++}
++
++pub fn update_trade_panel(
++    mut trade_text: Query<&mut Text, With<TradeText>>,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    desk: Res<TradeDesk>,
++) {
++    let Ok(mut text) = trade_text.get_single_mut() else {
++        return;
++    };
++    if !game.is_changed() && !market.is_changed() && !desk.is_changed() && !text.is_added() {
++        return;
++    }
++    let Some(partner) = desk.partner else {
++        text.sections[0].value = "No one to trade with".to_string();
++        return;
++    };
++    let mut content = format!(
++        "Partner: {} (Tab to change)\n\n",
++        game.players[partner].name
++    );
++    for (idx, line) in desk.lines(&game).into_iter().enumerate() {
++        let marker = if idx == desk.cursor { ">" } else { " " };
++        let row = match line {
++            TradeLine::Cash => format!("Cash: {:+}", Money(desk.cash)),
++            TradeLine::Stock(district) => format!(
++                "{} shares ({}): {:+}",
++                game.district_name(district),
++                Money(market.price(district)),
++                desk.stocks.get(&district).copied().unwrap_or(0)
++            ),
++            TradeLine::Shop(index) => {
++                let owner = if game.players[partner].properties.contains(&index) {
++                    &game.players[partner].name
++                } else {
++                    "you"
++                };
++                let included = if desk.shops.contains(&index) {
++                    "[x]"
++                } else {
++                    "[ ]"
++                };
++                format!(
++                    "{} {} shop #{} ({})",
++                    included,
++                    game.tile_label(index),
++                    index,
++                    owner
++                )
++            }
++        };
++        content.push_str(&format!("{} {}\n", marker, row));
++    }
++    if let Some(proposal) = desk.proposal(&game) {
++        content.push_str(&format!(
++            "\nYou give: {}\nYou get: {}\n",
++            proposal.give.describe(&game),
++            proposal.take.describe(&game)
++        ));
++    }
++    content.push_str(&desk.message);
++    text.sections[0].value = content;
++}
++
++/// Shows the trade panel's answer buttons while an offer waits on Y/N, and
++/// its drafting buttons the rest of the time.
++#[allow(clippy::type_complexity)]
++pub fn update_trade_buttons(
++    desk: Res<TradeDesk>,
++    mut drafts: Query<&mut Style, With<TradeDraftButtons>>,
++    mut answers: Query<&mut Style, (With<TradeAnswerButtons>, Without<TradeDraftButtons>)>,
++) {
++    let answering = desk.counter.is_some();
++    let display = |shown: bool| if shown { Display::Flex } else { Display::None };
++    for mut style in drafts.iter_mut() {
++        if style.display != display(!answering) {
++            style.display = display(!answering);
++        }
++    }
++    for mut style in answers.iter_mut() {
++        if style.display != display(answering) {
++            style.display = display(answering);
++        }
++    }
++}
++
++/// Spreads the tokens of players standing on the same tile evenly around a
++/// ring, in seat order from the top left, whenever anyone moves. A token
++/// alone on its tile sits in the middle.
++pub fn layout_tokens(game: Res<Game>, mut tokens: Query<(Ref<PlayerToken>, &mut TokenOffset)>) {
++    let added = tokens.iter().any(|(token, _)| token.is_added());
++    if !game.is_changed() && !added {
++        return;
++    }
++    let mut by_tile: HashMap<usize, Vec<usize>> = HashMap::new();
++    for (idx, player) in game.players.iter().enumerate() {
++        if !player.bankrupt {
++            by_tile.entry(player.position).or_default().push(idx);
++        }
++    }
++    for (token, mut offset) in tokens.iter_mut() {
++        let Some(player) = game.players.get(token.0) else {
++            continue;
++        };
++        let sharing = by_tile.get(&player.position).map_or(&[][..], Vec::as_slice);
++        let target = match sharing.iter().position(|idx| *idx == token.0) {
++            Some(slot) if sharing.len() > 1 => {
++                let angle = std::f32::consts::FRAC_PI_4 * 3.0
++                    - std::f32::consts::TAU * slot as f32 / sharing.len() as f32;
++                Vec2::from_angle(angle) * TOKEN_RING_RADIUS
++            }
++            _ => Vec2::ZERO,
++        };
++        if offset.0 != target {
++            offset.0 = target;
++        }
++    }
++}
++
++/// Glides each token towards its place on the tile its player currently
++/// stands on.
++pub fn sync_tokens(
++    time: Res<Time>,
++    settings: Res<Settings>,
++    game: Res<Game>,
++    mut tokens: Query<(&mut Transform, &PlayerToken, &TokenOffset)>,
++) {
++    for (mut transform, token, offset) in tokens.iter_mut() {
++        let Some(player) = game.players.get(token.0) else {
++            continue;
++        };
++        let target = (game.board[player.position].position + offset.0).extend(2.0);
++        let t = (time.delta_seconds() * 12.0 * settings.animation_speed).min(1.0);
++        transform.translation = transform.translation.lerp(target, t);
++    }
++}
++
++#[allow(clippy::type_complexity)]
++pub fn button_style(
++    mut buttons: Query<
++        (&Interaction, &mut BackgroundColor),
++        (
++            Changed<Interaction>,
++            Or<(
++                With<RollButton>,
++                With<DialogButton>,
++                With<BidButton>,
++                With<ShopDetailButton>,
++                With<MenuButton>,
++                With<ActionButton>,
++            )>,
++        ),
++    >,
++) {
++    for (interaction, mut color) in buttons.iter_mut() {
++        color.0 = match interaction {
++            Interaction::Pressed => ROLL_BUTTON_PRESSED_COLOR,
++            Interaction::Hovered => ROLL_BUTTON_HOVER_COLOR,
++            Interaction::None => ROLL_BUTTON_COLOR,
++        };
++    }
++}
++
++/// Opens the purchase dialog with the shop's price, fee and the stock price
++/// the district would move to if it is bought.
++pub fn show_purchase_dialog(
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    mut dialogs: Query<&mut Style, With<PurchaseDialog>>,
++    mut texts: Query<&mut Text, With<PurchaseText>>,
++) {
++    let Some(TileKind::Property {
++        district,
++        price,
++        base_fee,
++    }) = game
++        .pending_purchase
++        .map(|tile_index| &game.board[tile_index].kind)
++    else {
++        return;
++    };
++    let shop_count = game.district_shop_count.get(district).copied().unwrap_or(0);
++    let name = game.district_name(*district);
++    for mut style in dialogs.iter_mut() {
++        style.display = Display::Flex;
++    }
++    for mut text in texts.iter_mut() {
++        text.sections[0].value = format!(
++            "Buy {} for {}?\n\nShop fee: {}\n{} stock: {} -> {} per share\nCash after purchase: {}",
++            name,
++            Money(*price),
++            Money(*base_fee),
++            name,
++            Money(market.price(*district)),
++            Money(market.price_with_shops(*district, shop_count + 1)),
++            game.players[game.current_turn].cash - *price,
++        );
++    }
++}
++
++pub fn hide_purchase_dialog(mut dialogs: Query<&mut Style, With<PurchaseDialog>>) {
++    for mut style in dialogs.iter_mut() {
++        style.display = Display::None;
++    }
++}
++
++pub fn show_venture_panel(
++    deck: Res<VentureDeck>,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    mut panels: Query<&mut Style, With<VenturePanel>>,
++    mut texts: Query<&mut Text, With<VentureText>>,
++) {
++    let Some(card) = deck.active else {
++        return;
++    };
++    for mut style in panels.iter_mut() {
++        style.display = Display::Flex;
++    }
++    let mut description = card.description(&game);
++    // Cards that move the player preview where they would land, following
++    // the first exit at any junction on the way.
++    if let VentureEffect::Advance(steps) = card.effect {
++        let current = game.current_turn;
++        let landing = landing_tile(game.players[current].position, steps, &game);
++        description.push_str(&format!(
++            "\nLands on {}: {}",
++            game.tile_label(landing),
++            simulate_landing(landing, current, &game, &market).describe(&game)
++        ));
++    }
++    for mut text in texts.iter_mut() {
++        text.sections[0].value = format!(
++            "Venture Card\n\n{}\n{}\n\n(Enter to continue)",
++            card.name(&game),
++            description
++        );
++    }
++}
++
++pub fn hide_venture_panel(mut panels: Query<&mut Style, With<VenturePanel>>) {
++    for mut style in panels.iter_mut() {
++        style.display = Display::None;
++    }
++}
++
++pub fn set_arcade_text(texts: &mut Query<&mut Text, With<ArcadeText>>, value: String) {
++    for mut text in texts.iter_mut() {
++        text.sections[0].value = value.clone();
++    }
++}
++
++pub fn hide_arcade_panel(mut panels: Query<&mut Style, With<ArcadePanel>>) {
++    for mut style in panels.iter_mut() {
++        style.display = Display::None;
++    }
++}
++
++/// Shows the arcade buttons of the mini-game being played until it is over,
++/// then the one leaving the arcade.
++pub fn update_arcade_buttons(
++    arcade: Res<State<ArcadeGame>>,
++    outcome: Res<ArcadeOutcome>,
++    mut buttons: Query<(&mut Style, &ArcadeButton)>,
++) {
++    for (mut style, button) in buttons.iter_mut() {
++        let shown = match button {
++            ArcadeButton::Play(game) => *arcade.get() == *game && outcome.0.is_none(),
++            ArcadeButton::Leave => outcome.0.is_some(),
++        };
++        let display = if shown { Display::Flex } else { Display::None };
++        if style.display != display {
++            style.display = display;
++        }
++    }
++}
++
++pub fn describe_slot(reward: ArcadeReward) -> String {
++    match reward {
++        ArcadeReward::Nothing => "Nothing".to_string(),
++        ArcadeReward::Cash(amount) => Money(amount).to_string(),
++        ArcadeReward::Suit(suit) => suit.icon().to_string(),
++        ArcadeReward::SuitCard => "Suit Yourself".to_string(),
++    }
++}
++
++pub fn update_auction_panel(
++    auction: Option<Res<Auction>>,
++    game: Res<Game>,
++    mut texts: Query<&mut Text, With<AuctionText>>,
++) {
++    let Some(auction) = auction else {
++        return;
++    };
++    let TileKind::Property {
++        district, price, ..
++    } = game.board[auction.tile].kind
++    else {
++        return;
++    };
++    let bids = match auction.leader {
++        Some(leader) => format!(
++            "High bid: {} by {}\nNext bid: {}",
++            Money(auction.high_bid),
++            game.players[leader].name,
++            Money(auction.next_bid())
++        ),
++        None => format!("Opening bid: {}", Money(auction.high_bid)),
++    };
++    for mut text in texts.iter_mut() {
++        text.sections[0].value = format!(
++            "Auction: {} shop (value {})\n\n{}\nClosing in {:.1}s",
++            game.district_name(district),
++            Money(price),
++            bids,
++            auction.countdown.remaining_secs(),
++        );
++    }
++}
++
++pub fn hide_auction_panel(mut panels: Query<&mut Style, With<AuctionPanel>>) {
++    for mut style in panels.iter_mut() {
++        style.display = Display::None;
++    }
++}
++
++/// Takes eliminated players' tokens off the board.
++pub fn remove_bankrupt_tokens(
++    mut commands: Commands,
++    mut bankrupt_events: EventReader<PlayerBankrupt>,
++    tokens: Query<(Entity, &PlayerToken)>,
++) {
++    for event in bankrupt_events.read() {
++        for (entity, token) in tokens.iter() {
++            if token.0 == event.player {
++                commands.entity(entity).despawn_recursive();
++            }
++        }
++    }
++}
++
++/// Rewrites the turn prompt when the match, the turn phase, the walk, the
++/// trade desk or the language changes.
++pub fn update_prompt(
++    mut prompt_text: Query<&mut Text, With<PromptText>>,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    phase: Res<State<TurnPhase>>,
++    move_state: Res<MoveState>,
++    desk: Res<TradeDesk>,
++    locale: Res<Locale>,
++) {
++    let Ok(mut text) = prompt_text.get_single_mut() else {
++        return;
++    };
++    let changed = game.is_changed()
++        || market.is_changed()
++        || phase.is_changed()
++        || move_state.is_changed()
++        || desk.is_changed()
++        || locale.is_changed();
++    if !changed && !text.is_added() {
++        return;
++    }
++    let current = &game.players[game.current_turn];
++    let prompt = match (phase.get(), current.decider, game.winner) {
++        (_, _, Some(winner)) => {
++            let winner = &game.players[winner];
++            locale.format(
++                "prompt.winner",
++                &[
++                    ("name", &winner.name),
++                    ("worth", &winner.net_worth(&game.board, &market)),
++                ],
++            )
++        }
++        (TurnPhase::Moving, Decider::Person, _) if move_state.awaiting_suit_cards => locale.format(
++            "prompt.suit_cards",
++            &[("count", &current.missing_suits().len())],
++        ),
++        (TurnPhase::Moving, Decider::Person, _) if move_state.awaiting_direction => locale.format(
++            "prompt.junction",
++            &[(
++                "options",
++                &junction_options(
++                    current.position,
++                    move_state.steps_remaining,
++                    game.current_turn,
++                    &game,
++                    &market,
++                ),
++            )],
++        ),
++        (TurnPhase::Moving, _, _) => locale.format(
++            "prompt.moving",
++            &[
++                ("name", &current.name),
++                ("steps", &move_state.steps_remaining),
++            ],
++        ),
++        (TurnPhase::ShopDecision, Decider::Person, _) => {
++            locale.get("prompt.shop_decision").to_string()
++        }
++        (TurnPhase::Auction, _, _) => locale.get("prompt.auction").to_string(),
++        (TurnPhase::RollDice, Decider::Person, _) => {
++            locale.format("prompt.roll", &[("name", &current.name)])
++        }
++        (TurnPhase::StockPhase, Decider::Person, _) => locale.get("prompt.stock_phase").to_string(),
++        (TurnPhase::StockPhase, Decider::Brain, _) if desk.counter.is_some() => {
++            desk.message.clone()
++        }
++        (_, Decider::Brain, _) => locale.format("prompt.thinking", &[("name", &current.name)]),
++        _ => String::new(),
++    };
++    if text.sections[0].value != prompt {
++        text.sections[0].value = prompt;
++    }
++}
++
++/// Shows the buttons under the turn prompt whose choice is open: junction
++/// directions with an exit that way, the Suit Yourself answers, or the
++/// answers to a bot's trade offer.
++pub fn update_prompt_buttons(
++    app_state: Res<State<AppState>>,
++    game: Res<Game>,
++    phase: Res<State<TurnPhase>>,
++    move_state: Res<MoveState>,
++    desk: Res<TradeDesk>,
++    mut buttons: Query<(&mut Style, &PromptButton, &ActionButton)>,
++) {
++    let playing = *app_state.get() == AppState::InGame && game.winner.is_none();
++    let current = &game.players[game.current_turn];
++    let person = current.decider == Decider::Person;
++    let moving = playing && *phase.get() == TurnPhase::Moving && person;
++    for (mut style, button, action) in buttons.iter_mut() {
++        let shown = match button {
++            PromptButton::Junction => {
++                moving
++                    && move_state.awaiting_direction
++                    && arrow_direction(action.0)
++                        .and_then(|wanted| exit_toward(current.position, wanted, &game))
++                        .is_some()
++            }
++            PromptButton::SuitCards => moving && move_state.awaiting_suit_cards,
++            PromptButton::BotOffer => {
++                playing
++                    && *phase.get() == TurnPhase::StockPhase
++                    && !person
++                    && desk.counter.is_some()
++            }
++        };
++        let display = if shown { Display::Flex } else { Display::None };
++        if style.display != display {
++            style.display = display;
++        }
++    }
++}
++
++/// Rewrites the line naming the target and whose turn it is when either
++/// changes.
++pub fn update_info_text(
++    mut info_text: Query<&mut Text, With<InfoText>>,
++    game: Res<Game>,
++    locale: Res<Locale>,
++) {
++    let Ok(mut text) = info_text.get_single_mut() else {
++        return;
++    };
++    if !game.is_changed() && !locale.is_changed() && !text.is_added() {
++        return;
++    }
++    let info = locale.format(
++        "hud.info",
++        &[
++            ("target", &game.target_net_worth),
++            ("name", &game.players[game.current_turn].name),
++        ],
++    );
++    if text.sections[0].value != info {
++        text.sections[0].value = info;
++    }
++}
++
++/// Rewrites each player's sidebar panel when the match, the market, a roll
++/// or the language changes. Every panel is its own text entity and only the
++/// ones whose contents differ are touched, so a fee paid between two players
++/// doesn't relay out the other panels.
++pub fn update_player_panels(
++    mut panel_text: Query<(&mut Text, &PlayerPanelText)>,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    ui_state: Res<UiState>,
++    locale: Res<Locale>,
++) {
++    let changed =
++        game.is_changed() || market.is_changed() || ui_state.is_changed() || locale.is_changed();
++    for (mut text, panel) in panel_text.iter_mut() {
++        if !changed && !text.is_added() {
++            continue;
++        }
++        let idx = panel.0;
++        let player = &game.players[idx];
++        let status = if player.bankrupt {
++            locale.get("panel.bankrupt")
++        } else if player.on_break {
++            locale.get("panel.on_break")
++        } else if idx == game.current_turn {
++            locale.get("panel.taking_turn")
++        } else {
++            ""
++        };
++        let header = format!(
++            "{} [{}]{}\n",
++            player.name,
++            match player.kind {
++                PlayerKind::Human if player.autopilot => locale.get("panel.autopilot"),
++                PlayerKind::Human => locale.get("panel.human"),
++                PlayerKind::Bot => locale.get("panel.bot"),
++            },
++            status
++        );
++        let last_roll = ui_state
++            .last_rolls
++            .get(&idx)
++            .map_or("-".to_string(), |faces| describe_roll(faces));
++        let mut body = locale.format(
++            "panel.body",
++            &[
++                ("cash", &player.cash),
++                ("worth", &Money(player.net_worth(&game.board, &market))),
++                ("level", &player.level),
++                ("laps", &player.laps),
++                ("shops", &player.properties.len()),
++                ("cards", &player.suit_cards),
++                ("stocks", &describe_holdings(player, &game)),
++                ("roll", &last_roll),
++            ],
++        );
++        if player.debt > 0 {
++            body.push_str(&locale.format("panel.loan", &[("debt", &player.debt)]));
++        }
++        if text.sections[0].value != header || text.sections[1].value != body {
++            text.sections[0].value = header;
++            text.sections[1].value = body;
++        }
++    }
++}
++
++/// Lists a player's shares, e.g. "Harbor 20, Market 10", in district order.
++pub fn describe_holdings(player: &PlayerState, game: &Game) -> String {
++    let mut holdings: Vec<(DistrictId, i32)> = player
++        .stocks
++        .iter()
++        .filter(|(_, shares)| **shares != 0)
++        .map(|(district, shares)| (*district, *shares))
++        .collect();
++    if holdings.is_empty() {
++        return "-".to_string();
++    }
++    holdings.sort_unstable();
++    holdings
++        .iter()
++        .map(|(district, shares)| format!("{} {}", game.district_name(*district), shares))
++        .collect::<Vec<_>>()
++        .join(", ")
++}
++
++/// Sent whenever a player's cash goes up or down, for the HUD's delta labels.
++#[derive(Event, Debug, Clone, Copy)]
++pub struct CashChanged {
++    pub player: usize,
++    pub delta: i32,
++}
++
++/// Each player's cash as of the last `detect_cash_changes` run.
++#[derive(Resource, Default)]
++pub struct CashTracker(pub Vec<Money>);
++
++/// Builds the sidebar's per-player panels for the new match.
++pub fn spawn_player_panels(
++    mut commands: Commands,
++    game: Res<Game>,
++    containers: Query<Entity, With<PlayerPanels>>,
++    asset_server: Res<AssetServer>,
++    mut tracker: ResMut<CashTracker>,
++) {
++    let Ok(container) = containers.get_single() else {
++        return;
++    };
++    build_player_panels(&mut commands, container, &asset_server, &game, &mut tracker);
++}
++
++/// Replaces the panels under `container` with one per player in `game`.
++pub fn build_player_panels(
++    commands: &mut Commands,
++    container: Entity,
++    asset_server: &AssetServer,
++    game: &Game,
++    tracker: &mut CashTracker,
++) {
++    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
++    tracker.0 = game.players.iter().map(|player| player.cash).collect();
++    commands.entity(container).despawn_descendants();
++    commands.entity(container).with_children(|panels| {
++        for idx in 0..game.players.len() {
++            panels
++                .spawn(NodeBundle {
++                    style: Style {
++                        flex_direction: FlexDirection::Row,
++                        align_items: AlignItems::Center,
++                        padding: UiRect::all(Val::Px(6.0)),
++                        border: UiRect::left(Val::Px(4.0)),
++                        ..Default::default()
++                    },
++                    background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.35)),
++                    border_color: BorderColor(player_color(game, idx)),
++                    ..Default::default()
++                })
++                .with_children(|panel| {
++                    panel
++                        .spawn(NodeBundle {
++                            style: Style {
++                                flex_direction: FlexDirection::Column,
++                                align_items: AlignItems::Center,
++                                margin: UiRect::right(Val::Px(8.0)),
++                                flex_shrink: 0.0,
++                                ..Default::default()
++                            },
++                            ..Default::default()
++                        })
++                        .with_children(|column| {
++                            column.spawn((
++                                NodeBundle {
++                                    style: Style {
++                                        width: Val::Px(PORTRAIT_SIZE),
++                                        height: Val::Px(PORTRAIT_SIZE),
++                                        ..Default::default()
++                                    },
++                                    background_color: BackgroundColor(player_color(game, idx)),
++                                    ..Default::default()
++                                },
++                                PlayerPortrait(idx),
++                            ));
++                            column
++                                .spawn(NodeBundle {
++                                    style: Style {
++                                        height: Val::Px(SUIT_ICON_SIZE * 2.0),
++                                        align_items: AlignItems::Center,
++                                        ..Default::default()
++                                    },
++                                    ..Default::default()
++                                })
++                                .with_children(|row| {
++                                    for suit in SUITS {
++                                        row.spawn((
++                                            TextBundle::from_section(
++                                                suit.icon(),
++                                                TextStyle {
++                                                    font: font.clone(),
++                                                    font_size: SUIT_ICON_SIZE,
++                                                    color: SUIT_MISSING_COLOR,
++                                                },
++                                            ),
++                                            SuitIcon {
++                                                player: idx,
++                                                suit,
++                                                pulse: finished_timer(SUIT_PULSE_SECONDS),
++                                            },
++                                        ));
++                                    }
++                                });
++                        });
++                    panel.spawn((
++                        TextBundle::from_sections([
++                            TextSection::new(
++                                "",
++                                TextStyle {
++                                    font: font.clone(),
++                                    font_size: 17.0,
++                                    color: player_color(game, idx),
++                                },
++                            ),
++                            TextSection::new(
++                                "",
++                                TextStyle {
++                                    font: font.clone(),
++                                    font_size: 15.0,
++                                    color: Color::WHITE,
++                                },
++                            ),
++                        ]),
++                        PlayerPanelText(idx),
++                    ));
++                    panel.spawn((
++                        TextBundle::from_section(
++                            "",
++                            TextStyle {
++                                font: font.clone(),
++                                font_size: 18.0,
++                                color: CASH_GAIN_COLOR,
++                            },
++                        )
++                        .with_style(Style {
++                            margin: UiRect::left(Val::Auto),
++                            ..Default::default()
++                        }),
++                        CashDelta {
++                            player: idx,
++                            timer: Timer::from_seconds(CASH_DELTA_SECONDS, TimerMode::Once),
++                        },
++                    ));
++                });
++        }
++    });
++}
++
++/// Compares every player's cash with the last frame and sends `CashChanged`
++/// for each difference, so fees, salaries and trades all show up on the HUD.
++pub fn detect_cash_changes(
++    game: Res<Game>,
++    mut tracker: ResMut<CashTracker>,
++    mut cash_events: EventWriter<CashChanged>,
++) {
++    if !game.is_changed() {
++        return;
++    }
++    if tracker.0.len() != game.players.len() {
++        tracker.0 = game.players.iter().map(|player| player.cash).collect();
++        return;
++    }
++    for (idx, (seen, player)) in tracker.0.iter_mut().zip(&game.players).enumerate() {
++        if *seen != player.cash {
++            cash_events.send(CashChanged {
++                player: idx,
++                delta: (player.cash - *seen).get(),
++            });
++            *seen = player.cash;
++        }
++    }
++}
++
++/// Shows the latest cash change beside each player's panel, drifting it
++/// upwards and fading it out over `CASH_DELTA_SECONDS`.
++pub fn animate_cash_deltas(
++    time: Res<Time>,
++    mut cash_events: EventReader<CashChanged>,
++    mut deltas: Query<(&mut Text, &mut Style, &mut CashDelta)>,
++) {
++    let mut totals: HashMap<usize, i32> = HashMap::new();
++    for event in cash_events.read() {
++        *totals.entry(event.player).or_default() += event.delta;
++    }
++    for (mut text, mut style, mut delta) in deltas.iter_mut() {
++        if let Some(total) = totals.get(&delta.player) {
++            let color = if *total >= 0 {
++                CASH_GAIN_COLOR
++            } else {
++                CASH_LOSS_COLOR
++            };
++            text.sections[0].value = format!("{:+}", Money(*total));
++            text.sections[0].style.color = color;
++            delta.timer.reset();
++        } else if text.sections[0].value.is_empty() {
++            continue;
++        } else {
++            delta.timer.tick(time.delta());
++        }
++        let progress = delta.timer.fraction();
++        let color = text.sections[0].style.color;
++        text.sections[0].style.color = color.with_a(1.0 - progress);
++        style.top = Val::Px(-16.0 * progress);
++        if delta.timer.finished() {
++            text.sections[0].value.clear();
++        }
++    }
++}
++
++/// Spawns a popup over each player's token with the frame's net cash change.
++pub fn spawn_cash_popups(
++    mut commands: Commands,
++    mut cash_events: EventReader<CashChanged>,
++    tokens: Query<(&Transform, &PlayerToken)>,
++) {
++    let mut totals: HashMap<usize, i32> = HashMap::new();
++    for event in cash_events.read() {
++        *totals.entry(event.player).or_default() += event.delta;
++    }
++    for (transform, token) in tokens.iter() {
++        let Some(total) = totals.get(&token.0).filter(|total| **total != 0) else {
++            continue;
++        };
++        let color = if *total > 0 {
++            CASH_GAIN_COLOR
++        } else {
++            CASH_LOSS_COLOR
++        };
++        commands.spawn((
++            Text2dBundle {
++                text: Text::from_section(
++                    format!("{:+}", Money(*total)),
++                    TextStyle {
++                        font_size: 22.0,
++                        color,
++                        ..Default::default()
++                    },
++                ),
++                transform: Transform::from_translation(
++                    transform.translation.truncate().extend(10.0) + Vec3::Y * TILE_SIZE * 0.5,
++                ),
++                ..Default::default()
++            },
++            CashPopup(Timer::from_seconds(CASH_DELTA_SECONDS, TimerMode::Once)),
++        ));
++    }
++}
++
++/// Floats cash popups upwards while they fade, then despawns them.
++pub fn animate_cash_popups(
++    time: Res<Time>,
++    mut commands: Commands,
++    mut popups: Query<(Entity, &mut CashPopup, &mut Transform, &mut Text)>,
++) {
++    for (entity, mut popup, mut transform, mut text) in popups.iter_mut() {
++        if popup.0.tick(time.delta()).finished() {
++            commands.entity(entity).despawn_recursive();
++            continue;
++        }
++        transform.translation.y += CASH_POPUP_RISE * time.delta_seconds() / CASH_DELTA_SECONDS;
++        let color = text.sections[0].style.color;
++        text.sections[0].style.color = color.with_a(1.0 - popup.0.fraction());
++    }
++}
++
++/// Spawns a burst of sparkles flying out from `at`.
++pub fn spawn_sparkles(commands: &mut Commands, at: Vec2, color: Color) {
++    let mut rng = rand::thread_rng();
++    for idx in 0..SPARKLE_COUNT {
++        let angle =
++            std::f32::consts::TAU * (idx as f32 + rng.gen_range(0.0..0.8)) / SPARKLE_COUNT as f32;
++        commands.spawn((
++            SpriteBundle {
++                sprite: Sprite {
++                    color,
++                    custom_size: Some(Vec2::splat(rng.gen_range(4.0..8.0))),
++                    ..Default::default()
++                },
++                transform: Transform::from_translation(at.extend(9.0))
++                    .with_rotation(Quat::from_rotation_z(angle)),
++                ..Default::default()
++            },
++            Sparkle {
++                velocity: Vec2::from_angle(angle) * rng.gen_range(70.0..130.0),
++                timer: Timer::from_seconds(SPARKLE_SECONDS, TimerMode::Once),
++            },
++        ));
++    }
++}
++
++/// Sparkles on the tile and token when a player picks up a suit, and on the
++/// token when a promotion spends all four. Either way the player's suit icons
++/// pulse.
++pub fn suit_effects(
++    mut commands: Commands,
++    game: Res<Game>,
++    mut game_events: EventReader<GameEvent>,
++    mut salary_events: EventReader<SalaryPaid>,
++    tokens: Query<(&Transform, &PlayerToken)>,
++    mut icons: Query<&mut SuitIcon>,
++) {
++    let token_at = |player: usize| {
++        tokens
++            .iter()
++            .find(|(_, token)| token.0 == player)
++            .map(|(transform, _)| transform.translation.truncate())
++    };
++    let mut pulses: Vec<(usize, Option<Suit>)> = Vec::new();
++    for event in game_events.read() {
++        if let GameEvent::CollectedSuit { player, suit } = event {
++            let tile = game.board[game.players[*player].position].position;
++            spawn_sparkles(&mut commands, tile, SUIT_PULSE_COLOR);
++            if let Some(token) = token_at(*player) {
++                spawn_sparkles(&mut commands, token, Color::WHITE);
++            }
++            pulses.push((*player, Some(*suit)));
++        }
++    }
++    for event in salary_events.read() {
++        if let Some(token) = token_at(event.player) {
++            spawn_sparkles(&mut commands, token, SUIT_PULSE_COLOR);
++        }
++        pulses.push((event.player, None));
++    }
++    for mut icon in icons.iter_mut() {
++        if pulses.iter().any(|(player, suit)| {
++            *player == icon.player && suit.is_none_or(|suit| suit == icon.suit)
++        }) {
++            icon.pulse.reset();
++        }
++    }
++}
++
++/// Fills in the suit icons of the suits each player holds. A pulsing icon
++/// starts large and gold and settles back to its size and color.
++pub fn animate_suit_icons(
++    time: Res<Time>,
++    game: Res<Game>,
++    settings: Res<Settings>,
++    mut icons: Query<(&mut Text, &mut SuitIcon)>,
++) {
++    for (mut text, mut icon) in icons.iter_mut() {
++        let Some(player) = game.players.get(icon.player) else {
++            continue;
++        };
++        let color = if player.suits.contains(&icon.suit) {
++            Color::WHITE
++        } else {
++            SUIT_MISSING_COLOR
++        };
++        let pulse = 1.0 - icon.pulse.tick(time.delta()).fraction();
++        let style = &mut text.sections[0].style;
++        style.font_size = SUIT_ICON_SIZE * (1.0 + pulse) * settings.text_scale();
++        style.color = Color::rgba_from_array(
++            Vec4::from_array(color.as_rgba_f32())
++                .lerp(Vec4::from_array(SUIT_PULSE_COLOR.as_rgba_f32()), pulse),
++        );
++    }
++}
++
++/// Flies sparkles outwards, slowing and fading, then despawns them.
++pub fn animate_sparkles(
++    time: Res<Time>,
++    mut commands: Commands,
++    mut sparkles: Query<(Entity, &mut Sparkle, &mut Transform, &mut Sprite)>,
++) {
++    let delta = time.delta_seconds();
++    for (entity, mut sparkle, mut transform, mut sprite) in sparkles.iter_mut() {
++        if sparkle.timer.tick(time.delta()).finished() {
++            commands.entity(entity).despawn();
++            continue;
++        }
++        transform.translation += (sparkle.velocity * delta).extend(0.0);
++        sparkle.velocity *= 1.0 - (3.0 * delta).min(1.0);
++        transform.rotate_z(6.0 * delta);
++        sprite.color.set_a(1.0 - sparkle.timer.fraction());
++    }
++}
++
++/// Writes a roll as its faces and total, e.g. "3 + 5 = 8".
++pub fn describe_roll(faces: &[usize]) -> String {
++    match faces {
++        [single] => single.to_string(),
++        _ => format!(
++            "{} = {}",
++            faces
++                .iter()
++                .map(|face| face.to_string())
++                .collect::<Vec<_>>()
++                .join(" + "),
++            faces.iter().sum::<usize>()
++        ),
++    }
++}
++
++/// Replaces a tile's ownership flag with one in the new owner's token color,
++/// or removes it when the shop returns to the bank.
++pub fn update_ownership_markers(
++    mut commands: Commands,
++    game: Res<Game>,
++    mut ownership_events: EventReader<OwnershipChanged>,
++    tiles: Query<(Entity, &TileEntity, &Children)>,
++    markers: Query<Entity, With<OwnershipMarker>>,
++) {
++    // Only a tile's last change this frame matters: markers spawned through
++    // `commands` are not children yet, so they could not be replaced twice.
++    let latest: HashMap<usize, Option<usize>> = ownership_events
++        .read()
++        .map(|event| (event.tile, event.owner))
++        .collect();
++    for (tile_entity, tile, children) in tiles.iter() {
++        let Some(new_owner) = latest.get(&tile.0) else {
++            continue;
++        };
++        for child in children.iter() {
++            if markers.contains(*child) {
++                commands.entity(*child).despawn_recursive();
++            }
++        }
++        let Some(owner) = *new_owner else {
++            continue;
++        };
++        commands.entity(tile_entity).with_children(|tile| {
++            tile.spawn(ownership_marker(player_color(&game, owner)));
++        });
++    }
++}
++
++/// Glow behind a tile the active human would land on from a junction.
++#[derive(Component)]
++pub struct LandingHighlight;
++
++pub const LANDING_HIGHLIGHT_COLOR: Color = Color::rgba(1.0, 0.9, 0.2, 0.9);
++
++/// While the active human stands at a junction, highlights the tile each
++/// exit would land them on.
++pub fn highlight_landing_tiles(
++    mut commands: Commands,
++    game: Res<Game>,
++    move_state: Res<MoveState>,
++    phase: Res<State<TurnPhase>>,
++    mut shown: Local<Vec<usize>>,
++    tiles: Query<(Entity, &TileEntity)>,
++    highlights: Query<Entity, With<LandingHighlight>>,
++) {
++    if game.is_added() {
++        // The highlights of a quit match went with its board.
++        shown.clear();
++    }
++    let current = &game.players[game.current_turn];
++    let targets: Vec<usize> = if *phase.get() == TurnPhase::Moving
++        && move_state.awaiting_direction
++        && current.decider == Decider::Person
++    {
++        game.board[current.position]
++            .next
++            .iter()
++            .map(|exit| landing_tile(*exit, move_state.steps_remaining.saturating_sub(1), &game))
++            .collect()
++    } else {
++        Vec::new()
++    };
++    if targets == *shown {
++        return;
++    }
++    for entity in highlights.iter() {
++        commands.entity(entity).despawn_recursive();
++    }
++    for (entity, tile) in tiles.iter() {
++        if !targets.contains(&tile.0) {
++            continue;
++        }
++        commands.entity(entity).with_children(|parent| {
++            parent.spawn((
++                SpriteBundle {
++                    sprite: Sprite {
++                        color: LANDING_HIGHLIGHT_COLOR,
++                        custom_size: Some(Vec2::splat(TILE_SIZE + 10.0)),
++                        ..Default::default()
++                    },
++                    transform: Transform::from_xyz(0.0, 0.0, -0.1),
++                    ..Default::default()
++                },
++                LandingHighlight,
++            ));
++        });
++    }
++    *shown = targets;
++}
++
++/// Small square in the owner's `color` for the corner of a shop tile.
++pub fn ownership_marker(color: Color) -> (SpriteBundle, OwnershipMarker) {
++    let corner = Vec2::new(TILE_SIZE / 2.0 - 8.0, TILE_SIZE / 2.0 - 8.0);
++    (
++        SpriteBundle {
++            sprite: Sprite {
++                color,
++                custom_size: Some(Vec2::splat(12.0)),
++                ..Default::default()
++            },
++            transform: Transform::from_translation(corner.extend(1.5)),
++            ..Default::default()
++        },
++        OwnershipMarker,
++    )
++}
++
++/// Most recent lines kept in the event log panel.
++pub const EVENT_LOG_LINES: usize = 12;
++
++/// The event log panel's lines, oldest first.
++#[derive(Resource, Default)]
++pub struct EventLog(pub VecDeque<String>);
++
++#[derive(Component)]
++pub struct EventLogText;
++
++/// Appends each `GameEvent` to the log, dropping the oldest lines once it
++/// holds `EVENT_LOG_LINES`.
++pub fn record_game_events(
++    game: Res<Game>,
++    mut game_events: EventReader<GameEvent>,
++    mut log: ResMut<EventLog>,
++    mut log_text: Query<&mut Text, With<EventLogText>>,
++) {
++    let mut changed = false;
++    for event in game_events.read() {
++        log.0.push_back(event.describe(&game));
++        if log.0.len() > EVENT_LOG_LINES {
++            log.0.pop_front();
++        }
++        changed = true;
++    }
++    if !changed {
++        return;
++    }
++    if let Ok(mut text) = log_text.get_single_mut() {
++        text.sections[0].value = log.0.iter().cloned().collect::<Vec<_>>().join("\n");
++    }
++}
++
++/// Shows the promotion banner with the salary breakdown for each promotion,
++/// then hides it after a few seconds.
++pub fn promotion_banner(
++    time: Res<Time>,
++    game: Res<Game>,
++    mut salary_events: EventReader<SalaryPaid>,
++    mut banners: Query<(&mut Style, &mut PromotionBanner)>,
++    mut texts: Query<&mut Text, With<PromotionText>>,
++) {
++    let Ok((mut style, mut banner)) = banners.get_single_mut() else {
++        return;
++    };
++    if let Some(event) = salary_events.read().last() {
++        let breakdown = event.breakdown;
++        let mut details = format!(
++            "\n{} is promoted to level {}!\n\nLevel pay: {}\nShop bonus: {}\nStock bonus: {}\nSalary: {}",
++            game.players[event.player].name,
++            event.level,
++            Money(breakdown.level_pay),
++            Money(breakdown.shop_bonus),
++            Money(breakdown.stock_bonus),
++            Money(breakdown.total()),
++        );
++        if breakdown.landing_bonus > 0 {
++            details.push_str(&format!(
++                "\nIncludes {} for landing on the bank",
++                Money(breakdown.landing_bonus)
++            ));
++        }
++        if !event.boosted_districts.is_empty() {
++            details.push_str(&format!(
++                "\nStocks up in {}",
++                event
++                    .boosted_districts
++                    .iter()
++                    .map(|district| game.district_name(*district))
++                    .collect::<Vec<_>>()
++                    .join(", ")
++            ));
++        }
++        if event.loan_repaid > 0 {
++            details.push_str(&format!("\nLoan repaid: {}", Money(event.loan_repaid)));
++        }
++        for mut text in texts.iter_mut() {
++            text.sections[0].value = "PROMOTION!".to_string();
++            text.sections[1].value = details.clone();
++        }
++        banner.0.reset();
++        style.display = Display::Flex;
++    } else if style.display != Display::None && banner.0.tick(time.delta()).just_finished() {
++        style.display = Display::None;
++    }
++}
++
++/// What a banner announces on entering `phase`, if anything.
++pub fn phase_announcement(phase: TurnPhase, game: &Game) -> Option<String> {
++    match phase {
++        TurnPhase::RollDice => Some(format!("{}'s turn", game.players[game.current_turn].name)),
++        TurnPhase::StockPhase => Some("Stock phase".to_string()),
++        TurnPhase::Auction => Some("Auction!".to_string()),
++        TurnPhase::VentureCard => Some("Venture card".to_string()),
++        TurnPhase::Arcade => Some("Arcade!".to_string()),
++        TurnPhase::Casino => Some("Casino!".to_string()),
++        _ => None,
++    }
++}
++
++/// Replaces any phase banner with one for the phase just entered.
++pub fn announce_phase(
++    mut commands: Commands,
++    asset_server: Res<AssetServer>,
++    phase: Res<State<TurnPhase>>,
++    game: Res<Game>,
++    banners: Query<Entity, (With<PhaseBanner>, Without<MarketBanner>)>,
++) {
++    let Some(announcement) = phase_announcement(*phase.get(), &game) else {
++        return;
++    };
++    for entity in banners.iter() {
++        commands.entity(entity).despawn_recursive();
++    }
++    commands
++        .spawn((
++            NodeBundle {
++                style: Style {
++                    position_type: PositionType::Absolute,
++                    left: Val::Percent(-100.0),
++                    top: Val::Percent(22.0),
++                    width: Val::Percent(100.0),
++                    justify_content: JustifyContent::Center,
++                    padding: UiRect::vertical(Val::Px(10.0)),
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(PHASE_BANNER_COLOR),
++                z_index: ZIndex::Global(6),
++                ..Default::default()
++            },
++            PhaseBanner(Timer::from_seconds(PHASE_BANNER_SECONDS, TimerMode::Once)),
++        ))
++        .with_children(|banner| {
++            banner.spawn(TextBundle::from_section(
++                announcement,
++                TextStyle {
++                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
++                    font_size: 36.0,
++                    color: Color::WHITE,
++                },
++            ));
++        });
++}
++
++/// Sweeps a large banner across the screen for each market boom or crash.
++pub fn announce_market_swings(
++    mut commands: Commands,
++    asset_server: Res<AssetServer>,
++    mut game_events: EventReader<GameEvent>,
++    banners: Query<Entity, With<MarketBanner>>,
++) {
++    let Some(percent) = game_events
++        .read()
++        .filter_map(|event| match event {
++            GameEvent::MarketSwing { percent } => Some(*percent),
++            _ => None,
++        })
++        .last()
++    else {
++        return;
++    };
++    for entity in banners.iter() {
++        commands.entity(entity).despawn_recursive();
++    }
++    let (headline, color) = if percent >= 0 {
++        (
++            format!("MARKET BOOM! Stocks up {}%", percent),
++            MARKET_BOOM_COLOR,
++        )
++    } else {
++        (
++            format!("MARKET CRASH! Stocks down {}%", -percent),
++            MARKET_CRASH_COLOR,
++        )
++    };
++    commands
++        .spawn((
++            NodeBundle {
++                style: Style {
++                    position_type: PositionType::Absolute,
++                    left: Val::Percent(-100.0),
++                    top: Val::Percent(38.0),
++                    width: Val::Percent(100.0),
++                    justify_content: JustifyContent::Center,
++                    padding: UiRect::vertical(Val::Px(18.0)),
++                    ..Default::default()
++                },
++                background_color: BackgroundColor(color),
++                z_index: ZIndex::Global(7),
++                ..Default::default()
++            },
++            PhaseBanner(Timer::from_seconds(MARKET_BANNER_SECONDS, TimerMode::Once)),
++            MarketBanner,
++        ))
++        .with_children(|banner| {
++            banner.spawn(TextBundle::from_section(
++                headline,
++                TextStyle {
++                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
++                    font_size: 52.0,
++                    color: Color::WHITE,
++                },
++            ));
++        });
++}
++
++/// Slides phase banners in from the left, then fades them out and despawns
++/// them.
++pub fn animate_phase_banners(
++    time: Res<Time>,
++    mut commands: Commands,
++    mut banners: Query<(
++        Entity,
++        &mut PhaseBanner,
++        &mut Style,
++        &mut BackgroundColor,
++        &Children,
++    )>,
++    mut texts: Query<&mut Text>,
++) {
++    for (entity, mut banner, mut style, mut background, children) in banners.iter_mut() {
++        if banner.0.tick(time.delta()).finished() {
++            commands.entity(entity).despawn_recursive();
++            continue;
++        }
++        let elapsed = banner.0.elapsed_secs();
++        let slide = (elapsed / PHASE_BANNER_SLIDE).min(1.0);
++        style.left = Val::Percent(-100.0 * (1.0 - slide).powi(3));
++        let alpha = (banner.0.remaining_secs() / PHASE_BANNER_FADE).min(1.0);
++        background.0.set_a(PHASE_BANNER_COLOR.a() * alpha);
++        for child in children.iter() {
++            if let Ok(mut text) = texts.get_mut(*child) {
++                text.sections[0].style.color.set_a(alpha);
++            }
++        }
++    }
++}
++
++/// Remembers each district's latest price move so the stock panel can show
++/// it, and its recent prices for the portfolio sparklines.
++pub fn track_price_changes(
++    mut price_events: EventReader<StockPriceChanged>,
++    mut ui_state: ResMut<UiState>,
++) {
++    for event in price_events.read() {
++        ui_state
++            .last_price_change
++            .insert(event.district, event.new_price - event.old_price);
++        let history = ui_state.price_history.entry(event.district).or_default();
++        if history.is_empty() {
++            history.push(event.old_price);
++        }
++        history.push(event.new_price);
++        if history.len() > PRICE_HISTORY_LEN {
++            history.remove(0);
++        }
++    }
++}
++
++/// Draws `prices` as a row of bars scaled between their lowest and highest.
++pub fn sparkline(prices: &[i32]) -> String {
++    let (Some(low), Some(high)) = (prices.iter().min(), prices.iter().max()) else {
++        return String::new();
++    };
++    let range = (high - low).max(1) as usize;
++    prices
++        .iter()
++        .map(|price| SPARK_BARS[(price - low) as usize * (SPARK_BARS.len() - 1) / range])
++        .collect()
++}
++
++pub fn toggle_portfolio(
++    actions: Res<ActionInput>,
++    mut ui_state: ResMut<UiState>,
++    mut panels: Query<&mut Style, With<PortfolioPanel>>,
++) {
++    if actions.just_pressed(Action::Portfolio) {
++        ui_state.portfolio_open = !ui_state.portfolio_open;
++    }
++    for mut style in panels.iter_mut() {
++        style.display = if ui_state.portfolio_open {
++            Display::Flex
++        } else {
++            Display::None
++        };
++    }
++}
++
++/// Lists the active player's shares in each district: what they paid, what
++/// the shares are worth now and the unrealized gain, with a sparkline of the
++/// district's recent prices.
++pub fn update_portfolio(
++    mut portfolio_text: Query<&mut Text, With<PortfolioText>>,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    ui_state: Res<UiState>,
++    locale: Res<Locale>,
++) {
++    if !ui_state.portfolio_open {
++        return;
++    }
++    let Ok(mut text) = portfolio_text.get_single_mut() else {
++        return;
++    };
++    let changed =
++        game.is_changed() || market.is_changed() || ui_state.is_changed() || locale.is_changed();
++    if !changed && !text.is_added() {
++        return;
++    }
++    let holder = &game.players[game.current_turn];
++    let mut content = locale.format("portfolio.header", &[("name", &holder.name)]);
++    let mut holdings: Vec<(DistrictId, i32)> = holder
++        .stocks
++        .iter()
++        .filter(|(_, shares)| **shares != 0)
++        .map(|(district, shares)| (*district, *shares))
++        .collect();
++    holdings.sort_unstable();
++    if holdings.is_empty() {
++        content.push_str(locale.get("portfolio.empty"));
++    }
++    let (mut total_value, mut total_basis) = (0, 0);
++    for (district, shares) in holdings {
++        let price = market.price(district);
++        let value = shares * price;
++        let basis = holder.cost_basis.get(&district).copied().unwrap_or(0);
++        total_value += value;
++        total_basis += basis;
++        let history = ui_state
++            .price_history
++            .get(&district)
++            .map_or(String::new(), |prices| sparkline(prices));
++        content.push_str(&locale.format(
++            "portfolio.row",
++            &[
++                ("district", &game.district_name(district)),
++                ("shares", &shares),
++                ("price", &price),
++                ("basis", &basis),
++                ("value", &value),
++                ("gain", &format!("{:+}", value - basis)),
++                ("history", &history),
++            ],
++        ));
++    }
++    content.push_str(&locale.format(
++        "portfolio.total",
++        &[
++            ("value", &total_value),
++            ("gain", &format!("{:+}", total_value - total_basis)),
++        ],
++    ));
++    text.sections[0].value = content;
++}
++
++pub fn update_stock_panel(
++    mut stock_text: Query<&mut Text, With<StockText>>,
++    game: Res<Game>,
++    market: Res<StockMarket>,
++    ui_state: Res<UiState>,
++) {
++    if !game.is_changed() && !market.is_changed() && !ui_state.is_changed() {
++        return;
++    }
++    if let Ok(mut text) = stock_text.get_single_mut() {
++        let holder = &game.players[game.current_turn];
++        let mut content = String::new();
++        for (idx, district) in game.district_ids().into_iter().enumerate() {
++            let selected = idx == ui_state.stock_cursor;
++            content.push_str(&format!(
++                "{} {}. {}: {}/share ({:+}) | held {} | issued {}\n",
++                if selected { ">" } else { " " },
++                idx + 1,
++                game.district_name(district),
++                Money(market.price(district)),
++                Money(
++                    ui_state
++                        .last_price_change
++                        .get(&district)
++                        .copied()
++                        .unwrap_or(0)
++                ),
++                holder.stocks.get(&district).copied().unwrap_or(0),
++                market
++                    .shares_outstanding
++                    .get(&district)
++                    .copied()
++                    .unwrap_or(0),
++            ));
++        }
++        content.push_str(&format!("\nOrder: {:+} shares\n", ui_state.stock_order));
++        if holder.short_value(&market) > 0 {
++            content.push_str(&format!(
++                "Margin: {} cash, {} required\n",
++                holder.cash,
++                Money(holder.margin_required(&market))
++            ));
++        }
++        content.push_str(&ui_state.stock_message);
++        text.sections[0].value = content;
++    }
++}